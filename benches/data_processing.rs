@@ -4,7 +4,7 @@ use std::io::Write;
 
 use hydra::logs::{
     extract_assistant_message_text, format_cost, format_tokens,
-    update_session_stats_from_path_and_last_message, SessionStats,
+    update_session_stats_from_path_and_last_message, Currency, SessionStats,
 };
 use hydra::tmux::{apply_tmux_modifiers, keycode_to_tmux};
 
@@ -139,6 +139,7 @@ fn bench_format_functions(c: &mut Criterion) {
     }
 
     // format_cost
+    let usd = Currency::usd();
     for (label, val) in [
         ("zero", 0.0f64),
         ("small_0_50", 0.50),
@@ -146,7 +147,7 @@ fn bench_format_functions(c: &mut Criterion) {
         ("large_25", 25.0),
     ] {
         group.bench_function(format!("cost_{label}"), |b| {
-            b.iter(|| format_cost(black_box(val)));
+            b.iter(|| format_cost(black_box(val), &usd));
         });
     }
 
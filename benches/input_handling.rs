@@ -13,6 +13,7 @@ fn make_session(name: &str, visual_status: VisualStatus) -> Session {
     let (process_state, agent_state) = match visual_status {
         VisualStatus::Idle => (ProcessState::Alive, AgentState::Idle),
         VisualStatus::Running(_s) => (ProcessState::Alive, AgentState::Thinking),
+        VisualStatus::NeedsInput => (ProcessState::Alive, AgentState::WaitingForInput),
         VisualStatus::Exited => (
             ProcessState::Exited {
                 exit_code: None,
@@ -21,6 +22,7 @@ fn make_session(name: &str, visual_status: VisualStatus) -> Session {
             AgentState::Idle,
         ),
         VisualStatus::Booting => (ProcessState::Booting, AgentState::Idle),
+        VisualStatus::Stopped => (ProcessState::Stopped, AgentState::Idle),
     };
     Session {
         name: name.to_string(),
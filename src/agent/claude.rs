@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use async_trait::async_trait;
 
 use crate::agent::{AgentLogUpdate, AgentProvider, StatusStrategy};
-use crate::logs::SessionStats;
+use crate::logs::{ConversationEntry, SessionStats, TimedEntry};
 
 pub struct ClaudeProvider;
 
@@ -26,6 +26,15 @@ impl AgentProvider for ClaudeProvider {
         crate::logs::resolve_session_uuid(tmux_name).await
     }
 
+    fn log_file_path(&self, log_id: &str, cwd: &str) -> std::path::PathBuf {
+        crate::logs::session_jsonl_path(cwd, log_id)
+    }
+
+    fn log_resolution_hint(&self) -> &'static str {
+        "looked for --session-id in the pane's process tree, then for an open \
+         ~/.claude/projects/*.jsonl via lsof"
+    }
+
     fn update_from_log(
         &self,
         log_id: &str,
@@ -36,17 +45,86 @@ impl AgentProvider for ClaudeProvider {
         let last_message =
             crate::logs::update_session_stats_and_last_message(cwd, log_id, session_stats);
         let path = crate::logs::session_jsonl_path(cwd, log_id);
-        let (entries, new_offset) = crate::logs::parse_conversation_entries(&path, offset);
+        let (entries, new_offset) = read_timed_entries(&path, offset, session_stats);
 
         AgentLogUpdate {
-            entries,
+            entries: entries.into_iter().map(|(_, entry)| entry).collect(),
             new_offset,
             last_message,
             replace_conversation: false,
         }
     }
 
+    fn update_timed_from_log(
+        &self,
+        log_id: &str,
+        cwd: &str,
+        offset: u64,
+        session_stats: &mut SessionStats,
+    ) -> (Vec<TimedEntry>, u64) {
+        crate::logs::update_session_stats_and_last_message(cwd, log_id, session_stats);
+        let path = crate::logs::session_jsonl_path(cwd, log_id);
+        read_timed_entries(&path, offset, session_stats)
+    }
+
+    fn read_full_conversation(&self, log_id: &str, cwd: &str) -> Vec<ConversationEntry> {
+        // No tail seeding: exports want the whole history.
+        let path = crate::logs::session_jsonl_path(cwd, log_id);
+        crate::logs::parse_conversation_entries(&path, 0).0
+    }
+
+    fn read_timed_conversation(&self, log_id: &str, cwd: &str) -> Vec<TimedEntry> {
+        let path = crate::logs::session_jsonl_path(cwd, log_id);
+        crate::logs::parse_timed_conversation_entries(&path, 0, None).0
+    }
+
     fn preferred_status_strategy(&self) -> StatusStrategy {
         StatusStrategy::JsonlActivity
     }
 }
+
+/// Read new entries from the main log, plus every subagent log when
+/// subagent transcripts are enabled.
+fn read_timed_entries(
+    path: &std::path::Path,
+    offset: u64,
+    stats: &mut SessionStats,
+) -> (Vec<TimedEntry>, u64) {
+    if crate::logs::subagent_transcripts() {
+        read_with_subagents(path, offset, stats)
+    } else {
+        crate::logs::parse_timed_conversation_entries(
+            path,
+            offset,
+            crate::logs::conversation_tail_seed(),
+        )
+    }
+}
+
+/// Read new entries from the main log and every subagent log, merged by
+/// timestamp. Subagent offsets live in `stats` and restart with the main log.
+fn read_with_subagents(
+    path: &std::path::Path,
+    offset: u64,
+    stats: &mut SessionStats,
+) -> (Vec<TimedEntry>, u64) {
+    let seed = crate::logs::conversation_tail_seed();
+    if offset == 0 {
+        stats.subagent_offsets.clear();
+    }
+    let (main, new_offset) = crate::logs::parse_timed_conversation_entries(path, offset, seed);
+    let sidechains = crate::logs::subagent_log_paths(path)
+        .into_iter()
+        .map(|(agent_id, sub_path)| {
+            let sub_offset = stats.subagent_offsets.get(&agent_id).copied().unwrap_or(0);
+            let (entries, next) =
+                crate::logs::parse_timed_conversation_entries(&sub_path, sub_offset, seed);
+            stats.subagent_offsets.insert(agent_id.clone(), next);
+            (agent_id, entries)
+        })
+        .collect();
+    (
+        crate::logs::merge_sidechain_entries(main, sidechains),
+        new_offset,
+    )
+}
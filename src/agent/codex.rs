@@ -29,15 +29,28 @@ impl AgentProvider for CodexProvider {
             .map(|p| p.to_string_lossy().to_string())
     }
 
+    fn log_resolution_hint(&self) -> &'static str {
+        "looked for an open ~/.codex/sessions rollout file via lsof on the pane's process tree"
+    }
+
     fn update_from_log(
         &self,
         log_id: &str,
         _cwd: &str,
         offset: u64,
-        _session_stats: &mut SessionStats,
+        session_stats: &mut SessionStats,
     ) -> AgentLogUpdate {
         let path = PathBuf::from(log_id);
-        let (entries, new_offset) = crate::logs::parse_codex_conversation_entries(&path, offset);
+        let (entries, new_offset, model) = crate::logs::parse_codex_log(&path, offset);
+        if model.is_some() {
+            session_stats.last_model = model;
+        }
+
+        for entry in &entries {
+            if let ConversationEntry::ToolUse { tool_name, .. } = entry {
+                session_stats.count_tool(tool_name);
+            }
+        }
 
         let last_message = entries.iter().rev().find_map(|entry| match entry {
             ConversationEntry::AssistantText { text } => Some(text.clone()),
@@ -27,6 +27,11 @@ impl AgentProvider for GeminiProvider {
         crate::logs::resolve_gemini_session_path(tmux_name, cwd, claimed_paths).await
     }
 
+    fn log_resolution_hint(&self) -> &'static str {
+        "looked for an open ~/.gemini session file via lsof, then for the newest \
+         unclaimed session in the project's chats dir"
+    }
+
     fn refresh_cached_log_path(&self) -> bool {
         true
     }
@@ -40,7 +45,11 @@ impl AgentProvider for GeminiProvider {
     ) -> AgentLogUpdate {
         let path = PathBuf::from(log_id);
         let (entries, new_offset, last_message, gemini_stats) =
-            crate::logs::parse_gemini_session_entries(&path, offset);
+            crate::logs::parse_gemini_session_entries(
+                &path,
+                offset,
+                &session_stats.tool_categories,
+            );
         crate::logs::apply_gemini_stats(session_stats, &gemini_stats);
 
         AgentLogUpdate {
@@ -52,6 +61,8 @@ impl AgentProvider for GeminiProvider {
     }
 
     fn preferred_status_strategy(&self) -> StatusStrategy {
-        StatusStrategy::JsonlActivity
+        // The CLI titles its pane "✦ …" while responding and "◇ Ready" when
+        // idle, which updates well before the session JSON is rewritten.
+        StatusStrategy::PaneTitle
     }
 }
@@ -1,8 +1,9 @@
 use std::collections::HashSet;
+use std::path::PathBuf;
 
 use async_trait::async_trait;
 
-use crate::logs::{ConversationEntry, SessionStats};
+use crate::logs::{ConversationEntry, SessionStats, TimedEntry};
 use crate::session::AgentType;
 
 mod claude;
@@ -17,6 +18,9 @@ pub use gemini::GeminiProvider;
 pub enum StatusStrategy {
     OutputEvent,
     JsonlActivity,
+    /// Read the busy/idle marker the agent writes into its pane title, and
+    /// fall back to `JsonlActivity` when the title carries none.
+    PaneTitle,
 }
 
 #[derive(Debug, Default)]
@@ -40,6 +44,15 @@ pub trait AgentProvider: Send + Sync {
         claimed_paths: &HashSet<String>,
     ) -> Option<String>;
 
+    /// File backing a resolved log id. Most providers resolve to a path
+    /// already; Claude resolves to a session UUID under its projects dir.
+    fn log_file_path(&self, log_id: &str, _cwd: &str) -> PathBuf {
+        PathBuf::from(log_id)
+    }
+
+    /// What `resolve_log_path` tries, for error messages when it fails.
+    fn log_resolution_hint(&self) -> &'static str;
+
     /// Whether to periodically re-resolve a cached log path.
     /// Needed for providers whose backing log file can switch during runtime.
     fn refresh_cached_log_path(&self) -> bool {
@@ -54,6 +67,36 @@ pub trait AgentProvider: Send + Sync {
         session_stats: &mut SessionStats,
     ) -> AgentLogUpdate;
 
+    /// Like `update_from_log`, but keeps each new entry's log timestamp so
+    /// entries from several sessions can be interleaved. Providers whose
+    /// parser drops timestamps return `None` stamps.
+    fn update_timed_from_log(
+        &self,
+        log_id: &str,
+        cwd: &str,
+        offset: u64,
+        session_stats: &mut SessionStats,
+    ) -> (Vec<TimedEntry>, u64) {
+        let update = self.update_from_log(log_id, cwd, offset, session_stats);
+        let entries = update.entries.into_iter().map(|entry| (None, entry));
+        (entries.collect(), update.new_offset)
+    }
+
+    /// The whole conversation from the start of the log, for export.
+    fn read_full_conversation(&self, log_id: &str, cwd: &str) -> Vec<ConversationEntry> {
+        let mut stats = SessionStats::default();
+        self.update_from_log(log_id, cwd, 0, &mut stats).entries
+    }
+
+    /// The whole conversation with each entry's log timestamp, for replay.
+    /// Providers whose parser drops timestamps return `None` stamps.
+    fn read_timed_conversation(&self, log_id: &str, cwd: &str) -> Vec<TimedEntry> {
+        self.read_full_conversation(log_id, cwd)
+            .into_iter()
+            .map(|entry| (None, entry))
+            .collect()
+    }
+
     fn preferred_status_strategy(&self) -> StatusStrategy {
         StatusStrategy::OutputEvent
     }
@@ -70,3 +113,27 @@ pub fn provider_for(agent_type: &AgentType) -> &'static dyn AgentProvider {
         AgentType::Gemini => &GEMINI_PROVIDER,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claude_log_file_path_is_session_jsonl_under_projects() {
+        let uuid = "aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee";
+        let path = provider_for(&AgentType::Claude).log_file_path(uuid, "/work/proj");
+        assert_eq!(path, crate::logs::session_jsonl_path("/work/proj", uuid));
+        assert!(path.ends_with(format!(".claude/projects/-work-proj/{uuid}.jsonl")));
+    }
+
+    #[test]
+    fn path_based_providers_return_log_id_unchanged() {
+        let log = "/home/u/.codex/sessions/2026/rollout-1.jsonl";
+        for agent in [AgentType::Codex, AgentType::Gemini] {
+            assert_eq!(
+                provider_for(&agent).log_file_path(log, "/work"),
+                PathBuf::from(log)
+            );
+        }
+    }
+}
@@ -5,8 +5,11 @@ use std::time::Instant;
 use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::layout::{Position, Rect};
 
+use crate::bell::{Bell, BellTracker};
+use crate::config::{Config, ConfigChange, PreviewDensity};
 use crate::logs::{ConversationEntry, GlobalStats, SessionStats};
-use crate::session::{AgentType, Session};
+use crate::session::{scratch_name, AgentType, ProcessState, Session, VisualStatus};
+use crate::tmux::TmuxHealth;
 use crate::ui::state::{ComposeState, PreviewState};
 use crate::ui::UiLayout;
 
@@ -18,9 +21,57 @@ pub enum Mode {
     Browse,
     Compose,
     NewSessionAgent,
+    NewSessionName,
     ConfirmDelete,
+    /// Quitting with sessions still running: leave them or kill them all.
+    ConfirmQuit,
+    /// Typing a free-text note for the selected session.
+    EditNote,
+    Help,
 }
 
+/// What the preview pane shows for the selected session; `Tab` cycles it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PreviewView {
+    /// Parsed conversation from the agent's log, or the pane when there is none.
+    #[default]
+    Conversation,
+    /// Live tmux pane capture.
+    Pane,
+    /// Token, cost, file and duration details from the session's log.
+    Stats,
+    /// The agent's current TODO list from its log.
+    Todos,
+}
+
+impl PreviewView {
+    pub fn next(self) -> Self {
+        match self {
+            PreviewView::Conversation => PreviewView::Pane,
+            PreviewView::Pane => PreviewView::Stats,
+            PreviewView::Stats => PreviewView::Todos,
+            PreviewView::Todos => PreviewView::Conversation,
+        }
+    }
+}
+
+impl std::fmt::Display for PreviewView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PreviewView::Conversation => write!(f, "conversation"),
+            PreviewView::Pane => write!(f, "pane"),
+            PreviewView::Stats => write!(f, "stats"),
+            PreviewView::Todos => write!(f, "todos"),
+        }
+    }
+}
+
+/// Longest session name accepted by the new-session prompt.
+pub const MAX_SESSION_NAME_LEN: usize = 32;
+
+/// Longest note, in characters, accepted by the note prompt.
+pub const MAX_NOTE_LEN: usize = 200;
+
 #[derive(Debug, Clone)]
 struct PendingDelete {
     tmux_name: String,
@@ -32,11 +83,15 @@ struct PendingDelete {
 pub enum BackendCommand {
     CreateSession {
         agent_type: AgentType,
+        /// `None` picks the next free generated name.
+        name: Option<String>,
     },
     DeleteSession {
         tmux_name: String,
         name: String,
     },
+    /// Kill every running session and drop its record.
+    KillAllSessions,
     SendCompose {
         tmux_name: String,
         text: String,
@@ -56,6 +111,57 @@ pub enum BackendCommand {
         tmux_name: String,
         wants_scrollback: bool,
     },
+    /// Height of the preview pane, border included, so live captures fit it.
+    SetPreviewHeight(u16),
+    /// Send live pane captures as previews even for sessions with a parsed
+    /// conversation.
+    SetPanePreview(bool),
+    /// Recreate every recorded session that isn't running, e.g. after the
+    /// tmux server restarted.
+    ReviveAll,
+    /// Re-read the session's log and recapture its preview now, skipping
+    /// the refresh cadence.
+    RefreshSession {
+        tmux_name: String,
+    },
+    /// Copy `text` to the system clipboard; `what` names it in the status.
+    CopyToClipboard {
+        text: String,
+        what: &'static str,
+    },
+    /// Show only sessions with this tag (`None` shows all).
+    SetTagFilter(Option<String>),
+    /// Move a session one place up or down its status group and store the
+    /// new order in the manifest.
+    MoveSession {
+        name: String,
+        up: bool,
+    },
+    /// Run the configured `bell_command` for a session that stopped working.
+    RunBellCommand {
+        command: String,
+        name: String,
+        agent: String,
+    },
+    /// Replace a session's manifest note; blank clears it.
+    SetNote {
+        name: String,
+        note: String,
+    },
+    /// Re-read a tool result's full text from the session's log into
+    /// `StateSnapshot::tool_result_texts`.
+    ExpandToolResult {
+        tmux_name: String,
+        tool_use_id: String,
+    },
+    /// Save one changed setting to the config file.
+    SaveConfigChange(ConfigChange),
+    /// A launch check finished: why the session's agent died at launch, or
+    /// `None` if it is still running. Sent by the backend to itself.
+    LaunchChecked {
+        name: String,
+        error: Option<String>,
+    },
     Quit,
 }
 
@@ -70,6 +176,22 @@ pub struct StateSnapshot {
     pub diff_files: Vec<DiffFile>,
     pub conversations: HashMap<String, VecDeque<ConversationEntry>>,
     pub status_message: Option<String>,
+    /// Manifest tags by session name (all sessions, not just visible ones).
+    pub session_tags: HashMap<String, Vec<String>>,
+    /// Active tag filter; `sessions` only contains matching sessions.
+    pub tag_filter: Option<String>,
+    /// Manifest notes by session name.
+    pub session_notes: HashMap<String, String>,
+    /// Why sessions died right after launch, by session name.
+    pub launch_errors: HashMap<String, String>,
+    /// Resolved agent log file path by tmux session name.
+    pub log_paths: HashMap<String, String>,
+    /// Output of the configured status script by tmux session name.
+    pub status_annotations: HashMap<String, String>,
+    /// Full text of recently expanded tool results by tool-call id.
+    pub tool_result_texts: HashMap<String, String>,
+    /// Whether recorded sessions are missing from tmux, and why.
+    pub tmux_health: TmuxHealth,
 }
 
 /// Preview data sent from Backend → UI.
@@ -100,23 +222,52 @@ pub struct UiApp {
     pub selected: usize,
     pub mode: Mode,
     pub agent_selection: usize,
+    /// Name typed in `Mode::NewSessionName`; blank means auto-generate.
+    pub new_session_name: String,
+    /// Note typed in `Mode::EditNote`, for the session in `note_target`.
+    pub note_input: String,
+    note_target: Option<String>,
     pub should_quit: bool,
     pub preview: PreviewState,
+    pub preview_view: PreviewView,
     pub compose: ComposeState,
     compose_states: HashMap<String, ComposeState>,
     compose_target_tmux: Option<String>,
     compose_target_name: Option<String>,
     compose_target_missing: bool,
     pending_delete: Option<PendingDelete>,
+    /// Name of a session created with `C`, selected once a snapshot
+    /// includes it.
+    select_on_create: Option<String>,
+    /// Tmux name of the session the preview is pinned to; `None` follows
+    /// the selection.
+    pub pinned_preview: Option<String>,
     pub mouse_captured: bool,
     pub needs_redraw: bool,
+    /// Set when the terminal bell should ring; the event loop writes BEL
+    /// and clears it.
+    pub ring_bell: bool,
+    bell: BellTracker,
     pub diff_scroll_offset: u16,
+    /// Manual session-list viewport offset, in list items (group headers
+    /// included). `None` follows the selection; Ctrl-D / Ctrl-U set it and
+    /// moving the selection clears it.
+    pub list_scroll: Option<usize>,
+    pub help_scroll: u16,
+    /// UI ticks so far, driving the working-session spinner.
+    pub spinner_tick: u64,
     pub diff_tree_cache: (Vec<DiffFile>, usize, Vec<ratatui::text::Line<'static>>),
     pub terminal_size: (u16, u16),
+    /// Layout for `terminal_size`, cached for mouse hit-testing.
+    /// Recomputed by `handle_resize()`.
+    pub layout: UiLayout,
+    pub config: Config,
 
     // Preview cache (session → latest PreviewUpdate)
     preview_cache: HashMap<String, PreviewUpdate>,
     requested_preview: Option<String>,
+    /// Preview height last reported to the backend for live captures.
+    sent_preview_height: Option<u16>,
 
     // Channels
     cmd_tx: tokio::sync::mpsc::Sender<BackendCommand>,
@@ -137,21 +288,35 @@ impl UiApp {
             selected: 0,
             mode: Mode::Browse,
             agent_selection: 0,
+            new_session_name: String::new(),
+            note_input: String::new(),
+            note_target: None,
             should_quit: false,
             preview: PreviewState::new(),
+            preview_view: PreviewView::default(),
             compose: ComposeState::new(),
             compose_states: HashMap::new(),
             compose_target_tmux: None,
             compose_target_name: None,
             compose_target_missing: false,
             pending_delete: None,
+            select_on_create: None,
+            pinned_preview: None,
             mouse_captured: true,
             needs_redraw: true,
+            ring_bell: false,
+            bell: BellTracker::default(),
             diff_scroll_offset: 0,
+            list_scroll: None,
+            help_scroll: 0,
+            spinner_tick: 0,
             diff_tree_cache: (Vec::new(), 0, Vec::new()),
             terminal_size: (80, 24),
+            layout: crate::ui::compute_layout(Rect::new(0, 0, 80, 24)),
+            config: Config::default(),
             preview_cache: HashMap::new(),
             requested_preview: None,
+            sent_preview_height: None,
             cmd_tx,
             state_rx,
             preview_rx,
@@ -192,6 +357,12 @@ impl UiApp {
             if self.requested_preview.as_deref() == Some(update.tmux_name.as_str()) {
                 self.requested_preview = None;
             }
+            // Sent before the backend switched to pane captures.
+            if self.preview_view == PreviewView::Pane
+                && matches!(update.data, PreviewData::Conversation(_))
+            {
+                continue;
+            }
             self.preview_cache.insert(update.tmux_name.clone(), update);
             got_preview = true;
         }
@@ -200,6 +371,7 @@ impl UiApp {
         }
 
         self.refresh_preview_from_cache();
+        self.advance_spinner();
 
         // Auto-clear status messages after 5 seconds
         if let Some(set_at) = self.status_message_set_at {
@@ -210,12 +382,46 @@ impl UiApp {
         }
     }
 
+    /// Step the spinner one tick, redrawing when a working session's glyph
+    /// changes.
+    fn advance_spinner(&mut self) {
+        if self.config.disable_spinner {
+            return;
+        }
+        let before = crate::ui::spinner_frame(self.spinner_tick);
+        self.spinner_tick = self.spinner_tick.wrapping_add(1);
+        if crate::ui::spinner_frame(self.spinner_tick) != before
+            && self
+                .snapshot
+                .sessions
+                .iter()
+                .any(|s| matches!(s.visual_status(), VisualStatus::Running(_)))
+        {
+            self.needs_redraw = true;
+        }
+    }
+
     fn apply_snapshot(&mut self, snapshot: Arc<StateSnapshot>) {
-        let previous_selected_tmux = self
-            .snapshot
-            .sessions
-            .get(self.selected)
-            .map(|session| session.tmux_name.clone());
+        let created_tmux = self.select_on_create.as_deref().and_then(|name| {
+            snapshot
+                .sessions
+                .iter()
+                .find(|session| session.name == name)
+                .map(|session| session.tmux_name.clone())
+        });
+        let previous_selected_tmux = if created_tmux.is_some() {
+            self.select_on_create = None;
+            self.list_scroll = None;
+            if self.pinned_preview.is_none() {
+                self.preview.reset_on_selection_change();
+            }
+            created_tmux
+        } else {
+            self.snapshot
+                .sessions
+                .get(self.selected)
+                .map(|session| session.tmux_name.clone())
+        };
 
         // Only accept backend status when it has a new message.
         // Let the timer handle clearing (don't let backend's None stomp local messages).
@@ -226,6 +432,38 @@ impl UiApp {
         }
         self.snapshot = snapshot;
         self.prune_non_live_state(previous_selected_tmux.as_deref());
+        self.check_bell();
+    }
+
+    /// Ring the configured bell when the session shown in the preview
+    /// stops working.
+    fn check_bell(&mut self) {
+        let Some(bell) = self.config.bell() else {
+            return;
+        };
+        let tmux_name = self.active_preview_tmux();
+        let session = tmux_name
+            .as_deref()
+            .and_then(|t| self.snapshot.sessions.iter().find(|s| s.tmux_name == t));
+        let status = session.map(Session::visual_status);
+        let who = session.map(|s| (s.name.clone(), s.agent_type.to_string().to_lowercase()));
+        if !self
+            .bell
+            .observe(tmux_name.as_deref(), status.as_ref(), Instant::now())
+        {
+            return;
+        }
+        match (bell, who) {
+            (Bell::Terminal, _) => self.ring_bell = true,
+            (Bell::Command(command), Some((name, agent))) => {
+                self.queue_command(BackendCommand::RunBellCommand {
+                    command,
+                    name,
+                    agent,
+                });
+            }
+            (Bell::Command(_), None) => {}
+        }
     }
 
     fn prune_non_live_state(&mut self, previous_selected_tmux: Option<&str>) {
@@ -244,7 +482,12 @@ impl UiApp {
                 .pending_delete
                 .as_ref()
                 .map(|target| target.tmux_name.as_str()),
-            Mode::Browse | Mode::NewSessionAgent => previous_selected_tmux,
+            Mode::Browse
+            | Mode::NewSessionAgent
+            | Mode::NewSessionName
+            | Mode::ConfirmQuit
+            | Mode::EditNote
+            | Mode::Help => previous_selected_tmux,
         };
 
         if let Some(tmux_name) = preferred_tmux {
@@ -276,6 +519,16 @@ impl UiApp {
             }
         }
 
+        if self
+            .pinned_preview
+            .as_ref()
+            .is_some_and(|tmux_name| !live_keys.contains(tmux_name))
+        {
+            self.pinned_preview = None;
+            self.preview.reset_on_selection_change();
+            self.set_status("Pinned session is gone; preview follows the selection".to_string());
+        }
+
         if self.mode == Mode::Compose {
             if let Some(target_tmux) = self.compose_target_tmux.as_deref() {
                 if live_keys.contains(target_tmux) {
@@ -328,12 +581,33 @@ impl UiApp {
     fn apply_preview_update(&mut self, update: &PreviewUpdate) {
         match &update.data {
             PreviewData::Conversation(entries) => {
-                let text = crate::ui::render_conversation(entries);
+                // Inner width: the preview block has a border on each side.
+                let width = self.layout.preview.width.saturating_sub(2) as usize;
+                let view = crate::ui::ToolResultView {
+                    selected: self.preview.tool_cursor,
+                    expanded: Some(&self.preview.expanded_tool_results),
+                    texts: Some(&self.snapshot.tool_result_texts),
+                };
+                let (text, cursor_line) = crate::ui::render_conversation_with_view(
+                    entries,
+                    self.config.preview_density,
+                    Some(width),
+                    &self.config.currency(),
+                    &view,
+                );
+                // Keep the selected tool result on the same screen row as
+                // results expand and new output arrives.
+                let cursor_row = self.tool_cursor_row();
                 self.preview.line_count = text.lines.len() as u16;
                 self.preview.text = Some(text);
                 self.preview.content.clear();
+                self.preview.tool_cursor_line = cursor_line;
+                if let (Some(row), Some(line)) = (cursor_row, cursor_line) {
+                    self.set_preview_top(line.saturating_sub(row));
+                }
             }
             PreviewData::PaneCapture(content) => {
+                self.preview.tool_cursor_line = None;
                 self.preview.line_count = content.lines().count().min(u16::MAX as usize) as u16;
                 self.preview.text = ansi_to_tui::IntoText::into_text(content).ok();
                 self.preview.content = content.clone();
@@ -341,6 +615,125 @@ impl UiApp {
         }
     }
 
+    /// First visible preview line, the last possible one, and the
+    /// preview's inner height. `scroll_offset` counts up from the bottom.
+    fn preview_viewport(&self) -> (usize, usize, usize) {
+        let height = self.layout.preview.height.saturating_sub(2) as usize;
+        let max_top = (self.preview.line_count as usize).saturating_sub(height);
+        let top = max_top.saturating_sub(self.preview.scroll_offset as usize);
+        (top, max_top, height)
+    }
+
+    fn set_preview_top(&mut self, top: usize) {
+        let (_, max_top, _) = self.preview_viewport();
+        self.preview.scroll_offset = (max_top - top.min(max_top)) as u16;
+    }
+
+    /// Screen row of the selected tool result's title, if it is visible.
+    fn tool_cursor_row(&self) -> Option<usize> {
+        let line = self.preview.tool_cursor_line?;
+        let (top, _, height) = self.preview_viewport();
+        (top..top + height).contains(&line).then(|| line - top)
+    }
+
+    /// Scroll just far enough to show the selected tool result's title.
+    fn scroll_to_tool_cursor(&mut self) {
+        let Some(line) = self.preview.tool_cursor_line else {
+            return;
+        };
+        if self.tool_cursor_row().is_some() {
+            return;
+        }
+        let (top, _, height) = self.preview_viewport();
+        let top = if line < top {
+            line
+        } else {
+            (line + 1).saturating_sub(height)
+        };
+        self.set_preview_top(top);
+    }
+
+    /// Tmux name and tool-call ids (see `tool_result_ids`) of the
+    /// conversation in the preview, or why tool results can't be selected.
+    fn previewed_tool_results(&self) -> Result<(String, Vec<Option<String>>), &'static str> {
+        if self.preview_view != PreviewView::Conversation {
+            return Err("Tool results are selectable in the conversation view");
+        }
+        let tmux_name = self.active_preview_tmux().ok_or("No sessions")?;
+        let Some(PreviewData::Conversation(entries)) = self
+            .preview_cache
+            .get(&tmux_name)
+            .map(|update| &update.data)
+        else {
+            return Err("No conversation to select from");
+        };
+        let ids = crate::ui::tool_result_ids(entries, self.config.preview_density);
+        if ids.is_empty() {
+            return Err(match self.config.preview_density {
+                PreviewDensity::Compact => "Compact preview hides tool results (v for detailed)",
+                PreviewDensity::Detailed => "No tool results in this conversation",
+            });
+        }
+        Ok((tmux_name, ids))
+    }
+
+    /// Move the tool-result cursor to an older (`[`) or newer (`]`)
+    /// result. The first press selects the newest.
+    fn move_tool_cursor(&mut self, older: bool) {
+        let count = match self.previewed_tool_results() {
+            Ok((_, ids)) => ids.len(),
+            Err(msg) => {
+                self.set_status(msg.to_string());
+                return;
+            }
+        };
+        let next = match self.preview.tool_cursor.map(|i| i.min(count - 1)) {
+            None => count - 1,
+            Some(i) if older => i.saturating_sub(1),
+            Some(i) => (i + 1).min(count - 1),
+        };
+        self.preview.tool_cursor = Some(next);
+        self.refresh_preview_from_cache();
+        self.scroll_to_tool_cursor();
+    }
+
+    /// Expand the selected tool result to its full text, re-read from the
+    /// log, or collapse it if it is already expanded.
+    fn toggle_tool_result(&mut self) {
+        let (tmux_name, ids) = match self.previewed_tool_results() {
+            Ok(found) => found,
+            Err(msg) => {
+                self.set_status(msg.to_string());
+                return;
+            }
+        };
+        let Some(cursor) = self.preview.tool_cursor else {
+            self.set_status("Select a tool result with [ or ] first".to_string());
+            return;
+        };
+        let Some(Some(tool_use_id)) = ids.get(cursor).cloned() else {
+            self.set_status("This tool result can't be looked up in the log".to_string());
+            return;
+        };
+        if !self.preview.expanded_tool_results.remove(&tool_use_id) {
+            if !self.snapshot.tool_result_texts.contains_key(&tool_use_id) {
+                self.queue_command(BackendCommand::ExpandToolResult {
+                    tmux_name,
+                    tool_use_id: tool_use_id.clone(),
+                });
+            }
+            self.preview.expanded_tool_results.insert(tool_use_id);
+            self.refresh_preview_from_cache();
+            // Show as much of the full text as fits below the title.
+            if let Some(line) = self.preview.tool_cursor_line {
+                self.set_preview_top(line);
+            }
+            return;
+        }
+        self.refresh_preview_from_cache();
+        self.scroll_to_tool_cursor();
+    }
+
     fn clear_preview(&mut self) {
         self.preview.text = None;
         self.preview.content.clear();
@@ -350,11 +743,18 @@ impl UiApp {
     fn active_preview_tmux(&self) -> Option<String> {
         match self.mode {
             Mode::Compose => self.compose_target_tmux.clone(),
-            Mode::Browse | Mode::NewSessionAgent | Mode::ConfirmDelete => self
-                .snapshot
-                .sessions
-                .get(self.selected)
-                .map(|s| s.tmux_name.clone()),
+            Mode::Browse
+            | Mode::NewSessionAgent
+            | Mode::NewSessionName
+            | Mode::ConfirmDelete
+            | Mode::ConfirmQuit
+            | Mode::EditNote
+            | Mode::Help => self.pinned_preview.clone().or_else(|| {
+                self.snapshot
+                    .sessions
+                    .get(self.selected)
+                    .map(|s| s.tmux_name.clone())
+            }),
         }
     }
 
@@ -372,10 +772,28 @@ impl UiApp {
             return self.compose_target_name.as_deref();
         }
 
-        self.snapshot
-            .sessions
-            .get(self.selected)
-            .map(|session| session.name.as_str())
+        match self.pinned_preview.as_deref() {
+            Some(tmux_name) => self
+                .snapshot
+                .sessions
+                .iter()
+                .find(|session| session.tmux_name == tmux_name),
+            None => self.snapshot.sessions.get(self.selected),
+        }
+        .map(|session| session.name.as_str())
+    }
+
+    /// Log-derived stats for the session currently shown in the preview.
+    pub fn active_preview_stats(&self) -> Option<&SessionStats> {
+        let tmux_name = self.active_preview_tmux()?;
+        self.snapshot.session_stats.get(&tmux_name)
+    }
+
+    /// Tmux names of sessions that resolved to the same log file as another
+    /// session. Their previews and stats are mixed together, so the sidebar
+    /// flags them.
+    pub fn shared_log_sessions(&self) -> HashSet<String> {
+        shared_log_sessions(&self.snapshot.log_paths)
     }
 
     pub fn confirm_delete_target_name(&self) -> Option<&str> {
@@ -422,36 +840,67 @@ impl UiApp {
             Mode::Browse => self.handle_browse_key(key),
             Mode::Compose => self.handle_compose_key(key),
             Mode::NewSessionAgent => self.handle_agent_select_key(key.code),
+            Mode::NewSessionName => self.handle_session_name_key(key.code),
             Mode::ConfirmDelete => self.handle_confirm_delete_key(key.code),
+            Mode::ConfirmQuit => self.handle_confirm_quit_key(key.code),
+            Mode::EditNote => self.handle_note_key(key.code),
+            Mode::Help => self.handle_help_key(key.code),
         }
     }
 
-    /// Handle a bracketed paste event. Only active in Compose mode.
+    /// Handle a bracketed paste event. Only active in Compose mode and the
+    /// note prompt.
     pub fn handle_paste(&mut self, text: String) {
-        if self.mode == Mode::Compose {
-            self.compose.insert_text(&text);
-            self.needs_redraw = true;
+        match self.mode {
+            Mode::Compose => self.compose.insert_text(&text),
+            Mode::EditNote => {
+                for c in text.chars() {
+                    self.push_note_char(if c.is_control() { ' ' } else { c });
+                }
+            }
+            _ => return,
         }
+        self.needs_redraw = true;
     }
 
     fn handle_browse_key(&mut self, key: KeyEvent) {
         use crossterm::event::KeyModifiers;
         match key.code {
-            KeyCode::Char('q') => {
-                self.queue_command(BackendCommand::Quit);
-                self.should_quit = true;
-            }
+            KeyCode::Char('q') => self.request_quit(),
             KeyCode::Up if key.modifiers.contains(KeyModifiers::SHIFT) => {
                 self.diff_scroll_offset = self.diff_scroll_offset.saturating_add(1);
             }
             KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => {
                 self.diff_scroll_offset = self.diff_scroll_offset.saturating_sub(1);
             }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.scroll_list_half_page(true);
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.scroll_list_half_page(false);
+            }
             KeyCode::Char('j') | KeyCode::Down => self.select_next(),
             KeyCode::Char('k') | KeyCode::Up => self.select_prev(),
             KeyCode::Enter => self.enter_compose(),
             KeyCode::Char('n') => self.start_new_session(),
+            KeyCode::Char('C') => self.create_scratch_session(),
+            KeyCode::Char('N') => self.start_edit_note(),
             KeyCode::Char('d') => self.request_delete(),
+            KeyCode::Char('t') => self.toggle_timestamp_mode(),
+            KeyCode::Char('v') => self.toggle_preview_density(),
+            KeyCode::Tab => self.cycle_preview_view(),
+            KeyCode::Char('f') => self.cycle_tag_filter(),
+            KeyCode::Char('y') => self.copy_log_path(),
+            KeyCode::Char('M') => self.copy_stats_markdown(),
+            KeyCode::Char('R') => self.refresh_selected(),
+            KeyCode::Char('V') => self.revive_all(),
+            KeyCode::Char('p') => self.toggle_pin_preview(),
+            KeyCode::Char('K') => self.move_selected(true),
+            KeyCode::Char('J') => self.move_selected(false),
+            KeyCode::Char('[') => self.move_tool_cursor(true),
+            KeyCode::Char(']') => self.move_tool_cursor(false),
+            KeyCode::Char('x') => self.toggle_tool_result(),
+            KeyCode::Char('?') => self.open_help(),
             KeyCode::Char('c') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.mouse_captured = !self.mouse_captured;
             }
@@ -471,6 +920,87 @@ impl UiApp {
         }
     }
 
+    /// Move the selected session one place up or down its group. The
+    /// selection follows it when the reordered snapshot arrives.
+    fn move_selected(&mut self, up: bool) {
+        let Some(session) = self.snapshot.sessions.get(self.selected) else {
+            self.set_status("No sessions".to_string());
+            return;
+        };
+        let name = session.name.clone();
+        self.queue_command(BackendCommand::MoveSession { name, up });
+    }
+
+    /// Move the session-list viewport half a page without changing the
+    /// selection. Starts from wherever the list currently shows.
+    fn scroll_list_half_page(&mut self, down: bool) {
+        let items = crate::ui::session_list_items(self);
+        let heights: Vec<usize> = items.iter().map(|(h, _)| *h).collect();
+        let list_area = crate::ui::session_list_area(self, self.layout.sidebar);
+        let viewport = list_area.height.saturating_sub(2) as usize;
+        let current = self.list_scroll.unwrap_or_else(|| {
+            let selected_item = items
+                .iter()
+                .position(|(_, s)| *s == Some(self.selected))
+                .unwrap_or(0);
+            follow_list_offset(&heights, selected_item, viewport)
+        });
+        let step = (viewport / 2).max(1) as isize;
+        let delta = if down { step } else { -step };
+        self.list_scroll = Some(scroll_list(current, delta, &heights, viewport));
+    }
+
+    fn toggle_timestamp_mode(&mut self) {
+        self.config.timestamp_mode = self.config.timestamp_mode.toggled();
+        self.queue_command(BackendCommand::SaveConfigChange(
+            ConfigChange::TimestampMode(self.config.timestamp_mode),
+        ));
+        self.set_status(format!("Timestamps: {}", self.config.timestamp_mode));
+    }
+
+    fn toggle_preview_density(&mut self) {
+        self.config.preview_density = self.config.preview_density.toggled();
+        self.queue_command(BackendCommand::SaveConfigChange(
+            ConfigChange::PreviewDensity(self.config.preview_density),
+        ));
+        self.set_status(format!("Preview: {}", self.config.preview_density));
+        self.refresh_preview_from_cache();
+    }
+
+    /// Step the preview through conversation → pane → stats. Cached
+    /// previews are dropped when the backend switches between parsed
+    /// conversations and pane captures.
+    fn cycle_preview_view(&mut self) {
+        let was_pane = self.preview_view == PreviewView::Pane;
+        self.preview_view = self.preview_view.next();
+        let is_pane = self.preview_view == PreviewView::Pane;
+        if was_pane != is_pane {
+            self.queue_command(BackendCommand::SetPanePreview(is_pane));
+            self.preview_cache.clear();
+            self.requested_preview = None;
+        }
+        self.preview.scroll_to_bottom();
+        self.set_status(format!("Preview: {}", self.preview_view));
+        self.refresh_preview_from_cache();
+    }
+
+    /// Step the tag filter through: all sessions → each tag (sorted) → all.
+    fn cycle_tag_filter(&mut self) {
+        let mut tags: Vec<&String> = self.snapshot.session_tags.values().flatten().collect();
+        tags.sort();
+        tags.dedup();
+        if tags.is_empty() && self.snapshot.tag_filter.is_none() {
+            self.set_status("No tagged sessions (use `hydra tag`)".to_string());
+            return;
+        }
+        let next = next_tag_filter(&tags, self.snapshot.tag_filter.as_deref());
+        match &next {
+            Some(tag) => self.set_status(format!("Filter: #{tag}")),
+            None => self.set_status("Filter: all sessions".to_string()),
+        }
+        self.queue_command(BackendCommand::SetTagFilter(next));
+    }
+
     fn handle_compose_key(&mut self, key: KeyEvent) {
         use crossterm::event::KeyModifiers;
         match key.code {
@@ -586,13 +1116,8 @@ impl UiApp {
     fn handle_agent_select_key(&mut self, code: KeyCode) {
         match code {
             KeyCode::Enter => {
-                let agents = AgentType::all();
-                if let Some(agent_type) = agents.get(self.agent_selection) {
-                    self.queue_command(BackendCommand::CreateSession {
-                        agent_type: agent_type.clone(),
-                    });
-                }
-                self.mode = Mode::Browse;
+                self.new_session_name.clear();
+                self.mode = Mode::NewSessionName;
             }
             KeyCode::Esc => self.cancel_mode(),
             KeyCode::Char('j') | KeyCode::Down => self.agent_select_next(),
@@ -601,6 +1126,114 @@ impl UiApp {
         }
     }
 
+    fn handle_session_name_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.confirm_new_session(),
+            KeyCode::Esc => self.cancel_mode(),
+            KeyCode::Backspace => {
+                self.new_session_name.pop();
+            }
+            KeyCode::Char(c)
+                if is_session_name_char(c)
+                    && self.new_session_name.len() < MAX_SESSION_NAME_LEN =>
+            {
+                self.new_session_name.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    /// Queue creation of the picked agent under the typed name. A name that
+    /// is already taken keeps the prompt open so it can be edited.
+    fn confirm_new_session(&mut self) {
+        let Some(agent_type) = AgentType::all().get(self.agent_selection).cloned() else {
+            self.cancel_mode();
+            return;
+        };
+        let name = std::mem::take(&mut self.new_session_name);
+        if self.snapshot.sessions.iter().any(|s| s.name == name) {
+            self.set_status(format!("Session '{name}' already exists"));
+            self.new_session_name = name;
+            return;
+        }
+        let names: Vec<String> = self
+            .snapshot
+            .sessions
+            .iter()
+            .map(|s| s.name.clone())
+            .collect();
+        if let Some(other) = crate::session::tmux_name_clash(&name, &names) {
+            self.set_status(format!("Session name '{name}' clashes with '{other}'"));
+            self.new_session_name = name;
+            return;
+        }
+        self.queue_command(BackendCommand::CreateSession {
+            agent_type,
+            name: (!name.is_empty()).then_some(name),
+        });
+        self.mode = Mode::Browse;
+    }
+
+    /// Open the note prompt for the selected session, prefilled with its
+    /// current note.
+    fn start_edit_note(&mut self) {
+        let Some(session) = self.snapshot.sessions.get(self.selected) else {
+            self.set_status("No sessions".to_string());
+            return;
+        };
+        let name = session.name.clone();
+        self.note_input = self
+            .snapshot
+            .session_notes
+            .get(&name)
+            .cloned()
+            .unwrap_or_default();
+        self.note_target = Some(name);
+        self.mode = Mode::EditNote;
+    }
+
+    fn handle_note_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => {
+                if let Some(name) = self.note_target.take() {
+                    let note = std::mem::take(&mut self.note_input);
+                    self.queue_command(BackendCommand::SetNote { name, note });
+                }
+                self.mode = Mode::Browse;
+            }
+            KeyCode::Esc => self.cancel_mode(),
+            KeyCode::Backspace => {
+                self.note_input.pop();
+            }
+            KeyCode::Char(c) => self.push_note_char(c),
+            _ => {}
+        }
+    }
+
+    fn push_note_char(&mut self, c: char) {
+        if self.note_input.chars().count() < MAX_NOTE_LEN {
+            self.note_input.push(c);
+        }
+    }
+
+    /// Name of the session whose note is being edited.
+    pub fn note_target_name(&self) -> Option<&str> {
+        self.note_target.as_deref()
+    }
+
+    fn handle_help_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('?') | KeyCode::Char('q') => self.cancel_mode(),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.help_scroll = self.help_scroll.saturating_add(1);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.help_scroll = self.help_scroll.saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+
     fn handle_confirm_delete_key(&mut self, code: KeyCode) {
         match code {
             KeyCode::Char('y') => {
@@ -627,15 +1260,79 @@ impl UiApp {
         }
     }
 
+    /// Quit, asking first whether to leave or kill the sessions still
+    /// running unless there are none or `skip_quit_confirm` is set.
+    pub fn request_quit(&mut self) {
+        if self.config.skip_quit_confirm || self.running_sessions().next().is_none() {
+            self.quit();
+        } else {
+            self.mode = Mode::ConfirmQuit;
+        }
+    }
+
+    fn quit(&mut self) {
+        self.queue_command(BackendCommand::Quit);
+        self.should_quit = true;
+    }
+
+    /// Sessions with a tmux session behind them, i.e. all but stopped ones.
+    pub fn running_sessions(&self) -> impl Iterator<Item = &Session> {
+        self.snapshot
+            .sessions
+            .iter()
+            .filter(|s| s.process_state != ProcessState::Stopped)
+    }
+
+    fn handle_confirm_quit_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('l') | KeyCode::Char('q') | KeyCode::Enter => self.quit(),
+            KeyCode::Char('K') => {
+                // Queued ahead of Quit, so the backend kills before it stops.
+                self.queue_command(BackendCommand::KillAllSessions);
+                self.quit();
+            }
+            KeyCode::Esc => self.cancel_mode(),
+            _ => {}
+        }
+    }
+
+    /// Show the newly selected session in the preview, unless the preview
+    /// is pinned to another one.
+    fn preview_selected(&mut self) {
+        if self.pinned_preview.is_some() {
+            return;
+        }
+        self.preview.reset_on_selection_change();
+        self.refresh_preview_from_cache();
+        if let Some(session) = self.snapshot.sessions.get(self.selected) {
+            let tmux_name = session.tmux_name.clone();
+            self.request_preview(&tmux_name, false);
+        }
+    }
+
+    /// Pin the preview to the selected session, or unpin it.
+    pub fn toggle_pin_preview(&mut self) {
+        if let Some(pinned) = self.pinned_preview.take() {
+            if self.active_preview_tmux() != Some(pinned) {
+                self.preview_selected();
+            }
+            self.set_status("Unpinned preview".to_string());
+            return;
+        }
+        let Some(session) = self.snapshot.sessions.get(self.selected) else {
+            self.set_status("No sessions".to_string());
+            return;
+        };
+        let msg = format!("Pinned preview to '{}'", session.name);
+        self.pinned_preview = Some(session.tmux_name.clone());
+        self.set_status(msg);
+    }
+
     pub fn select_next(&mut self) {
         if !self.snapshot.sessions.is_empty() {
             self.selected = (self.selected + 1) % self.snapshot.sessions.len();
-            self.preview.reset_on_selection_change();
-            self.refresh_preview_from_cache();
-            if let Some(session) = self.snapshot.sessions.get(self.selected) {
-                let tmux_name = session.tmux_name.clone();
-                self.request_preview(&tmux_name, false);
-            }
+            self.list_scroll = None;
+            self.preview_selected();
         }
     }
 
@@ -646,12 +1343,8 @@ impl UiApp {
             } else {
                 self.selected - 1
             };
-            self.preview.reset_on_selection_change();
-            self.refresh_preview_from_cache();
-            if let Some(session) = self.snapshot.sessions.get(self.selected) {
-                let tmux_name = session.tmux_name.clone();
-                self.request_preview(&tmux_name, false);
-            }
+            self.list_scroll = None;
+            self.preview_selected();
         }
     }
 
@@ -661,6 +1354,13 @@ impl UiApp {
             return;
         }
         if let Some(session) = self.snapshot.sessions.get(self.selected) {
+            if session.process_state == ProcessState::Stopped {
+                let name = session.name.clone();
+                self.set_status(format!(
+                    "'{name}' is stopped. Run `hydra resume {name}` to restart it."
+                ));
+                return;
+            }
             let tmux_name = session.tmux_name.clone();
             self.compose = self
                 .compose_states
@@ -687,12 +1387,91 @@ impl UiApp {
     pub fn start_new_session(&mut self) {
         self.mode = Mode::NewSessionAgent;
         self.agent_selection = 0;
+        self.new_session_name.clear();
         self.clear_status();
     }
 
-    pub fn request_delete(&mut self) {
-        if self.snapshot.sessions.is_empty() {
-            self.set_status("No sessions to delete".to_string());
+    /// Create a Claude session under a fresh `scratch-<timestamp>` name,
+    /// without prompting, and select it once it appears.
+    pub fn create_scratch_session(&mut self) {
+        let existing: Vec<String> = self
+            .snapshot
+            .sessions
+            .iter()
+            .map(|s| s.name.clone())
+            .collect();
+        let name = scratch_name(&existing, chrono::Local::now().naive_local());
+        self.queue_command(BackendCommand::CreateSession {
+            agent_type: AgentType::Claude,
+            name: Some(name.clone()),
+        });
+        self.select_on_create = Some(name);
+        self.clear_status();
+    }
+
+    /// Copy the selected session's resolved log file path to the clipboard.
+    pub fn copy_log_path(&mut self) {
+        let Some(session) = self.snapshot.sessions.get(self.selected) else {
+            self.set_status("No sessions".to_string());
+            return;
+        };
+        match self.snapshot.log_paths.get(&session.tmux_name) {
+            Some(path) => {
+                let text = path.clone();
+                self.queue_command(BackendCommand::CopyToClipboard {
+                    text,
+                    what: "log path",
+                });
+            }
+            None => {
+                let msg = format!("No log file resolved for '{}' yet", session.name);
+                self.set_status(msg);
+            }
+        }
+    }
+
+    /// Copy the visible sessions' stats as a Markdown table.
+    pub fn copy_stats_markdown(&mut self) {
+        if self.snapshot.sessions.is_empty() {
+            self.set_status("No sessions".to_string());
+            return;
+        }
+        let text = crate::stats_markdown::render(
+            &self.snapshot.sessions,
+            &self.snapshot.session_stats,
+            chrono::Utc::now(),
+            &self.config.currency(),
+        );
+        self.queue_command(BackendCommand::CopyToClipboard {
+            text,
+            what: "stats table",
+        });
+    }
+
+    pub fn refresh_selected(&mut self) {
+        let Some(session) = self.snapshot.sessions.get(self.selected) else {
+            self.set_status("No sessions".to_string());
+            return;
+        };
+        let tmux_name = session.tmux_name.clone();
+        let msg = format!("Refreshed '{}'", session.name);
+        self.queue_command(BackendCommand::RefreshSession { tmux_name });
+        self.set_status(msg);
+    }
+
+    pub fn revive_all(&mut self) {
+        self.queue_command(BackendCommand::ReviveAll);
+        self.set_status("Reviving recorded sessions…".to_string());
+    }
+
+    pub fn open_help(&mut self) {
+        self.mode = Mode::Help;
+        self.help_scroll = 0;
+    }
+
+    pub fn request_delete(&mut self) {
+        if self.snapshot.sessions.is_empty() {
+            self.set_status("No sessions to delete".to_string());
             return;
         }
         if let Some(session) = self.snapshot.sessions.get(self.selected) {
@@ -709,13 +1488,29 @@ impl UiApp {
         if self.mode == Mode::ConfirmDelete {
             self.pending_delete = None;
         }
+        if self.mode == Mode::EditNote {
+            self.note_target = None;
+            self.note_input.clear();
+        }
         self.mode = Mode::Browse;
     }
 
+    /// Apply a terminal resize: recompute the cached layout and any
+    /// width-dependent caches so the next frame and mouse hit-tests match.
+    pub fn handle_resize(&mut self, width: u16, height: u16) {
+        self.terminal_size = (width, height);
+        self.layout = crate::ui::compute_layout(Rect::new(0, 0, width, height));
+        let preview_height = self.layout.preview.height;
+        if self.sent_preview_height != Some(preview_height) {
+            self.sent_preview_height = Some(preview_height);
+            self.queue_command(BackendCommand::SetPreviewHeight(preview_height));
+        }
+        self.update_diff_tree();
+        self.needs_redraw = true;
+    }
+
     pub fn update_diff_tree(&mut self) {
-        let area = ratatui::layout::Rect::new(0, 0, self.terminal_size.0, self.terminal_size.1);
-        let layout = crate::ui::compute_layout(area);
-        let sidebar_width = layout.sidebar.width;
+        let sidebar_width = self.layout.sidebar.width;
         let width = sidebar_width.saturating_sub(2) as usize;
         let files = &self.snapshot.diff_files;
         if self.diff_tree_cache.0 != *files || self.diff_tree_cache.1 != width {
@@ -768,26 +1563,18 @@ impl UiApp {
                     let sidebar_inner = inner(sidebar);
                     if sidebar_inner.contains(pos) {
                         let row_offset = (mouse.row - sidebar_inner.y) as usize;
+                        let items = crate::ui::session_list_items(self);
+                        let heights: Vec<usize> = items.iter().map(|(h, _)| *h).collect();
+                        let viewport =
+                            inner(crate::ui::session_list_area(self, sidebar)).height as usize;
+                        let first = self
+                            .list_scroll
+                            .map_or(0, |offset| offset.min(max_list_scroll(&heights, viewport)));
                         let mut cumulative = 0usize;
                         let mut target_idx = None;
-                        let mut current_group: Option<u8> = None;
-                        for (i, session) in self.snapshot.sessions.iter().enumerate() {
-                            let group = session.sort_order();
-                            if current_group != Some(group) {
-                                current_group = Some(group);
-                                if row_offset == cumulative {
-                                    break;
-                                }
-                                cumulative += 1;
-                            }
-                            let item_height =
-                                if self.snapshot.last_messages.contains_key(&session.tmux_name) {
-                                    2
-                                } else {
-                                    1
-                                };
+                        for &(item_height, session_idx) in &items[first.min(items.len())..] {
                             if row_offset < cumulative + item_height {
-                                target_idx = Some(i);
+                                target_idx = session_idx;
                                 break;
                             }
                             cumulative += item_height;
@@ -795,12 +1582,8 @@ impl UiApp {
                         if let Some(idx) = target_idx {
                             if self.selected != idx {
                                 self.selected = idx;
-                                self.preview.reset_on_selection_change();
-                                self.refresh_preview_from_cache();
-                                if let Some(session) = self.snapshot.sessions.get(self.selected) {
-                                    let tmux_name = session.tmux_name.clone();
-                                    self.request_preview(&tmux_name, false);
-                                }
+                                self.list_scroll = None;
+                                self.preview_selected();
                             }
                         }
                     } else if preview.contains(pos) {
@@ -824,22 +1607,15 @@ impl UiApp {
                 _ => {}
             },
             Mode::Compose => match mouse.kind {
-                MouseEventKind::ScrollUp => {
-                    if preview.contains(pos) {
-                        self.scroll_preview_up();
-                    }
+                MouseEventKind::ScrollUp if preview.contains(pos) => {
+                    self.scroll_preview_up();
                 }
-                MouseEventKind::ScrollDown => {
-                    if preview.contains(pos) {
-                        self.scroll_preview_down();
-                    }
+                MouseEventKind::ScrollDown if preview.contains(pos) => {
+                    self.scroll_preview_down();
                 }
-                MouseEventKind::Down(MouseButton::Left) => {
-                    if inner(preview).contains(pos) {
-                        self.preview.scroll_offset = 0;
-                    }
+                MouseEventKind::Down(MouseButton::Left) if inner(preview).contains(pos) => {
+                    self.preview.scroll_offset = 0;
                 }
-                MouseEventKind::Down(_) => {}
                 _ => {}
             },
             _ => {}
@@ -860,6 +1636,73 @@ impl UiApp {
     }
 }
 
+/// Keys of `log_paths` whose path is also the value of another key.
+pub(crate) fn shared_log_sessions(log_paths: &HashMap<String, String>) -> HashSet<String> {
+    let mut by_path: HashMap<&str, Vec<&String>> = HashMap::new();
+    for (session, path) in log_paths {
+        by_path.entry(path.as_str()).or_default().push(session);
+    }
+    by_path
+        .into_values()
+        .filter(|sessions| sessions.len() > 1)
+        .flatten()
+        .cloned()
+        .collect()
+}
+
+/// Largest list offset that still fills the viewport; scrolling further would
+/// only reveal empty rows below the last item.
+pub(crate) fn max_list_scroll(heights: &[usize], viewport: usize) -> usize {
+    let mut rows = 0;
+    for (i, height) in heights.iter().enumerate().rev() {
+        rows += height;
+        if rows > viewport {
+            return i + 1;
+        }
+    }
+    0
+}
+
+/// First visible item when the list follows `selected`, matching how a
+/// fresh ratatui `ListState` scrolls to keep the selection on screen.
+fn follow_list_offset(heights: &[usize], selected: usize, viewport: usize) -> usize {
+    let Some(visible) = heights.get(..=selected) else {
+        return 0;
+    };
+    let mut rows: usize = visible.iter().sum();
+    let mut offset = 0;
+    while rows > viewport && offset < selected {
+        rows -= heights[offset];
+        offset += 1;
+    }
+    offset
+}
+
+/// Move a list offset by `delta` items, clamped to `0..=max_list_scroll`.
+fn scroll_list(offset: usize, delta: isize, heights: &[usize], viewport: usize) -> usize {
+    offset
+        .saturating_add_signed(delta)
+        .min(max_list_scroll(heights, viewport))
+}
+
+/// Characters allowed in a session name typed in the TUI. tmux treats `.`
+/// and `:` as target separators, so names stick to a safe subset.
+fn is_session_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+/// The filter after `current` when cycling through sorted `tags`.
+fn next_tag_filter(tags: &[&String], current: Option<&str>) -> Option<String> {
+    let next = match current {
+        None => tags.first(),
+        Some(current) => tags
+            .iter()
+            .position(|t| t.as_str() == current)
+            .and_then(|i| tags.get(i + 1)),
+    };
+    next.map(|t| t.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1054,6 +1897,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn enter_on_stopped_session_points_at_resume() {
+        let (mut app, _cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![Session::stopped("test", "alpha", AgentType::Codex)];
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(app.mode, Mode::Browse);
+        assert!(app.compose_target_tmux.is_none());
+        assert!(app
+            .status_message
+            .as_ref()
+            .is_some_and(|msg| msg.contains("hydra resume alpha")));
+    }
+
     #[test]
     fn compose_shift_enter_inserts_newline() {
         let (mut app, _cmd_rx) = make_app();
@@ -1280,6 +2138,498 @@ mod tests {
             .is_some_and(|msg| msg.contains("No sessions")));
     }
 
+    // ── New session flow ─────────────────────────────────────────────
+
+    fn press(app: &mut UiApp, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn type_text(app: &mut UiApp, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn agent_enter_moves_to_name_prompt() {
+        let (mut app, mut cmd_rx) = make_app();
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(app.mode, Mode::NewSessionAgent);
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.mode, Mode::NewSessionName);
+        assert_eq!(app.agent_selection, 1);
+        assert!(app.new_session_name.is_empty());
+        assert!(cmd_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn name_prompt_creates_session_with_typed_name() {
+        let (mut app, mut cmd_rx) = make_app();
+        press(&mut app, KeyCode::Char('n'));
+        press(&mut app, KeyCode::Enter);
+        type_text(&mut app, "fix-bugx");
+        press(&mut app, KeyCode::Backspace);
+        assert_eq!(app.new_session_name, "fix-bug");
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.mode, Mode::Browse);
+        match cmd_rx.try_recv() {
+            Ok(BackendCommand::CreateSession { agent_type, name }) => {
+                assert_eq!(agent_type, AgentType::all()[0]);
+                assert_eq!(name.as_deref(), Some("fix-bug"));
+            }
+            other => panic!("expected CreateSession, got {other:?}"),
+        }
+        assert!(app.new_session_name.is_empty());
+    }
+
+    #[test]
+    fn scratch_key_creates_claude_session_and_selects_it() {
+        let (mut app, mut cmd_rx) = make_app();
+        let alpha = make_named_session("alpha", "hydra-test-alpha", AgentType::Codex);
+        app.snapshot_mut().sessions = vec![alpha.clone()];
+        press(&mut app, KeyCode::Char('C'));
+
+        assert_eq!(app.mode, Mode::Browse);
+        let name = match cmd_rx.try_recv() {
+            Ok(BackendCommand::CreateSession {
+                agent_type: AgentType::Claude,
+                name: Some(name),
+            }) => name,
+            other => panic!("expected CreateSession, got {other:?}"),
+        };
+        assert!(name.starts_with("scratch-"), "{name}");
+
+        let scratch = make_named_session(&name, &format!("hydra-test-{name}"), AgentType::Claude);
+        app.apply_full_snapshot(&StateSnapshot {
+            sessions: vec![alpha.clone(), scratch],
+            ..StateSnapshot::default()
+        });
+        assert_eq!(app.snapshot.sessions[app.selected].name, name);
+
+        // Only the first snapshot with the session moves the selection.
+        app.select_prev();
+        app.apply_full_snapshot(&app.snapshot.as_ref().clone());
+        assert_eq!(app.snapshot.sessions[app.selected].name, "alpha");
+    }
+
+    #[test]
+    fn note_key_edits_the_selected_sessions_note() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![make_session(AgentType::Claude)];
+        app.snapshot_mut().session_notes =
+            HashMap::from([("alpha".to_string(), "exploring".to_string())]);
+        press(&mut app, KeyCode::Char('N'));
+        assert_eq!(app.mode, Mode::EditNote);
+        assert_eq!(app.note_input, "exploring");
+        assert_eq!(app.note_target_name(), Some("alpha"));
+
+        type_text(&mut app, " approach B");
+        press(&mut app, KeyCode::Backspace);
+        app.handle_paste("B\n".to_string());
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.mode, Mode::Browse);
+        match cmd_rx.try_recv() {
+            Ok(BackendCommand::SetNote { name, note }) => {
+                assert_eq!(name, "alpha");
+                assert_eq!(note, "exploring approach B ");
+            }
+            other => panic!("expected SetNote, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn note_prompt_escape_discards_the_edit() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![make_session(AgentType::Claude)];
+        press(&mut app, KeyCode::Char('N'));
+        assert_eq!(app.note_input, "");
+        type_text(&mut app, &"x".repeat(MAX_NOTE_LEN + 5));
+        assert_eq!(app.note_input.chars().count(), MAX_NOTE_LEN);
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.mode, Mode::Browse);
+        assert!(app.note_input.is_empty());
+        assert_eq!(app.note_target_name(), None);
+        assert!(cmd_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn note_key_without_sessions_stays_in_browse() {
+        let (mut app, _cmd_rx) = make_app();
+        press(&mut app, KeyCode::Char('N'));
+        assert_eq!(app.mode, Mode::Browse);
+    }
+
+    #[test]
+    fn quit_without_running_sessions_exits_at_once() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![Session::stopped("test", "parked", AgentType::Claude)];
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.should_quit);
+        assert!(matches!(cmd_rx.try_recv(), Ok(BackendCommand::Quit)));
+    }
+
+    #[test]
+    fn quit_with_running_sessions_asks_first() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![make_session(AgentType::Claude)];
+        press(&mut app, KeyCode::Char('q'));
+        assert_eq!(app.mode, Mode::ConfirmQuit);
+        assert!(!app.should_quit);
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.mode, Mode::Browse);
+        assert!(!app.should_quit);
+
+        press(&mut app, KeyCode::Char('q'));
+        press(&mut app, KeyCode::Char('l'));
+        assert!(app.should_quit);
+        assert!(matches!(cmd_rx.try_recv(), Ok(BackendCommand::Quit)));
+        assert!(cmd_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn kill_all_on_quit_kills_before_quitting() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![make_session(AgentType::Claude)];
+        press(&mut app, KeyCode::Char('q'));
+        press(&mut app, KeyCode::Char('K'));
+        assert!(app.should_quit);
+        assert!(matches!(
+            cmd_rx.try_recv(),
+            Ok(BackendCommand::KillAllSessions)
+        ));
+        assert!(matches!(cmd_rx.try_recv(), Ok(BackendCommand::Quit)));
+    }
+
+    #[test]
+    fn skip_quit_confirm_exits_at_once() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.config.skip_quit_confirm = true;
+        app.snapshot_mut().sessions = vec![make_session(AgentType::Claude)];
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.should_quit);
+        assert!(matches!(cmd_rx.try_recv(), Ok(BackendCommand::Quit)));
+    }
+
+    #[test]
+    fn blank_name_requests_generated_name() {
+        let (mut app, mut cmd_rx) = make_app();
+        press(&mut app, KeyCode::Char('n'));
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+        assert!(matches!(
+            cmd_rx.try_recv(),
+            Ok(BackendCommand::CreateSession { name: None, .. })
+        ));
+    }
+
+    #[test]
+    fn name_prompt_filters_unsafe_chars_and_caps_length() {
+        let (mut app, _cmd_rx) = make_app();
+        press(&mut app, KeyCode::Char('n'));
+        press(&mut app, KeyCode::Enter);
+        type_text(&mut app, "a.b:c d_e/");
+        assert_eq!(app.new_session_name, "abcd_e");
+
+        type_text(&mut app, &"x".repeat(MAX_SESSION_NAME_LEN));
+        assert_eq!(app.new_session_name.len(), MAX_SESSION_NAME_LEN);
+    }
+
+    #[test]
+    fn name_prompt_rejects_existing_name() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![make_session(AgentType::Claude)];
+        press(&mut app, KeyCode::Char('n'));
+        press(&mut app, KeyCode::Enter);
+        type_text(&mut app, "alpha");
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.mode, Mode::NewSessionName);
+        assert_eq!(app.new_session_name, "alpha");
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Session 'alpha' already exists")
+        );
+        assert!(cmd_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn name_prompt_rejects_name_sharing_a_tmux_session() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![make_named_session(
+            "a.b",
+            "hydra-test-a_b",
+            AgentType::Claude,
+        )];
+        press(&mut app, KeyCode::Char('n'));
+        press(&mut app, KeyCode::Enter);
+        type_text(&mut app, "a_b");
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.mode, Mode::NewSessionName);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Session name 'a_b' clashes with 'a.b'")
+        );
+        assert!(cmd_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn name_prompt_esc_cancels_and_next_prompt_starts_blank() {
+        let (mut app, mut cmd_rx) = make_app();
+        press(&mut app, KeyCode::Char('n'));
+        press(&mut app, KeyCode::Enter);
+        type_text(&mut app, "draft");
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.mode, Mode::Browse);
+        assert!(cmd_rx.try_recv().is_err());
+
+        press(&mut app, KeyCode::Char('n'));
+        press(&mut app, KeyCode::Enter);
+        assert!(app.new_session_name.is_empty());
+    }
+
+    #[test]
+    fn copy_log_path_queues_resolved_path() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![make_session(AgentType::Claude)];
+        app.snapshot_mut().log_paths.insert(
+            "hydra-test-alpha".to_string(),
+            "/home/u/.claude/projects/-work/abc.jsonl".to_string(),
+        );
+        press(&mut app, KeyCode::Char('y'));
+        match cmd_rx.try_recv() {
+            Ok(BackendCommand::CopyToClipboard { text, what }) => {
+                assert_eq!(text, "/home/u/.claude/projects/-work/abc.jsonl");
+                assert_eq!(what, "log path");
+            }
+            other => panic!("expected CopyToClipboard, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn copy_log_path_reports_unresolved_log() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![make_session(AgentType::Codex)];
+        press(&mut app, KeyCode::Char('y'));
+        assert!(cmd_rx.try_recv().is_err());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No log file resolved for 'alpha' yet")
+        );
+    }
+
+    #[test]
+    fn copy_stats_markdown_queues_table_for_visible_sessions() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![make_session(AgentType::Claude)];
+        press(&mut app, KeyCode::Char('M'));
+        match cmd_rx.try_recv() {
+            Ok(BackendCommand::CopyToClipboard { text, what }) => {
+                assert!(text.starts_with("| Session | Status |"));
+                assert!(text.contains("\n| alpha | "));
+                assert_eq!(what, "stats table");
+            }
+            other => panic!("expected CopyToClipboard, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn copy_stats_markdown_without_sessions_reports_it() {
+        let (mut app, mut cmd_rx) = make_app();
+        press(&mut app, KeyCode::Char('M'));
+        assert!(cmd_rx.try_recv().is_err());
+        assert_eq!(app.status_message.as_deref(), Some("No sessions"));
+    }
+
+    #[test]
+    fn refresh_key_queues_refresh_for_selected_session() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![make_session(AgentType::Claude)];
+        press(&mut app, KeyCode::Char('R'));
+        match cmd_rx.try_recv() {
+            Ok(BackendCommand::RefreshSession { tmux_name }) => {
+                assert_eq!(tmux_name, "hydra-test-alpha");
+            }
+            other => panic!("expected RefreshSession, got {other:?}"),
+        }
+        assert_eq!(app.status_message.as_deref(), Some("Refreshed 'alpha'"));
+    }
+
+    #[test]
+    fn refresh_key_without_sessions_reports_it() {
+        let (mut app, mut cmd_rx) = make_app();
+        press(&mut app, KeyCode::Char('R'));
+        assert!(cmd_rx.try_recv().is_err());
+        assert_eq!(app.status_message.as_deref(), Some("No sessions"));
+    }
+
+    fn app_with_two_sessions() -> (UiApp, tokio::sync::mpsc::Receiver<BackendCommand>) {
+        let (mut app, cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![
+            make_named_session("alpha", "hydra-test-alpha", AgentType::Claude),
+            make_named_session("bravo", "hydra-test-bravo", AgentType::Codex),
+        ];
+        (app, cmd_rx)
+    }
+
+    #[test]
+    fn pinned_preview_ignores_selection_changes() {
+        let (mut app, mut cmd_rx) = app_with_two_sessions();
+        press(&mut app, KeyCode::Char('p'));
+        assert_eq!(app.pinned_preview.as_deref(), Some("hydra-test-alpha"));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Pinned preview to 'alpha'")
+        );
+        app.preview.set_text("alpha output".to_string());
+
+        app.select_next();
+        assert_eq!(app.selected, 1);
+        assert_eq!(
+            app.active_preview_tmux().as_deref(),
+            Some("hydra-test-alpha")
+        );
+        assert_eq!(app.active_preview_name(), Some("alpha"));
+        assert!(app.preview.text.is_some());
+        assert!(cmd_rx.try_recv().is_err());
+
+        app.select_prev();
+        app.select_prev();
+        assert_eq!(
+            app.active_preview_tmux().as_deref(),
+            Some("hydra-test-alpha")
+        );
+        assert!(cmd_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn unpinning_returns_the_preview_to_the_selection() {
+        let (mut app, mut cmd_rx) = app_with_two_sessions();
+        press(&mut app, KeyCode::Char('p'));
+        app.select_next();
+        press(&mut app, KeyCode::Char('p'));
+        assert_eq!(app.pinned_preview, None);
+        assert_eq!(app.status_message.as_deref(), Some("Unpinned preview"));
+        assert_eq!(
+            app.active_preview_tmux().as_deref(),
+            Some("hydra-test-bravo")
+        );
+        match cmd_rx.try_recv() {
+            Ok(BackendCommand::RequestPreview { tmux_name, .. }) => {
+                assert_eq!(tmux_name, "hydra-test-bravo");
+            }
+            other => panic!("expected RequestPreview, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn pin_is_dropped_when_its_session_goes_away() {
+        let (mut app, _cmd_rx) = app_with_two_sessions();
+        press(&mut app, KeyCode::Char('p'));
+        app.select_next();
+        app.apply_snapshot(Arc::new(StateSnapshot {
+            sessions: vec![make_named_session(
+                "bravo",
+                "hydra-test-bravo",
+                AgentType::Codex,
+            )],
+            ..StateSnapshot::default()
+        }));
+        assert_eq!(app.pinned_preview, None);
+        assert_eq!(
+            app.active_preview_tmux().as_deref(),
+            Some("hydra-test-bravo")
+        );
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Pinned session is gone; preview follows the selection")
+        );
+    }
+
+    #[test]
+    fn bell_rings_when_the_selected_session_stops_working() {
+        let (mut app, mut cmd_rx) = make_app();
+        let mut working = make_session(AgentType::Claude);
+        working.agent_state = crate::session::AgentState::Thinking;
+        let snapshot_with = |session: Session| StateSnapshot {
+            sessions: vec![session],
+            ..StateSnapshot::default()
+        };
+
+        // Off by default.
+        app.apply_full_snapshot(&snapshot_with(working.clone()));
+        app.apply_full_snapshot(&snapshot_with(make_session(AgentType::Claude)));
+        assert!(!app.ring_bell);
+
+        app.config.bell = true;
+        app.apply_full_snapshot(&snapshot_with(working.clone()));
+        assert!(!app.ring_bell);
+        app.apply_full_snapshot(&snapshot_with(make_session(AgentType::Claude)));
+        assert!(app.ring_bell);
+        assert!(cmd_rx.try_recv().is_err());
+
+        // A command replaces the terminal bell; the debounce is per app.
+        let (mut app, mut cmd_rx) = make_app();
+        app.config.bell_command = Some("say done".to_string());
+        app.apply_full_snapshot(&snapshot_with(working));
+        let mut waiting = make_session(AgentType::Claude);
+        waiting.agent_state = crate::session::AgentState::WaitingForInput;
+        app.apply_full_snapshot(&snapshot_with(waiting));
+        assert!(!app.ring_bell);
+        match cmd_rx.try_recv() {
+            Ok(BackendCommand::RunBellCommand {
+                command,
+                name,
+                agent,
+            }) => {
+                assert_eq!(command, "say done");
+                assert_eq!(name, "alpha");
+                assert_eq!(agent, "claude");
+            }
+            other => panic!("expected RunBellCommand, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn shift_j_and_k_queue_moves_and_the_selection_follows() {
+        let (mut app, mut cmd_rx) = app_with_two_sessions();
+        press(&mut app, KeyCode::Char('J'));
+        assert!(matches!(
+            cmd_rx.try_recv(),
+            Ok(BackendCommand::MoveSession { name, up: false }) if name == "alpha"
+        ));
+        app.apply_snapshot(Arc::new(StateSnapshot {
+            sessions: vec![
+                make_named_session("bravo", "hydra-test-bravo", AgentType::Codex),
+                make_named_session("alpha", "hydra-test-alpha", AgentType::Claude),
+            ],
+            ..StateSnapshot::default()
+        }));
+        assert_eq!(app.selected, 1);
+
+        press(&mut app, KeyCode::Char('K'));
+        assert!(matches!(
+            cmd_rx.try_recv(),
+            Ok(BackendCommand::MoveSession { name, up: true }) if name == "alpha"
+        ));
+    }
+
+    #[test]
+    fn revive_key_queues_revive_all() {
+        let (mut app, mut cmd_rx) = make_app();
+        press(&mut app, KeyCode::Char('V'));
+        assert!(matches!(cmd_rx.try_recv(), Ok(BackendCommand::ReviveAll)));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Reviving recorded sessions…")
+        );
+    }
+
     // ── Feature 3: Status auto-clear ─────────────────────────────────
 
     #[test]
@@ -1455,4 +2805,541 @@ mod tests {
         app.enter_compose();
         assert_eq!(app.compose.history.len(), 1);
     }
+
+    #[test]
+    fn f_cycles_tag_filter_through_sorted_tags() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().session_tags = HashMap::from([
+            ("alpha".to_string(), vec!["infra".to_string()]),
+            (
+                "bravo".to_string(),
+                vec!["api".to_string(), "infra".to_string()],
+            ),
+        ]);
+
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            app.handle_key(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE));
+            match cmd_rx.try_recv() {
+                Ok(BackendCommand::SetTagFilter(tag)) => {
+                    app.snapshot_mut().tag_filter = tag.clone();
+                    seen.push(tag);
+                }
+                other => panic!("expected SetTagFilter, got {other:?}"),
+            }
+        }
+        assert_eq!(
+            seen,
+            [Some("api".to_string()), Some("infra".to_string()), None]
+        );
+    }
+
+    #[test]
+    fn shared_log_sessions_flags_every_claimant_of_a_path() {
+        let paths: HashMap<String, String> = [
+            ("hydra-p-a", "/logs/one.json"),
+            ("hydra-p-b", "/logs/one.json"),
+            ("hydra-p-c", "/logs/two.json"),
+            ("hydra-p-d", "/logs/one.json"),
+            ("hydra-p-e", "/logs/three.json"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let mut shared: Vec<String> = shared_log_sessions(&paths).into_iter().collect();
+        shared.sort();
+        assert_eq!(shared, ["hydra-p-a", "hydra-p-b", "hydra-p-d"]);
+        assert!(shared_log_sessions(&HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn max_list_scroll_keeps_last_page_full() {
+        // 10 one-row items in a 4-row viewport: items 6..=9 fill the last page.
+        assert_eq!(max_list_scroll(&[1; 10], 4), 6);
+        // Everything fits: no scrolling.
+        assert_eq!(max_list_scroll(&[1; 3], 4), 0);
+        assert_eq!(max_list_scroll(&[], 4), 0);
+        // Two-row items: the last two (4 rows) fill the viewport.
+        assert_eq!(max_list_scroll(&[1, 2, 2, 2], 4), 2);
+    }
+
+    #[test]
+    fn scroll_list_clamps_to_bounds() {
+        let heights = [1; 10];
+        assert_eq!(scroll_list(0, 2, &heights, 4), 2);
+        assert_eq!(scroll_list(5, 2, &heights, 4), 6);
+        assert_eq!(scroll_list(1, -2, &heights, 4), 0);
+        assert_eq!(scroll_list(0, 2, &[1; 3], 4), 0);
+    }
+
+    #[test]
+    fn follow_list_offset_keeps_selection_visible() {
+        let heights = [1; 10];
+        assert_eq!(follow_list_offset(&heights, 2, 4), 0);
+        assert_eq!(follow_list_offset(&heights, 7, 4), 4);
+        assert_eq!(follow_list_offset(&[1, 2, 2, 2], 3, 4), 2);
+        assert_eq!(follow_list_offset(&heights, 20, 4), 0);
+    }
+
+    #[test]
+    fn ctrl_d_scrolls_list_without_changing_selection() {
+        let (mut app, _cmd_rx) = make_app();
+        app.handle_resize(80, 12);
+        app.snapshot_mut().sessions = (0..20)
+            .map(|i| {
+                let name = format!("s{i:02}");
+                make_named_session(&name, &format!("hydra-test-{name}"), AgentType::Claude)
+            })
+            .collect();
+
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        app.handle_key(ctrl('d'));
+        let first = app.list_scroll.expect("manual scroll");
+        assert!(first > 0);
+        assert_eq!(app.selected, 0);
+
+        for _ in 0..20 {
+            app.handle_key(ctrl('d'));
+        }
+        let bottom = app.list_scroll.unwrap();
+        app.handle_key(ctrl('d'));
+        assert_eq!(app.list_scroll, Some(bottom), "clamped at the end");
+
+        for _ in 0..20 {
+            app.handle_key(ctrl('u'));
+        }
+        assert_eq!(app.list_scroll, Some(0));
+        assert_eq!(app.selected, 0);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert_eq!(
+            app.list_scroll, None,
+            "moving the selection follows it again"
+        );
+    }
+
+    #[test]
+    fn f_without_tags_sets_status_only() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.handle_key(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE));
+        assert!(cmd_rx.try_recv().is_err());
+        assert!(app.status_message.unwrap().contains("hydra tag"));
+    }
+
+    #[test]
+    fn t_toggles_timestamp_mode_and_saves_config() {
+        use crate::session::TimestampMode;
+
+        let (mut app, mut cmd_rx) = make_app();
+        assert_eq!(app.config.timestamp_mode, TimestampMode::Absolute);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+        assert_eq!(app.config.timestamp_mode, TimestampMode::Relative);
+        match cmd_rx.try_recv() {
+            Ok(BackendCommand::SaveConfigChange(change)) => {
+                assert_eq!(change, ConfigChange::TimestampMode(TimestampMode::Relative));
+            }
+            other => panic!("expected SaveConfigChange, got {other:?}"),
+        }
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+        assert_eq!(app.config.timestamp_mode, TimestampMode::Absolute);
+    }
+
+    #[test]
+    fn v_toggles_preview_density_and_saves_config() {
+        use crate::config::PreviewDensity;
+
+        let (mut app, mut cmd_rx) = make_app();
+        assert_eq!(app.config.preview_density, PreviewDensity::Detailed);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE));
+        assert_eq!(app.config.preview_density, PreviewDensity::Compact);
+        match cmd_rx.try_recv() {
+            Ok(BackendCommand::SaveConfigChange(change)) => {
+                assert_eq!(
+                    change,
+                    ConfigChange::PreviewDensity(PreviewDensity::Compact)
+                );
+            }
+            other => panic!("expected SaveConfigChange, got {other:?}"),
+        }
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE));
+        assert_eq!(app.config.preview_density, PreviewDensity::Detailed);
+    }
+
+    #[test]
+    fn tab_cycles_preview_view() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().sessions = vec![make_session(AgentType::Claude)];
+        assert_eq!(app.preview_view, PreviewView::Conversation);
+        let tab = || KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+
+        app.handle_key(tab());
+        assert_eq!(app.preview_view, PreviewView::Pane);
+        assert_eq!(app.status_message.as_deref(), Some("Preview: pane"));
+        assert!(matches!(
+            cmd_rx.try_recv(),
+            Ok(BackendCommand::SetPanePreview(true))
+        ));
+        assert!(matches!(
+            cmd_rx.try_recv(),
+            Ok(BackendCommand::RequestPreview { .. })
+        ));
+
+        app.handle_key(tab());
+        assert_eq!(app.preview_view, PreviewView::Stats);
+        assert!(matches!(
+            cmd_rx.try_recv(),
+            Ok(BackendCommand::SetPanePreview(false))
+        ));
+
+        // Stats, todos and conversation all use parsed previews: nothing to resend.
+        while cmd_rx.try_recv().is_ok() {}
+        app.handle_key(tab());
+        assert_eq!(app.preview_view, PreviewView::Todos);
+        app.handle_key(tab());
+        assert_eq!(app.preview_view, PreviewView::Conversation);
+        assert!(!std::iter::from_fn(|| cmd_rx.try_recv().ok())
+            .any(|cmd| matches!(cmd, BackendCommand::SetPanePreview(_))));
+    }
+
+    #[test]
+    fn pane_view_ignores_conversation_previews() {
+        let (cmd_tx, _cmd_rx) = tokio::sync::mpsc::channel(8);
+        let (_state_tx, state_rx) = tokio::sync::watch::channel(Arc::new(StateSnapshot::default()));
+        let (preview_tx, preview_rx) = tokio::sync::mpsc::channel(8);
+        let mut app = UiApp::new(state_rx, preview_rx, cmd_tx);
+        let session = make_session(AgentType::Claude);
+        app.snapshot_mut().sessions = vec![session.clone()];
+        app.preview_view = PreviewView::Pane;
+
+        for data in [
+            PreviewData::PaneCapture("$ pane".to_string()),
+            PreviewData::Conversation(VecDeque::new()),
+        ] {
+            preview_tx
+                .try_send(PreviewUpdate {
+                    tmux_name: session.tmux_name.clone(),
+                    data,
+                    has_scrollback: false,
+                })
+                .unwrap();
+        }
+        app.poll_state();
+        assert_eq!(app.preview.content, "$ pane");
+    }
+
+    fn tool_result(id: Option<&str>, summary: &str) -> ConversationEntry {
+        ConversationEntry::ToolResult {
+            filenames: Vec::new(),
+            summary: Some(summary.to_string()),
+            tool_use_id: id.map(str::to_string),
+        }
+    }
+
+    /// App previewing a conversation with three tool results, the last
+    /// without a tool-call id.
+    fn app_with_tool_results() -> (UiApp, tokio::sync::mpsc::Receiver<BackendCommand>) {
+        let (mut app, cmd_rx) = make_app();
+        let session = make_session(AgentType::Claude);
+        app.snapshot_mut().sessions = vec![session.clone()];
+        let entries = VecDeque::from([
+            ConversationEntry::UserMessage {
+                text: "run it".to_string(),
+            },
+            tool_result(Some("toolu_1"), "first"),
+            tool_result(Some("toolu_2"), "second"),
+            tool_result(None, "third"),
+        ]);
+        app.preview_cache.insert(
+            session.tmux_name.clone(),
+            PreviewUpdate {
+                tmux_name: session.tmux_name,
+                data: PreviewData::Conversation(entries),
+                has_scrollback: false,
+            },
+        );
+        app.refresh_preview_from_cache();
+        (app, cmd_rx)
+    }
+
+    fn preview_lines(app: &UiApp) -> Vec<String> {
+        app.preview
+            .text
+            .as_ref()
+            .unwrap()
+            .lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn brackets_move_tool_cursor_from_the_newest_result() {
+        let (mut app, _cmd_rx) = app_with_tool_results();
+        press(&mut app, KeyCode::Char(']'));
+        assert_eq!(app.preview.tool_cursor, Some(2));
+        for expected in [1, 0, 0] {
+            press(&mut app, KeyCode::Char('['));
+            assert_eq!(app.preview.tool_cursor, Some(expected));
+        }
+        press(&mut app, KeyCode::Char(']'));
+        assert_eq!(app.preview.tool_cursor, Some(1));
+        assert!(preview_lines(&app).contains(&"▶ TOOL RESULT".to_string()));
+
+        app.select_next();
+        assert_eq!(app.preview.tool_cursor, None);
+    }
+
+    #[test]
+    fn x_expands_and_collapses_the_selected_tool_result() {
+        let (mut app, mut cmd_rx) = app_with_tool_results();
+        press(&mut app, KeyCode::Char('['));
+        press(&mut app, KeyCode::Char('['));
+        press(&mut app, KeyCode::Char('x'));
+        match cmd_rx.try_recv() {
+            Ok(BackendCommand::ExpandToolResult {
+                tmux_name,
+                tool_use_id,
+            }) => {
+                assert_eq!(tmux_name, "hydra-test-alpha");
+                assert_eq!(tool_use_id, "toolu_2");
+            }
+            other => panic!("expected ExpandToolResult, got {other:?}"),
+        }
+        assert!(app.preview.expanded_tool_results.contains("toolu_2"));
+        assert!(preview_lines(&app).contains(&"  > (loading full result…)".to_string()));
+
+        app.snapshot_mut()
+            .tool_result_texts
+            .insert("toolu_2".to_string(), "full\noutput".to_string());
+        app.refresh_preview_from_cache();
+        let lines = preview_lines(&app);
+        assert!(lines.contains(&"  > full".to_string()));
+        assert!(lines.contains(&"  > output".to_string()));
+        assert!(!lines.contains(&"  > second".to_string()));
+
+        press(&mut app, KeyCode::Char('x'));
+        assert!(app.preview.expanded_tool_results.is_empty());
+        assert!(cmd_rx.try_recv().is_err());
+        assert!(preview_lines(&app).contains(&"  > second".to_string()));
+
+        // Text already read doesn't need another trip to the log.
+        press(&mut app, KeyCode::Char('x'));
+        assert!(cmd_rx.try_recv().is_err());
+        assert!(preview_lines(&app).contains(&"  > full".to_string()));
+    }
+
+    #[test]
+    fn x_explains_when_nothing_can_be_expanded() {
+        let (mut app, mut cmd_rx) = app_with_tool_results();
+        press(&mut app, KeyCode::Char('x'));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Select a tool result with [ or ] first")
+        );
+
+        press(&mut app, KeyCode::Char(']'));
+        press(&mut app, KeyCode::Char('x'));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("This tool result can't be looked up in the log")
+        );
+
+        app.config.preview_density = PreviewDensity::Compact;
+        press(&mut app, KeyCode::Char('['));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Compact preview hides tool results (v for detailed)")
+        );
+        assert!(cmd_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn spinner_redraws_only_for_working_sessions() {
+        let (mut app, _cmd_rx) = make_app();
+        let mut session = make_session(AgentType::Claude);
+        app.snapshot_mut().sessions = vec![session.clone()];
+        app.needs_redraw = false;
+        for _ in 0..4 {
+            app.advance_spinner();
+        }
+        assert!(!app.needs_redraw);
+
+        session.agent_state = crate::session::AgentState::Thinking;
+        app.snapshot_mut().sessions = vec![session];
+        app.advance_spinner();
+        app.advance_spinner();
+        assert!(app.needs_redraw);
+
+        app.needs_redraw = false;
+        app.config.disable_spinner = true;
+        let tick = app.spinner_tick;
+        for _ in 0..4 {
+            app.advance_spinner();
+        }
+        assert_eq!(app.spinner_tick, tick);
+        assert!(!app.needs_redraw);
+    }
+
+    #[test]
+    fn question_mark_opens_and_closes_help() {
+        let (mut app, _cmd_rx) = make_app();
+        app.handle_key(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE));
+        assert_eq!(app.mode, Mode::Help);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert_eq!(app.help_scroll, 1);
+
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.mode, Mode::Browse);
+    }
+
+    /// Feed every plausible key to each non-text-entry mode and require that
+    /// any key with an observable effect has an entry in `KEYBINDINGS`.
+    /// Compose mode is excluded: every printable key inserts text there.
+    #[test]
+    fn every_handled_key_is_documented() {
+        use crate::keybindings::is_documented;
+
+        fn fingerprint(
+            app: &UiApp,
+            cmd_rx: &mut tokio::sync::mpsc::Receiver<BackendCommand>,
+        ) -> String {
+            let queued = cmd_rx.try_recv().is_ok();
+            format!(
+                "{:?}|{:?}|{}|{}|{}|{}|{:?}|{:?}|{}|{}|{}|{}",
+                app.mode,
+                app.preview_view,
+                app.selected,
+                app.agent_selection,
+                app.should_quit,
+                app.mouse_captured,
+                app.status_message,
+                app.config,
+                app.preview.scroll_offset,
+                app.diff_scroll_offset,
+                app.help_scroll,
+                queued,
+            )
+        }
+
+        fn app_in_mode(mode: &Mode) -> (UiApp, tokio::sync::mpsc::Receiver<BackendCommand>) {
+            let (mut app, mut cmd_rx) = make_app();
+            app.snapshot_mut().sessions = vec![
+                make_named_session("alpha", "hydra-test-alpha", AgentType::Claude),
+                make_named_session("bravo", "hydra-test-bravo", AgentType::Codex),
+            ];
+            app.preview.scroll_offset = 5;
+            app.diff_scroll_offset = 5;
+            app.help_scroll = 5;
+            match mode {
+                Mode::Browse => {}
+                Mode::NewSessionAgent => app.start_new_session(),
+                Mode::NewSessionName => {
+                    app.start_new_session();
+                    app.handle_key(KeyEvent::from(KeyCode::Enter));
+                }
+                Mode::ConfirmDelete => app.request_delete(),
+                Mode::ConfirmQuit => app.request_quit(),
+                Mode::EditNote => app.start_edit_note(),
+                Mode::Help => {
+                    app.open_help();
+                    app.help_scroll = 5;
+                }
+                Mode::Compose => unreachable!(),
+            }
+            while cmd_rx.try_recv().is_ok() {}
+            (app, cmd_rx)
+        }
+
+        let mut codes: Vec<KeyCode> = (' '..='~').map(KeyCode::Char).collect();
+        codes.extend([
+            KeyCode::Enter,
+            KeyCode::Esc,
+            KeyCode::Tab,
+            KeyCode::BackTab,
+            KeyCode::Backspace,
+            KeyCode::Delete,
+            KeyCode::Insert,
+            KeyCode::Up,
+            KeyCode::Down,
+            KeyCode::Left,
+            KeyCode::Right,
+            KeyCode::Home,
+            KeyCode::End,
+            KeyCode::PageUp,
+            KeyCode::PageDown,
+        ]);
+        codes.extend((1..=12).map(KeyCode::F));
+        let modifiers = [
+            KeyModifiers::NONE,
+            KeyModifiers::SHIFT,
+            KeyModifiers::CONTROL,
+            KeyModifiers::ALT,
+        ];
+
+        for mode in [
+            Mode::Browse,
+            Mode::NewSessionAgent,
+            Mode::NewSessionName,
+            Mode::ConfirmDelete,
+            Mode::ConfirmQuit,
+            Mode::EditNote,
+            Mode::Help,
+        ] {
+            for &code in &codes {
+                for &mods in &modifiers {
+                    let (mut app, mut cmd_rx) = app_in_mode(&mode);
+                    let before = fingerprint(&app, &mut cmd_rx);
+                    app.handle_key(KeyEvent::new(code, mods));
+                    let after = fingerprint(&app, &mut cmd_rx);
+                    if before != after {
+                        assert!(
+                            is_documented(&mode, code),
+                            "{code:?} ({mods:?}) is handled in {mode:?} but missing from KEYBINDINGS"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn handle_resize_recomputes_cached_layout() {
+        let (mut app, mut cmd_rx) = make_app();
+        app.snapshot_mut().diff_files = vec![DiffFile {
+            path: "src/main.rs".into(),
+            insertions: 1,
+            deletions: 0,
+            untracked: false,
+        }];
+        app.update_diff_tree();
+        let old_width = app.diff_tree_cache.1;
+        app.needs_redraw = false;
+
+        app.handle_resize(200, 50);
+
+        assert_eq!(app.terminal_size, (200, 50));
+        assert_eq!(
+            app.layout.preview,
+            crate::ui::compute_layout(Rect::new(0, 0, 200, 50)).preview
+        );
+        assert_eq!(app.layout.help.y, 49);
+        assert!(
+            app.diff_tree_cache.1 > old_width,
+            "diff tree rebuilt for new width"
+        );
+        assert!(app.needs_redraw);
+        assert!(matches!(
+            cmd_rx.try_recv(),
+            Ok(BackendCommand::SetPreviewHeight(h)) if h == app.layout.preview.height
+        ));
+
+        // Only height changes are reported again.
+        app.handle_resize(180, 50);
+        assert!(cmd_rx.try_recv().is_err());
+    }
 }
@@ -1,24 +1,30 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use tokio::sync::{broadcast, mpsc, watch};
 
-use crate::agent::provider_for;
+use crate::agent::{provider_for, StatusStrategy};
 use crate::app::{BackendCommand, PreviewUpdate, StateSnapshot};
+use crate::config::LayoutStep;
 use crate::session::{AgentState, AgentType, ProcessState, Session, VisualStatus};
-use crate::tmux::SessionManager;
+use crate::tmux::{SessionManager, TmuxHealth};
 use crate::tmux_control::{TmuxControlConnection, TmuxNotification};
 
 mod message_runtime;
 mod preview_runtime;
 mod session_runtime;
 pub mod state;
+mod status_script_runtime;
 
 use message_runtime::MessageRuntime;
 use preview_runtime::PreviewRuntime;
 use session_runtime::SessionRuntime;
+use status_script_runtime::{ScriptResult, StatusScriptRuntime};
+
+/// Full tool results kept for the UI; older ones are re-read on demand.
+const MAX_TOOL_RESULT_TEXTS: usize = 16;
 
 /// The backend actor runs in `tokio::spawn` and owns all I/O state.
 /// It processes commands from the UI, handles `%output` notifications,
@@ -30,9 +36,48 @@ pub struct Backend {
     manifest_dir: PathBuf,
 
     sessions: Vec<Session>,
+    /// Manifest tags by session name, reloaded each session tick so edits
+    /// from `hydra tag` in another shell show up live.
+    session_tags: HashMap<String, Vec<String>>,
+    /// Manifest notes by session name, reloaded alongside the tags.
+    session_notes: HashMap<String, String>,
+    /// Full tool results read for the UI, by tool-call id, with ids in the
+    /// order they were read so the oldest can be dropped.
+    tool_result_texts: HashMap<String, String>,
+    tool_result_order: VecDeque<String>,
+    /// Manifest session names, for restoring names sanitized for tmux.
+    manifest_names: Vec<String>,
+    /// Placeholders for records stopped with `hydra stop`, sorted by name.
+    /// They have no tmux session, so they are only listed, never polled.
+    stopped_sessions: Vec<Session>,
+    /// Whether recorded sessions are missing, and why.
+    tmux_health: TmuxHealth,
+    /// When set, only sessions carrying this tag are sent to the UI.
+    tag_filter: Option<String>,
+    /// Layout presets from config, reapplied to revived sessions.
+    layouts: HashMap<String, Vec<LayoutStep>>,
+    /// Idle threshold after which sessions are auto-killed; `None` = off.
+    idle_kill_after: Option<Duration>,
+    /// Delay before checking a launched agent for an instant death; `None` = off.
+    launch_check_after: Option<Duration>,
+    /// Manifest launch errors by session name, reloaded alongside the tags.
+    launch_errors: HashMap<String, String>,
+    /// Manual list positions from the manifest, by session name.
+    session_order: HashMap<String, u32>,
+    /// Manifest working directories, by session name. Sessions without a
+    /// record run in `cwd`.
+    session_cwds: HashMap<String, String>,
     session_runtime: SessionRuntime,
     message_runtime: MessageRuntime,
     preview_runtime: PreviewRuntime,
+    status_script_runtime: StatusScriptRuntime,
+    /// Finished status script runs; taken by `run()` for its select loop.
+    script_results: Option<mpsc::Receiver<ScriptResult>>,
+    /// Launch checks report back here as `LaunchChecked` commands, so the
+    /// wait never blocks the actor.
+    launch_check_tx: mpsc::Sender<BackendCommand>,
+    /// Taken by `run()` for its select loop.
+    launch_check_rx: Option<mpsc::Receiver<BackendCommand>>,
 
     status_message: Option<String>,
     status_message_set_at: Option<Instant>,
@@ -53,15 +98,35 @@ impl Backend {
         preview_tx: mpsc::Sender<PreviewUpdate>,
         control_conn: Option<Arc<TmuxControlConnection>>,
     ) -> Self {
+        let (script_tx, script_rx) = mpsc::channel(64);
+        let (launch_check_tx, launch_check_rx) = mpsc::channel(64);
         Self {
             manager,
             project_id,
             cwd,
             manifest_dir,
             sessions: Vec::new(),
+            session_tags: HashMap::new(),
+            session_notes: HashMap::new(),
+            tool_result_texts: HashMap::new(),
+            tool_result_order: VecDeque::new(),
+            manifest_names: Vec::new(),
+            stopped_sessions: Vec::new(),
+            tmux_health: TmuxHealth::Ok,
+            tag_filter: None,
+            layouts: HashMap::new(),
+            idle_kill_after: None,
+            launch_check_after: None,
+            launch_errors: HashMap::new(),
+            session_cwds: HashMap::new(),
+            session_order: HashMap::new(),
             session_runtime: SessionRuntime::new(),
             message_runtime: MessageRuntime::new(),
             preview_runtime: PreviewRuntime::new(),
+            status_script_runtime: StatusScriptRuntime::new(script_tx),
+            script_results: Some(script_rx),
+            launch_check_tx,
+            launch_check_rx: Some(launch_check_rx),
             status_message: None,
             status_message_set_at: None,
             state_tx,
@@ -78,13 +143,36 @@ impl Backend {
     /// Run the backend event loop.
     pub async fn run(mut self, mut cmd_rx: mpsc::Receiver<BackendCommand>) {
         // Initial setup.
+        let config = match crate::config::try_load_config(&self.manifest_dir) {
+            Ok(config) => config,
+            Err(e) => {
+                self.set_status(format!("Using default settings: {e}"));
+                crate::config::Config::default()
+            }
+        };
+        self.message_runtime
+            .set_stats_exclusions(&config.stats_exclude);
+        self.message_runtime
+            .set_extra_log_globs(&config.extra_log_globs);
+        self.message_runtime
+            .set_tool_categories(config.session_tool_categories());
+        self.idle_kill_after = config.idle_kill_after();
+        self.launch_check_after = config.launch_check_after();
+        self.layouts = config.layouts;
+        self.preview_runtime
+            .set_scrollback_lines(config.preview_scrollback.unwrap_or(0));
+        self.status_script_runtime
+            .set_command(config.status_command.clone());
         self.revive_sessions().await;
+        self.refresh_session_tags().await;
         self.refresh_sessions().await;
         self.send_snapshot();
 
         // Subscribe to notifications if control mode is available.
         let mut notif_rx: Option<broadcast::Receiver<TmuxNotification>> =
             self.control_conn.as_ref().map(|c| c.subscribe());
+        let mut script_rx = self.script_results.take();
+        let mut launch_check_rx = self.launch_check_rx.take();
 
         // Status/preview refresh cadence.
         let mut session_tick = tokio::time::interval(Duration::from_millis(500));
@@ -111,9 +199,30 @@ impl Backend {
                         self.handle_notification(notif);
                     }
                 }
+                result = async {
+                    match script_rx.as_mut() {
+                        Some(rx) => rx.recv().await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    if let Some(result) = result {
+                        if self.status_script_runtime.apply(result) {
+                            self.send_snapshot();
+                        }
+                    }
+                }
+                Some(cmd) = async {
+                    match launch_check_rx.as_mut() {
+                        Some(rx) => rx.recv().await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    self.handle_command(cmd).await;
+                }
                 _ = session_tick.tick() => {
                     let prev_sessions = self.sessions.clone();
                     let prev_status_message = self.status_message.clone();
+                    let prev_health = self.tmux_health;
 
                     // Auto-clear status messages after 4.5s (UI clears at 5s)
                     if let Some(set_at) = self.status_message_set_at {
@@ -123,9 +232,19 @@ impl Backend {
                         }
                     }
 
+                    let tags_changed = self.refresh_session_tags().await;
                     self.refresh_sessions().await;
+                    self.kill_idle_sessions().await;
+                    let (cwds, cwd) = (&self.session_cwds, &self.cwd);
+                    self.status_script_runtime.spawn_due(
+                        &self.sessions,
+                        |s| crate::manifest::session_cwd(cwds, cwd, &s.name).to_string(),
+                        Instant::now(),
+                    );
                     if sessions_changed(&prev_sessions, &self.sessions)
+                        || tags_changed
                         || self.status_message != prev_status_message
+                        || self.tmux_health != prev_health
                     {
                         self.send_snapshot();
                     }
@@ -204,14 +323,18 @@ impl Backend {
     async fn handle_command(&mut self, cmd: BackendCommand) -> bool {
         match cmd {
             BackendCommand::Quit => return true,
-            BackendCommand::CreateSession { agent_type } => {
-                self.create_session(agent_type).await;
+            BackendCommand::CreateSession { agent_type, name } => {
+                self.create_session(agent_type, name).await;
                 self.send_snapshot();
             }
             BackendCommand::DeleteSession { tmux_name, name } => {
                 self.delete_session(&tmux_name, &name).await;
                 self.send_snapshot();
             }
+            BackendCommand::KillAllSessions => {
+                self.kill_all_sessions().await;
+                self.send_snapshot();
+            }
             BackendCommand::SendCompose { tmux_name, text } => {
                 if let Err(e) = self.manager.send_text_enter(&tmux_name, &text).await {
                     self.set_status(format!("Failed to send message: {e}"));
@@ -242,17 +365,154 @@ impl Backend {
                 self.preview_runtime
                     .queue_request(&tmux_name, wants_scrollback);
             }
+            BackendCommand::SetPreviewHeight(height) => {
+                self.preview_runtime.set_preview_height(height);
+            }
+            BackendCommand::SetPanePreview(enabled) => {
+                self.preview_runtime.set_prefer_pane(enabled);
+            }
+            BackendCommand::ReviveAll => {
+                if self.revive_sessions().await == 0 {
+                    self.set_status("No recorded sessions to revive".to_string());
+                }
+                self.refresh_session_tags().await;
+                self.refresh_sessions().await;
+                self.send_snapshot();
+            }
+            BackendCommand::RefreshSession { tmux_name } => {
+                self.message_runtime.force_refresh(&tmux_name);
+                self.refresh_messages();
+                self.preview_runtime.queue_request(&tmux_name, false);
+                self.send_preview_for_all().await;
+            }
+            BackendCommand::CopyToClipboard { text, what } => {
+                match crate::system::clipboard::copy(&text).await {
+                    Ok(_) if text.contains('\n') => {
+                        self.set_status(format!("Copied {what} ({} lines)", text.lines().count()))
+                    }
+                    Ok(_) => self.set_status(format!("Copied {what}: {text}")),
+                    Err(e) => self.set_status(format!("Failed to copy {what}: {e}")),
+                }
+                self.send_snapshot();
+            }
+            BackendCommand::SetTagFilter(tag) => {
+                self.tag_filter = tag;
+                self.send_snapshot();
+            }
+            BackendCommand::MoveSession { name, up } => {
+                self.move_session(&name, up).await;
+                self.send_snapshot();
+            }
+            BackendCommand::RunBellCommand {
+                command,
+                name,
+                agent,
+            } => {
+                if let Err(e) = crate::bell::spawn_command(&command, &name, &agent) {
+                    self.set_status(format!("Bell command failed: {e}"));
+                    self.send_snapshot();
+                }
+            }
+            BackendCommand::SetNote { name, note } => {
+                self.set_note(&name, &note).await;
+                self.send_snapshot();
+            }
+            BackendCommand::ExpandToolResult {
+                tmux_name,
+                tool_use_id,
+            } => {
+                self.expand_tool_result(&tmux_name, tool_use_id).await;
+                self.send_snapshot();
+            }
+            BackendCommand::LaunchChecked { name, error } => {
+                self.record_launch_check(&name, error).await;
+                self.send_snapshot();
+            }
+            BackendCommand::SaveConfigChange(change) => {
+                if let Err(e) = crate::config::save_config_change(&self.manifest_dir, &change).await
+                {
+                    self.set_status(format!("Failed to save config: {e}"));
+                    self.send_snapshot();
+                }
+            }
         }
         false
     }
 
-    async fn create_session(&mut self, agent_type: AgentType) {
-        let existing: Vec<String> = self.sessions.iter().map(|s| s.name.clone()).collect();
-        let name = crate::session::generate_name(&existing);
+    async fn set_note(&mut self, name: &str, note: &str) {
+        match crate::manifest::set_session_note(&self.manifest_dir, &self.project_id, name, note)
+            .await
+        {
+            Ok(Some(note)) => {
+                self.session_notes.insert(name.to_string(), note);
+                self.set_status(format!("Noted '{name}'"));
+            }
+            Ok(None) => {
+                self.session_notes.remove(name);
+                self.set_status(format!("Cleared note on '{name}'"));
+            }
+            Err(e) => self.set_status(format!("Failed to save note: {e}")),
+        }
+    }
+
+    async fn expand_tool_result(&mut self, tmux_name: &str, tool_use_id: String) {
+        let Some(path) = self
+            .message_runtime
+            .log_paths(&self.sessions, |s| self.session_cwd(s).to_string())
+            .remove(tmux_name)
+        else {
+            self.set_status("No log file for this session".to_string());
+            return;
+        };
+        let id = tool_use_id.clone();
+        let text = tokio::task::spawn_blocking(move || {
+            crate::logs::read_full_tool_result(std::path::Path::new(&path), &id)
+        })
+        .await
+        .ok()
+        .flatten();
+        let Some(text) = text else {
+            self.set_status("Tool result not found in the log".to_string());
+            return;
+        };
+        if self
+            .tool_result_texts
+            .insert(tool_use_id.clone(), text)
+            .is_none()
+        {
+            self.tool_result_order.push_back(tool_use_id);
+        }
+        while self.tool_result_order.len() > MAX_TOOL_RESULT_TEXTS {
+            if let Some(oldest) = self.tool_result_order.pop_front() {
+                self.tool_result_texts.remove(&oldest);
+            }
+        }
+    }
+
+    async fn create_session(&mut self, agent_type: AgentType, name: Option<String>) {
+        let name = name.unwrap_or_else(|| {
+            let existing: Vec<String> = self.sessions.iter().map(|s| s.name.clone()).collect();
+            crate::session::generate_name(&existing)
+        });
         let pid = self.project_id.clone();
         let cwd = self.cwd.clone();
         let manifest_dir = self.manifest_dir.clone();
 
+        // Names like `a.b` and `a_b` share one tmux session; refuse the
+        // second instead of attaching it to the first one's pane.
+        let mut existing: Vec<String> = crate::manifest::load_manifest(&manifest_dir, &pid)
+            .await
+            .sessions
+            .into_keys()
+            .collect();
+        existing.extend(self.sessions.iter().map(|s| s.name.clone()));
+        if let Some(other) = crate::session::tmux_name_clash(&name, &existing) {
+            self.set_status(format!(
+                "Session name '{name}' clashes with '{other}': both map to the same tmux session"
+            ));
+            return;
+        }
+
         let record = crate::manifest::SessionRecord::for_new_session(&name, &agent_type, &cwd);
         let provider = provider_for(&agent_type);
         let cmd = provider.create_command(&name, &cwd);
@@ -262,12 +522,13 @@ impl Backend {
             .create_session(&pid, &name, &agent_type, &cwd, Some(&cmd))
             .await;
         match result {
-            Ok(_) => {
+            Ok(tmux_name) => {
                 let mut msg = format!("Created session '{}' with {}", name, agent_type);
                 if let Err(e) = crate::manifest::add_session(&manifest_dir, &pid, record).await {
                     msg.push_str(&format!(" (warning: manifest save failed: {e})"));
                 }
                 self.set_status(msg);
+                self.spawn_launch_check(vec![(name, tmux_name)]);
                 self.refresh_sessions().await;
             }
             Err(e) => {
@@ -276,10 +537,52 @@ impl Backend {
         }
     }
 
+    /// Check each launched `(name, tmux_name)` for an agent that already
+    /// died, off the actor: one wait covers them all, and each result comes
+    /// back as a `LaunchChecked` command. A no-op when the check is off.
+    fn spawn_launch_check(&self, launched: Vec<(String, String)>) {
+        let Some(wait) = self.launch_check_after else {
+            return;
+        };
+        if launched.is_empty() {
+            return;
+        }
+        let tx = self.launch_check_tx.clone();
+        tokio::spawn(async move {
+            let mut delay = wait;
+            for (name, tmux_name) in launched {
+                let error = crate::tmux::check_launch(&tmux_name, delay).await;
+                delay = Duration::ZERO;
+                let _ = tx.send(BackendCommand::LaunchChecked { name, error }).await;
+            }
+        });
+    }
+
+    /// Store a launch check's result on the session record (clearing an
+    /// earlier error otherwise) and report an instant death in the status.
+    async fn record_launch_check(&mut self, name: &str, error: Option<String>) {
+        if let Some(error) = &error {
+            self.set_status(format!(
+                "'{name}' exited at launch: {}",
+                crate::tmux::launch_error_summary(error)
+            ));
+        }
+        let _ =
+            crate::manifest::set_launch_error(&self.manifest_dir, &self.project_id, name, error)
+                .await;
+        self.refresh_session_tags().await;
+    }
+
     async fn delete_session(&mut self, tmux_name: &str, name: &str) {
         let pid = self.project_id.clone();
         let manifest_dir = self.manifest_dir.clone();
-        let result = self.manager.kill_session(tmux_name).await;
+        let live = self.sessions.iter().any(|s| s.tmux_name == tmux_name);
+        let result = if self.is_stopped(name) && !live {
+            // Nothing left in tmux; deleting just drops the record.
+            Ok(())
+        } else {
+            self.manager.kill_session(tmux_name).await
+        };
         match result {
             Ok(_) => {
                 let mut msg = format!("Killed session '{name}'");
@@ -295,13 +598,107 @@ impl Backend {
         self.refresh_sessions().await;
     }
 
-    async fn revive_sessions(&mut self) {
+    /// Kill every live session and remove its manifest record. Stopped
+    /// sessions keep theirs.
+    async fn kill_all_sessions(&mut self) {
+        let pid = self.project_id.clone();
+        let manifest_dir = self.manifest_dir.clone();
+        let targets: Vec<(String, String)> = self
+            .sessions
+            .iter()
+            .map(|s| (s.tmux_name.clone(), s.name.clone()))
+            .collect();
+        let mut killed = 0;
+        for (tmux_name, name) in targets {
+            match self.manager.kill_session(&tmux_name).await {
+                Ok(_) => {
+                    let _ = crate::manifest::remove_session(&manifest_dir, &pid, &name).await;
+                    killed += 1;
+                }
+                Err(e) => self.set_status(format!("Failed to kill '{name}': {e}")),
+            }
+        }
+        if killed > 0 {
+            let noun = if killed == 1 { "session" } else { "sessions" };
+            self.set_status(format!("Killed {killed} {noun}"));
+        }
+        self.refresh_sessions().await;
+    }
+
+    /// Kill sessions idle longer than the configured threshold.
+    async fn kill_idle_sessions(&mut self) {
+        let Some(threshold) = self.idle_kill_after else {
+            return;
+        };
+        let now = chrono::Utc::now();
+        let stats = self.message_runtime.session_stats();
+        let due: Vec<(String, String)> = self
+            .sessions
+            .iter()
+            .filter(|s| {
+                session_runtime::idle_kill_due(
+                    &s.visual_status(),
+                    stats.get(&s.tmux_name),
+                    now,
+                    threshold,
+                )
+            })
+            .map(|s| (s.tmux_name.clone(), s.name.clone()))
+            .collect();
+        if due.is_empty() {
+            return;
+        }
+
+        let pid = self.project_id.clone();
+        let manifest_dir = self.manifest_dir.clone();
+        let mins = threshold.as_secs() / 60;
+        let mut killed = Vec::new();
+        let mut unsaved = Vec::new();
+        for (tmux_name, name) in due {
+            match self.manager.kill_session(&tmux_name).await {
+                Ok(_) => {
+                    if let Err(e) =
+                        crate::manifest::remove_session(&manifest_dir, &pid, &name).await
+                    {
+                        unsaved.push(format!("'{name}': {e}"));
+                    }
+                    killed.push(name);
+                }
+                Err(e) => self.set_status(format!("Failed to auto-kill '{name}': {e}")),
+            }
+        }
+        if !killed.is_empty() {
+            let names = killed
+                .iter()
+                .map(|n| format!("'{n}'"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let noun = if killed.len() == 1 {
+                "session"
+            } else {
+                "sessions"
+            };
+            let mut msg = format!("Auto-killed idle {noun} {names} (idle > {mins}m)");
+            if !unsaved.is_empty() {
+                msg.push_str(&format!(
+                    " (warning: manifest update failed for {})",
+                    unsaved.join(", ")
+                ));
+            }
+            self.set_status(msg);
+            self.refresh_sessions().await;
+        }
+    }
+
+    /// Recreate recorded sessions that aren't running. Returns how many
+    /// were attempted.
+    async fn revive_sessions(&mut self) -> u32 {
         let pid = self.project_id.clone();
         let manifest_dir = self.manifest_dir.clone();
         let mut manifest = crate::manifest::load_manifest(&manifest_dir, &pid).await;
 
         if manifest.sessions.is_empty() {
-            return;
+            return 0;
         }
 
         let agent_mapping: HashMap<String, AgentType> = manifest
@@ -316,16 +713,22 @@ impl Backend {
         self.manager.prepopulate_agent_cache(&agent_mapping);
 
         let live = self.manager.list_sessions(&pid).await.unwrap_or_default();
-        let live_names: std::collections::HashSet<String> =
-            live.iter().map(|s| s.name.clone()).collect();
+        let live_tmux_names: std::collections::HashSet<String> =
+            live.iter().map(|s| s.tmux_name.clone()).collect();
 
         let mut revived = 0u32;
         let mut failed = 0u32;
         let mut manifest_dirty = false;
+        let mut launched = Vec::new();
 
-        let names: Vec<String> = manifest.sessions.keys().cloned().collect();
+        let names: Vec<String> = manifest
+            .sessions
+            .values()
+            .filter(|r| !r.stopped)
+            .map(|r| r.name.clone())
+            .collect();
         for name in names {
-            if live_names.contains(&name) {
+            if live_tmux_names.contains(&crate::session::tmux_session_name(&pid, &name)) {
                 continue;
             }
 
@@ -334,10 +737,24 @@ impl Backend {
             let success = match record.agent_type.parse::<AgentType>() {
                 Ok(agent) => {
                     let resume_cmd = record.resume_command();
-                    self.manager
+                    let created = self
+                        .manager
                         .create_session(&pid, &name, &agent, &record.cwd, Some(&resume_cmd))
-                        .await
-                        .is_ok()
+                        .await;
+                    if let (Ok(tmux_name), Some(steps)) = (
+                        &created,
+                        record.layout.as_ref().and_then(|l| self.layouts.get(l)),
+                    ) {
+                        // Best effort: a broken preset shouldn't fail the revive.
+                        let _ = self
+                            .manager
+                            .apply_layout(tmux_name, &record.cwd, steps)
+                            .await;
+                    }
+                    if let Ok(tmux_name) = &created {
+                        launched.push((name.clone(), tmux_name.clone()));
+                    }
+                    created.is_ok()
                 }
                 Err(_) => false,
             };
@@ -366,6 +783,7 @@ impl Backend {
         if manifest_dirty {
             let _ = crate::manifest::save_manifest(&manifest_dir, &pid, &manifest).await;
         }
+        self.spawn_launch_check(launched);
 
         if revived > 0 || failed > 0 {
             let msg = if failed == 0 {
@@ -375,6 +793,100 @@ impl Backend {
             };
             self.set_status(msg);
         }
+        revived + failed
+    }
+
+    /// Move `name` one place up or down the list as the user sees it,
+    /// within its status group, and store the new order in the manifest.
+    async fn move_session(&mut self, name: &str, up: bool) {
+        let mut visible: Vec<(String, u8)> = self
+            .listed_sessions()
+            .filter(|s| self.matches_tag_filter(s))
+            .map(|s| (s.name.clone(), s.sort_order()))
+            .collect();
+        let Some(index) = visible.iter().position(|(n, _)| n == name) else {
+            return;
+        };
+        let group = visible[index].1;
+        let moved = crate::session::move_item(&mut visible, index, up);
+        // Groups follow status, so only a neighbour in the same one can swap.
+        if moved.is_none() || visible[index].1 != group {
+            let end = if up { "top" } else { "bottom" };
+            self.set_status(format!("'{name}' is already at the {end} of its group"));
+            return;
+        }
+        let listed: Vec<String> = self.listed_sessions().map(|s| s.name.clone()).collect();
+        let visible: Vec<String> = visible.into_iter().map(|(n, _)| n).collect();
+        let order = crate::session::merge_order(&listed, &visible);
+        if let Err(e) =
+            crate::manifest::set_session_order(&self.manifest_dir, &self.project_id, &order).await
+        {
+            self.set_status(format!("Failed to save session order: {e}"));
+            return;
+        }
+        self.refresh_session_tags().await;
+        crate::session::sort_sessions(&mut self.sessions, &self.session_order);
+    }
+
+    /// Reload session tags, notes, names, order and stopped records from the
+    /// manifest. Returns true if any of them changed.
+    async fn refresh_session_tags(&mut self) -> bool {
+        let manifest = crate::manifest::load_manifest(&self.manifest_dir, &self.project_id).await;
+        self.manifest_names = manifest.sessions.keys().cloned().collect();
+        self.session_cwds = crate::manifest::session_cwds(&manifest);
+        let order = crate::manifest::session_order(&manifest);
+        let order_changed = order != self.session_order;
+        self.session_order = order;
+        let mut stopped: Vec<Session> = manifest
+            .sessions
+            .values()
+            .filter(|r| r.stopped)
+            .filter_map(|r| {
+                let agent = r.agent_type.parse().ok()?;
+                Some(Session::stopped(&self.project_id, &r.name, agent))
+            })
+            .collect();
+        crate::session::sort_sessions(&mut stopped, &self.session_order);
+        let stopped_changed = !stopped
+            .iter()
+            .map(|s| &s.name)
+            .eq(self.stopped_sessions.iter().map(|s| &s.name));
+        self.stopped_sessions = stopped;
+
+        let notes = crate::manifest::session_notes(&manifest);
+        let notes_changed = notes != self.session_notes;
+        self.session_notes = notes;
+
+        let launch_errors = crate::manifest::launch_errors(&manifest);
+        let launch_errors_changed = launch_errors != self.launch_errors;
+        self.launch_errors = launch_errors;
+
+        let tags = crate::manifest::session_tags(&manifest);
+        if tags == self.session_tags {
+            return stopped_changed || notes_changed || launch_errors_changed || order_changed;
+        }
+        self.session_tags = tags;
+        true
+    }
+
+    /// Where `session` runs, so a worktree session's logs resolve from the
+    /// worktree rather than the project directory.
+    fn session_cwd(&self, session: &Session) -> &str {
+        crate::manifest::session_cwd(&self.session_cwds, &self.cwd, &session.name)
+    }
+
+    fn is_stopped(&self, name: &str) -> bool {
+        self.stopped_sessions.iter().any(|s| s.name == name)
+    }
+
+    fn matches_tag_filter(&self, session: &Session) -> bool {
+        match &self.tag_filter {
+            None => true,
+            Some(tag) => self
+                .session_tags
+                .get(&session.name)
+                .is_some_and(|tags| tags.contains(tag)),
+        }
     }
 
     async fn refresh_sessions(&mut self) {
@@ -383,6 +895,7 @@ impl Backend {
 
         match result {
             Ok(mut sessions) => {
+                crate::session::restore_display_names(&mut sessions, &pid, &self.manifest_names);
                 let now = Instant::now();
                 let prev_statuses: HashMap<String, VisualStatus> = self
                     .sessions
@@ -391,23 +904,29 @@ impl Backend {
                     .collect();
 
                 let pane_status = self.manager.batch_pane_status().await;
+                let wants_titles = sessions.iter().any(|s| {
+                    provider_for(&s.agent_type).preferred_status_strategy()
+                        == StatusStrategy::PaneTitle
+                });
+                let pane_titles = if wants_titles {
+                    self.manager.batch_pane_titles().await
+                } else {
+                    None
+                };
 
                 self.session_runtime.apply_statuses(
                     &mut sessions,
                     &prev_statuses,
                     self.message_runtime.session_stats(),
                     pane_status.as_ref(),
-                    self.control_conn.is_some(),
+                    pane_titles.as_ref(),
                     now,
                 );
 
-                sessions.sort_by(|a, b| {
-                    a.sort_order()
-                        .cmp(&b.sort_order())
-                        .then(a.name.cmp(&b.name))
-                });
+                crate::session::sort_sessions(&mut sessions, &self.session_order);
 
                 self.sessions = sessions;
+                self.tmux_health = self.check_tmux_health().await;
             }
             Err(e) => {
                 self.preview_runtime.clear_cache();
@@ -419,13 +938,36 @@ impl Backend {
         self.session_runtime.prune(&live_keys);
         self.message_runtime.prune(&live_keys);
         self.preview_runtime.prune(&live_keys);
+        self.status_script_runtime.prune(&live_keys);
+    }
+
+    /// Compare live sessions with the manifest. The server is only asked
+    /// about when none of this project's sessions are left, since any live
+    /// one proves it's up.
+    async fn check_tmux_health(&self) -> TmuxHealth {
+        let recorded: Vec<String> = self
+            .manifest_names
+            .iter()
+            .filter(|name| !self.is_stopped(name))
+            .map(|name| crate::session::tmux_session_name(&self.project_id, name))
+            .collect();
+        let live: HashSet<String> = self.sessions.iter().map(|s| s.tmux_name.clone()).collect();
+        let server_running =
+            !live.is_empty() || recorded.is_empty() || self.manager.server_running().await;
+        crate::tmux::classify_health(server_running, &recorded, &live)
     }
 
     fn refresh_messages(&mut self) {
-        let sessions: Vec<(String, AgentType)> = self
+        let sessions: Vec<(String, AgentType, String)> = self
             .sessions
             .iter()
-            .map(|session| (session.tmux_name.clone(), session.agent_type.clone()))
+            .map(|session| {
+                (
+                    session.tmux_name.clone(),
+                    session.agent_type.clone(),
+                    self.session_cwd(session).to_string(),
+                )
+            })
             .collect();
 
         if let Some(update) = self.message_runtime.tick(&sessions, &self.cwd) {
@@ -437,15 +979,40 @@ impl Backend {
         }
     }
 
+    /// Live sessions followed by stopped ones, in list order, before the
+    /// tag filter.
+    fn listed_sessions(&self) -> impl Iterator<Item = &Session> {
+        let live: HashSet<&String> = self.sessions.iter().map(|s| &s.tmux_name).collect();
+        let stopped = self
+            .stopped_sessions
+            .iter()
+            .filter(move |s| !live.contains(&s.tmux_name));
+        self.sessions.iter().chain(stopped)
+    }
+
     fn send_snapshot(&self) {
         let snapshot = StateSnapshot {
-            sessions: self.sessions.clone(),
+            sessions: self
+                .listed_sessions()
+                .filter(|s| self.matches_tag_filter(s))
+                .cloned()
+                .collect(),
+            session_tags: self.session_tags.clone(),
+            tag_filter: self.tag_filter.clone(),
+            session_notes: self.session_notes.clone(),
+            launch_errors: self.launch_errors.clone(),
             last_messages: self.message_runtime.last_messages().clone(),
             session_stats: self.message_runtime.session_stats().clone(),
             global_stats: self.message_runtime.global_stats().clone(),
             diff_files: self.message_runtime.diff_files().to_vec(),
             conversations: self.message_runtime.snapshot_conversations(),
             status_message: self.status_message.clone(),
+            log_paths: self
+                .message_runtime
+                .log_paths(&self.sessions, |s| self.session_cwd(s).to_string()),
+            status_annotations: self.status_script_runtime.annotations().clone(),
+            tool_result_texts: self.tool_result_texts.clone(),
+            tmux_health: self.tmux_health,
         };
 
         let _ = self.state_tx.send(Arc::new(snapshot));
@@ -464,6 +1031,8 @@ impl Backend {
     }
 }
 
+/// The directory session `name` runs in: its manifest cwd, else the
+/// project cwd.
 fn sessions_changed(previous: &[Session], current: &[Session]) -> bool {
     if previous.len() != current.len() {
         return true;
@@ -1,9 +1,10 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use crate::agent::provider_for;
 use crate::backend::state::{BackgroundRefreshState, ConversationBuffer};
-use crate::logs::{ConversationEntry, GlobalStats, SessionStats};
+use crate::logs::{ConversationEntry, ExtraLogGlobs, GlobalStats, SessionStats, ToolCategories};
 use crate::models::DiffFile;
-use crate::session::AgentType;
+use crate::session::{AgentType, Session};
 
 pub(crate) struct MessageRuntime {
     last_messages: HashMap<String, String>,
@@ -30,6 +31,18 @@ impl MessageRuntime {
         }
     }
 
+    pub(crate) fn set_stats_exclusions(&mut self, prefixes: &[String]) {
+        self.global_stats.set_excluded_projects(prefixes);
+    }
+
+    pub(crate) fn set_tool_categories(&mut self, categories: ToolCategories) {
+        self.bg.set_tool_categories(categories);
+    }
+
+    pub(crate) fn set_extra_log_globs(&mut self, globs: &ExtraLogGlobs) {
+        self.global_stats.set_extra_log_globs(globs);
+    }
+
     pub(crate) fn last_messages(&self) -> &HashMap<String, String> {
         &self.last_messages
     }
@@ -50,6 +63,23 @@ impl MessageRuntime {
         &self.conversations
     }
 
+    /// Resolved log file paths by tmux session name. `cwd_for` gives the
+    /// directory each session runs in.
+    pub(crate) fn log_paths(
+        &self,
+        sessions: &[Session],
+        cwd_for: impl Fn(&Session) -> String,
+    ) -> HashMap<String, String> {
+        sessions
+            .iter()
+            .filter_map(|s| {
+                let log_id = self.bg.log_ids().get(&s.tmux_name)?;
+                let path = provider_for(&s.agent_type).log_file_path(log_id, &cwd_for(s));
+                Some((s.tmux_name.clone(), path.to_string_lossy().into_owned()))
+            })
+            .collect()
+    }
+
     pub(crate) fn snapshot_conversations(&self) -> HashMap<String, VecDeque<ConversationEntry>> {
         self.conversations
             .iter()
@@ -65,9 +95,15 @@ impl MessageRuntime {
         buf.extend(vec![ConversationEntry::UserMessage { text }]);
     }
 
+    pub(crate) fn force_refresh(&mut self, tmux_name: &str) {
+        self.bg.force_refresh(tmux_name);
+    }
+
+    /// `sessions` are `(tmux name, agent, session cwd)`; `cwd` is the
+    /// project directory, used for the git diff.
     pub(crate) fn tick(
         &mut self,
-        sessions: &[(String, AgentType)],
+        sessions: &[(String, AgentType, String)],
         cwd: &str,
     ) -> Option<MessageTickResult> {
         let conversation_offsets: HashMap<String, u64> = self
@@ -93,7 +129,8 @@ impl MessageRuntime {
                     .get(tmux_name)
                     .map(|buf| buf.read_offset)
                     .unwrap_or(0);
-                if *new_offset != old_offset {
+                // Subagent logs can grow while the main log's offset stands still.
+                if *new_offset != old_offset || result.conversations.contains_key(tmux_name) {
                     Some(tmux_name.clone())
                 } else {
                     None
@@ -127,7 +164,7 @@ impl MessageRuntime {
                 .entry(tmux_name.clone())
                 .or_insert_with(ConversationBuffer::new);
             if replace {
-                buf.entries.clear();
+                buf.clear();
             }
             buf.extend(new_entries);
         }
@@ -141,7 +178,7 @@ impl MessageRuntime {
                 .conversations
                 .entry(tmux_name.clone())
                 .or_insert_with(ConversationBuffer::new);
-            buf.entries.clear();
+            buf.clear();
         }
 
         Some(MessageTickResult { changed_sessions })
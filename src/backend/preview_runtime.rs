@@ -23,6 +23,12 @@ pub(crate) struct PreviewRuntime {
     dirty_preview_sessions: HashSet<String>,
     requested_previews: HashMap<String, bool>,
     round_robin_cursor: usize,
+    /// Preview pane height (border included) last reported by the UI.
+    preview_height: u16,
+    /// Extra history lines captured above the visible preview rows.
+    scrollback_lines: u32,
+    /// Capture panes even when a parsed conversation is available.
+    prefer_pane: bool,
 }
 
 impl PreviewRuntime {
@@ -32,9 +38,24 @@ impl PreviewRuntime {
             dirty_preview_sessions: HashSet::new(),
             requested_previews: HashMap::new(),
             round_robin_cursor: 0,
+            preview_height: 0,
+            scrollback_lines: 0,
+            prefer_pane: false,
         }
     }
 
+    pub(crate) fn set_preview_height(&mut self, height: u16) {
+        self.preview_height = height;
+    }
+
+    pub(crate) fn set_scrollback_lines(&mut self, lines: u32) {
+        self.scrollback_lines = lines;
+    }
+
+    pub(crate) fn set_prefer_pane(&mut self, prefer_pane: bool) {
+        self.prefer_pane = prefer_pane;
+    }
+
     pub(crate) fn mark_dirty(&mut self, tmux_name: &str) {
         self.dirty_preview_sessions.insert(tmux_name.to_string());
     }
@@ -102,11 +123,13 @@ impl PreviewRuntime {
                 continue;
             }
 
-            if let Some(update) =
-                Self::preview_from_conversation(conversations, &candidate.tmux_name)
-            {
-                resolved.push(update);
-                continue;
+            if !self.prefer_pane {
+                if let Some(update) =
+                    Self::preview_from_conversation(conversations, &candidate.tmux_name)
+                {
+                    resolved.push(update);
+                    continue;
+                }
             }
 
             if allow_live_capture {
@@ -124,6 +147,8 @@ impl PreviewRuntime {
         }
 
         // Phase 2: Execute live captures concurrently.
+        let capture_lines =
+            crate::tmux::preview_capture_lines(self.preview_height, self.scrollback_lines);
         if !to_capture.is_empty() {
             let capture_futures: Vec<_> = to_capture
                 .into_iter()
@@ -135,10 +160,13 @@ impl PreviewRuntime {
                             .unwrap_or_else(|_| "[unable to capture pane]".to_string());
                         (tmux_name, content, true)
                     } else {
-                        let content = manager
-                            .capture_pane(&tmux_name)
-                            .await
-                            .unwrap_or_else(|_| "[unable to capture pane]".to_string());
+                        let capture = if capture_lines == 0 {
+                            manager.capture_pane(&tmux_name).await
+                        } else {
+                            manager.capture_pane_tail(&tmux_name, capture_lines).await
+                        };
+                        let content =
+                            capture.unwrap_or_else(|_| "[unable to capture pane]".to_string());
                         (tmux_name, content, false)
                     }
                 })
@@ -273,6 +301,7 @@ mod tests {
     struct SequenceManager {
         captures: Mutex<VecDeque<String>>,
         capture_calls: Mutex<usize>,
+        tail_requests: Mutex<Vec<u32>>,
     }
 
     impl SequenceManager {
@@ -280,6 +309,7 @@ mod tests {
             Self {
                 captures: Mutex::new(captures.iter().map(|s| s.to_string()).collect()),
                 capture_calls: Mutex::new(0),
+                tail_requests: Mutex::new(Vec::new()),
             }
         }
 
@@ -330,6 +360,14 @@ mod tests {
         async fn capture_pane_scrollback(&self, _tmux_name: &str) -> Result<String> {
             Ok(String::new())
         }
+
+        async fn capture_pane_tail(&self, tmux_name: &str, lines: u32) -> Result<String> {
+            self.tail_requests
+                .lock()
+                .expect("tail_requests lock poisoned")
+                .push(lines);
+            self.capture_pane(tmux_name).await
+        }
     }
 
     fn test_session(tmux_name: &str) -> Session {
@@ -374,4 +412,56 @@ mod tests {
 
         assert_eq!(manager.capture_calls(), 2);
     }
+
+    #[tokio::test]
+    async fn live_capture_is_sized_to_the_preview() {
+        let manager = SequenceManager::new(&["first", "second"]);
+        let mut runtime = PreviewRuntime::new();
+        let conversations = HashMap::new();
+        let sessions = vec![test_session("hydra-test-alpha")];
+        let (preview_tx, mut preview_rx) = mpsc::channel(8);
+
+        // No height reported yet: the visible pane is captured as-is.
+        runtime
+            .send_preview_for_all(&manager, &conversations, &sessions, &preview_tx, false)
+            .await;
+        assert_eq!(pane_content(preview_rx.try_recv().unwrap()), "first");
+        assert!(manager.tail_requests.lock().unwrap().is_empty());
+
+        runtime.set_preview_height(20);
+        runtime.set_scrollback_lines(50);
+        runtime
+            .send_preview_for_all(&manager, &conversations, &sessions, &preview_tx, false)
+            .await;
+        assert_eq!(pane_content(preview_rx.try_recv().unwrap()), "second");
+        assert_eq!(*manager.tail_requests.lock().unwrap(), [68]);
+    }
+
+    #[tokio::test]
+    async fn prefer_pane_captures_sessions_with_a_conversation() {
+        let manager = SequenceManager::new(&["pane"]);
+        let mut runtime = PreviewRuntime::new();
+        let mut buffer = ConversationBuffer::new();
+        buffer.extend(vec![crate::logs::ConversationEntry::AssistantText {
+            text: "hello".to_string(),
+        }]);
+        let conversations = HashMap::from([("hydra-test-alpha".to_string(), buffer)]);
+        let sessions = vec![test_session("hydra-test-alpha")];
+        let (preview_tx, mut preview_rx) = mpsc::channel(8);
+
+        runtime
+            .send_preview_for_all(&manager, &conversations, &sessions, &preview_tx, false)
+            .await;
+        assert!(matches!(
+            preview_rx.try_recv().unwrap().data,
+            PreviewData::Conversation(_)
+        ));
+        assert_eq!(manager.capture_calls(), 0);
+
+        runtime.set_prefer_pane(true);
+        runtime
+            .send_preview_for_all(&manager, &conversations, &sessions, &preview_tx, false)
+            .await;
+        assert_eq!(pane_content(preview_rx.try_recv().unwrap()), "pane");
+    }
 }
@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
 
 use crate::agent::{provider_for, StatusStrategy};
 use crate::backend::state::{OutputDetector, TaskTimers};
@@ -34,7 +36,7 @@ impl SessionRuntime {
         prev_statuses: &HashMap<String, VisualStatus>,
         session_stats: &HashMap<String, SessionStats>,
         pane_status: Option<&HashMap<String, (bool, u64)>>,
-        use_output_events: bool,
+        pane_titles: Option<&HashMap<String, String>>,
         now: Instant,
     ) {
         for session in sessions.iter_mut() {
@@ -52,31 +54,35 @@ impl SessionRuntime {
 
             self.dead_ticks.insert(tmux_name.clone(), 0);
 
+            // Active subagents keep a session working even when the main
+            // agent's own timestamps look idle.
             let log_running = session_stats
                 .get(&tmux_name)
-                .and_then(|stats| stats.task_elapsed())
-                .is_some();
+                .is_some_and(SessionStats::is_working);
             let recent_output = self.output_detector.has_recent_output(&tmux_name);
             let has_log_stats = session_stats.contains_key(&tmux_name);
             let strategy = provider_for(&session.agent_type).preferred_status_strategy();
 
+            // Prefer durable log events when available, but allow output
+            // events as a startup fallback until logs are discovered.
+            let from_logs = log_running || (!has_log_stats && recent_output);
             let running = match strategy {
-                StatusStrategy::JsonlActivity => {
-                    // Prefer durable log events when available, but allow output
-                    // events as a startup fallback until logs are discovered.
-                    log_running || (!has_log_stats && recent_output)
-                }
-                StatusStrategy::OutputEvent => {
-                    if use_output_events {
-                        recent_output || log_running
-                    } else {
-                        log_running || recent_output
-                    }
-                }
+                StatusStrategy::JsonlActivity => from_logs,
+                StatusStrategy::OutputEvent => recent_output || log_running,
+                StatusStrategy::PaneTitle => pane_titles
+                    .and_then(|titles| titles.get(&tmux_name))
+                    .and_then(|title| pane_title_running(title))
+                    .unwrap_or(from_logs),
             };
 
             session.process_state = ProcessState::Alive;
-            session.agent_state = if running {
+            let awaiting_input = session_stats
+                .get(&tmux_name)
+                .is_some_and(|st| st.awaiting_input);
+            session.agent_state = if awaiting_input {
+                // Blocked on the user, even if the log still looks busy.
+                AgentState::WaitingForInput
+            } else if running {
                 AgentState::Thinking
             } else {
                 AgentState::Idle
@@ -134,3 +140,229 @@ impl SessionRuntime {
         }
     }
 }
+
+/// Busy/idle state from an agent's pane title, or `None` when the title
+/// carries no known marker (e.g. tmux's default of the hostname).
+///
+/// - Braille spinner frames (Claude Code) and `✦` (Gemini CLI) mean working.
+/// - `✳` (Claude Code), `◇ Ready` and `✋ Action Required` (Gemini CLI) mean
+///   the agent is idle or waiting on the user.
+pub(crate) fn pane_title_running(title: &str) -> Option<bool> {
+    let first = title.trim_start().chars().next()?;
+    match first {
+        '\u{2801}'..='\u{28FF}' | '✦' => Some(true),
+        '✳' | '◇' | '✋' => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether an idle session should be auto-killed. Conservative by design:
+/// only idle or exited sessions with a known last activity qualify, and any
+/// sign of work (a running task or active subagents) keeps the session.
+pub(crate) fn idle_kill_due(
+    status: &VisualStatus,
+    stats: Option<&SessionStats>,
+    now: DateTime<Utc>,
+    threshold: Duration,
+) -> bool {
+    if !matches!(status, VisualStatus::Idle | VisualStatus::Exited) {
+        return false;
+    }
+    let Some(stats) = stats else {
+        return false;
+    };
+    if stats.active_subagents > 0 || stats.is_working() {
+        return false;
+    }
+    let Some(last) = stats.last_activity_at() else {
+        return false;
+    };
+    (now - last)
+        .to_std()
+        .is_ok_and(|idle_for| idle_for > threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::AgentType;
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    fn stats_idle_for(now: DateTime<Utc>, secs: i64) -> SessionStats {
+        let ts = (now - chrono::Duration::seconds(secs)).to_rfc3339();
+        SessionStats {
+            last_user_ts: Some(ts.clone()),
+            last_assistant_ts: Some(ts),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pane_title_running_maps_known_markers() {
+        assert_eq!(pane_title_running("⠐ Refactoring parser"), Some(true));
+        assert_eq!(pane_title_running("⣾ Compiling"), Some(true));
+        assert_eq!(pane_title_running("✦  Analyzing the codebase"), Some(true));
+        assert_eq!(pane_title_running("✳ Refactor parser"), Some(false));
+        assert_eq!(pane_title_running("◇  Ready (hydra)"), Some(false));
+        assert_eq!(
+            pane_title_running("✋  Action Required (hydra)"),
+            Some(false)
+        );
+        assert_eq!(pane_title_running("  ✦ leading space"), Some(true));
+    }
+
+    #[test]
+    fn pane_title_running_ignores_unknown_titles() {
+        assert_eq!(pane_title_running("devbox.local"), None);
+        assert_eq!(pane_title_running(""), None);
+        assert_eq!(pane_title_running("   "), None);
+        // The blank braille cell is padding, not a spinner frame.
+        assert_eq!(pane_title_running("\u{2800}idle"), None);
+    }
+
+    #[test]
+    fn pane_title_strategy_prefers_title_over_logs() {
+        let gemini = |name: &str| Session {
+            name: name.to_string(),
+            tmux_name: format!("hydra-test-{name}"),
+            agent_type: AgentType::Gemini,
+            process_state: ProcessState::Alive,
+            agent_state: AgentState::Unknown,
+            last_activity_at: Instant::now(),
+            task_elapsed: None,
+            _alive: true,
+        };
+        let mut sessions = vec![gemini("busy"), gemini("ready"), gemini("plain")];
+        let working = SessionStats {
+            last_user_ts: Some(Utc::now().to_rfc3339()),
+            ..Default::default()
+        };
+        let stats: HashMap<String, SessionStats> = sessions
+            .iter()
+            .map(|s| (s.tmux_name.clone(), working.clone()))
+            .collect();
+        let titles = HashMap::from([
+            ("hydra-test-busy".to_string(), "✦ Thinking".to_string()),
+            (
+                "hydra-test-ready".to_string(),
+                "◇  Ready (proj)".to_string(),
+            ),
+            ("hydra-test-plain".to_string(), "devbox".to_string()),
+        ]);
+
+        let mut runtime = SessionRuntime::new();
+        runtime.apply_statuses(
+            &mut sessions,
+            &HashMap::new(),
+            &stats,
+            None,
+            Some(&titles),
+            Instant::now(),
+        );
+
+        let states: Vec<_> = sessions.iter().map(|s| s.agent_state.clone()).collect();
+        // "plain" has no marker, so the working log decides.
+        assert_eq!(
+            states,
+            [AgentState::Thinking, AgentState::Idle, AgentState::Thinking]
+        );
+    }
+
+    #[test]
+    fn awaiting_input_takes_precedence_over_running() {
+        let claude = |name: &str| Session {
+            name: name.to_string(),
+            tmux_name: format!("hydra-test-{name}"),
+            agent_type: AgentType::Claude,
+            process_state: ProcessState::Alive,
+            agent_state: AgentState::Unknown,
+            last_activity_at: Instant::now(),
+            task_elapsed: None,
+            _alive: true,
+        };
+        let mut sessions = vec![claude("blocked"), claude("busy")];
+        let working = SessionStats {
+            last_user_ts: Some(Utc::now().to_rfc3339()),
+            ..Default::default()
+        };
+        let blocked = SessionStats {
+            awaiting_input: true,
+            ..working.clone()
+        };
+        let stats = HashMap::from([
+            ("hydra-test-blocked".to_string(), blocked),
+            ("hydra-test-busy".to_string(), working),
+        ]);
+
+        let mut runtime = SessionRuntime::new();
+        runtime.apply_statuses(
+            &mut sessions,
+            &HashMap::new(),
+            &stats,
+            None,
+            None,
+            Instant::now(),
+        );
+
+        assert_eq!(sessions[0].visual_status(), VisualStatus::NeedsInput);
+        assert_eq!(
+            sessions[1].visual_status(),
+            VisualStatus::Running("Thinking".to_string())
+        );
+    }
+
+    #[test]
+    fn idle_kill_due_respects_threshold_boundary() {
+        let now = Utc::now();
+        let due = |secs| {
+            idle_kill_due(
+                &VisualStatus::Idle,
+                Some(&stats_idle_for(now, secs)),
+                now,
+                HOUR,
+            )
+        };
+        assert!(!due(3599));
+        assert!(!due(3600), "exactly at the threshold is not yet due");
+        assert!(due(3601));
+    }
+
+    #[test]
+    fn idle_kill_due_never_kills_sessions_with_subagents() {
+        let now = Utc::now();
+        let mut stats = stats_idle_for(now, 10 * 3600);
+        stats.active_subagents = 1;
+        assert!(!idle_kill_due(&VisualStatus::Idle, Some(&stats), now, HOUR));
+        stats.active_subagents = 0;
+        assert!(idle_kill_due(&VisualStatus::Idle, Some(&stats), now, HOUR));
+    }
+
+    #[test]
+    fn idle_kill_due_never_kills_running_or_unknown_sessions() {
+        let now = Utc::now();
+        let stats = stats_idle_for(now, 10 * 3600);
+        let running = VisualStatus::Running("Thinking".to_string());
+        assert!(!idle_kill_due(&running, Some(&stats), now, HOUR));
+        assert!(!idle_kill_due(
+            &VisualStatus::Booting,
+            Some(&stats),
+            now,
+            HOUR
+        ));
+        assert!(!idle_kill_due(&VisualStatus::Idle, None, now, HOUR));
+        let no_activity = SessionStats::default();
+        assert!(!idle_kill_due(
+            &VisualStatus::Idle,
+            Some(&no_activity),
+            now,
+            HOUR
+        ));
+        assert!(idle_kill_due(
+            &VisualStatus::Exited,
+            Some(&stats),
+            now,
+            HOUR
+        ));
+    }
+}
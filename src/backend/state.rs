@@ -2,15 +2,19 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::time::{Duration, Instant};
 
 use crate::agent::provider_for;
-use crate::logs::{ConversationEntry, GlobalStats, SessionStats};
+use crate::logs::{ConversationEntry, GlobalStats, SessionStats, ToolCategories};
 use crate::models::DiffFile;
 use crate::session::{AgentType, Session, VisualStatus};
 use crate::system::git::get_git_diff_numstat;
 
-/// Per-session conversation buffer parsed from JSONL logs.
+/// Per-session conversation buffer parsed from JSONL logs. Holds at most
+/// `MAX_ENTRIES` entries; once older ones are evicted, a
+/// `HistoryTruncated` marker leads the buffer. `read_offset` is tracked
+/// separately, so eviction never stalls reading.
 pub(crate) struct ConversationBuffer {
     pub(crate) entries: VecDeque<ConversationEntry>,
     pub(crate) read_offset: u64,
+    dropped: usize,
 }
 
 impl ConversationBuffer {
@@ -20,17 +24,31 @@ impl ConversationBuffer {
         Self {
             entries: VecDeque::new(),
             read_offset: 0,
+            dropped: 0,
         }
     }
 
     pub(crate) fn extend(&mut self, new_entries: Vec<ConversationEntry>) {
-        for entry in new_entries {
-            if self.entries.len() >= Self::MAX_ENTRIES {
-                self.entries.pop_front();
-            }
-            self.entries.push_back(entry);
+        if self.dropped > 0 {
+            self.entries.pop_front();
+        }
+        self.entries.extend(new_entries);
+        let excess = self.entries.len().saturating_sub(Self::MAX_ENTRIES);
+        self.entries.drain(..excess);
+        self.dropped += excess;
+        if self.dropped > 0 {
+            self.entries
+                .push_front(ConversationEntry::HistoryTruncated {
+                    dropped: self.dropped,
+                });
         }
     }
+
+    /// Drop every entry, ahead of re-reading the log from scratch.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.dropped = 0;
+    }
 }
 
 /// Results from a background message/stats refresh task.
@@ -118,7 +136,7 @@ impl TaskTimers {
                         Some(now.duration_since(start))
                     });
                 }
-                VisualStatus::Idle | VisualStatus::Booting => {
+                VisualStatus::Idle | VisualStatus::NeedsInput | VisualStatus::Booting => {
                     if log_elapsed.is_some() {
                         session.task_elapsed = log_elapsed;
                     } else if let (Some(&start), Some(&last)) = (
@@ -133,7 +151,7 @@ impl TaskTimers {
                         }
                     }
                 }
-                VisualStatus::Exited => {
+                VisualStatus::Exited | VisualStatus::Stopped => {
                     self.task_starts.remove(&name);
                     self.task_last_active.remove(&name);
                 }
@@ -149,11 +167,18 @@ impl TaskTimers {
 }
 
 /// Background task state for async message/stats/diff refresh.
+/// Message refresh cadence: every 40 ticks (~2 seconds at 50ms tick rate).
+const MESSAGE_REFRESH_TICKS: u8 = 40;
+
 pub(crate) struct BackgroundRefreshState {
     log_uuids: HashMap<String, String>,
     uuid_retry_cooldowns: HashMap<String, u8>,
     message_tick: u8,
+    /// Set by `force_refresh`; starts a refresh off-cadence.
+    refresh_requested: bool,
     bg_refresh_rx: Option<tokio::sync::oneshot::Receiver<MessageRefreshResult>>,
+    /// Installed on the stats of each newly tracked session.
+    tool_categories: ToolCategories,
 }
 
 impl BackgroundRefreshState {
@@ -162,15 +187,29 @@ impl BackgroundRefreshState {
             log_uuids: HashMap::new(),
             uuid_retry_cooldowns: HashMap::new(),
             message_tick: 0,
+            refresh_requested: false,
             bg_refresh_rx: None,
+            tool_categories: ToolCategories::default(),
         }
     }
 
+    pub(crate) fn set_tool_categories(&mut self, categories: ToolCategories) {
+        self.tool_categories = categories;
+    }
+
+    /// Start a refresh on the next tick (or as soon as the running one
+    /// finishes) instead of waiting for the cadence, and let `tmux_name`
+    /// retry log resolution right away.
+    pub(crate) fn force_refresh(&mut self, tmux_name: &str) {
+        self.uuid_retry_cooldowns.remove(tmux_name);
+        self.refresh_requested = true;
+    }
+
     /// Poll for completed background results and spawn new tasks on cadence.
     /// Returns `Some(result)` when a background task completes.
     pub(crate) fn tick(
         &mut self,
-        sessions: &[(String, AgentType)],
+        sessions: &[(String, AgentType, String)],
         session_stats: &HashMap<String, SessionStats>,
         global_stats: &GlobalStats,
         cwd: &str,
@@ -194,8 +233,7 @@ impl BackgroundRefreshState {
         }
 
         self.message_tick = self.message_tick.wrapping_add(1);
-        // Run every 40 ticks (~2 seconds at 50ms tick rate).
-        if !self.message_tick.is_multiple_of(40) {
+        if !self.message_tick.is_multiple_of(MESSAGE_REFRESH_TICKS) && !self.refresh_requested {
             return completed;
         }
 
@@ -210,10 +248,14 @@ impl BackgroundRefreshState {
         let uuid_retry_cooldowns = self.uuid_retry_cooldowns.clone();
         let session_stats = session_stats.clone();
         let global_stats = global_stats.clone();
-        let cwd = cwd.to_string();
+        let context = RefreshContext {
+            cwd: cwd.to_string(),
+            tool_categories: self.tool_categories.clone(),
+        };
 
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.bg_refresh_rx = Some(rx);
+        self.refresh_requested = false;
 
         tokio::spawn(async move {
             let result = compute_message_refresh(
@@ -222,8 +264,8 @@ impl BackgroundRefreshState {
                 uuid_retry_cooldowns,
                 session_stats,
                 global_stats,
-                cwd,
                 conversation_offsets,
+                context,
             )
             .await;
             let _ = tx.send(result);
@@ -232,6 +274,11 @@ impl BackgroundRefreshState {
         completed
     }
 
+    /// Resolved log ids by tmux session name.
+    pub(crate) fn log_ids(&self) -> &HashMap<String, String> {
+        &self.log_uuids
+    }
+
     /// Remove entries for sessions that no longer exist.
     pub(crate) fn prune(&mut self, live_keys: &HashSet<&String>) {
         self.log_uuids.retain(|k, _| live_keys.contains(k));
@@ -240,16 +287,25 @@ impl BackgroundRefreshState {
     }
 }
 
+/// Settings a background refresh reads but never changes.
+struct RefreshContext {
+    /// Project directory, for the git diff.
+    cwd: String,
+    /// Installed on the stats of each newly tracked session.
+    tool_categories: ToolCategories,
+}
+
 /// Background task: compute message refresh results off the main event loop.
 /// Runs UUID/rollout resolution, JSONL parsing, global stats, and git diff in a background task.
+/// Each session's log resolves from its own cwd; the git diff uses the project cwd.
 async fn compute_message_refresh(
-    sessions: Vec<(String, AgentType)>,
+    sessions: Vec<(String, AgentType, String)>,
     mut log_uuids: HashMap<String, String>,
     mut uuid_retry_cooldowns: HashMap<String, u8>,
     mut session_stats: HashMap<String, SessionStats>,
     mut global_stats: GlobalStats,
-    cwd: String,
     mut conversation_offsets: HashMap<String, u64>,
+    context: RefreshContext,
 ) -> MessageRefreshResult {
     /// Retry unresolved UUID discovery every ~30s (6 refresh cycles at 5s each).
     const UUID_RETRY_COOLDOWN_CYCLES: u8 = 6;
@@ -265,9 +321,9 @@ async fn compute_message_refresh(
     // newest-first. This prevents older Gemini sessions from claiming files that
     // belong to newer sessions (newer sessions have tighter filename filters).
     let mut sessions = sessions.to_vec();
-    sessions.sort_by(|(a, _), (b, _)| b.cmp(a));
+    sessions.sort_by(|(a, _, _), (b, _, _)| b.cmp(a));
 
-    for (tmux_name, agent_type) in &sessions {
+    for (tmux_name, agent_type, session_cwd) in &sessions {
         let provider = provider_for(agent_type);
         let cached_log_id = log_uuids.get(tmux_name).cloned();
         let has_cached_log = cached_log_id.is_some();
@@ -296,7 +352,7 @@ async fn compute_message_refresh(
                     .map(|(_, path)| path.clone())
                     .collect();
                 let resolved = provider
-                    .resolve_log_path(tmux_name, &cwd, &claimed_paths)
+                    .resolve_log_path(tmux_name, session_cwd, &claimed_paths)
                     .await;
 
                 if let Some(id) = resolved {
@@ -328,11 +384,16 @@ async fn compute_message_refresh(
         // Read last message, update stats, and parse conversation.
         if let Some(log_id) = log_uuids.get(tmux_name).cloned() {
             uuid_retry_cooldowns.remove(tmux_name);
-            let mut stats = session_stats.remove(tmux_name).unwrap_or_default();
+            let mut stats = session_stats
+                .remove(tmux_name)
+                .unwrap_or_else(|| SessionStats {
+                    tool_categories: context.tool_categories.clone(),
+                    ..Default::default()
+                });
             let previous_offset = conversation_offsets.remove(tmux_name).unwrap_or(0);
             let conv_offset = if log_path_changed { 0 } else { previous_offset };
 
-            let cwd_clone = cwd.clone();
+            let cwd_clone = session_cwd.clone();
             let agent_type_clone = agent_type.clone();
             let (update, stats) = tokio::task::spawn_blocking(move || {
                 let provider = provider_for(&agent_type_clone);
@@ -366,7 +427,7 @@ async fn compute_message_refresh(
     .unwrap();
 
     // Refresh per-file git diff stats.
-    let diff_files = get_git_diff_numstat(&cwd).await;
+    let diff_files = get_git_diff_numstat(&context.cwd).await;
 
     MessageRefreshResult {
         log_uuids,
@@ -381,3 +442,76 @@ async fn compute_message_refresh(
         conversation_replace,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(i: usize) -> ConversationEntry {
+        ConversationEntry::UserMessage {
+            text: format!("msg {i}"),
+        }
+    }
+
+    fn texts(buf: &ConversationBuffer) -> Vec<String> {
+        buf.entries
+            .iter()
+            .filter_map(|entry| match entry {
+                ConversationEntry::UserMessage { text } => Some(text.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn conversation_buffer_under_cap_has_no_marker() {
+        let mut buf = ConversationBuffer::new();
+        buf.extend((0..3).map(user).collect());
+        assert_eq!(buf.entries.len(), 3);
+        assert_eq!(texts(&buf), ["msg 0", "msg 1", "msg 2"]);
+    }
+
+    #[test]
+    fn conversation_buffer_evicts_oldest_behind_a_marker() {
+        let max = ConversationBuffer::MAX_ENTRIES;
+        let mut buf = ConversationBuffer::new();
+        buf.extend((0..max + 2).map(user).collect());
+        assert!(matches!(
+            buf.entries.front(),
+            Some(ConversationEntry::HistoryTruncated { dropped: 2 })
+        ));
+        assert_eq!(buf.entries.len(), max + 1);
+        assert_eq!(texts(&buf).first().map(String::as_str), Some("msg 2"));
+
+        // Later appends keep a single marker at the front and count every drop.
+        buf.extend(vec![user(max + 2)]);
+        buf.extend(vec![user(max + 3)]);
+        assert!(matches!(
+            buf.entries.front(),
+            Some(ConversationEntry::HistoryTruncated { dropped: 4 })
+        ));
+        let markers = buf
+            .entries
+            .iter()
+            .filter(|e| matches!(e, ConversationEntry::HistoryTruncated { .. }))
+            .count();
+        assert_eq!(markers, 1);
+        assert_eq!(buf.entries.len(), max + 1);
+        let texts = texts(&buf);
+        assert_eq!(texts.first().map(String::as_str), Some("msg 4"));
+        assert_eq!(
+            texts.last().map(String::as_str),
+            Some(format!("msg {}", max + 3).as_str())
+        );
+    }
+
+    #[test]
+    fn conversation_buffer_clear_resets_the_marker() {
+        let mut buf = ConversationBuffer::new();
+        buf.extend((0..ConversationBuffer::MAX_ENTRIES + 1).map(user).collect());
+        buf.clear();
+        buf.extend(vec![user(0)]);
+        assert_eq!(texts(&buf), ["msg 0"]);
+        assert_eq!(buf.entries.len(), 1);
+    }
+}
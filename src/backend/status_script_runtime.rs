@@ -0,0 +1,226 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc;
+
+use crate::session::Session;
+use crate::system::status_script::{self, ScriptContext, STATUS_SCRIPT_TIMEOUT};
+
+/// Result of one script run: tmux session name and its new annotation.
+pub(crate) type ScriptResult = (String, Option<String>);
+
+/// Runs the configured status script for each session on a fixed cadence
+/// and keeps the latest annotation per session. Scripts run in spawned
+/// tasks so a slow one never holds up the backend loop; results come back
+/// over `results_tx`.
+pub(crate) struct StatusScriptRuntime {
+    command: Option<String>,
+    annotations: HashMap<String, String>,
+    last_run: HashMap<String, Instant>,
+    in_flight: HashSet<String>,
+    results_tx: mpsc::Sender<ScriptResult>,
+}
+
+impl StatusScriptRuntime {
+    /// How often the script is rerun for each session.
+    pub(crate) const INTERVAL: Duration = Duration::from_secs(10);
+
+    pub(crate) fn new(results_tx: mpsc::Sender<ScriptResult>) -> Self {
+        Self {
+            command: None,
+            annotations: HashMap::new(),
+            last_run: HashMap::new(),
+            in_flight: HashSet::new(),
+            results_tx,
+        }
+    }
+
+    /// Set the shell command to run; blank or `None` turns scripts off.
+    pub(crate) fn set_command(&mut self, command: Option<String>) {
+        self.command = command.filter(|c| !c.trim().is_empty());
+    }
+
+    pub(crate) fn annotations(&self) -> &HashMap<String, String> {
+        &self.annotations
+    }
+
+    /// Start a run for every session whose last one is older than
+    /// `INTERVAL` and isn't still going. `cwd_for` gives the directory each
+    /// session runs in.
+    pub(crate) fn spawn_due(
+        &mut self,
+        sessions: &[Session],
+        cwd_for: impl Fn(&Session) -> String,
+        now: Instant,
+    ) {
+        let Some(command) = &self.command else {
+            return;
+        };
+        for session in sessions {
+            let due = self
+                .last_run
+                .get(&session.tmux_name)
+                .is_none_or(|at| now.duration_since(*at) >= Self::INTERVAL);
+            if !due || self.in_flight.contains(&session.tmux_name) {
+                continue;
+            }
+            self.last_run.insert(session.tmux_name.clone(), now);
+            self.in_flight.insert(session.tmux_name.clone());
+
+            let command = command.clone();
+            let ctx = ScriptContext {
+                name: session.name.clone(),
+                tmux_name: session.tmux_name.clone(),
+                agent: session.agent_type.to_string().to_lowercase(),
+                cwd: cwd_for(session),
+            };
+            let tx = self.results_tx.clone();
+            tokio::spawn(async move {
+                // A failing or slow script just clears the annotation.
+                let annotation = status_script::run(&command, &ctx, STATUS_SCRIPT_TIMEOUT)
+                    .await
+                    .unwrap_or(None);
+                let _ = tx.send((ctx.tmux_name, annotation)).await;
+            });
+        }
+    }
+
+    /// Record a finished run. Returns true if the annotation changed.
+    pub(crate) fn apply(&mut self, (tmux_name, annotation): ScriptResult) -> bool {
+        if !self.in_flight.remove(&tmux_name) {
+            // Session was pruned while its script ran.
+            return false;
+        }
+        match annotation {
+            Some(text) => self.annotations.insert(tmux_name, text.clone()) != Some(text),
+            None => self.annotations.remove(&tmux_name).is_some(),
+        }
+    }
+
+    pub(crate) fn prune(&mut self, live_keys: &HashSet<&String>) {
+        self.annotations.retain(|k, _| live_keys.contains(k));
+        self.last_run.retain(|k, _| live_keys.contains(k));
+        self.in_flight.retain(|k| live_keys.contains(k));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{AgentState, AgentType, ProcessState};
+
+    fn session(name: &str) -> Session {
+        Session {
+            name: name.to_string(),
+            tmux_name: format!("hydra-test-{name}"),
+            agent_type: AgentType::Claude,
+            process_state: ProcessState::Alive,
+            agent_state: AgentState::Idle,
+            last_activity_at: Instant::now(),
+            task_elapsed: None,
+            _alive: true,
+        }
+    }
+
+    fn cwd() -> String {
+        std::env::temp_dir().to_string_lossy().into_owned()
+    }
+
+    #[tokio::test]
+    async fn runs_script_and_records_annotation() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let mut runtime = StatusScriptRuntime::new(tx);
+        runtime.set_command(Some("echo \"CI $HYDRA_SESSION\"".to_string()));
+        runtime.spawn_due(&[session("alpha")], |_| cwd(), Instant::now());
+
+        let result = rx.recv().await.unwrap();
+        assert_eq!(
+            result,
+            ("hydra-test-alpha".to_string(), Some("CI alpha".to_string()))
+        );
+        assert!(runtime.apply(result.clone()));
+        assert_eq!(
+            runtime
+                .annotations()
+                .get("hydra-test-alpha")
+                .map(String::as_str),
+            Some("CI alpha")
+        );
+    }
+
+    #[tokio::test]
+    async fn waits_for_the_interval_between_runs() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let mut runtime = StatusScriptRuntime::new(tx);
+        runtime.set_command(Some("echo ok".to_string()));
+        let sessions = [session("alpha")];
+        let start = Instant::now();
+        runtime.spawn_due(&sessions, |_| cwd(), start);
+        let result = rx.recv().await.unwrap();
+        runtime.apply(result);
+
+        runtime.spawn_due(&sessions, |_| cwd(), start + Duration::from_secs(1));
+        assert!(rx.try_recv().is_err());
+        runtime.spawn_due(&sessions, |_| cwd(), start + StatusScriptRuntime::INTERVAL);
+        assert!(rx.recv().await.is_some());
+    }
+
+    #[test]
+    fn apply_clears_annotation_on_failure_and_ignores_pruned_sessions() {
+        let (tx, _rx) = mpsc::channel(8);
+        let mut runtime = StatusScriptRuntime::new(tx);
+        let key = "hydra-test-alpha".to_string();
+        runtime.in_flight.insert(key.clone());
+        assert!(runtime.apply((key.clone(), Some("red".to_string()))));
+
+        runtime.in_flight.insert(key.clone());
+        assert!(!runtime.apply((key.clone(), Some("red".to_string()))));
+
+        runtime.in_flight.insert(key.clone());
+        assert!(runtime.apply((key.clone(), None)));
+        assert!(runtime.annotations().is_empty());
+
+        assert!(!runtime.apply((key, Some("late".to_string()))));
+        assert!(runtime.annotations().is_empty());
+    }
+
+    #[tokio::test]
+    async fn runs_in_each_sessions_own_cwd() {
+        let dirs = [tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap()];
+        let cwds: HashMap<String, String> = ["alpha", "bravo"]
+            .iter()
+            .zip(&dirs)
+            .map(|(name, dir)| {
+                let path = std::fs::canonicalize(dir.path()).unwrap();
+                (name.to_string(), path.to_string_lossy().into_owned())
+            })
+            .collect();
+        let (tx, mut rx) = mpsc::channel(8);
+        let mut runtime = StatusScriptRuntime::new(tx);
+        runtime.set_command(Some("echo \"$(pwd -P)|$HYDRA_CWD\"".to_string()));
+        runtime.spawn_due(
+            &[session("alpha"), session("bravo")],
+            |s| cwds[&s.name].clone(),
+            Instant::now(),
+        );
+
+        let mut results = vec![rx.recv().await.unwrap(), rx.recv().await.unwrap()];
+        results.sort();
+        for ((tmux_name, annotation), name) in results.into_iter().zip(["alpha", "bravo"]) {
+            assert_eq!(tmux_name, format!("hydra-test-{name}"));
+            let cwd = &cwds[name];
+            assert_eq!(annotation, Some(format!("{cwd}|{cwd}")));
+        }
+    }
+
+    #[tokio::test]
+    async fn no_command_means_no_runs() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let mut runtime = StatusScriptRuntime::new(tx);
+        runtime.set_command(Some("   ".to_string()));
+        runtime.spawn_due(&[session("alpha")], |_| cwd(), Instant::now());
+        assert!(runtime.in_flight.is_empty());
+        drop(runtime);
+        assert!(rx.recv().await.is_none());
+    }
+}
@@ -0,0 +1,138 @@
+//! Audible cue when the watched session stops working (`bell` and
+//! `bell_command` in config.json).
+
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use tokio::process::Command;
+
+use crate::session::VisualStatus;
+
+/// Shortest gap between two bells, so a session flickering between
+/// working and idle rings once.
+pub const BELL_DEBOUNCE: Duration = Duration::from_secs(10);
+
+/// How to ring.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bell {
+    /// Write BEL to the terminal.
+    Terminal,
+    /// Run this shell command instead.
+    Command(String),
+}
+
+/// Watches one session's status across snapshots and decides when to ring.
+#[derive(Debug, Default)]
+pub struct BellTracker {
+    /// Tmux name of the session seen last time, and whether it was working.
+    watched: Option<(String, bool)>,
+    last_bell: Option<Instant>,
+}
+
+impl BellTracker {
+    /// Record the watched session's status. True when the same session
+    /// went from working to idle or needing input and no bell rang within
+    /// `BELL_DEBOUNCE`. Switching to another session never rings.
+    pub fn observe(
+        &mut self,
+        tmux_name: Option<&str>,
+        status: Option<&VisualStatus>,
+        now: Instant,
+    ) -> bool {
+        let was_working = match (&self.watched, tmux_name) {
+            (Some((previous, working)), Some(name)) => previous == name && *working,
+            _ => false,
+        };
+        let working = matches!(status, Some(VisualStatus::Running(_)));
+        self.watched = tmux_name.map(|name| (name.to_string(), working));
+        let stopped = matches!(status, Some(VisualStatus::Idle | VisualStatus::NeedsInput));
+        if !was_working || !stopped {
+            return false;
+        }
+        if self
+            .last_bell
+            .is_some_and(|at| now.duration_since(at) < BELL_DEBOUNCE)
+        {
+            return false;
+        }
+        self.last_bell = Some(now);
+        true
+    }
+}
+
+/// Start `command` through `sh -c` without waiting for it. The session is
+/// passed as `HYDRA_SESSION` and `HYDRA_AGENT`; its output is discarded.
+pub fn spawn_command(command: &str, name: &str, agent: &str) -> Result<()> {
+    Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("HYDRA_SESSION", name)
+        .env("HYDRA_AGENT", agent)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("bell command failed to start")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn running() -> VisualStatus {
+        VisualStatus::Running("Thinking".to_string())
+    }
+
+    #[test]
+    fn rings_when_the_watched_session_stops_working() {
+        let start = Instant::now();
+        for done in [VisualStatus::Idle, VisualStatus::NeedsInput] {
+            let mut tracker = BellTracker::default();
+            assert!(!tracker.observe(Some("a"), Some(&running()), start));
+            assert!(tracker.observe(Some("a"), Some(&done), start));
+            // Staying idle doesn't ring again.
+            assert!(!tracker.observe(Some("a"), Some(&done), start + BELL_DEBOUNCE));
+        }
+    }
+
+    #[test]
+    fn other_transitions_stay_quiet() {
+        let start = Instant::now();
+        let mut tracker = BellTracker::default();
+        // Idle from the start, then starting work.
+        assert!(!tracker.observe(Some("a"), Some(&VisualStatus::Idle), start));
+        assert!(!tracker.observe(Some("a"), Some(&running()), start));
+        // Dying isn't finishing.
+        assert!(!tracker.observe(Some("a"), Some(&VisualStatus::Exited), start));
+        assert!(!tracker.observe(Some("a"), Some(&VisualStatus::Idle), start));
+        // Nothing watched.
+        assert!(!tracker.observe(None, None, start));
+    }
+
+    #[test]
+    fn switching_sessions_does_not_ring() {
+        let start = Instant::now();
+        let mut tracker = BellTracker::default();
+        tracker.observe(Some("a"), Some(&running()), start);
+        assert!(!tracker.observe(Some("b"), Some(&VisualStatus::Idle), start));
+        // Back on `a`, which finished while unwatched.
+        assert!(!tracker.observe(Some("a"), Some(&VisualStatus::Idle), start));
+    }
+
+    #[test]
+    fn bells_are_debounced() {
+        let start = Instant::now();
+        let mut tracker = BellTracker::default();
+        let mut ring_at = |secs: u64| {
+            let now = start + Duration::from_secs(secs);
+            tracker.observe(Some("a"), Some(&running()), now);
+            tracker.observe(Some("a"), Some(&VisualStatus::Idle), now)
+        };
+        assert!(ring_at(0));
+        assert!(!ring_at(3));
+        assert!(!ring_at(9));
+        assert!(ring_at(10));
+    }
+}
@@ -0,0 +1,171 @@
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether CLI (non-TUI) output may contain ANSI color escapes.
+static COLOR_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Whether `--quiet` was passed: progress and confirmation lines are dropped.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// ANSI styles used by CLI output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Paint {
+    Bold,
+    Dim,
+    Red,
+    Yellow,
+    Cyan,
+    Blue,
+}
+
+impl Paint {
+    fn code(self) -> &'static str {
+        match self {
+            Paint::Bold => "1",
+            Paint::Dim => "2",
+            Paint::Red => "1;31",
+            Paint::Yellow => "1;33",
+            Paint::Cyan => "36",
+            Paint::Blue => "34",
+        }
+    }
+}
+
+/// Decide whether to color output: off when `--no-color` is passed or the
+/// `NO_COLOR` env var is set to a non-empty value (see no-color.org).
+pub fn color_wanted(no_color_flag: bool, no_color_env: Option<&str>) -> bool {
+    !no_color_flag && no_color_env.is_none_or(str::is_empty)
+}
+
+/// Configure color once at startup from the CLI flag and environment.
+pub fn init(no_color_flag: bool) {
+    let env = std::env::var("NO_COLOR").ok();
+    COLOR_ENABLED.store(
+        color_wanted(no_color_flag, env.as_deref()),
+        Ordering::Relaxed,
+    );
+}
+
+pub fn color_enabled() -> bool {
+    COLOR_ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Print a progress or confirmation line to stdout, unless `--quiet` was
+/// passed. A command's actual output (listings, reports) and errors don't
+/// go through here.
+pub fn info(line: impl std::fmt::Display) {
+    info_to(&mut std::io::stdout(), quiet(), line);
+}
+
+fn info_to(out: &mut impl Write, quiet: bool, line: impl std::fmt::Display) {
+    if !quiet {
+        let _ = writeln!(out, "{line}");
+    }
+}
+
+/// Wrap `text` in `paint` when color is enabled.
+pub fn paint(text: &str, paint: Paint) -> String {
+    paint_with(text, paint, color_enabled())
+}
+
+fn paint_with(text: &str, paint: Paint, color: bool) -> String {
+    if color {
+        format!("\x1b[{}m{text}\x1b[0m", paint.code())
+    } else {
+        text.to_string()
+    }
+}
+
+/// Pass pre-styled text through, stripping ANSI escapes when color is off.
+pub fn styled(text: &str) -> String {
+    if color_enabled() {
+        text.to_string()
+    } else {
+        strip_ansi(text)
+    }
+}
+
+/// Remove ANSI CSI escape sequences (`ESC [ ... final-byte`) from `text`.
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            chars.next();
+            // Parameter/intermediate bytes run until a final byte in 0x40..=0x7E.
+            for c in chars.by_ref() {
+                if ('\x40'..='\x7e').contains(&c) {
+                    break;
+                }
+            }
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// A prominent `WARNING:`-style banner for problems the user must not miss.
+pub fn banner(label: &str, message: &str) -> String {
+    banner_with(label, message, color_enabled())
+}
+
+fn banner_with(label: &str, message: &str, color: bool) -> String {
+    format!(
+        "{} {message}",
+        paint_with(&format!("{label}:"), Paint::Red, color)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_wanted_respects_flag_and_env() {
+        assert!(color_wanted(false, None));
+        assert!(color_wanted(false, Some("")));
+        assert!(!color_wanted(false, Some("1")));
+        assert!(!color_wanted(true, None));
+    }
+
+    #[test]
+    fn banner_without_color_has_no_escapes() {
+        let out = banner_with("warning", "binary may end up root-owned", false);
+        assert_eq!(out, "warning: binary may end up root-owned");
+        assert!(!out.contains('\x1b'));
+    }
+
+    #[test]
+    fn banner_with_color_is_wrapped() {
+        let out = banner_with("warning", "careful", true);
+        assert!(out.starts_with("\x1b[1;31mwarning:\x1b[0m"));
+        assert_eq!(strip_ansi(&out), "warning: careful");
+    }
+
+    #[test]
+    fn info_is_dropped_when_quiet() {
+        let mut out = Vec::new();
+        info_to(&mut out, false, "Updating hydra to v1.2.0...");
+        info_to(&mut out, true, "Downloading crates ...");
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Updating hydra to v1.2.0...\n"
+        );
+    }
+
+    #[test]
+    fn strip_ansi_removes_csi_sequences_only() {
+        assert_eq!(strip_ansi("\x1b[1;33mhi\x1b[0m there"), "hi there");
+        assert_eq!(strip_ansi("plain [brackets]"), "plain [brackets]");
+        assert_eq!(strip_ansi("日本\x1b[2m語\x1b[0m"), "日本語");
+    }
+}
@@ -0,0 +1,115 @@
+//! Bodies of the CLI subcommands that read session logs. They share the
+//! resolution below, which hands each session the same log the TUI would.
+
+pub mod diff_files;
+pub mod ls;
+pub mod serve;
+pub mod stats;
+pub mod transcript;
+
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
+
+use hydra::session::{AgentType, Session};
+use hydra::{agent, logs, manifest, tmux};
+
+/// A session and the agent log it resolved to.
+struct SessionLog {
+    session: Session,
+    /// Directory the session runs in, which `log_id` is relative to.
+    cwd: String,
+    log_id: Option<String>,
+}
+
+/// Resolve every session of `project_id` to its agent log, returned in
+/// name order. Sessions are resolved in TUI order (names descending), so
+/// a log claimed by one session is never handed to another.
+async fn resolve_session_logs(project_id: &str, cwd: &str) -> Result<Vec<SessionLog>> {
+    let manager = tmux::TmuxSessionManager::new();
+    let mut sessions =
+        manifest::list_named_sessions(&manager, &manifest::default_base_dir(), project_id).await?;
+    sessions.sort_by(|a, b| b.name.cmp(&a.name));
+    let cwds = manifest::load_session_cwds(&manifest::default_base_dir(), project_id).await;
+    let mut claimed_paths = HashSet::new();
+    let mut resolved = Vec::with_capacity(sessions.len());
+    for session in sessions {
+        let session_cwd = manifest::session_cwd(&cwds, cwd, &session.name).to_string();
+        let log_id = agent::provider_for(&session.agent_type)
+            .resolve_log_path(&session.tmux_name, &session_cwd, &claimed_paths)
+            .await;
+        claimed_paths.extend(log_id.clone());
+        resolved.push(SessionLog {
+            session,
+            cwd: session_cwd,
+            log_id,
+        });
+    }
+    resolved.reverse();
+    Ok(resolved)
+}
+
+/// Every session of `project_id` in name order, with stats parsed from its
+/// whole agent log. Stats are `None` when no log can be resolved.
+pub async fn resolved_session_stats(
+    project_id: &str,
+    cwd: &str,
+    categories: &logs::ToolCategories,
+) -> Result<Vec<(Session, Option<logs::SessionStats>)>> {
+    let mut sessions = Vec::new();
+    for log in resolve_session_logs(project_id, cwd).await? {
+        let stats = match &log.log_id {
+            Some(log_id) => {
+                parse_log_stats(&log.session.agent_type, log_id, &log.cwd, categories).await
+            }
+            None => None,
+        };
+        sessions.push((log.session, stats));
+    }
+    Ok(sessions)
+}
+
+/// Parse a whole agent log into fresh `SessionStats`.
+async fn parse_log_stats(
+    agent_type: &AgentType,
+    log_id: &str,
+    cwd: &str,
+    categories: &logs::ToolCategories,
+) -> Option<logs::SessionStats> {
+    let agent_type = agent_type.clone();
+    let log_id = log_id.to_string();
+    let cwd = cwd.to_string();
+    let tool_categories = categories.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut stats = logs::SessionStats {
+            tool_categories,
+            ..Default::default()
+        };
+        agent::provider_for(&agent_type).update_from_log(&log_id, &cwd, 0, &mut stats);
+        stats
+    })
+    .await
+    .ok()
+}
+
+/// Resolve the agent log id for session `name`, along with the directory it
+/// runs in (which the id is relative to), failing with a hint when the
+/// provider can't find one.
+pub async fn resolve_session_log(
+    project_id: &str,
+    cwd: &str,
+    name: &str,
+) -> Result<(AgentType, String, String)> {
+    let logs = resolve_session_logs(project_id, cwd).await?;
+    let Some(log) = logs.into_iter().find(|log| log.session.name == name) else {
+        bail!("No session named '{name}' in this project");
+    };
+    let agent_type = log.session.agent_type;
+    let Some(log_id) = log.log_id else {
+        bail!(
+            "Could not resolve a log file for '{name}' [{agent_type}]: {}",
+            agent::provider_for(&agent_type).log_resolution_hint()
+        );
+    };
+    Ok((agent_type, log_id, log.cwd))
+}
@@ -0,0 +1,107 @@
+//! `hydra diff-files`: the files two sessions touched, split three ways.
+
+use std::collections::HashSet;
+
+use anyhow::{bail, Context, Result};
+
+use hydra::cli_style::{self, Paint};
+use hydra::config;
+
+use super::resolved_session_stats;
+
+pub async fn cmd_diff_files(
+    project_id: &str,
+    cwd: &str,
+    a: &str,
+    b: &str,
+    user_config: &config::Config,
+) -> Result<()> {
+    if a == b {
+        bail!("Pick two different sessions to compare");
+    }
+    let categories = user_config.session_tool_categories();
+    let sessions = resolved_session_stats(project_id, cwd, &categories).await?;
+    let mut files = [None, None];
+    for (slot, name) in files.iter_mut().zip([a, b]) {
+        let Some((_, stats)) = sessions.iter().find(|(s, _)| s.name == name) else {
+            bail!("No session named '{name}' in this project");
+        };
+        *slot = stats.as_ref().map(|st| &st.files);
+    }
+    let [files_a, files_b] = files;
+    let files_a = files_a.with_context(|| format!("Could not resolve a log file for '{a}'"))?;
+    let files_b = files_b.with_context(|| format!("Could not resolve a log file for '{b}'"))?;
+
+    let split = partition_files(files_a, files_b);
+    for (heading, files, paint) in [
+        (format!("Only in {a}"), &split.only_a, Paint::Bold),
+        (format!("Only in {b}"), &split.only_b, Paint::Bold),
+        ("In both".to_string(), &split.both, Paint::Yellow),
+    ] {
+        println!(
+            "{}",
+            cli_style::paint(&format!("{heading} ({})", files.len()), paint)
+        );
+        for file in files {
+            println!("  {file}");
+        }
+    }
+    Ok(())
+}
+
+/// Files touched by two sessions, split three ways and sorted.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct FilePartition {
+    only_a: Vec<String>,
+    only_b: Vec<String>,
+    both: Vec<String>,
+}
+
+fn partition_files(a: &HashSet<String>, b: &HashSet<String>) -> FilePartition {
+    let sorted = |files: Vec<&String>| {
+        let mut files: Vec<String> = files.into_iter().cloned().collect();
+        files.sort();
+        files
+    };
+    FilePartition {
+        only_a: sorted(a.difference(b).collect()),
+        only_b: sorted(b.difference(a).collect()),
+        both: sorted(a.intersection(b).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_set(files: &[&str]) -> HashSet<String> {
+        files.iter().map(|f| f.to_string()).collect()
+    }
+
+    #[test]
+    fn partition_files_splits_three_ways() {
+        let a = file_set(&["src/b.rs", "src/a.rs", "README.md"]);
+        let b = file_set(&["src/a.rs", "Cargo.toml", "README.md"]);
+        assert_eq!(
+            partition_files(&a, &b),
+            FilePartition {
+                only_a: vec!["src/b.rs".to_string()],
+                only_b: vec!["Cargo.toml".to_string()],
+                both: vec!["README.md".to_string(), "src/a.rs".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn partition_files_handles_empty_and_disjoint_sets() {
+        let a = file_set(&["x.rs"]);
+        let empty = HashSet::new();
+        let split = partition_files(&a, &empty);
+        assert_eq!(split.only_a, ["x.rs"]);
+        assert!(split.only_b.is_empty() && split.both.is_empty());
+
+        let split = partition_files(&a, &file_set(&["y.rs"]));
+        assert_eq!((split.only_a.len(), split.only_b.len()), (1, 1));
+        assert!(split.both.is_empty());
+    }
+}
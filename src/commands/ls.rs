@@ -0,0 +1,132 @@
+//! `hydra ls`: this project's sessions, one line each or as a table.
+
+use std::io::{self, IsTerminal};
+
+use anyhow::Result;
+use unicode_width::UnicodeWidthStr;
+
+use hydra::cli_style::{self, Paint};
+use hydra::{config, logs, ls_long, manifest, session};
+
+use super::resolved_session_stats;
+
+/// Tags as `#a #b`.
+pub fn format_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|t| format!("#{t}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub async fn cmd_ls(
+    project_id: &str,
+    cwd: &str,
+    tag: Option<&str>,
+    long: bool,
+    user_config: &config::Config,
+) -> Result<()> {
+    let categories = user_config.session_tool_categories();
+    let mut sessions = resolved_session_stats(project_id, cwd, &categories).await?;
+    let session_tags = manifest::load_session_tags(&manifest::default_base_dir(), project_id).await;
+    if let Some(tag) = tag {
+        sessions.retain(|(s, _)| {
+            session_tags
+                .get(&s.name)
+                .is_some_and(|t| t.iter().any(|t| t == tag))
+        });
+    }
+    if sessions.is_empty() {
+        println!("No sessions for this project.");
+        return Ok(());
+    }
+
+    if long {
+        let now = chrono::Utc::now();
+        let currency = user_config.currency();
+        let rows: Vec<Vec<String>> = sessions
+            .iter()
+            .map(|(s, stats)| ls_long::row(s, stats.as_ref(), now, &currency))
+            .collect();
+        // Only fit the table to a terminal; piped output keeps every column.
+        let max_width = io::stdout()
+            .is_terminal()
+            .then(|| crossterm::terminal::size().ok())
+            .flatten()
+            .map(|(cols, _)| cols as usize);
+        print!("{}", ls_long::render(&rows, max_width));
+        return Ok(());
+    }
+    let mut rows = Vec::with_capacity(sessions.len());
+    for (s, stats) in sessions {
+        let mut label = format!("{} [{}]", s.name, s.agent_type);
+        if let Some(model) = stats.as_ref().and_then(|st| st.last_model.as_deref()) {
+            label.push(' ');
+            label.push_str(logs::short_model_name(model));
+        }
+        if let Some(tags) = session_tags.get(&s.name) {
+            label.push(' ');
+            label.push_str(&format_tags(tags));
+        }
+        let activity = stats
+            .as_ref()
+            .map(|st| {
+                let mut activity = logs::format_activity(st.edits, st.bash_cmds, st.web_tools);
+                if let Some(subagents) = logs::format_subagents(st.active_subagents) {
+                    if !activity.is_empty() {
+                        activity.push(' ');
+                    }
+                    activity.push_str(&subagents);
+                }
+                activity
+            })
+            .unwrap_or_default();
+        let last = stats.and_then(|st| st.last_activity_at());
+        rows.push((label, activity, last));
+    }
+
+    let now = chrono::Utc::now();
+    // Terminal columns, not chars, so wide characters in names stay aligned.
+    let width = rows
+        .iter()
+        .map(|(label, _, _)| label.width())
+        .max()
+        .unwrap_or(0);
+    let activity_width = rows
+        .iter()
+        .map(|(_, activity, _)| activity.width())
+        .max()
+        .unwrap_or(0);
+    for (label, activity, last) in rows {
+        let last = last
+            .map(|ts| session::format_timestamp(&ts, now, user_config.timestamp_mode))
+            .unwrap_or_else(|| "\u{2014}".to_string());
+        let last = cli_style::paint(&last, Paint::Dim);
+        let label = pad_to_width(&label, width);
+        if activity_width == 0 {
+            println!("{label}  {last}");
+        } else {
+            println!(
+                "{label}  {}  {last}",
+                pad_to_width(&activity, activity_width)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `text` padded with spaces to `width` terminal columns.
+fn pad_to_width(text: &str, width: usize) -> String {
+    format!("{text}{}", " ".repeat(width.saturating_sub(text.width())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pad_to_width_counts_terminal_columns() {
+        assert_eq!(pad_to_width("abc", 6), "abc   ");
+        assert_eq!(pad_to_width("日本", 6), "日本  ");
+        assert_eq!(pad_to_width("toolong", 3), "toolong");
+    }
+}
@@ -0,0 +1,87 @@
+//! `hydra serve`: keeps the stats behind the HTTP endpoint fresh.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use hydra::{config, feed, logs, manifest, serve, tmux};
+
+/// How often `hydra serve` refreshes its cached stats.
+const SERVE_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+pub async fn cmd_serve(
+    project_id: &str,
+    cwd: &str,
+    user_config: &config::Config,
+    bind: &str,
+    port: u16,
+) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind((bind, port))
+        .await
+        .with_context(|| format!("Failed to bind {bind}:{port}"))?;
+    let state = Arc::new(tokio::sync::RwLock::new(serve::ServeState::default()));
+    println!(
+        "Serving /stats and /sessions on http://{}",
+        listener.local_addr()?
+    );
+
+    let refresh = {
+        let state = Arc::clone(&state);
+        let project_id = project_id.to_string();
+        let cwd = cwd.to_string();
+        let mut global = logs::GlobalStats::default();
+        global.set_excluded_projects(&user_config.stats_exclude);
+        global.set_extra_log_globs(&user_config.extra_log_globs);
+        let tool_categories = user_config.session_tool_categories();
+        async move {
+            let manager = tmux::TmuxSessionManager::new();
+            let mut logs = feed::Feed::new(tool_categories);
+            let mut tick = tokio::time::interval(SERVE_REFRESH_INTERVAL);
+            loop {
+                tick.tick().await;
+                global = tokio::task::spawn_blocking(move || {
+                    logs::update_global_stats(&mut global);
+                    global
+                })
+                .await?;
+                let stats = serve::global_stats_json(&global);
+                let sessions = serve_sessions_json(&manager, &project_id, &cwd, &mut logs).await;
+                let mut state = state.write().await;
+                state.stats = stats;
+                state.sessions = sessions;
+            }
+        }
+    };
+
+    tokio::select! {
+        res = serve::run(listener, state) => res,
+        res = refresh => res,
+        _ = tokio::signal::ctrl_c() => Ok(()),
+    }
+}
+
+/// Build the `/sessions` document. `logs` keeps each session's log offset
+/// and stats between refreshes, so a tick only parses what was appended.
+async fn serve_sessions_json(
+    manager: &tmux::TmuxSessionManager,
+    project_id: &str,
+    cwd: &str,
+    logs: &mut feed::Feed,
+) -> serde_json::Value {
+    let mut sessions =
+        manifest::list_named_sessions(manager, &manifest::default_base_dir(), project_id)
+            .await
+            .unwrap_or_default();
+    sessions.sort_by(|a, b| a.name.cmp(&b.name));
+    let cwds = manifest::load_session_cwds(&manifest::default_base_dir(), project_id).await;
+    logs.poll(&sessions, |s| {
+        manifest::session_cwd(&cwds, cwd, &s.name).to_string()
+    })
+    .await;
+    let items = sessions
+        .iter()
+        .map(|s| serve::session_json(s, logs.session_stats(&s.tmux_name)))
+        .collect();
+    serve::sessions_json(project_id, cwd, items)
+}
@@ -0,0 +1,114 @@
+//! `hydra stats` and `hydra report`: usage totals, globally and per
+//! session.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+
+use hydra::{config, logs, manifest, stats_csv, stats_markdown, stats_summary, usage_report};
+
+use super::resolved_session_stats;
+
+pub fn cmd_stats(pause: bool, resume: bool) -> Result<()> {
+    let base_dir = manifest::default_base_dir();
+    if pause || resume {
+        config::set_stats_paused(&base_dir, pause)?;
+    }
+    match config::stats_paused_since(&base_dir) {
+        Some(since) => println!("Stats collection paused since {since}."),
+        None => println!("Stats collection is running."),
+    }
+    Ok(())
+}
+
+pub fn cmd_stats_csv(user_config: &config::Config) -> Result<()> {
+    let mut global = logs::GlobalStats::default();
+    global.set_excluded_projects(&user_config.stats_exclude);
+    global.set_extra_log_globs(&user_config.extra_log_globs);
+    logs::update_global_stats(&mut global);
+    print!("{}", stats_csv::render([(global.date(), &global)]));
+    Ok(())
+}
+
+/// Per-session breakdown of today's usage for this project's sessions.
+/// Sessions without activity today are left out; the rest contribute
+/// their log totals.
+pub async fn cmd_stats_summary(
+    project_id: &str,
+    cwd: &str,
+    top: usize,
+    user_config: &config::Config,
+) -> Result<()> {
+    let categories = user_config.session_tool_categories();
+    let sessions = resolved_session_stats(project_id, cwd, &categories).await?;
+    let today = chrono::Local::now().date_naive();
+    let mut usage = Vec::new();
+    for (s, stats) in sessions {
+        let Some(stats) = stats else {
+            continue;
+        };
+        let active_today = stats
+            .last_activity_at()
+            .is_some_and(|ts| ts.with_timezone(&chrono::Local).date_naive() == today);
+        if active_today {
+            usage.push(stats_summary::SessionUsage::new(
+                &s.name,
+                s.agent_type.clone(),
+                &stats,
+            ));
+        }
+    }
+    print!(
+        "{}",
+        stats_summary::render(
+            &stats_summary::summarize(usage, top),
+            &user_config.currency()
+        )
+    );
+    Ok(())
+}
+
+pub async fn cmd_stats_markdown(
+    project_id: &str,
+    cwd: &str,
+    user_config: &config::Config,
+) -> Result<()> {
+    let categories = user_config.session_tool_categories();
+    let mut sessions = Vec::new();
+    let mut stats = HashMap::new();
+    for (s, st) in resolved_session_stats(project_id, cwd, &categories).await? {
+        if let Some(st) = st {
+            stats.insert(s.tmux_name.clone(), st);
+        }
+        sessions.push(s);
+    }
+    print!(
+        "{}",
+        stats_markdown::render(
+            &sessions,
+            &stats,
+            chrono::Utc::now(),
+            &user_config.currency()
+        )
+    );
+    Ok(())
+}
+
+pub fn cmd_report(
+    user_config: &config::Config,
+    from: chrono::NaiveDate,
+    to: chrono::NaiveDate,
+) -> Result<()> {
+    if from > to {
+        bail!("--from {from} is after --to {to}");
+    }
+    let mut stats = logs::GlobalStats::default();
+    stats.set_excluded_projects(&user_config.stats_exclude);
+    stats.set_extra_log_globs(&user_config.extra_log_globs);
+    logs::scan_range_stats(&mut stats, from, to);
+    print!(
+        "{}",
+        usage_report::render(from, to, &stats, &user_config.currency())
+    );
+    Ok(())
+}
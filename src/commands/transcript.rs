@@ -0,0 +1,120 @@
+//! `hydra open-log`, `export`, `replay` and `feed`: reading a session's
+//! conversation back out of its agent log.
+
+use std::time::Duration;
+
+use anyhow::Result;
+
+use hydra::cli_style::{self, Paint};
+use hydra::{agent, config, export, feed, manifest, redact, replay, session, tmux};
+
+use super::resolve_session_log;
+
+/// How often `hydra feed` polls session logs for new entries.
+const FEED_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub async fn cmd_open_log(project_id: &str, cwd: &str, name: &str) -> Result<()> {
+    let (agent_type, log_id, log_cwd) = resolve_session_log(project_id, cwd, name).await?;
+    let provider = agent::provider_for(&agent_type);
+    println!("{}", provider.log_file_path(&log_id, &log_cwd).display());
+    Ok(())
+}
+
+pub async fn cmd_export(
+    project_id: &str,
+    cwd: &str,
+    name: &str,
+    format: export::Format,
+    redact: bool,
+    user_config: &config::Config,
+) -> Result<()> {
+    let (agent_type, log_id, log_cwd) = resolve_session_log(project_id, cwd, name).await?;
+    let redactor = redact.then(|| redact::Redactor::for_cwds(&[&log_cwd, cwd]));
+    let entries = tokio::task::spawn_blocking(move || {
+        agent::provider_for(&agent_type).read_full_conversation(&log_id, &log_cwd)
+    })
+    .await?;
+    let transcript = export::render(name, &entries, format, &user_config.currency());
+    if let Some(redactor) = redactor {
+        print!("{}", redactor.apply(&transcript));
+    } else {
+        print!("{transcript}");
+    }
+    Ok(())
+}
+
+pub async fn cmd_replay(
+    project_id: &str,
+    cwd: &str,
+    name: &str,
+    speed: f64,
+    redact: bool,
+    user_config: &config::Config,
+) -> Result<()> {
+    let currency = user_config.currency();
+    let (agent_type, log_id, log_cwd) = resolve_session_log(project_id, cwd, name).await?;
+    // A worktree session's log paths are under its own cwd, not the project's.
+    let redactor = redact.then(|| redact::Redactor::for_cwds(&[&log_cwd, cwd]));
+    let entries = tokio::task::spawn_blocking(move || {
+        agent::provider_for(&agent_type).read_timed_conversation(&log_id, &log_cwd)
+    })
+    .await?;
+
+    let mut prev = None;
+    for (i, (at, entry)) in replay::in_timestamp_order(entries).into_iter().enumerate() {
+        if i > 0 {
+            let delay = replay::replay_delay(prev, at, speed, replay::MAX_DELAY);
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+            }
+        }
+        prev = at;
+        let (label, mut text) = feed::summarize_entry(&entry, &currency);
+        if let Some(redactor) = &redactor {
+            text = redactor.apply(&text);
+        }
+        let time = match at {
+            Some(at) => session::format_timestamp(
+                &at.with_timezone(&chrono::Local),
+                chrono::Utc::now(),
+                user_config.timestamp_mode,
+            ),
+            None => "--:--:--".to_string(),
+        };
+        let time = cli_style::paint(&time, Paint::Dim);
+        let label = cli_style::paint(label, Paint::Bold);
+        println!("{time} {label}: {text}");
+    }
+    Ok(())
+}
+
+pub async fn cmd_feed(project_id: &str, cwd: &str, user_config: &config::Config) -> Result<()> {
+    let manager = tmux::TmuxSessionManager::new();
+    let mut feed = feed::Feed::new(user_config.session_tool_categories());
+    let currency = user_config.currency();
+    let mut tick = tokio::time::interval(FEED_POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = tick.tick() => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+        let sessions =
+            manifest::list_named_sessions(&manager, &manifest::default_base_dir(), project_id)
+                .await?;
+        let cwds = manifest::load_session_cwds(&manifest::default_base_dir(), project_id).await;
+        let cwd_for = |s: &session::Session| manifest::session_cwd(&cwds, cwd, &s.name).to_string();
+        for item in feed.poll(&sessions, cwd_for).await {
+            let (label, text) = feed::summarize_entry(&item.entry, &currency);
+            let time = session::format_timestamp(
+                &item.at.with_timezone(&chrono::Local),
+                chrono::Utc::now(),
+                user_config.timestamp_mode,
+            );
+            let time = cli_style::paint(&time, Paint::Dim);
+            let session = cli_style::paint(&format!("[{}]", item.session), Paint::Cyan);
+            let label = cli_style::paint(label, Paint::Bold);
+            println!("{time} {session} {label}: {text}");
+        }
+    }
+}
@@ -0,0 +1,623 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::bell::Bell;
+use crate::logs::{Currency, ExtraLogGlobs, TokenFormat, TokenStyle, ToolCategories, ToolCategory};
+use crate::session::TimestampMode;
+
+/// Minutes a bash command runs before the sidebar flags it, when
+/// `long_bash_mins` is unset.
+pub const DEFAULT_LONG_BASH_MINS: u64 = 5;
+
+/// User preferences persisted across runs in `~/.hydra/config.json`.
+/// Unknown or missing fields fall back to defaults so older files keep loading.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct Config {
+    pub timestamp_mode: TimestampMode,
+    pub preview_density: PreviewDensity,
+    /// Project path prefixes whose Claude logs are left out of the daily
+    /// global stats (e.g. `"~/experiments"`).
+    pub stats_exclude: Vec<String>,
+    /// Timeout for `lsof` during log resolution; defaults to
+    /// `logs::DEFAULT_LSOF_TIMEOUT` when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lsof_timeout_ms: Option<u64>,
+    /// Named pane layout presets applied after session creation
+    /// (`hydra new --layout NAME`).
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub layouts: HashMap<String, Vec<LayoutStep>>,
+    /// Kill sessions idle for this many minutes. Off when unset; the
+    /// `HYDRA_IDLE_KILL_MINS` env var takes precedence.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idle_kill_mins: Option<u64>,
+    /// Merge Claude subagent logs into the session preview.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub subagent_transcripts: bool,
+    /// Extra tool names counted as edits, shell commands or web tools, on
+    /// top of the built-in names (`{"str_replace_editor": "edit"}`).
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub tool_categories: HashMap<String, ToolCategory>,
+    /// Extra globs per provider for log files outside the standard
+    /// directories, counted in the daily global stats.
+    #[serde(skip_serializing_if = "ExtraLogGlobs::is_empty")]
+    pub extra_log_globs: ExtraLogGlobs,
+    /// KB from the end of a Claude log parsed when its preview first loads;
+    /// older history is omitted. 0 parses whole logs. Defaults to
+    /// `logs::DEFAULT_CONVERSATION_TAIL_SEED_BYTES`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview_tail_kb: Option<u64>,
+    /// Lines of tmux scrollback captured above the visible preview rows.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview_scrollback: Option<u32>,
+    /// Shell command run per session every few seconds; the first line it
+    /// prints is shown next to the session. Off when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_command: Option<String>,
+    /// `compact` token counts (`45.3k`) or `exact` ones (`45,312`).
+    pub token_style: TokenStyle,
+    /// Decimal places for compact token counts, 0 to 3; defaults to 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_precision: Option<u8>,
+    /// Accent color per agent (`{"codex": "green"}`): a color name,
+    /// `#rrggbb` or a 0-255 palette index. Unset agents keep the default.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub agent_colors: HashMap<String, String>,
+    /// Quit at once instead of asking whether to leave running sessions
+    /// or kill them.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub skip_quit_confirm: bool,
+    /// Flag sessions whose bash command has run this many minutes; 0 turns
+    /// it off. Defaults to `DEFAULT_LONG_BASH_MINS`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long_bash_mins: Option<u64>,
+    /// Show a static dot instead of a spinner next to working sessions.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub disable_spinner: bool,
+    /// Wait this long after creating a session, then record the pane's last
+    /// output if the agent already died. Off when unset or 0.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub launch_check_ms: Option<u64>,
+    /// Show costs in another currency at a fixed rate
+    /// (`{"symbol": "€", "rate": 0.92}`); USD when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<Currency>,
+    /// Ring the terminal bell when the watched session stops working.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub bell: bool,
+    /// Run this shell command instead of the terminal bell; setting it
+    /// turns the bell on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bell_command: Option<String>,
+}
+
+/// How much of the conversation the preview shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewDensity {
+    /// Every entry: tool uses and results, system events, progress.
+    #[default]
+    Detailed,
+    /// Only user and assistant messages.
+    Compact,
+}
+
+impl PreviewDensity {
+    pub fn toggled(self) -> Self {
+        match self {
+            PreviewDensity::Detailed => PreviewDensity::Compact,
+            PreviewDensity::Compact => PreviewDensity::Detailed,
+        }
+    }
+}
+
+impl std::fmt::Display for PreviewDensity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PreviewDensity::Detailed => write!(f, "detailed"),
+            PreviewDensity::Compact => write!(f, "compact"),
+        }
+    }
+}
+
+/// One step of a layout preset, run against the session's active pane.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutStep {
+    /// Split the active pane; the new pane becomes active.
+    Split(SplitDirection),
+    /// Type a command into the active pane and press Enter.
+    SendKeys(String),
+    /// Make the pane with this tmux index active.
+    SelectPane(u32),
+    /// Apply a built-in tmux layout, e.g. `"even-horizontal"`.
+    SelectLayout(String),
+}
+
+/// `horizontal` places the new pane to the right, `vertical` below.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitDirection {
+    Horizontal,
+    Vertical,
+}
+
+impl Config {
+    pub fn lsof_timeout(&self) -> std::time::Duration {
+        self.lsof_timeout_ms
+            .map(std::time::Duration::from_millis)
+            .unwrap_or(crate::logs::DEFAULT_LSOF_TIMEOUT)
+    }
+
+    pub fn token_format(&self) -> TokenFormat {
+        TokenFormat::new(
+            self.token_style,
+            self.token_precision
+                .unwrap_or(TokenFormat::DEFAULT_PRECISION),
+        )
+    }
+
+    /// The `tool_categories` mapping, to install on session stats.
+    pub fn session_tool_categories(&self) -> ToolCategories {
+        ToolCategories::new(self.tool_categories.clone())
+    }
+
+    /// The display currency: USD unless a currency with a positive, finite
+    /// rate is configured.
+    pub fn currency(&self) -> Currency {
+        self.currency
+            .clone()
+            .filter(|c| c.rate.is_finite() && c.rate > 0.0)
+            .unwrap_or_default()
+    }
+
+    /// How to ring when the watched session stops working, or `None` when
+    /// the bell is off.
+    pub fn bell(&self) -> Option<Bell> {
+        match self.bell_command.as_deref().map(str::trim) {
+            Some(command) if !command.is_empty() => Some(Bell::Command(command.to_string())),
+            _ => self.bell.then_some(Bell::Terminal),
+        }
+    }
+
+    /// Bytes of a Claude log's tail seeded into a new preview, or `None` to
+    /// parse whole logs.
+    pub fn preview_tail_seed(&self) -> Option<u64> {
+        match self.preview_tail_kb {
+            Some(0) => None,
+            Some(kb) => Some(kb.saturating_mul(1024)),
+            None => Some(crate::logs::DEFAULT_CONVERSATION_TAIL_SEED_BYTES),
+        }
+    }
+
+    /// How long a bash command runs before it is flagged, or `None` when
+    /// flagging is off.
+    pub fn long_bash_after(&self) -> Option<std::time::Duration> {
+        let mins = self.long_bash_mins.unwrap_or(DEFAULT_LONG_BASH_MINS);
+        (mins > 0).then(|| std::time::Duration::from_secs(mins * 60))
+    }
+
+    /// How long after a launch to check for an agent that died at once, or
+    /// `None` when the check is off.
+    pub fn launch_check_after(&self) -> Option<std::time::Duration> {
+        let ms = self.launch_check_ms.filter(|&ms| ms > 0)?;
+        Some(std::time::Duration::from_millis(ms))
+    }
+
+    /// Idle threshold for auto-kill, or `None` when the feature is off.
+    pub fn idle_kill_after(&self) -> Option<std::time::Duration> {
+        let env = std::env::var("HYDRA_IDLE_KILL_MINS").ok();
+        idle_kill_after(env.as_deref(), self.idle_kill_mins)
+    }
+}
+
+/// Resolve the auto-kill threshold: a valid env value wins over the config
+/// value, and zero (or a value too large to count in seconds) disables the
+/// feature either way.
+fn idle_kill_after(env: Option<&str>, config_mins: Option<u64>) -> Option<std::time::Duration> {
+    let mins = env
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .or(config_mins)?;
+    let secs = mins.checked_mul(60)?;
+    (mins > 0).then(|| std::time::Duration::from_secs(secs))
+}
+
+/// Return the config file path: `<base_dir>/config.json`
+pub fn config_path(base_dir: &Path) -> PathBuf {
+    base_dir.join("config.json")
+}
+
+/// Load config from disk. Returns defaults on missing or corrupt file;
+/// `try_load_config` reports the corrupt case.
+/// Synchronous so the UI can read it once at startup before the event loop.
+pub fn load_config(base_dir: &Path) -> Config {
+    try_load_config(base_dir).unwrap_or_default()
+}
+
+/// Load config from disk, failing when the file exists but can't be read
+/// or parsed. A missing file gives the defaults.
+pub fn try_load_config(base_dir: &Path) -> Result<Config> {
+    let path = config_path(base_dir);
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .with_context(|| format!("{} is not a valid config", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// A setting changed from the TUI, saved on its own.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigChange {
+    TimestampMode(TimestampMode),
+    PreviewDensity(PreviewDensity),
+}
+
+impl ConfigChange {
+    /// The config key this change writes, and its new value.
+    fn entry(&self) -> Result<(&'static str, serde_json::Value)> {
+        Ok(match self {
+            ConfigChange::TimestampMode(mode) => ("timestamp_mode", serde_json::to_value(mode)?),
+            ConfigChange::PreviewDensity(density) => {
+                ("preview_density", serde_json::to_value(density)?)
+            }
+        })
+    }
+}
+
+/// Write one setting into the config file, leaving every other key as it
+/// is on disk. Refuses to touch a file that doesn't parse, so a typo is
+/// never replaced with defaults.
+pub async fn save_config_change(base_dir: &Path, change: &ConfigChange) -> Result<()> {
+    let path = config_path(base_dir);
+    let mut doc = match tokio::fs::read_to_string(&path).await {
+        Ok(contents) => {
+            serde_json::from_str::<Config>(&contents)
+                .with_context(|| format!("{} is not a valid config", path.display()))?;
+            serde_json::from_str(&contents)?
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            serde_json::Value::Object(serde_json::Map::new())
+        }
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let fields = doc
+        .as_object_mut()
+        .with_context(|| format!("{} is not a JSON object", path.display()))?;
+    let (key, value) = change.entry()?;
+    fields.insert(key.to_string(), value);
+    write_config_file(base_dir, &serde_json::to_string_pretty(&doc)?).await
+}
+
+/// Save config to disk atomically (write-to-temp-then-rename).
+pub async fn save_config(base_dir: &Path, config: &Config) -> Result<()> {
+    write_config_file(base_dir, &serde_json::to_string_pretty(config)?).await
+}
+
+async fn write_config_file(base_dir: &Path, json: &str) -> Result<()> {
+    let path = config_path(base_dir);
+    tokio::fs::create_dir_all(base_dir).await?;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let tmp_name = format!(
+        "config.{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed),
+    );
+    let tmp_path = path.with_file_name(tmp_name);
+    tokio::fs::write(&tmp_path, json).await?;
+    tokio::fs::rename(&tmp_path, &path).await?;
+    Ok(())
+}
+
+/// Marker whose presence pauses global stats accumulation
+/// (`hydra stats --pause`): `<base_dir>/stats-paused`. It holds the RFC 3339
+/// time the pause started.
+pub fn stats_pause_path(base_dir: &Path) -> PathBuf {
+    base_dir.join("stats-paused")
+}
+
+/// When stats collection was paused, or `None` if it is running.
+/// Synchronous: checked from the blocking global stats refresh.
+pub fn stats_paused_since(base_dir: &Path) -> Option<String> {
+    std::fs::read_to_string(stats_pause_path(base_dir))
+        .ok()
+        .map(|since| since.trim().to_string())
+}
+
+/// Pause or resume global stats accumulation for every running hydra.
+pub fn set_stats_paused(base_dir: &Path, paused: bool) -> Result<()> {
+    let path = stats_pause_path(base_dir);
+    if paused {
+        if !path.exists() {
+            std::fs::create_dir_all(base_dir)?;
+            std::fs::write(&path, chrono::Local::now().to_rfc3339())?;
+        }
+    } else if let Err(e) = std::fs::remove_file(&path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            return Err(e.into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_pause_marker_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("hydra");
+        assert_eq!(stats_paused_since(&base), None);
+
+        set_stats_paused(&base, true).unwrap();
+        let since = stats_paused_since(&base).unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(&since).is_ok());
+        // Pausing again keeps the original start time.
+        set_stats_paused(&base, true).unwrap();
+        assert_eq!(stats_paused_since(&base), Some(since));
+
+        set_stats_paused(&base, false).unwrap();
+        assert_eq!(stats_paused_since(&base), None);
+        set_stats_paused(&base, false).unwrap();
+    }
+
+    #[test]
+    fn load_config_missing_file_returns_default() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load_config(dir.path()), Config::default());
+    }
+
+    #[test]
+    fn load_config_corrupt_file_returns_default() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(config_path(dir.path()), "{not json").unwrap();
+        assert_eq!(load_config(dir.path()), Config::default());
+        assert!(try_load_config(dir.path()).is_err());
+    }
+
+    #[tokio::test]
+    async fn save_config_change_keeps_the_other_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            config_path(dir.path()),
+            r#"{"status_command":"ci-status","future_option":true}"#,
+        )
+        .unwrap();
+        save_config_change(
+            dir.path(),
+            &ConfigChange::TimestampMode(TimestampMode::Relative),
+        )
+        .await
+        .unwrap();
+
+        let config = try_load_config(dir.path()).unwrap();
+        assert_eq!(config.timestamp_mode, TimestampMode::Relative);
+        assert_eq!(config.status_command.as_deref(), Some("ci-status"));
+        let raw = std::fs::read_to_string(config_path(dir.path())).unwrap();
+        assert!(raw.contains("future_option"));
+    }
+
+    #[tokio::test]
+    async fn save_config_change_leaves_a_corrupt_file_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(config_path(dir.path()), r#"{"layouts": oops}"#).unwrap();
+        let change = ConfigChange::PreviewDensity(PreviewDensity::Compact);
+        assert!(save_config_change(dir.path(), &change).await.is_err());
+        assert_eq!(
+            std::fs::read_to_string(config_path(dir.path())).unwrap(),
+            r#"{"layouts": oops}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn save_config_change_creates_a_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("hydra");
+        let change = ConfigChange::PreviewDensity(PreviewDensity::Compact);
+        save_config_change(&base, &change).await.unwrap();
+        assert_eq!(
+            try_load_config(&base).unwrap().preview_density,
+            PreviewDensity::Compact
+        );
+    }
+
+    #[test]
+    fn load_config_ignores_unknown_and_missing_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(config_path(dir.path()), r#"{"future_option":true}"#).unwrap();
+        assert_eq!(load_config(dir.path()), Config::default());
+    }
+
+    #[test]
+    fn load_config_parses_layout_presets() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            config_path(dir.path()),
+            r#"{"layouts":{"logs":[{"split":"horizontal"},{"send_keys":"make watch"},{"select_layout":"even-horizontal"},{"select_pane":0}]}}"#,
+        )
+        .unwrap();
+        let config = load_config(dir.path());
+        assert_eq!(
+            config.layouts["logs"],
+            [
+                LayoutStep::Split(SplitDirection::Horizontal),
+                LayoutStep::SendKeys("make watch".to_string()),
+                LayoutStep::SelectLayout("even-horizontal".to_string()),
+                LayoutStep::SelectPane(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn load_config_parses_agent_colors() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            config_path(dir.path()),
+            r##"{"agent_colors":{"codex":"green","claude":"#d97757"}}"##,
+        )
+        .unwrap();
+        let config = load_config(dir.path());
+        assert_eq!(config.agent_colors["codex"], "green");
+        assert_eq!(config.agent_colors["claude"], "#d97757");
+    }
+
+    #[test]
+    fn token_format_defaults_to_one_decimal_compact() {
+        assert_eq!(Config::default().token_format(), TokenFormat::default());
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            config_path(dir.path()),
+            r#"{"token_style":"exact","token_precision":7}"#,
+        )
+        .unwrap();
+        let format = load_config(dir.path()).token_format();
+        assert_eq!(format.style, TokenStyle::Exact);
+        assert_eq!(format.precision, TokenFormat::MAX_PRECISION);
+    }
+
+    #[test]
+    fn bell_is_off_by_default_and_a_command_replaces_the_terminal_bell() {
+        assert_eq!(Config::default().bell(), None);
+        let mut config = Config {
+            bell: true,
+            ..Default::default()
+        };
+        assert_eq!(config.bell(), Some(Bell::Terminal));
+        config.bell_command = Some("  ".to_string());
+        assert_eq!(config.bell(), Some(Bell::Terminal));
+        config.bell = false;
+        config.bell_command = Some(" say done ".to_string());
+        assert_eq!(config.bell(), Some(Bell::Command("say done".to_string())));
+    }
+
+    #[test]
+    fn currency_defaults_to_usd_and_ignores_bad_rates() {
+        assert_eq!(Config::default().currency(), Currency::usd());
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            config_path(dir.path()),
+            r#"{"currency":{"symbol":"£","rate":0.79}}"#,
+        )
+        .unwrap();
+        let currency = load_config(dir.path()).currency();
+        assert_eq!(crate::logs::format_cost(10.0, &currency), "£7.90");
+
+        let mut config = Config::default();
+        for rate in [0.0, -1.0, f64::NAN] {
+            config.currency = Some(Currency {
+                symbol: "£".to_string(),
+                rate,
+            });
+            assert_eq!(config.currency(), Currency::usd());
+        }
+    }
+
+    #[test]
+    fn launch_check_after_is_off_unless_set() {
+        let mut config = Config::default();
+        assert_eq!(config.launch_check_after(), None);
+        config.launch_check_ms = Some(0);
+        assert_eq!(config.launch_check_after(), None);
+        config.launch_check_ms = Some(500);
+        assert_eq!(
+            config.launch_check_after(),
+            Some(std::time::Duration::from_millis(500))
+        );
+    }
+
+    #[test]
+    fn preview_tail_seed_defaults_and_can_be_disabled() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.preview_tail_seed(),
+            Some(crate::logs::DEFAULT_CONVERSATION_TAIL_SEED_BYTES)
+        );
+        config.preview_tail_kb = Some(64);
+        assert_eq!(config.preview_tail_seed(), Some(64 * 1024));
+        config.preview_tail_kb = Some(0);
+        assert_eq!(config.preview_tail_seed(), None);
+    }
+
+    #[test]
+    fn long_bash_after_defaults_and_can_be_disabled() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.long_bash_after(),
+            Some(std::time::Duration::from_secs(DEFAULT_LONG_BASH_MINS * 60))
+        );
+        config.long_bash_mins = Some(0);
+        assert_eq!(config.long_bash_after(), None);
+    }
+
+    #[test]
+    fn idle_kill_after_is_opt_in() {
+        assert_eq!(idle_kill_after(None, None), None);
+        assert_eq!(idle_kill_after(Some("0"), Some(30)), None);
+        assert_eq!(idle_kill_after(None, Some(0)), None);
+        assert_eq!(idle_kill_after(None, Some(u64::MAX)), None);
+        assert_eq!(idle_kill_after(Some(&u64::MAX.to_string()), Some(30)), None);
+    }
+
+    #[test]
+    fn idle_kill_after_env_overrides_config() {
+        let mins = |m: u64| Some(std::time::Duration::from_secs(m * 60));
+        assert_eq!(idle_kill_after(Some("120"), Some(30)), mins(120));
+        assert_eq!(idle_kill_after(None, Some(30)), mins(30));
+        // An unparsable env value falls back to the config.
+        assert_eq!(idle_kill_after(Some("soon"), Some(30)), mins(30));
+    }
+
+    #[tokio::test]
+    async fn roundtrip_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            timestamp_mode: TimestampMode::Relative,
+            preview_density: PreviewDensity::Compact,
+            stats_exclude: vec!["~/experiments".to_string()],
+            lsof_timeout_ms: Some(500),
+            idle_kill_mins: Some(120),
+            subagent_transcripts: true,
+            preview_scrollback: Some(200),
+            preview_tail_kb: Some(2048),
+            status_command: Some("ci-status --short".to_string()),
+            token_style: TokenStyle::Exact,
+            token_precision: Some(2),
+            tool_categories: HashMap::from([(
+                "str_replace_editor".to_string(),
+                ToolCategory::Edit,
+            )]),
+            layouts: HashMap::from([(
+                "logs".to_string(),
+                vec![
+                    LayoutStep::Split(SplitDirection::Vertical),
+                    LayoutStep::SendKeys("tail -f app.log".to_string()),
+                ],
+            )]),
+            agent_colors: HashMap::from([("codex".to_string(), "green".to_string())]),
+            extra_log_globs: ExtraLogGlobs {
+                claude: vec!["/mnt/logs/claude/**/*.jsonl".to_string()],
+                ..Default::default()
+            },
+            skip_quit_confirm: true,
+            disable_spinner: true,
+            long_bash_mins: Some(15),
+            launch_check_ms: Some(1500),
+            currency: Some(Currency {
+                symbol: "€".to_string(),
+                rate: 0.92,
+            }),
+            bell: true,
+            bell_command: Some("afplay /System/Library/Sounds/Glass.aiff".to_string()),
+        };
+        save_config(dir.path(), &config).await.unwrap();
+
+        let raw = std::fs::read_to_string(config_path(dir.path())).unwrap();
+        assert!(raw.contains(r#""timestamp_mode": "relative""#));
+        assert!(raw.contains(r#""str_replace_editor": "edit""#));
+        assert_eq!(load_config(dir.path()), config);
+    }
+}
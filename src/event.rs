@@ -13,7 +13,8 @@ pub enum Event {
     Mouse(MouseEvent),
     Paste(String),
     Tick,
-    Resize,
+    /// New terminal size (columns, rows).
+    Resize(u16, u16),
 }
 
 pub struct EventHandler {
@@ -48,29 +49,16 @@ impl EventHandler {
                         }
                     }
                     event = reader.next() => {
-                        match event {
-                            Some(Ok(CrosstermEvent::Key(key))) => {
-                                if tx.send(Event::Key(key)).await.is_err() {
-                                    break;
-                                }
-                            }
-                            Some(Ok(CrosstermEvent::Mouse(mouse))) => {
-                                if tx.send(Event::Mouse(mouse)).await.is_err() {
-                                    break;
-                                }
-                            }
-                            Some(Ok(CrosstermEvent::Paste(text))) => {
-                                if tx.send(Event::Paste(text)).await.is_err() {
-                                    break;
-                                }
-                            }
-                            Some(Ok(CrosstermEvent::Resize(_, _))) => {
-                                if tx.send(Event::Resize).await.is_err() {
-                                    break;
-                                }
-                            }
+                        let event = match event {
+                            Some(Ok(CrosstermEvent::Key(key))) => Event::Key(key),
+                            Some(Ok(CrosstermEvent::Mouse(mouse))) => Event::Mouse(mouse),
+                            Some(Ok(CrosstermEvent::Paste(text))) => Event::Paste(text),
+                            Some(Ok(CrosstermEvent::Resize(w, h))) => Event::Resize(w, h),
                             Some(Err(_)) | None => break,
-                            _ => {}
+                            _ => continue,
+                        };
+                        if tx.send(event).await.is_err() {
+                            break;
                         }
                     }
                 }
@@ -140,7 +128,7 @@ mod tests {
         let mut handler = EventHandler::from_stream(stream, Duration::from_secs(60));
 
         let e = handler.next().await.unwrap();
-        assert!(matches!(e, Event::Resize));
+        assert!(matches!(e, Event::Resize(120, 40)));
     }
 
     #[tokio::test]
@@ -220,7 +208,10 @@ mod tests {
 
         assert!(matches!(handler.next().await.unwrap(), Event::Key(_)));
         assert!(matches!(handler.next().await.unwrap(), Event::Mouse(_)));
-        assert!(matches!(handler.next().await.unwrap(), Event::Resize));
+        assert!(matches!(
+            handler.next().await.unwrap(),
+            Event::Resize(80, 24)
+        ));
         assert!(matches!(handler.next().await.unwrap(), Event::Key(k) if k.code == KeyCode::Esc));
     }
 
@@ -0,0 +1,223 @@
+use clap::ValueEnum;
+
+use crate::feed::summarize_entry;
+use crate::logs::{ConversationEntry, Currency};
+
+/// Output format for `hydra export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    #[default]
+    Md,
+    Html,
+    Txt,
+}
+
+/// One transcript item, flattened for rendering: full-text messages keep
+/// their body, everything else becomes a one-line event.
+enum Part<'a> {
+    Message {
+        role: &'static str,
+        agent: Option<&'a str>,
+        text: &'a str,
+    },
+    Event {
+        label: &'static str,
+        text: String,
+    },
+}
+
+fn part<'a>(entry: &'a ConversationEntry, currency: &Currency) -> Part<'a> {
+    match entry {
+        ConversationEntry::UserMessage { text } => Part::Message {
+            role: "User",
+            agent: None,
+            text,
+        },
+        ConversationEntry::AssistantText { text } => Part::Message {
+            role: "Assistant",
+            agent: None,
+            text,
+        },
+        ConversationEntry::Sidechain { agent_id, entry } => match part(entry, currency) {
+            Part::Message { role, text, .. } => Part::Message {
+                role,
+                agent: Some(agent_id),
+                text,
+            },
+            Part::Event { label, text } => Part::Event {
+                label,
+                text: format!("[{agent_id}] {text}"),
+            },
+        },
+        other => {
+            let (label, text) = summarize_entry(other, currency);
+            Part::Event { label, text }
+        }
+    }
+}
+
+/// Render a session transcript titled `title` in `format`, with run costs
+/// in `currency`.
+pub fn render(
+    title: &str,
+    entries: &[ConversationEntry],
+    format: Format,
+    currency: &Currency,
+) -> String {
+    let parts: Vec<Part> = entries.iter().map(|entry| part(entry, currency)).collect();
+    match format {
+        Format::Md => render_markdown(title, &parts),
+        Format::Html => render_html(title, &parts),
+        Format::Txt => render_txt(title, &parts),
+    }
+}
+
+fn heading(role: &str, agent: Option<&str>) -> String {
+    match agent {
+        Some(agent) => format!("{role} [{agent}]"),
+        None => role.to_string(),
+    }
+}
+
+fn render_markdown(title: &str, parts: &[Part]) -> String {
+    let mut out = format!("# {title}\n");
+    for part in parts {
+        match part {
+            Part::Message { role, agent, text } => {
+                out.push_str(&format!(
+                    "\n## {}\n\n{}\n",
+                    heading(role, *agent),
+                    text.trim_end()
+                ));
+            }
+            Part::Event { label, text } => {
+                out.push_str(&format!("\n> **{label}** {text}\n"));
+            }
+        }
+    }
+    out
+}
+
+fn render_txt(title: &str, parts: &[Part]) -> String {
+    let mut out = format!("{title}\n");
+    for part in parts {
+        match part {
+            Part::Message { role, agent, text } => {
+                out.push_str(&format!("\n{}:\n", heading(&role.to_uppercase(), *agent)));
+                for line in text.trim_end().lines() {
+                    out.push_str(&format!("  {line}\n"));
+                }
+            }
+            Part::Event { label, text } => {
+                out.push_str(&format!("\n{label}: {text}\n"));
+            }
+        }
+    }
+    out
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:50rem;margin:2rem auto;\
+padding:0 1rem;line-height:1.5}\
+.msg{margin:1rem 0;padding:.5rem 1rem;border-radius:6px}\
+.user{background:#e8f1ff}.assistant{background:#eef8ee}\
+.msg h2{font-size:.8rem;margin:0;text-transform:uppercase;color:#555}\
+.msg pre{white-space:pre-wrap;margin:.25rem 0;font-family:inherit}\
+.event{color:#666;font-size:.85rem;font-family:ui-monospace,monospace;margin:.25rem 1rem}";
+
+fn render_html(title: &str, parts: &[Part]) -> String {
+    let title = escape_html(title);
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>{HTML_STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+    );
+    for part in parts {
+        match part {
+            Part::Message { role, agent, text } => {
+                out.push_str(&format!(
+                    "<div class=\"msg {}\"><h2>{}</h2><pre>{}</pre></div>\n",
+                    role.to_lowercase(),
+                    escape_html(&heading(role, *agent)),
+                    escape_html(text.trim_end())
+                ));
+            }
+            Part::Event { label, text } => {
+                out.push_str(&format!(
+                    "<div class=\"event\"><b>{label}</b> {}</div>\n",
+                    escape_html(text)
+                ));
+            }
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<ConversationEntry> {
+        vec![
+            ConversationEntry::UserMessage {
+                text: "Is <b> & <i> valid?".to_string(),
+            },
+            ConversationEntry::AssistantText {
+                text: "Yes: a < b && b > c".to_string(),
+            },
+            ConversationEntry::ToolUse {
+                tool_name: "Bash".to_string(),
+                details: Some("echo '<tag>'".to_string()),
+            },
+            ConversationEntry::Sidechain {
+                agent_id: "agent-a".to_string(),
+                entry: Box::new(ConversationEntry::AssistantText {
+                    text: "found it".to_string(),
+                }),
+            },
+        ]
+    }
+
+    #[test]
+    fn html_escapes_message_text() {
+        let html = render("s1 <proj>", &sample(), Format::Html, &Currency::usd());
+        assert!(html.contains("Is &lt;b&gt; &amp; &lt;i&gt; valid?"));
+        assert!(html.contains("a &lt; b &amp;&amp; b &gt; c"));
+        assert!(html.contains("echo &#39;&lt;tag&gt;&#39;"));
+        assert!(html.contains("<title>s1 &lt;proj&gt;</title>"));
+        assert!(!html.contains("<b> &"));
+        assert!(html.contains("<style>"));
+    }
+
+    #[test]
+    fn markdown_uses_headings_and_quoted_events() {
+        let md = render("s1", &sample(), Format::Md, &Currency::usd());
+        assert!(md.starts_with("# s1\n"));
+        assert!(md.contains("## User\n\nIs <b> & <i> valid?\n"));
+        assert!(md.contains("> **TOOL** Bash echo '<tag>'"));
+        assert!(md.contains("## Assistant [agent-a]\n\nfound it"));
+    }
+
+    #[test]
+    fn txt_uses_plain_prefixes() {
+        let txt = render("s1", &sample(), Format::Txt, &Currency::usd());
+        assert!(txt.contains("\nUSER:\n  Is <b> & <i> valid?\n"));
+        assert!(txt.contains("\nTOOL: Bash echo '<tag>'\n"));
+        assert!(txt.contains("\nASSISTANT [agent-a]:\n  found it\n"));
+        assert!(!txt.contains('#'));
+    }
+}
@@ -0,0 +1,514 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+
+use crate::agent::{provider_for, AgentProvider};
+use crate::logs::{
+    format_run_result, todo_progress, truncate_to_width, ConversationEntry, Currency, SessionStats,
+    TimedEntry, TodoStatus, ToolCategories,
+};
+use crate::session::{AgentType, Session};
+
+/// Upper bound on entries emitted per session per poll. A session that dumps
+/// a huge batch between polls only contributes its most recent entries.
+const MAX_ENTRIES_PER_POLL: usize = 200;
+
+/// Widest single-line summary printed for an entry, in terminal columns.
+const MAX_SUMMARY_WIDTH: usize = 160;
+
+/// A new conversation entry from one session, stamped for merging.
+#[derive(Debug, Clone)]
+pub struct FeedItem {
+    pub at: DateTime<Utc>,
+    pub session: String,
+    pub entry: ConversationEntry,
+}
+
+/// Incremental read position for one session's log.
+struct SessionCursor {
+    log_id: String,
+    offset: u64,
+    stats: SessionStats,
+}
+
+/// Looks up the provider for an agent; tests swap in one backed by plain files.
+type ProviderLookup = fn(&AgentType) -> &'static dyn AgentProvider;
+
+/// Tails conversation logs across sessions, yielding only entries that
+/// appeared since the previous poll. Sessions that vanish drop their cursor,
+/// so memory stays proportional to the live session count.
+pub struct Feed {
+    cursors: HashMap<String, SessionCursor>,
+    provider_for: ProviderLookup,
+    /// Installed on each session's stats.
+    tool_categories: ToolCategories,
+}
+
+impl Default for Feed {
+    fn default() -> Self {
+        Self {
+            cursors: HashMap::new(),
+            provider_for,
+            tool_categories: ToolCategories::default(),
+        }
+    }
+}
+
+impl Feed {
+    pub fn new(tool_categories: ToolCategories) -> Self {
+        Self {
+            tool_categories,
+            ..Self::default()
+        }
+    }
+
+    /// Poll every session's log and return new entries merged by timestamp.
+    /// The first time a session is seen its existing history is skipped.
+    /// `cwd_for` gives the directory each session runs in, where its log
+    /// resolves from.
+    pub async fn poll(
+        &mut self,
+        sessions: &[Session],
+        cwd_for: impl Fn(&Session) -> String,
+    ) -> Vec<FeedItem> {
+        let live: HashSet<&str> = sessions.iter().map(|s| s.tmux_name.as_str()).collect();
+        self.cursors.retain(|name, _| live.contains(name.as_str()));
+
+        // Reverse-alpha so newer sessions claim logs first, matching the TUI.
+        let mut sessions: Vec<&Session> = sessions.iter().collect();
+        sessions.sort_by(|a, b| b.name.cmp(&a.name));
+
+        let now = Utc::now();
+        let mut items = Vec::new();
+        for session in sessions {
+            let provider = (self.provider_for)(&session.agent_type);
+            let cwd = cwd_for(session);
+            let cwd = cwd.as_str();
+            let known = self.cursors.contains_key(&session.tmux_name);
+
+            if !known || provider.refresh_cached_log_path() {
+                let claimed: HashSet<String> = self
+                    .cursors
+                    .iter()
+                    .filter(|(name, _)| **name != session.tmux_name)
+                    .map(|(_, c)| c.log_id.clone())
+                    .collect();
+                let Some(log_id) = provider
+                    .resolve_log_path(&session.tmux_name, cwd, &claimed)
+                    .await
+                else {
+                    continue;
+                };
+                match self.cursors.get(&session.tmux_name) {
+                    Some(cursor) if cursor.log_id == log_id => {}
+                    existing => {
+                        // A brand-new session starts at the end of its log; a
+                        // session that switched logs reads the new one in full.
+                        let skip_history = existing.is_none();
+                        let mut cursor = SessionCursor {
+                            log_id,
+                            offset: 0,
+                            stats: SessionStats {
+                                tool_categories: self.tool_categories.clone(),
+                                ..Default::default()
+                            },
+                        };
+                        if skip_history {
+                            read_new_entries(provider, &mut cursor, cwd).await;
+                        }
+                        self.cursors.insert(session.tmux_name.clone(), cursor);
+                        if skip_history {
+                            continue;
+                        }
+                    }
+                }
+            }
+
+            let Some(cursor) = self.cursors.get_mut(&session.tmux_name) else {
+                continue;
+            };
+            let mut entries = read_new_entries(provider, cursor, cwd).await;
+            if entries.len() > MAX_ENTRIES_PER_POLL {
+                entries.drain(..entries.len() - MAX_ENTRIES_PER_POLL);
+            }
+            // Untimed entries (and providers without timestamps) inherit the
+            // stamp before them, falling back to the log's last activity.
+            let mut at = cursor.stats.last_activity_at().unwrap_or(now);
+            items.extend(entries.into_iter().map(|(stamp, entry)| {
+                at = stamp.unwrap_or(at);
+                FeedItem {
+                    at,
+                    session: session.name.clone(),
+                    entry,
+                }
+            }));
+        }
+
+        merge_by_timestamp(items)
+    }
+
+    /// Stats for a session as of the last poll, covering its whole log
+    /// even though the feed skipped its earlier entries.
+    pub fn session_stats(&self, tmux_name: &str) -> Option<&SessionStats> {
+        self.cursors.get(tmux_name).map(|cursor| &cursor.stats)
+    }
+}
+
+/// Parse entries appended since `cursor.offset` and advance the cursor.
+async fn read_new_entries(
+    provider: &'static dyn AgentProvider,
+    cursor: &mut SessionCursor,
+    cwd: &str,
+) -> Vec<TimedEntry> {
+    let log_id = cursor.log_id.clone();
+    let cwd = cwd.to_string();
+    let offset = cursor.offset;
+    let mut stats = std::mem::take(&mut cursor.stats);
+    let result = tokio::task::spawn_blocking(move || {
+        let update = provider.update_timed_from_log(&log_id, &cwd, offset, &mut stats);
+        (update, stats)
+    })
+    .await;
+    let Ok(((entries, new_offset), stats)) = result else {
+        return Vec::new();
+    };
+    cursor.stats = stats;
+    cursor.offset = new_offset;
+    entries
+}
+
+/// Order items by timestamp. The sort is stable, so entries sharing a
+/// timestamp keep their log order.
+pub fn merge_by_timestamp(mut items: Vec<FeedItem>) -> Vec<FeedItem> {
+    items.sort_by_key(|item| item.at);
+    items
+}
+
+/// One-line label and text for an entry, e.g. `("TOOL", "Bash ls -la")`.
+/// Run costs are shown in `currency`.
+pub fn summarize_entry(entry: &ConversationEntry, currency: &Currency) -> (&'static str, String) {
+    let (label, text) = match entry {
+        ConversationEntry::UserMessage { text } => ("USER", text.clone()),
+        ConversationEntry::AssistantText { text } => ("ASSISTANT", text.clone()),
+        ConversationEntry::ToolUse { tool_name, details } => match details {
+            Some(details) => ("TOOL", format!("{tool_name} {details}")),
+            None => ("TOOL", tool_name.clone()),
+        },
+        ConversationEntry::ToolResult {
+            filenames, summary, ..
+        } => {
+            let text = summary.clone().unwrap_or_else(|| filenames.join(", "));
+            ("RESULT", text)
+        }
+        ConversationEntry::QueueOperation { operation, task_id } => match task_id {
+            Some(task_id) => ("SUBAGENT", format!("{operation} ({task_id})")),
+            None => ("SUBAGENT", operation.clone()),
+        },
+        ConversationEntry::Progress { kind, detail } => ("PROGRESS", format!("{kind}: {detail}")),
+        ConversationEntry::SystemEvent { subtype, detail } => {
+            ("SYSTEM", format!("{subtype}: {detail}"))
+        }
+        ConversationEntry::FileHistorySnapshot { tracked_files, .. } => {
+            ("SNAPSHOT", format!("{tracked_files} tracked files"))
+        }
+        ConversationEntry::Unparsed { reason, .. } => ("UNPARSED", reason.clone()),
+        ConversationEntry::HistoryTruncated { dropped } => {
+            ("TRUNCATED", format!("{dropped} earlier entries"))
+        }
+        ConversationEntry::HistoryOmitted { bytes } => (
+            "TRUNCATED",
+            format!("{} KB of earlier history", bytes.div_ceil(1024)),
+        ),
+        ConversationEntry::Sidechain { agent_id, entry } => {
+            let (label, text) = summarize_entry(entry, currency);
+            (label, format!("[{agent_id}] {text}"))
+        }
+        ConversationEntry::TodoUpdate { todos } => {
+            let (done, total) = todo_progress(todos);
+            let current = todos.iter().find(|t| t.status == TodoStatus::InProgress);
+            match current {
+                Some(todo) => (
+                    "TODOS",
+                    format!("{done}/{total} done, now: {}", todo.content),
+                ),
+                None => ("TODOS", format!("{done}/{total} done")),
+            }
+        }
+        ConversationEntry::RunResult {
+            cost_usd,
+            duration_ms,
+            turns,
+        } => (
+            "RUN",
+            format_run_result(*cost_usd, *duration_ms, *turns, currency),
+        ),
+    };
+    (label, truncate_line(&text))
+}
+
+/// Collapse whitespace to a single line and cap its display width.
+fn truncate_line(text: &str) -> String {
+    let compact = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    truncate_to_width(&compact, MAX_SUMMARY_WIDTH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::TodoItem;
+
+    fn item(at: &str, session: &str, text: &str) -> FeedItem {
+        FeedItem {
+            at: at.parse().unwrap(),
+            session: session.to_string(),
+            entry: ConversationEntry::AssistantText {
+                text: text.to_string(),
+            },
+        }
+    }
+
+    fn order(items: &[FeedItem]) -> Vec<String> {
+        items
+            .iter()
+            .map(|i| match &i.entry {
+                ConversationEntry::AssistantText { text } => format!("{}:{text}", i.session),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merge_interleaves_two_sources_by_timestamp() {
+        let items = vec![
+            item("2026-02-24T10:00:00Z", "alpha", "a1"),
+            item("2026-02-24T10:00:10Z", "alpha", "a2"),
+            item("2026-02-24T10:00:05Z", "bravo", "b1"),
+            item("2026-02-24T10:00:15Z", "bravo", "b2"),
+        ];
+        assert_eq!(
+            order(&merge_by_timestamp(items)),
+            ["alpha:a1", "bravo:b1", "alpha:a2", "bravo:b2"]
+        );
+    }
+
+    #[test]
+    fn merge_keeps_batch_order_for_equal_timestamps() {
+        let items = vec![
+            item("2026-02-24T10:00:05Z", "bravo", "b1"),
+            item("2026-02-24T10:00:05Z", "bravo", "b2"),
+            item("2026-02-24T10:00:00Z", "alpha", "a1"),
+        ];
+        assert_eq!(
+            order(&merge_by_timestamp(items)),
+            ["alpha:a1", "bravo:b1", "bravo:b2"]
+        );
+    }
+
+    #[test]
+    fn summarize_entry_is_single_line_and_bounded() {
+        let entry = ConversationEntry::UserMessage {
+            text: format!("first line\nsecond {}", "x".repeat(500)),
+        };
+        let (label, text) = summarize_entry(&entry, &Currency::usd());
+        assert_eq!(label, "USER");
+        assert!(!text.contains('\n'));
+        assert!(text.starts_with("first line second"));
+        assert_eq!(
+            unicode_width::UnicodeWidthStr::width(text.as_str()),
+            MAX_SUMMARY_WIDTH
+        );
+    }
+
+    #[test]
+    fn summarize_entry_counts_todos_and_names_the_current_one() {
+        let todo = |content: &str, status| TodoItem {
+            content: content.to_string(),
+            status,
+        };
+        let entry = ConversationEntry::TodoUpdate {
+            todos: vec![
+                todo("Write parser", TodoStatus::Completed),
+                todo("Add tests", TodoStatus::InProgress),
+                todo("Update docs", TodoStatus::Pending),
+            ],
+        };
+        assert_eq!(
+            summarize_entry(&entry, &Currency::usd()),
+            ("TODOS", "1/3 done, now: Add tests".to_string())
+        );
+    }
+
+    /// Resolves each session to `<cwd>/<tmux name>.jsonl` and parses it as
+    /// a Claude log, counting parsed entries in `tokens_out`.
+    struct FileProvider;
+
+    #[async_trait::async_trait]
+    impl AgentProvider for FileProvider {
+        fn id(&self) -> &'static str {
+            "file"
+        }
+
+        fn create_command(&self, _session_name: &str, _cwd: &str) -> String {
+            String::new()
+        }
+
+        async fn resolve_log_path(
+            &self,
+            tmux_name: &str,
+            cwd: &str,
+            _claimed_paths: &HashSet<String>,
+        ) -> Option<String> {
+            Some(format!("{cwd}/{tmux_name}.jsonl"))
+        }
+
+        fn log_resolution_hint(&self) -> &'static str {
+            ""
+        }
+
+        fn update_from_log(
+            &self,
+            log_id: &str,
+            _cwd: &str,
+            offset: u64,
+            _session_stats: &mut SessionStats,
+        ) -> crate::agent::AgentLogUpdate {
+            let (entries, new_offset) =
+                crate::logs::parse_conversation_entries(std::path::Path::new(log_id), offset);
+            crate::agent::AgentLogUpdate {
+                entries,
+                new_offset,
+                ..Default::default()
+            }
+        }
+
+        fn update_timed_from_log(
+            &self,
+            log_id: &str,
+            _cwd: &str,
+            offset: u64,
+            session_stats: &mut SessionStats,
+        ) -> (Vec<TimedEntry>, u64) {
+            let (entries, new_offset) = crate::logs::parse_timed_conversation_entries(
+                std::path::Path::new(log_id),
+                offset,
+                None,
+            );
+            session_stats.tokens_out += entries.len() as u64;
+            (entries, new_offset)
+        }
+    }
+
+    fn assistant_line(at: &str, text: &str) -> String {
+        format!(
+            r#"{{"type":"assistant","timestamp":"{at}","message":{{"content":[{{"type":"text","text":"{text}"}}]}}}}"#
+        ) + "\n"
+    }
+
+    fn append(path: &std::path::Path, lines: &[String]) {
+        use std::io::Write;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(lines.concat().as_bytes()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn poll_interleaves_two_logs_by_entry_timestamp() {
+        static FILE_PROVIDER: FileProvider = FileProvider;
+        let dir = tempfile::tempdir().unwrap();
+        let cwd = dir.path().to_str().unwrap();
+        let sessions = [
+            Session::stopped("proj", "alpha", AgentType::Claude),
+            Session::stopped("proj", "bravo", AgentType::Claude),
+        ];
+        let log = |s: &Session| dir.path().join(format!("{}.jsonl", s.tmux_name));
+        append(
+            &log(&sessions[0]),
+            &[assistant_line("2026-02-24T09:00:00Z", "old")],
+        );
+        append(
+            &log(&sessions[1]),
+            &[assistant_line("2026-02-24T09:00:00Z", "old")],
+        );
+
+        let mut feed = Feed {
+            provider_for: |_| &FILE_PROVIDER,
+            ..Feed::default()
+        };
+        // Existing history is skipped.
+        assert!(feed.poll(&sessions, |_| cwd.to_string()).await.is_empty());
+
+        append(
+            &log(&sessions[0]),
+            &[
+                assistant_line("2026-02-24T10:00:00Z", "a1"),
+                assistant_line("2026-02-24T10:00:10Z", "a2"),
+            ],
+        );
+        append(
+            &log(&sessions[1]),
+            &[
+                assistant_line("2026-02-24T10:00:05Z", "b1"),
+                assistant_line("2026-02-24T10:00:15Z", "b2"),
+            ],
+        );
+        let items = feed.poll(&sessions, |_| cwd.to_string()).await;
+        assert_eq!(
+            order(&items),
+            ["alpha:a1", "bravo:b1", "alpha:a2", "bravo:b2"]
+        );
+        let stamps: Vec<String> = items.iter().map(|i| i.at.to_rfc3339()).collect();
+        assert_eq!(
+            stamps,
+            [
+                "2026-02-24T10:00:00+00:00",
+                "2026-02-24T10:00:05+00:00",
+                "2026-02-24T10:00:10+00:00",
+                "2026-02-24T10:00:15+00:00",
+            ]
+        );
+        assert!(feed.poll(&sessions, |_| cwd.to_string()).await.is_empty());
+
+        // Each poll parsed only what was appended: one old line, two new.
+        let stats = feed.session_stats(&sessions[0].tmux_name).unwrap();
+        assert_eq!(stats.tokens_out, 3);
+        assert!(feed.session_stats("gone").is_none());
+    }
+
+    #[tokio::test]
+    async fn poll_with_no_sessions_is_empty() {
+        let mut feed = Feed::default();
+        assert!(feed.poll(&[], |_| "/tmp".to_string()).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn poll_resolves_a_worktree_sessions_log_from_its_own_cwd() {
+        static FILE_PROVIDER: FileProvider = FileProvider;
+        let project = tempfile::tempdir().unwrap();
+        let worktree = tempfile::tempdir().unwrap();
+        let sessions = [Session::stopped("proj", "feat", AgentType::Claude)];
+        let log = worktree
+            .path()
+            .join(format!("{}.jsonl", sessions[0].tmux_name));
+        append(&log, &[assistant_line("2026-02-24T09:00:00Z", "old")]);
+
+        let cwds = HashMap::from([(
+            "feat".to_string(),
+            worktree.path().to_str().unwrap().to_string(),
+        )]);
+        let project_cwd = project.path().to_str().unwrap();
+        let cwd_for =
+            |s: &Session| crate::manifest::session_cwd(&cwds, project_cwd, &s.name).to_string();
+        let mut feed = Feed {
+            provider_for: |_| &FILE_PROVIDER,
+            ..Feed::default()
+        };
+        assert!(feed.poll(&sessions, cwd_for).await.is_empty());
+
+        append(&log, &[assistant_line("2026-02-24T10:00:00Z", "new")]);
+        let items = feed.poll(&sessions, cwd_for).await;
+        assert_eq!(order(&items), ["feat:new"]);
+    }
+}
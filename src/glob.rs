@@ -0,0 +1,70 @@
+//! Shell-style wildcard matching, shared by `hydra migrate --pattern` and
+//! the `extra_log_globs` config.
+
+/// Shell-style match of `text` against `pattern`: `*` is any run of
+/// characters, `?` any one character.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    match_chars(pattern, text, false)
+}
+
+/// Whether some extension of `text` would match `pattern`, e.g. whether a
+/// directory path can lead to a matching file.
+pub fn glob_match_prefix(pattern: &str, text: &str) -> bool {
+    match_chars(pattern, text, true)
+}
+
+fn match_chars(pattern: &str, text: &str, prefix: bool) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    // Any rest of the pattern can be matched by some continuation.
+    prefix || pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_match_supports_star_and_question_mark() {
+        assert!(glob_match("agent-*", "agent-1"));
+        assert!(glob_match("agent-*", "agent-"));
+        assert!(glob_match("*-api-*", "work-api-2"));
+        assert!(glob_match("a?c", "abc"));
+        assert!(glob_match("*", "anything"));
+        assert!(!glob_match("agent-*", "my-agent-1"));
+        assert!(!glob_match("a?c", "ac"));
+        assert!(!glob_match("exact", "exactly"));
+    }
+
+    #[test]
+    fn glob_match_prefix_accepts_text_a_match_can_continue() {
+        assert!(glob_match_prefix("/logs/?/c/*.jsonl", "/logs/a/"));
+        assert!(glob_match_prefix("/logs/?/c/*.jsonl", "/logs/a/c/"));
+        assert!(glob_match_prefix("/logs/*/c/x", "/logs/a/b/"));
+        assert!(glob_match_prefix("abc", ""));
+        assert!(!glob_match_prefix("/logs/?/c/*.jsonl", "/logs/a/d/"));
+        assert!(!glob_match_prefix("/logs/?/c/*.jsonl", "/logs/ab/"));
+    }
+}
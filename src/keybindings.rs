@@ -0,0 +1,395 @@
+use crossterm::event::KeyCode;
+
+use crate::app::Mode;
+
+/// One documented key binding. `codes` lists every `KeyCode` the handler
+/// matches for this action (modifier variants share the base code); `keys`
+/// is the human-readable label shown in the help overlay.
+#[derive(Debug)]
+pub struct KeyBinding {
+    pub mode: Mode,
+    pub codes: &'static [KeyCode],
+    pub keys: &'static str,
+    pub description: &'static str,
+}
+
+/// Single source of truth for key bindings, in display order.
+/// Adding a binding to a `handle_*_key` function without an entry here fails
+/// `every_handled_key_is_documented` in `app.rs`.
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    // ── Browse ──
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('j'), KeyCode::Down],
+        keys: "j / Down",
+        description: "Select next session",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('k'), KeyCode::Up],
+        keys: "k / Up",
+        description: "Select previous session",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('J'), KeyCode::Char('K')],
+        keys: "Shift+J / Shift+K",
+        description: "Move the session down / up its group",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Up, KeyCode::Down],
+        keys: "Shift+Up/Down",
+        description: "Scroll the changes tree",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('d'), KeyCode::Char('u')],
+        keys: "Ctrl+D / Ctrl+U",
+        description: "Scroll the session list half a page",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::PageUp, KeyCode::PageDown],
+        keys: "PgUp / PgDn",
+        description: "Scroll the preview by a page",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Home, KeyCode::End],
+        keys: "Home / End",
+        description: "Jump to top / bottom of the preview",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Enter],
+        keys: "Enter",
+        description: "Compose a message to the session",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('n')],
+        keys: "n",
+        description: "New session",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('C')],
+        keys: "C",
+        description: "New scratch Claude session, no prompts",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('N')],
+        keys: "N",
+        description: "Edit the selected session's note",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('d')],
+        keys: "d",
+        description: "Delete session",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('c')],
+        keys: "c",
+        description: "Toggle copy mode (release mouse)",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('c')],
+        keys: "Ctrl+C",
+        description: "Interrupt the selected agent",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('t')],
+        keys: "t",
+        description: "Toggle absolute / relative timestamps",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('v')],
+        keys: "v",
+        description: "Toggle compact / detailed preview",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Tab],
+        keys: "Tab",
+        description: "Cycle conversation/pane/stats/todos view",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('f')],
+        keys: "f",
+        description: "Cycle tag filter",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('y')],
+        keys: "y",
+        description: "Copy the session's log file path",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('M')],
+        keys: "M",
+        description: "Copy session stats as a Markdown table",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('R')],
+        keys: "R",
+        description: "Refresh the session's log and preview now",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('p')],
+        keys: "p",
+        description: "Pin the preview to this session / unpin",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('V')],
+        keys: "V",
+        description: "Revive every recorded session that isn't running",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('['), KeyCode::Char(']')],
+        keys: "[ / ]",
+        description: "Select an older / newer tool result in the preview",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('x')],
+        keys: "x",
+        description: "Expand / collapse the selected tool result",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('?')],
+        keys: "?",
+        description: "Show this help",
+    },
+    KeyBinding {
+        mode: Mode::Browse,
+        codes: &[KeyCode::Char('q')],
+        keys: "q",
+        description: "Quit (asks first while sessions run)",
+    },
+    // ── Compose ──
+    KeyBinding {
+        mode: Mode::Compose,
+        codes: &[KeyCode::Enter],
+        keys: "Enter",
+        description: "Send message",
+    },
+    KeyBinding {
+        mode: Mode::Compose,
+        codes: &[KeyCode::Enter],
+        keys: "Shift+Enter",
+        description: "Insert newline",
+    },
+    KeyBinding {
+        mode: Mode::Compose,
+        codes: &[KeyCode::Up, KeyCode::Down],
+        keys: "Up / Down",
+        description: "Move between lines / browse history",
+    },
+    KeyBinding {
+        mode: Mode::Compose,
+        codes: &[
+            KeyCode::Left,
+            KeyCode::Right,
+            KeyCode::Char('b'),
+            KeyCode::Char('f'),
+        ],
+        keys: "Alt+B/F, Ctrl+Left/Right",
+        description: "Move by word",
+    },
+    KeyBinding {
+        mode: Mode::Compose,
+        codes: &[KeyCode::Char('w')],
+        keys: "Ctrl+W",
+        description: "Delete word before cursor",
+    },
+    KeyBinding {
+        mode: Mode::Compose,
+        codes: &[KeyCode::Char('u')],
+        keys: "Ctrl+U",
+        description: "Clear line",
+    },
+    KeyBinding {
+        mode: Mode::Compose,
+        codes: &[KeyCode::Esc, KeyCode::Char('c')],
+        keys: "Esc / Ctrl+C",
+        description: "Cancel (draft kept)",
+    },
+    // ── New session ──
+    KeyBinding {
+        mode: Mode::NewSessionAgent,
+        codes: &[
+            KeyCode::Char('j'),
+            KeyCode::Down,
+            KeyCode::Char('k'),
+            KeyCode::Up,
+        ],
+        keys: "j / k",
+        description: "Select agent",
+    },
+    KeyBinding {
+        mode: Mode::NewSessionAgent,
+        codes: &[KeyCode::Enter],
+        keys: "Enter",
+        description: "Choose agent, then name the session",
+    },
+    KeyBinding {
+        mode: Mode::NewSessionAgent,
+        codes: &[KeyCode::Esc],
+        keys: "Esc",
+        description: "Cancel",
+    },
+    // ── Session name ──
+    KeyBinding {
+        mode: Mode::NewSessionName,
+        codes: &[KeyCode::Backspace],
+        keys: "a-z 0-9 - _ / Backspace",
+        description: "Edit the name",
+    },
+    KeyBinding {
+        mode: Mode::NewSessionName,
+        codes: &[KeyCode::Enter],
+        keys: "Enter",
+        description: "Create session (blank = generated name)",
+    },
+    KeyBinding {
+        mode: Mode::NewSessionName,
+        codes: &[KeyCode::Esc],
+        keys: "Esc",
+        description: "Cancel",
+    },
+    // ── Confirm delete ──
+    KeyBinding {
+        mode: Mode::ConfirmDelete,
+        codes: &[KeyCode::Char('y')],
+        keys: "y",
+        description: "Kill the session",
+    },
+    KeyBinding {
+        mode: Mode::ConfirmDelete,
+        codes: &[KeyCode::Char('n'), KeyCode::Esc],
+        keys: "n / Esc",
+        description: "Cancel",
+    },
+    // ── Confirm quit ──
+    KeyBinding {
+        mode: Mode::ConfirmQuit,
+        codes: &[KeyCode::Char('l'), KeyCode::Char('q'), KeyCode::Enter],
+        keys: "l / q / Enter",
+        description: "Quit, leaving sessions running",
+    },
+    KeyBinding {
+        mode: Mode::ConfirmQuit,
+        codes: &[KeyCode::Char('K')],
+        keys: "K",
+        description: "Kill all sessions, then quit",
+    },
+    KeyBinding {
+        mode: Mode::ConfirmQuit,
+        codes: &[KeyCode::Esc],
+        keys: "Esc",
+        description: "Cancel",
+    },
+    // ── Note ──
+    KeyBinding {
+        mode: Mode::EditNote,
+        codes: &[KeyCode::Backspace],
+        keys: "text / Backspace",
+        description: "Edit the note",
+    },
+    KeyBinding {
+        mode: Mode::EditNote,
+        codes: &[KeyCode::Enter],
+        keys: "Enter",
+        description: "Save the note (blank clears it)",
+    },
+    KeyBinding {
+        mode: Mode::EditNote,
+        codes: &[KeyCode::Esc],
+        keys: "Esc",
+        description: "Cancel",
+    },
+    // ── Help ──
+    KeyBinding {
+        mode: Mode::Help,
+        codes: &[
+            KeyCode::Char('j'),
+            KeyCode::Down,
+            KeyCode::Char('k'),
+            KeyCode::Up,
+        ],
+        keys: "j / k",
+        description: "Scroll help",
+    },
+    KeyBinding {
+        mode: Mode::Help,
+        codes: &[KeyCode::Esc, KeyCode::Char('?'), KeyCode::Char('q')],
+        keys: "Esc / ? / q",
+        description: "Close help",
+    },
+];
+
+/// Section heading for a mode in the help overlay.
+pub fn mode_title(mode: &Mode) -> &'static str {
+    match mode {
+        Mode::Browse => "Browse",
+        Mode::Compose => "Compose",
+        Mode::NewSessionAgent => "New session",
+        Mode::NewSessionName => "Session name",
+        Mode::ConfirmDelete => "Confirm delete",
+        Mode::ConfirmQuit => "Confirm quit",
+        Mode::EditNote => "Note",
+        Mode::Help => "Help",
+    }
+}
+
+/// Bindings documented for `mode`, in display order.
+pub fn bindings_for(mode: &Mode) -> impl Iterator<Item = &'static KeyBinding> + '_ {
+    KEYBINDINGS.iter().filter(move |b| &b.mode == mode)
+}
+
+/// Whether `code` is documented for `mode`.
+pub fn is_documented(mode: &Mode, code: KeyCode) -> bool {
+    bindings_for(mode).any(|b| b.codes.contains(&code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_mode_has_bindings() {
+        for mode in [
+            Mode::Browse,
+            Mode::Compose,
+            Mode::NewSessionAgent,
+            Mode::NewSessionName,
+            Mode::ConfirmDelete,
+            Mode::ConfirmQuit,
+            Mode::EditNote,
+            Mode::Help,
+        ] {
+            assert!(bindings_for(&mode).next().is_some(), "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn is_documented_matches_codes() {
+        assert!(is_documented(&Mode::Browse, KeyCode::Char('?')));
+        assert!(!is_documented(&Mode::Browse, KeyCode::Char('z')));
+        assert!(is_documented(&Mode::Help, KeyCode::Esc));
+    }
+}
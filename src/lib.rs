@@ -1,13 +1,31 @@
 pub mod agent;
 pub mod app;
 pub mod backend;
+pub mod bell;
+pub mod cli_style;
+pub mod config;
 pub mod event;
+pub mod export;
+pub mod feed;
+pub mod glob;
+pub mod keybindings;
 pub mod logs;
+pub mod ls_long;
 pub mod manifest;
+pub mod migrate;
 pub mod models;
+pub mod proc_tree;
+pub mod redact;
+pub mod replay;
+pub mod serve;
 pub mod session;
 pub mod state;
+pub mod stats_csv;
+pub mod stats_markdown;
+pub mod stats_summary;
 pub mod system;
 pub mod tmux;
 pub mod tmux_control;
 pub mod ui;
+pub mod usage_report;
+pub mod worktree;
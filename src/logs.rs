@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io::{Read, Seek, SeekFrom};
 use std::path::PathBuf;
 use std::time::Duration;
@@ -15,6 +15,20 @@ const MAX_TREE_DEPTH: usize = 5;
 /// Maximum total PIDs collected during a process tree walk.
 const MAX_TREE_PIDS: usize = 100;
 
+/// Default timeout for `lsof`, which can stall on network mounts. Kept well
+/// under `CMD_TIMEOUT` so a slow lsof doesn't freeze log resolution.
+pub const DEFAULT_LSOF_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Current lsof timeout in milliseconds; see `set_lsof_timeout`.
+static LSOF_TIMEOUT_MS: std::sync::atomic::AtomicU64 =
+    std::sync::atomic::AtomicU64::new(DEFAULT_LSOF_TIMEOUT.as_millis() as u64);
+
+/// Upper bound on cached lsof outputs (one per distinct PID list).
+const MAX_LSOF_CACHE_ENTRIES: usize = 64;
+
+/// Last successful lsof output per PID list, used when lsof times out.
+static LSOF_CACHE: std::sync::Mutex<Option<HashMap<String, String>>> = std::sync::Mutex::new(None);
+
 /// Run a Command with a timeout, returning its Output.
 async fn run_cmd_timeout(cmd: &mut Command) -> AnyhowResult<std::process::Output> {
     match tokio::time::timeout(CMD_TIMEOUT, cmd.output()).await {
@@ -23,6 +37,242 @@ async fn run_cmd_timeout(cmd: &mut Command) -> AnyhowResult<std::process::Output
     }
 }
 
+/// Override the lsof timeout (from `Config::lsof_timeout_ms`).
+pub fn set_lsof_timeout(timeout: Duration) {
+    LSOF_TIMEOUT_MS.store(
+        timeout.as_millis() as u64,
+        std::sync::atomic::Ordering::Relaxed,
+    );
+}
+
+fn lsof_timeout() -> Duration {
+    Duration::from_millis(LSOF_TIMEOUT_MS.load(std::sync::atomic::Ordering::Relaxed))
+}
+
+/// Whether Claude previews merge in subagent transcripts; see
+/// `set_subagent_transcripts`.
+static SUBAGENT_TRANSCRIPTS: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+/// Enable merging subagent logs into the preview (from
+/// `Config::subagent_transcripts`).
+pub fn set_subagent_transcripts(enabled: bool) {
+    SUBAGENT_TRANSCRIPTS.store(enabled, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn subagent_transcripts() -> bool {
+    SUBAGENT_TRANSCRIPTS.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// A way of mapping a pane to its agent log, in the order resolvers try them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveStrategy {
+    /// `--session-id` on a process command line.
+    Cmdline,
+    /// A log file held open by the pane's process tree.
+    Lsof,
+    /// The newest unclaimed log in the project's log dir.
+    Mtime,
+}
+
+impl ResolveStrategy {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "cmdline" => Some(Self::Cmdline),
+            "lsof" => Some(Self::Lsof),
+            "mtime" => Some(Self::Mtime),
+            _ => None,
+        }
+    }
+}
+
+const CLAUDE_RESOLVE_ORDER: &[ResolveStrategy] = &[ResolveStrategy::Cmdline, ResolveStrategy::Lsof];
+const CODEX_RESOLVE_ORDER: &[ResolveStrategy] = &[ResolveStrategy::Lsof];
+const GEMINI_RESOLVE_ORDER: &[ResolveStrategy] = &[ResolveStrategy::Lsof, ResolveStrategy::Mtime];
+
+/// Strategy forced by `HYDRA_RESOLVE_STRATEGY`, for debugging mis-resolution.
+/// Unrecognized values are ignored.
+pub fn forced_resolve_strategy() -> Option<ResolveStrategy> {
+    ResolveStrategy::parse(&std::env::var("HYDRA_RESOLVE_STRATEGY").ok()?)
+}
+
+/// The strategies to try from `order`: all of them, or only `forced` when
+/// set. A provider that lacks the forced strategy gets an empty plan.
+fn resolve_plan(
+    order: &[ResolveStrategy],
+    forced: Option<ResolveStrategy>,
+) -> Vec<ResolveStrategy> {
+    order
+        .iter()
+        .copied()
+        .filter(|s| forced.is_none_or(|f| f == *s))
+        .collect()
+}
+
+#[derive(Debug)]
+enum LsofOutcome {
+    Completed(String),
+    TimedOut,
+    Failed,
+}
+
+async fn run_lsof(pid_list: &str, timeout: Duration) -> LsofOutcome {
+    let mut cmd = Command::new("lsof");
+    cmd.args(["-p", pid_list]).kill_on_drop(true);
+    match tokio::time::timeout(timeout, cmd.output()).await {
+        Ok(Ok(output)) => {
+            LsofOutcome::Completed(String::from_utf8_lossy(&output.stdout).into_owned())
+        }
+        Ok(Err(_)) => LsofOutcome::Failed,
+        Err(_) => LsofOutcome::TimedOut,
+    }
+}
+
+/// Record a completed lsof run in `cache`, or on timeout fall back to the
+/// previous output for the same PID list so resolution keeps its last answer
+/// instead of dropping the session's log.
+fn apply_lsof_outcome(
+    cache: &mut HashMap<String, String>,
+    pid_list: &str,
+    outcome: LsofOutcome,
+) -> Option<String> {
+    match outcome {
+        LsofOutcome::Completed(stdout) => {
+            if cache.len() >= MAX_LSOF_CACHE_ENTRIES && !cache.contains_key(pid_list) {
+                cache.clear();
+            }
+            cache.insert(pid_list.to_string(), stdout.clone());
+            Some(stdout)
+        }
+        LsofOutcome::TimedOut => cache.get(pid_list).cloned(),
+        LsofOutcome::Failed => None,
+    }
+}
+
+/// Run `lsof -p <pids>` with the lsof timeout, falling back to the cached
+/// output from the previous run for these PIDs if it times out.
+async fn lsof_output(pids: &[u32]) -> Option<String> {
+    if pids.is_empty() {
+        return None;
+    }
+    let pid_list = pids
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let outcome = run_lsof(&pid_list, lsof_timeout()).await;
+    let mut cache = LSOF_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    apply_lsof_outcome(cache.get_or_insert_with(HashMap::new), &pid_list, outcome)
+}
+
+/// Whether a Claude `user` entry is a prompt typed by the user, as opposed
+/// to a tool result or an injected meta message.
+fn is_claude_prompt(v: &serde_json::Value) -> bool {
+    if v.get("toolUseResult").is_some() || v.get("isMeta").and_then(|m| m.as_bool()) == Some(true) {
+        return false;
+    }
+    let content = v.get("message").and_then(|m| m.get("content"));
+    !content.and_then(|c| c.as_array()).is_some_and(|items| {
+        items
+            .iter()
+            .any(|item| item.get("type").and_then(|t| t.as_str()) == Some("tool_result"))
+    })
+}
+
+/// Lines of a JSONL chunk with any trailing `\r` removed. `str::lines()`
+/// only strips `\r\n` pairs, so the last line of a chunk cut just before its
+/// `\n` would otherwise keep the `\r` of a CRLF-terminated log.
+fn log_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+}
+
+/// Log lines longer than this are skipped instead of parsed. Lines this
+/// big are almost always inlined file or image data (a base64 screenshot in
+/// a tool result) and carry nothing the stats or preview use.
+pub const MAX_LOG_LINE_BYTES: usize = 2 * 1024 * 1024;
+
+fn is_oversized_line(line: &str) -> bool {
+    line.len() > MAX_LOG_LINE_BYTES
+}
+
+/// `Unparsed` stand-in for an oversized line: its size and a summary of
+/// its start, without touching the rest of it.
+fn oversized_line_entry(line: &str) -> ConversationEntry {
+    let head_end = line.char_indices().nth(400).map_or(line.len(), |(i, _)| i);
+    ConversationEntry::Unparsed {
+        reason: format!(
+            "Line too long to parse ({:.1} MB)",
+            line.len() as f64 / (1024.0 * 1024.0)
+        ),
+        raw: summarize_jsonl_line(&line[..head_end], 220),
+    }
+}
+
+/// Time-to-first-token: how long the agent took to answer each prompt,
+/// measured from the user message to the next assistant message.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Ttft {
+    /// Oldest prompt still waiting for an answer.
+    pending_prompt: Option<chrono::DateTime<chrono::Utc>>,
+    /// Prompts answered so far.
+    pub count: u32,
+    pub total_ms: u64,
+    pub last_ms: Option<u64>,
+}
+
+impl Ttft {
+    /// Note a prompt sent at `at`. Follow-ups sent before the agent answers
+    /// don't restart the clock.
+    pub fn prompt(&mut self, at: chrono::DateTime<chrono::Utc>) {
+        self.pending_prompt.get_or_insert(at);
+    }
+
+    /// Note an assistant message at `at`, closing out the pending prompt.
+    pub fn reply(&mut self, at: chrono::DateTime<chrono::Utc>) {
+        let Some(prompt) = self.pending_prompt.take() else {
+            return;
+        };
+        let ms = (at - prompt).num_milliseconds().max(0) as u64;
+        self.count += 1;
+        self.total_ms += ms;
+        self.last_ms = Some(ms);
+    }
+
+    pub fn average(&self) -> Option<std::time::Duration> {
+        (self.count > 0)
+            .then(|| std::time::Duration::from_millis(self.total_ms / u64::from(self.count)))
+    }
+
+    pub fn last(&self) -> Option<std::time::Duration> {
+        self.last_ms.map(std::time::Duration::from_millis)
+    }
+}
+
+/// A bash command still running, tracked from Claude `bash_progress`
+/// entries until its tool result arrives.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunningBash {
+    /// Tool call the progress belongs to.
+    pub tool_use_id: Option<String>,
+    /// The first progress entry's timestamp minus its elapsed time.
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Elapsed seconds reported by the newest progress entry.
+    pub elapsed_secs: u64,
+}
+
+impl RunningBash {
+    /// How long the command has run at `now`: the wall-clock time since it
+    /// started, or the reported elapsed time if that is longer.
+    pub fn running_for(&self, now: chrono::DateTime<chrono::Utc>) -> std::time::Duration {
+        let since_start = self
+            .started_at
+            .and_then(|start| (now - start).to_std().ok())
+            .unwrap_or_default();
+        since_start.max(std::time::Duration::from_secs(self.elapsed_secs))
+    }
+}
+
 /// Per-session stats aggregated from Claude Code JSONL logs.
 /// Updated incrementally — only new bytes are parsed on each refresh.
 #[derive(Debug, Default, Clone)]
@@ -32,21 +282,67 @@ pub struct SessionStats {
     pub tokens_out: u64,
     pub tokens_cache_read: u64,
     pub tokens_cache_write: u64,
+    /// Portion of `tokens_cache_write` written with the extended 1-hour TTL.
+    pub tokens_cache_write_1h: u64,
     pub edits: u16,
     pub bash_cmds: u16,
+    /// Web search / fetch invocations, which some providers bill separately.
+    pub web_tools: u16,
+    /// Which counter each tool call bumps; the built-in names unless the
+    /// creator installs the configured mapping.
+    pub tool_categories: ToolCategories,
+    /// Every file the agent touched, read or modified.
     pub files: HashSet<String>,
+    /// The subset of `files` the agent wrote or edited.
+    pub modified_files: HashSet<String>,
     /// Files in order of most recent edit (last = most recent).
     /// Deduplicated: each path appears at most once.
     pub recent_files: Vec<String>,
+    /// ISO 8601 timestamp of the first user message (session start).
+    pub first_user_ts: Option<String>,
     /// ISO 8601 timestamp of the most recent user message (task start).
     pub last_user_ts: Option<String>,
     /// ISO 8601 timestamp of the most recent assistant message (task end).
     pub last_assistant_ts: Option<String>,
     pub read_offset: u64,
+    /// Lines over `MAX_LOG_LINE_BYTES` skipped without parsing.
+    pub skipped_lines: u32,
+    /// The last read ended inside an oversized line, so the next one drops
+    /// everything up to its newline.
+    pub skipping_line: bool,
     /// Active subagent count (from queue-operation enqueue/remove entries).
     pub active_subagents: u16,
+    /// The newest message or progress entry is a `waiting_for_task`
+    /// progress event: the agent is blocked until the user acts.
+    pub awaiting_input: bool,
+    /// Total time spent executing tools, summed from `toolUseResult` duration
+    /// fields. Zero when the log carries no per-tool timing.
+    pub tool_time_ms: u64,
+    /// Conversation read offsets for subagent logs, keyed by agent id.
+    pub subagent_offsets: HashMap<String, u64>,
+    /// Model named by the newest assistant/turn entry, as logged.
+    pub last_model: Option<String>,
+    /// Per-turn token counts (input + output) with their log timestamps,
+    /// oldest first. Only the last `TOKEN_SAMPLE_RETENTION` is kept.
+    pub token_samples: VecDeque<(chrono::DateTime<chrono::Utc>, u64)>,
+    /// The agent's newest TODO list, in its own order. Each update replaces
+    /// the whole list; empty until the agent writes one.
+    pub todos: Vec<TodoItem>,
+    /// Response latency for each prompt in the session.
+    pub ttft: Ttft,
+    /// The bash command in flight, if its progress has been logged.
+    pub running_bash: Option<RunningBash>,
+    /// Claude's own totals from the newest `result` entry, to check the
+    /// incremental sums against.
+    pub last_run: Option<RunResult>,
 }
 
+/// How far back `SessionStats::token_samples` reaches.
+const TOKEN_SAMPLE_RETENTION: chrono::TimeDelta = chrono::TimeDelta::hours(1);
+
+/// Upper bound on retained token samples, for sessions with very fast turns.
+const MAX_TOKEN_SAMPLES: usize = 2048;
+
 /// Upper bound for per-session touched file history.
 /// Keeps enough history for real projects while preventing unbounded growth.
 const MAX_SESSION_TRACKED_FILES: usize = 4096;
@@ -56,7 +352,7 @@ impl SessionStats {
     pub fn cost_usd(&self) -> f64 {
         let input = self.tokens_in as f64 * CLAUDE_INPUT_USD_PER_MTOK / 1_000_000.0;
         let output = self.tokens_out as f64 * CLAUDE_OUTPUT_USD_PER_MTOK / 1_000_000.0;
-        input + output
+        input + output + claude_cache_write_cost_usd(self.tokens_cache_write_1h)
     }
 
     #[cfg(test)]
@@ -89,6 +385,87 @@ impl SessionStats {
         }
     }
 
+    /// Whether the log shows work in progress: a task awaiting its reply, or
+    /// subagents still running after the main agent's last message.
+    pub fn is_working(&self) -> bool {
+        self.active_subagents > 0 || self.task_elapsed().is_some()
+    }
+
+    /// Wall-clock span from the first user message to the latest activity,
+    /// across every task in the session. None until a user message is seen.
+    pub fn session_duration(&self) -> Option<std::time::Duration> {
+        let first = self.started_at()?;
+        let last = self.last_activity_at()?;
+        Some((last - first).to_std().unwrap_or_default())
+    }
+
+    /// Timestamp of the first user message, when the session started.
+    pub fn started_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        parse_iso_timestamp(self.first_user_ts.as_deref()?)
+    }
+
+    /// Most recent user or assistant log timestamp.
+    pub fn last_activity_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        latest_timestamp(
+            self.last_user_ts.as_deref(),
+            self.last_assistant_ts.as_deref(),
+        )
+    }
+
+    /// Estimated cost in USD of this session's logged usage, priced with
+    /// `agent`'s rates the same way the daily totals are.
+    pub fn estimated_cost_usd(&self, agent: &crate::session::AgentType) -> f64 {
+        let mut priced = GlobalStats::default();
+        match agent {
+            crate::session::AgentType::Claude => add_claude_usage(
+                &mut priced,
+                self.tokens_in,
+                self.tokens_out,
+                self.tokens_cache_read,
+                self.tokens_cache_write,
+                self.tokens_cache_write_1h,
+            ),
+            crate::session::AgentType::Codex => add_codex_usage(
+                &mut priced,
+                self.tokens_in,
+                self.tokens_out,
+                self.tokens_cache_read,
+            ),
+            // Session stats already fold Gemini thoughts into output.
+            crate::session::AgentType::Gemini => add_gemini_usage(
+                &mut priced,
+                self.tokens_in,
+                self.tokens_out,
+                self.tokens_cache_read,
+                0,
+            ),
+        }
+        priced.cost_usd()
+    }
+
+    /// Bump the counter for one call of tool `name`, if it has one.
+    pub fn count_tool(&mut self, name: &str) {
+        match self.tool_categories.category(name) {
+            Some(ToolCategory::Edit) => self.edits += 1,
+            Some(ToolCategory::Bash) => self.bash_cmds += 1,
+            Some(ToolCategory::Web) => self.web_tools += 1,
+            None => {}
+        }
+    }
+
+    /// Files touched only by reads and searches, sorted.
+    pub fn read_only_files(&self) -> Vec<&String> {
+        let mut files: Vec<&String> = self.files.difference(&self.modified_files).collect();
+        files.sort();
+        files
+    }
+
+    /// Record a file the agent wrote or edited.
+    pub fn touch_modified_file(&mut self, path: String) {
+        self.modified_files.insert(path.clone());
+        self.touch_file(path);
+    }
+
     /// Record a file touch, updating both the dedup set and recency order.
     pub fn touch_file(&mut self, path: String) {
         // Existing path: move it to the end (most recent).
@@ -103,6 +480,7 @@ impl SessionStats {
             if let Some(evicted) = self.recent_files.first().cloned() {
                 self.recent_files.remove(0);
                 self.files.remove(&evicted);
+                self.modified_files.remove(&evicted);
             } else {
                 break;
             }
@@ -111,6 +489,89 @@ impl SessionStats {
         self.files.insert(path.clone());
         self.recent_files.push(path);
     }
+
+    /// Aggregate tool execution time, if the log reported any.
+    pub fn tool_time(&self) -> Option<std::time::Duration> {
+        (self.tool_time_ms > 0).then(|| std::time::Duration::from_millis(self.tool_time_ms))
+    }
+
+    /// How long the in-flight bash command has run, if at least `threshold`.
+    pub fn long_running_bash(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        threshold: std::time::Duration,
+    ) -> Option<std::time::Duration> {
+        let running_for = self.running_bash.as_ref()?.running_for(now);
+        (running_for >= threshold).then_some(running_for)
+    }
+
+    /// Record `tokens` consumed by a turn logged at `at`, dropping samples
+    /// that fall out of the retention window behind the newest one.
+    pub fn record_token_sample(&mut self, at: chrono::DateTime<chrono::Utc>, tokens: u64) {
+        let pos = self.token_samples.partition_point(|(t, _)| *t <= at);
+        self.token_samples.insert(pos, (at, tokens));
+        let newest = self.token_samples.back().map_or(at, |(t, _)| *t);
+        while self.token_samples.front().is_some_and(|(t, _)| {
+            newest - *t > TOKEN_SAMPLE_RETENTION || self.token_samples.len() > MAX_TOKEN_SAMPLES
+        }) {
+            self.token_samples.pop_front();
+        }
+    }
+
+    /// Tokens consumed by turns logged in `(now - window, now]`.
+    pub fn tokens_in_window(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        window: chrono::TimeDelta,
+    ) -> u64 {
+        let start = now - window;
+        self.token_samples
+            .iter()
+            .filter(|(t, _)| *t > start && *t <= now)
+            .map(|(_, tokens)| tokens)
+            .sum()
+    }
+
+    /// Tokens consumed in the trailing hour.
+    pub fn tokens_last_hour(&self) -> u64 {
+        self.tokens_in_window(chrono::Utc::now(), TOKEN_SAMPLE_RETENTION)
+    }
+}
+
+/// Duration fields Claude attaches to `toolUseResult` objects, depending on
+/// the tool (e.g. `durationMs` for Grep/WebFetch, `totalDurationMs` for Task).
+const TOOL_RESULT_DURATION_FIELDS: &[&str] = &["durationMs", "totalDurationMs", "duration_ms"];
+
+/// Fold a `toolUseResult` object into session stats: touched filenames and
+/// per-tool execution time. Edit and Write results carry a top-level
+/// `filePath`; Read nests it under `file`, and Glob/Grep list `filenames`.
+fn apply_tool_use_result(result: &serde_json::Value, stats: &mut SessionStats) {
+    if let Some(path) = result.get("filePath").and_then(|p| p.as_str()) {
+        stats.touch_modified_file(path.to_string());
+    }
+    if let Some(path) = result
+        .get("file")
+        .and_then(|f| f.get("filePath"))
+        .and_then(|p| p.as_str())
+    {
+        stats.touch_file(path.to_string());
+    }
+    if let Some(filenames) = result.get("filenames").and_then(|f| f.as_array()) {
+        for fname in filenames {
+            if let Some(s) = fname.as_str() {
+                stats.touch_file(s.to_string());
+            }
+        }
+    }
+
+    if let Some(ms) = TOOL_RESULT_DURATION_FIELDS
+        .iter()
+        .find_map(|field| result.get(*field).and_then(|d| d.as_f64()))
+    {
+        if ms.is_finite() && ms > 0.0 {
+            stats.tool_time_ms = stats.tool_time_ms.saturating_add(ms.round() as u64);
+        }
+    }
 }
 
 /// Parse an ISO 8601 timestamp string into a chrono DateTime.
@@ -118,25 +579,284 @@ fn parse_iso_timestamp(s: &str) -> Option<chrono::DateTime<chrono::Utc>> {
     s.parse::<chrono::DateTime<chrono::Utc>>().ok()
 }
 
-/// Format a token count compactly: 1234 → "1.2k", 1234567 → "1.2M"
+/// Most recent of two optional ISO-8601 timestamps. Unparseable values are
+/// ignored, so one bad field doesn't hide the other.
+pub fn latest_timestamp(a: Option<&str>, b: Option<&str>) -> Option<chrono::DateTime<chrono::Utc>> {
+    let a = a.and_then(parse_iso_timestamp);
+    let b = b.and_then(parse_iso_timestamp);
+    a.max(b)
+}
+
+/// Whether token counts are scaled to k/M or printed in full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenStyle {
+    /// `45.3k`, `1.2M`.
+    #[default]
+    Compact,
+    /// `45,312`.
+    Exact,
+}
+
+/// How every token count in the UI and CLI is displayed; see
+/// `set_token_format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenFormat {
+    pub style: TokenStyle,
+    /// Decimal places for `Compact`, at most `MAX_PRECISION`.
+    pub precision: u8,
+}
+
+impl TokenFormat {
+    pub const DEFAULT_PRECISION: u8 = 1;
+    pub const MAX_PRECISION: u8 = 3;
+    pub const DEFAULT: Self = Self::new(TokenStyle::Compact, Self::DEFAULT_PRECISION);
+
+    pub const fn new(style: TokenStyle, precision: u8) -> Self {
+        let precision = if precision > Self::MAX_PRECISION {
+            Self::MAX_PRECISION
+        } else {
+            precision
+        };
+        Self { style, precision }
+    }
+}
+
+impl Default for TokenFormat {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Current token display format; see `set_token_format`.
+static TOKEN_FORMAT: std::sync::RwLock<TokenFormat> = std::sync::RwLock::new(TokenFormat::DEFAULT);
+
+/// Set how `format_tokens` renders counts (from `Config::token_format`).
+pub fn set_token_format(format: TokenFormat) {
+    *TOKEN_FORMAT.write().unwrap_or_else(|e| e.into_inner()) = format;
+}
+
+/// Format a token count in the configured style: 1234 → "1.2k",
+/// 1234567 → "1.2M" by default.
 pub fn format_tokens(n: u64) -> String {
-    if n >= 1_000_000 {
-        format!("{:.1}M", n as f64 / 1_000_000.0)
-    } else if n >= 1_000 {
-        format!("{:.1}k", n as f64 / 1_000.0)
+    let format = *TOKEN_FORMAT.read().unwrap_or_else(|e| e.into_inner());
+    format_tokens_as(n, format)
+}
+
+/// Format a token count in `format`, regardless of the configured one.
+pub fn format_tokens_as(n: u64, format: TokenFormat) -> String {
+    let precision = usize::from(format.precision);
+    match format.style {
+        TokenStyle::Exact => group_thousands(n),
+        TokenStyle::Compact if n >= 1_000_000 => {
+            format!("{:.precision$}M", n as f64 / 1_000_000.0)
+        }
+        TokenStyle::Compact if n >= 1_000 => format!("{:.precision$}k", n as f64 / 1_000.0),
+        TokenStyle::Compact => format!("{n}"),
+    }
+}
+
+/// `45312` → `"45,312"`.
+fn group_thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(digit);
+    }
+    out
+}
+
+/// Compact edits/bash-commands/web-tools summary, e.g. "✎12 ⌘4 ⌕2".
+/// Zero counters are omitted; returns an empty string when all are zero.
+pub fn format_activity(edits: u16, bash_cmds: u16, web_tools: u16) -> String {
+    let mut parts = Vec::new();
+    if edits > 0 {
+        parts.push(format!("\u{270E}{edits}"));
+    }
+    if bash_cmds > 0 {
+        parts.push(format!("\u{2318}{bash_cmds}"));
+    }
+    if web_tools > 0 {
+        parts.push(format!("\u{2315}{web_tools}"));
+    }
+    parts.join(" ")
+}
+
+/// Compact model label for display: drops the `claude-` prefix and a
+/// trailing `-YYYYMMDD` release date (`claude-sonnet-4-20250514` → `sonnet-4`).
+pub fn short_model_name(model: &str) -> &str {
+    let model = model.strip_prefix("claude-").unwrap_or(model);
+    match model.rsplit_once('-') {
+        Some((base, date)) if date.len() == 8 && date.bytes().all(|b| b.is_ascii_digit()) => base,
+        _ => model,
+    }
+}
+
+/// The agent whose provider serves `model`, judged from its name.
+fn provider_for_model(model: &str) -> Option<crate::session::AgentType> {
+    let model = model.trim().to_ascii_lowercase();
+    if model.contains("claude") {
+        Some(crate::session::AgentType::Claude)
+    } else if model.contains("gemini") {
+        Some(crate::session::AgentType::Gemini)
+    } else if model.starts_with("gpt-")
+        || model.contains("codex")
+        || (model.starts_with('o') && model[1..].starts_with(|c: char| c.is_ascii_digit()))
+    {
+        Some(crate::session::AgentType::Codex)
     } else {
-        format!("{n}")
+        None
+    }
+}
+
+/// A logged model name worth showing; Claude writes placeholders such as
+/// `<synthetic>` for locally generated messages.
+fn real_model_name(model: Option<&str>) -> Option<String> {
+    model
+        .map(str::trim)
+        .filter(|m| !m.is_empty() && !m.starts_with('<'))
+        .map(str::to_string)
+}
+
+/// "3 subagents" / "1 subagent", or `None` when no subagents are active.
+pub fn format_subagents(count: u16) -> Option<String> {
+    match count {
+        0 => None,
+        1 => Some("1 subagent".to_string()),
+        n => Some(format!("{n} subagents")),
+    }
+}
+
+/// Extra log file globs per provider, scanned for global stats on top of
+/// the standard log directories. `*` matches across `/`, so `**` works too.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ExtraLogGlobs {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub claude: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub codex: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub gemini: Vec<String>,
+}
+
+impl ExtraLogGlobs {
+    pub fn is_empty(&self) -> bool {
+        self.claude.is_empty() && self.codex.is_empty() && self.gemini.is_empty()
+    }
+
+    /// The same globs with a leading `~/` replaced by `home`.
+    fn expand_home(&self, home: Option<&std::path::Path>) -> Self {
+        let expand = |globs: &[String]| -> Vec<String> {
+            globs
+                .iter()
+                .map(|g| g.trim())
+                .filter(|g| !g.is_empty())
+                .map(|g| match (g.strip_prefix("~/"), home) {
+                    (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+                    _ => g.to_string(),
+                })
+                .collect()
+        };
+        Self {
+            claude: expand(&self.claude),
+            codex: expand(&self.codex),
+            gemini: expand(&self.gemini),
+        }
+    }
+}
+
+/// Session counter a tool call increments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolCategory {
+    Edit,
+    Bash,
+    Web,
+}
+
+/// Tool-name mappings (from `Config::tool_categories`) layered over the
+/// built-in names, e.g. an MCP `str_replace_editor` counted as `Edit`.
+/// Cheap to clone; the default has no extra names.
+#[derive(Debug, Default, Clone)]
+pub struct ToolCategories(Option<std::sync::Arc<HashMap<String, ToolCategory>>>);
+
+impl ToolCategories {
+    pub fn new(categories: HashMap<String, ToolCategory>) -> Self {
+        Self((!categories.is_empty()).then(|| std::sync::Arc::new(categories)))
+    }
+
+    /// The counter a tool call increments: the configured mapping first,
+    /// then the defaults.
+    pub fn category(&self, name: &str) -> Option<ToolCategory> {
+        self.0
+            .as_ref()
+            .and_then(|map| map.get(name).copied())
+            .or_else(|| default_tool_category(name))
+    }
+}
+
+/// Built-in tool names per counter, across agent vocabularies.
+fn default_tool_category(name: &str) -> Option<ToolCategory> {
+    match name {
+        // Claude, Gemini, Codex
+        "Write" | "Edit" | "write_file" | "edit_file" | "replace_in_file" | "apply_patch" => {
+            Some(ToolCategory::Edit)
+        }
+        "Bash" | "run_shell_command" | "shell" | "exec_command" => Some(ToolCategory::Bash),
+        name if is_web_tool(name) => Some(ToolCategory::Web),
+        _ => None,
+    }
+}
+
+/// Whether a tool call is a web search or fetch, across agent vocabularies
+/// (Claude `WebSearch`/`WebFetch`, Codex `web_search`, Gemini
+/// `google_web_search`/`web_fetch`).
+pub fn is_web_tool(name: &str) -> bool {
+    matches!(
+        name,
+        "WebSearch" | "WebFetch" | "web_search" | "web_fetch" | "google_web_search"
+    )
+}
+
+/// Currency costs are displayed in. Costs are computed in USD and
+/// converted at a fixed rate (`Config::currency`).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Currency {
+    /// Printed before the amount, e.g. `"€"`.
+    pub symbol: String,
+    /// Units of this currency per US dollar, e.g. `0.92`.
+    pub rate: f64,
+}
+
+impl Currency {
+    pub fn usd() -> Self {
+        Self {
+            symbol: "$".to_string(),
+            rate: 1.0,
+        }
     }
 }
 
-/// Format cost in USD compactly.
-pub fn format_cost(usd: f64) -> String {
-    if usd < 0.005 {
-        "$0.00".to_string()
-    } else if usd < 10.0 {
-        format!("${:.2}", usd)
+impl Default for Currency {
+    fn default() -> Self {
+        Self::usd()
+    }
+}
+
+/// Format a USD cost compactly in `currency`.
+pub fn format_cost(usd: f64, currency: &Currency) -> String {
+    let amount = usd * currency.rate;
+    let symbol = &currency.symbol;
+    if amount < 0.005 {
+        format!("{symbol}0.00")
+    } else if amount < 10.0 {
+        format!("{symbol}{amount:.2}")
     } else {
-        format!("${:.0}", usd)
+        format!("{symbol}{amount:.0}")
     }
 }
 
@@ -168,6 +888,62 @@ pub fn update_session_stats_and_last_message(
     update_session_stats_from_path_and_last_message(&path, stats)
 }
 
+/// Track the bash command a `bash_progress` entry reports on. Progress for
+/// a new tool call replaces the old command.
+fn observe_bash_progress(v: &serde_json::Value, stats: &mut SessionStats) {
+    let tool_use_id = v
+        .get("toolUseID")
+        .and_then(|id| id.as_str())
+        .map(str::to_string);
+    let elapsed_secs = v
+        .get("data")
+        .and_then(|d| d.get("elapsedTimeSeconds"))
+        .and_then(|e| e.as_u64())
+        .unwrap_or(0);
+    if let Some(running) = stats
+        .running_bash
+        .as_mut()
+        .filter(|running| running.tool_use_id == tool_use_id)
+    {
+        running.elapsed_secs = running.elapsed_secs.max(elapsed_secs);
+        return;
+    }
+    let started_at = v
+        .get("timestamp")
+        .and_then(|t| t.as_str())
+        .and_then(parse_iso_timestamp)
+        .map(|at| at - chrono::Duration::seconds(elapsed_secs as i64));
+    stats.running_bash = Some(RunningBash {
+        tool_use_id,
+        started_at,
+        elapsed_secs,
+    });
+}
+
+/// Stop tracking the running bash command once a user entry carries its
+/// tool result (any tool result, when the progress had no tool call id).
+fn clear_finished_bash(v: &serde_json::Value, stats: &mut SessionStats) {
+    let Some(running) = &stats.running_bash else {
+        return;
+    };
+    let results = v
+        .get("message")
+        .and_then(|m| m.get("content"))
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|item| item.get("type").and_then(|t| t.as_str()) == Some("tool_result"));
+    let finished = match running.tool_use_id.as_deref() {
+        Some(id) => results
+            .filter_map(tool_use_id_of)
+            .any(|result_id| result_id == id),
+        None => results.count() > 0 || v.get("toolUseResult").is_some(),
+    };
+    if finished {
+        stats.running_bash = None;
+    }
+}
+
 /// Core stats parser — reads from a specific file path.
 /// Separated from `update_session_stats` for testability (avoids HOME env var).
 #[cfg(test)]
@@ -203,52 +979,105 @@ pub fn update_session_stats_from_path_and_last_message(
         return None;
     }
 
+    // Finish skipping an oversized line cut off by the last read.
+    let mut start = stats.read_offset;
+    if stats.skipping_line {
+        match buf.iter().position(|&b| b == b'\n') {
+            Some(idx) => {
+                buf.drain(..=idx);
+                start += idx as u64 + 1;
+                stats.skipping_line = false;
+            }
+            None => {
+                stats.read_offset += buf.len() as u64;
+                return None;
+            }
+        }
+    }
+
     let last_newline = buf.iter().rposition(|&b| b == b'\n');
+    let tail_len = last_newline.map_or(buf.len(), |idx| buf.len() - idx - 1);
     let (valid_buf, new_offset) = match last_newline {
-        Some(idx) => (&buf[..idx], stats.read_offset + idx as u64 + 1),
-        None => return None, // Wait for a complete line
+        Some(idx) => (&buf[..idx], start + idx as u64 + 1),
+        None if tail_len <= MAX_LOG_LINE_BYTES => {
+            stats.read_offset = start;
+            return None; // Wait for a complete line
+        }
+        None => (&buf[..0], start),
     };
 
     let text = String::from_utf8_lossy(valid_buf);
     let mut last_text: Option<String> = None;
 
-    for line in text.lines() {
+    for line in log_lines(&text) {
         // Skip empty lines
         if line.len() < 10 {
             continue;
         }
+        if is_oversized_line(line) {
+            stats.skipped_lines += 1;
+            continue;
+        }
+
+        // Progress entries: only `waiting_for_task` leaves the agent blocked.
+        if line.contains("\"progress\"") {
+            if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
+                if v.get("type").and_then(|t| t.as_str()) == Some("progress") {
+                    let kind = v
+                        .get("data")
+                        .and_then(|d| d.get("type"))
+                        .and_then(|t| t.as_str());
+                    stats.awaiting_input = kind == Some("waiting_for_task");
+                    if kind == Some("bash_progress") {
+                        observe_bash_progress(&v, stats);
+                    }
+                    continue;
+                }
+            }
+        }
 
         // Fast path: assistant messages. Parse once and update both stats + last text.
         if line.contains("\"assistant\"") {
             if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
                 if v.get("type").and_then(|t| t.as_str()) == Some("assistant") {
+                    stats.awaiting_input = false;
                     if let Some(ts) = v.get("timestamp").and_then(|t| t.as_str()) {
                         stats.last_assistant_ts = Some(ts.to_string());
+                        if let Some(at) = parse_iso_timestamp(ts) {
+                            stats.ttft.reply(at);
+                        }
                     }
 
                     if let Some(text) = extract_assistant_message_text(&v) {
                         last_text = Some(text);
                     }
 
+                    let model = v.get("message").and_then(|m| m.get("model"));
+                    if let Some(model) = real_model_name(model.and_then(|m| m.as_str())) {
+                        stats.last_model = Some(model);
+                    }
+
                     // Extract token usage
                     if let Some(usage) = v.get("message").and_then(|m| m.get("usage")) {
                         stats.turns += 1;
-                        stats.tokens_in += usage
+                        let input = usage
                             .get("input_tokens")
                             .and_then(|t| t.as_u64())
                             .unwrap_or(0);
-                        stats.tokens_out += usage
-                            .get("output_tokens")
-                            .and_then(|t| t.as_u64())
-                            .unwrap_or(0);
+                        let output = claude_output_tokens(usage);
+                        stats.tokens_in += input;
+                        stats.tokens_out += output;
+                        let ts = v.get("timestamp").and_then(|t| t.as_str());
+                        if let Some(ts) = ts.and_then(parse_iso_timestamp) {
+                            stats.record_token_sample(ts, input + output);
+                        }
                         stats.tokens_cache_read += usage
                             .get("cache_read_input_tokens")
                             .and_then(|t| t.as_u64())
                             .unwrap_or(0);
-                        stats.tokens_cache_write += usage
-                            .get("cache_creation_input_tokens")
-                            .and_then(|t| t.as_u64())
-                            .unwrap_or(0);
+                        let (cache_write, cache_write_1h) = claude_cache_write_tokens(usage);
+                        stats.tokens_cache_write += cache_write;
+                        stats.tokens_cache_write_1h += cache_write_1h;
                     }
 
                     // Count tool calls from content array
@@ -260,11 +1089,10 @@ pub fn update_session_stats_from_path_and_last_message(
                         for item in content {
                             if item.get("type").and_then(|t| t.as_str()) == Some("tool_use") {
                                 if let Some(name) = item.get("name").and_then(|n| n.as_str()) {
-                                    match name {
-                                        "Write" | "Edit" => stats.edits += 1,
-                                        "Bash" => stats.bash_cmds += 1,
-                                        _ => {}
-                                    }
+                                    stats.count_tool(name);
+                                }
+                                if let Some(todos) = parse_todo_update(item) {
+                                    stats.todos = todos;
                                 }
                             }
                         }
@@ -281,32 +1109,50 @@ pub fn update_session_stats_from_path_and_last_message(
         {
             if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
                 if v.get("type").and_then(|t| t.as_str()) == Some("user") {
+                    stats.awaiting_input = false;
                     if let Some(ts) = v.get("timestamp").and_then(|t| t.as_str()) {
+                        stats.first_user_ts.get_or_insert_with(|| ts.to_string());
                         stats.last_user_ts = Some(ts.to_string());
+                        if is_claude_prompt(&v) {
+                            if let Some(at) = parse_iso_timestamp(ts) {
+                                stats.ttft.prompt(at);
+                            }
+                            // A new prompt means any earlier command is over,
+                            // even one killed before logging its result.
+                            stats.running_bash = None;
+                        }
                     }
+                    // Tool results are user entries too.
+                    if let Some(result) = v.get("toolUseResult") {
+                        apply_tool_use_result(result, stats);
+                    }
+                    clear_finished_bash(&v, stats);
                 }
             }
             continue;
         }
 
-        // Fast path: tool results with filenames
-        if line.contains("\"filenames\"") && line.contains("\"toolUseResult\"") {
+        // Fast path: tool results (filenames, execution time)
+        if line.contains("\"toolUseResult\"") {
             if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
-                if let Some(filenames) = v
-                    .get("toolUseResult")
-                    .and_then(|r| r.get("filenames"))
-                    .and_then(|f| f.as_array())
-                {
-                    for fname in filenames {
-                        if let Some(s) = fname.as_str() {
-                            stats.touch_file(s.to_string());
-                        }
-                    }
+                if let Some(result) = v.get("toolUseResult") {
+                    apply_tool_use_result(result, stats);
                 }
+                clear_finished_bash(&v, stats);
             }
             continue;
         }
 
+        // Run summaries, written when a headless run completes.
+        if line.contains("\"result\"") {
+            if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
+                if let Some(run) = parse_run_result(&v) {
+                    stats.last_run = Some(run);
+                    continue;
+                }
+            }
+        }
+
         // Fast path: queue-operation entries for subagent tracking
         if line.contains("\"queue-operation\"") {
             if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
@@ -326,6 +1172,13 @@ pub fn update_session_stats_from_path_and_last_message(
     }
 
     stats.read_offset = new_offset;
+    // An unfinished line already over the limit won't be parsed once it
+    // ends either, so skip past it now rather than rereading it every time.
+    if tail_len > MAX_LOG_LINE_BYTES {
+        stats.skipped_lines += 1;
+        stats.skipping_line = true;
+        stats.read_offset += tail_len as u64;
+    }
     last_text
 }
 
@@ -333,9 +1186,46 @@ const FILE_DISCOVERY_INTERVAL_SECS: i64 = 30;
 
 // Claude Sonnet token pricing (USD per million tokens).
 // Update these when Anthropic changes pricing.
-// Cached tokens (read/write) are not charged to the user.
+// Cache reads are not charged to the user.
 const CLAUDE_INPUT_USD_PER_MTOK: f64 = 3.0;
 const CLAUDE_OUTPUT_USD_PER_MTOK: f64 = 15.0;
+// Standard (5-minute) cache writes are left out like cache reads. The
+// extended 1-hour TTL bills at 2x input against 1.25x for a standard write,
+// so only that surcharge is charged.
+const CLAUDE_CACHE_WRITE_1H_PREMIUM_USD_PER_MTOK: f64 = 2.25;
+
+/// Split a Claude `usage` object's cache writes into (total, 1-hour TTL).
+/// Newer logs break writes down under `cache_creation` by TTL; older logs only
+/// carry `cache_creation_input_tokens`, which counts as standard writes.
+fn claude_cache_write_tokens(usage: &serde_json::Value) -> (u64, u64) {
+    let legacy_total = usage
+        .get("cache_creation_input_tokens")
+        .and_then(|t| t.as_u64());
+    let Some(breakdown) = usage.get("cache_creation").filter(|c| c.is_object()) else {
+        return (legacy_total.unwrap_or(0), 0);
+    };
+    let tokens = |key: &str| breakdown.get(key).and_then(|t| t.as_u64()).unwrap_or(0);
+    let one_hour = tokens("ephemeral_1h_input_tokens");
+    let total = legacy_total.unwrap_or_else(|| tokens("ephemeral_5m_input_tokens") + one_hour);
+    (total.max(one_hour), one_hour)
+}
+
+/// Output tokens from a Claude `usage` object, including extended thinking.
+/// Thinking is billed as output; logs that report it separately (as
+/// `thinking_tokens` or `reasoning_tokens`) leave it out of `output_tokens`.
+fn claude_output_tokens(usage: &serde_json::Value) -> u64 {
+    let tokens = |key: &str| usage.get(key).and_then(|t| t.as_u64());
+    let output = tokens("output_tokens").unwrap_or(0);
+    let thinking = tokens("thinking_tokens")
+        .or_else(|| tokens("reasoning_tokens"))
+        .unwrap_or(0);
+    output + thinking
+}
+
+/// Cost of Claude cache writes: the 1-hour TTL surcharge on `one_hour` tokens.
+fn claude_cache_write_cost_usd(one_hour: u64) -> f64 {
+    one_hour as f64 * CLAUDE_CACHE_WRITE_1H_PREMIUM_USD_PER_MTOK / 1_000_000.0
+}
 
 // Uses OpenAI's published GPT-5 Codex token pricing as an estimate.
 // Update these when OpenAI changes pricing.
@@ -349,11 +1239,22 @@ struct CodexFileState {
     last_input_tokens: u64,
     last_output_tokens: u64,
     last_cached_input_tokens: u64,
+    /// Codex session id from the rollout's `session_meta` line.
+    session_id: Option<String>,
 }
 
-/// Machine-wide stats for today, aggregated across Claude and Codex logs.
-/// Updated incrementally — only new bytes are parsed on each refresh.
-#[derive(Debug, Clone, Default)]
+/// Highest cumulative `token_count` snapshot seen for a Codex session.
+#[derive(Debug, Clone, Copy, Default)]
+struct CodexSessionTotals {
+    total_tokens: u64,
+    input_tokens: u64,
+    output_tokens: u64,
+    cached_input_tokens: u64,
+}
+
+/// Machine-wide stats for today, aggregated across Claude and Codex logs.
+/// Updated incrementally — only new bytes are parsed on each refresh.
+#[derive(Debug, Clone, Default)]
 pub struct GlobalStats {
     // Aggregate totals displayed in the UI.
     pub tokens_in: u64,
@@ -365,20 +1266,31 @@ pub struct GlobalStats {
     pub claude_tokens_out: u64,
     pub claude_tokens_cache_read: u64,
     pub claude_tokens_cache_write: u64,
+    /// Portion of `claude_tokens_cache_write` written with the 1-hour TTL.
+    pub claude_tokens_cache_write_1h: u64,
     pub codex_tokens_in: u64,
     pub codex_tokens_out: u64,
     pub codex_tokens_cache_read: u64,
     pub gemini_tokens_in: u64,
     pub gemini_tokens_out: u64,
     pub gemini_tokens_cached: u64,
+    /// Gemini reasoning ("thoughts") tokens, billed at the output rate.
+    pub gemini_tokens_thoughts: u64,
+    /// Model named by the newest usage entry that names one. Without a
+    /// provider breakdown it picks the rates the aggregate totals are
+    /// priced at.
+    pub last_model: Option<String>,
     /// Per-file read offsets for incremental Claude log reading.
     file_offsets: HashMap<PathBuf, u64>,
     /// Per-file incremental state for Codex token_count parsing.
     codex_file_states: HashMap<PathBuf, CodexFileState>,
+    /// Per-session high-water totals, so a session resumed into a new
+    /// rollout file isn't counted again from zero.
+    codex_session_totals: HashMap<String, CodexSessionTotals>,
     /// Per-file sizes for Gemini session change detection.
     gemini_file_sizes: HashMap<PathBuf, u64>,
     /// Per-file token totals for Gemini (to compute deltas on re-parse).
-    gemini_file_tokens: HashMap<PathBuf, (u64, u64, u64)>,
+    gemini_file_tokens: HashMap<PathBuf, (u64, u64, u64, u64)>,
     /// Cached file list to avoid recursive scans on every refresh.
     known_claude_files: Vec<PathBuf>,
     /// Cached file list to avoid recursive scans on every refresh.
@@ -389,9 +1301,81 @@ pub struct GlobalStats {
     last_file_discovery_ts: i64,
     /// Date string (YYYY-MM-DD) these stats are for; reset when date changes.
     date: String,
+    /// Escaped Claude project directory prefixes to skip (see `set_excluded_projects`).
+    excluded_claude_projects: Vec<String>,
+    /// Home-expanded extra log globs (see `set_extra_log_globs`).
+    extra_log_globs: ExtraLogGlobs,
+    /// While set, refreshes advance file offsets without adding to totals
+    /// (see `hydra stats --pause`).
+    paused: bool,
 }
 
 impl GlobalStats {
+    /// Exclude Claude projects under these path prefixes from global totals.
+    /// Matching happens on the escaped directory names in `~/.claude/projects`
+    /// (`/` → `-`), so a prefix also matches sibling paths that escape the
+    /// same way. Changing the list forces a full rescan on the next update.
+    pub fn set_excluded_projects(&mut self, prefixes: &[String]) {
+        let home = dirs::home_dir();
+        let escaped: Vec<String> = prefixes
+            .iter()
+            .map(|p| {
+                let p = p.trim_end_matches('/');
+                match (p.strip_prefix("~/"), &home) {
+                    (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+                    _ => p.to_string(),
+                }
+            })
+            .filter(|p| !p.is_empty())
+            .map(|p| escape_project_path(&p))
+            .collect();
+        if escaped != self.excluded_claude_projects {
+            self.excluded_claude_projects = escaped;
+            // Clearing the date makes the next update reset totals and rediscover files.
+            self.date.clear();
+        }
+    }
+
+    /// Also scan files matching these globs. Changing them forces a full
+    /// rescan on the next update.
+    pub fn set_extra_log_globs(&mut self, globs: &ExtraLogGlobs) {
+        let expanded = globs.expand_home(dirs::home_dir().as_deref());
+        if expanded != self.extra_log_globs {
+            self.extra_log_globs = expanded;
+            self.date.clear();
+        }
+    }
+
+    /// Whether a Claude log file lives in an excluded project directory.
+    fn is_excluded_claude_file(
+        &self,
+        projects_dir: &std::path::Path,
+        path: &std::path::Path,
+    ) -> bool {
+        if self.excluded_claude_projects.is_empty() {
+            return false;
+        }
+        let Some(project) = path
+            .strip_prefix(projects_dir)
+            .ok()
+            .and_then(|rel| rel.components().next())
+            .and_then(|c| c.as_os_str().to_str())
+        else {
+            return false;
+        };
+        self.excluded_claude_projects.iter().any(|prefix| {
+            project == prefix
+                || project
+                    .strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.starts_with('-'))
+        })
+    }
+
+    /// Whether the last refresh skipped new usage (`hydra stats --pause`).
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
     fn has_provider_breakdown(&self) -> bool {
         self.claude_tokens_in > 0
             || self.claude_tokens_out > 0
@@ -403,6 +1387,7 @@ impl GlobalStats {
             || self.gemini_tokens_in > 0
             || self.gemini_tokens_out > 0
             || self.gemini_tokens_cached > 0
+            || self.gemini_tokens_thoughts > 0
     }
 
     pub fn has_usage(&self) -> bool {
@@ -417,6 +1402,7 @@ impl GlobalStats {
                 || self.gemini_tokens_in > 0
                 || self.gemini_tokens_out > 0
                 || self.gemini_tokens_cached > 0
+                || self.gemini_tokens_thoughts > 0
         } else {
             self.tokens_in > 0
                 || self.tokens_out > 0
@@ -425,88 +1411,140 @@ impl GlobalStats {
         }
     }
 
-    pub fn claude_display_tokens(&self) -> u64 {
+    /// These stats with a provider breakdown. Aggregate-only totals are
+    /// moved onto the provider `last_model` names, or Claude when it names
+    /// none, so they are priced at that provider's rates.
+    fn with_breakdown(&self) -> std::borrow::Cow<'_, Self> {
         if self.has_provider_breakdown() {
-            self.claude_tokens_in + self.claude_tokens_out
-        } else {
-            self.tokens_in + self.tokens_out
+            return std::borrow::Cow::Borrowed(self);
         }
+        let mut priced = GlobalStats::default();
+        let provider = self.last_model.as_deref().and_then(provider_for_model);
+        match provider.unwrap_or(crate::session::AgentType::Claude) {
+            crate::session::AgentType::Claude => add_claude_usage(
+                &mut priced,
+                self.tokens_in,
+                self.tokens_out,
+                self.tokens_cache_read,
+                self.tokens_cache_write,
+                0,
+            ),
+            crate::session::AgentType::Codex => add_codex_usage(
+                &mut priced,
+                self.tokens_in,
+                self.tokens_out,
+                self.tokens_cache_read,
+            ),
+            crate::session::AgentType::Gemini => add_gemini_usage(
+                &mut priced,
+                self.tokens_in,
+                self.tokens_out,
+                self.tokens_cache_read,
+                0,
+            ),
+        }
+        std::borrow::Cow::Owned(priced)
+    }
+
+    pub fn claude_display_tokens(&self) -> u64 {
+        let stats = self.with_breakdown();
+        stats.claude_tokens_in + stats.claude_tokens_out
     }
 
     pub fn codex_display_tokens(&self) -> u64 {
-        if self.has_provider_breakdown() {
-            // codex_tokens_in includes cached; subtract them out.
-            let uncached_in = self
-                .codex_tokens_in
-                .saturating_sub(self.codex_tokens_cache_read);
-            uncached_in + self.codex_tokens_out
-        } else {
-            0
-        }
+        let stats = self.with_breakdown();
+        // codex_tokens_in includes cached; subtract them out.
+        let uncached_in = stats
+            .codex_tokens_in
+            .saturating_sub(stats.codex_tokens_cache_read);
+        uncached_in + stats.codex_tokens_out
     }
 
     pub fn gemini_display_tokens(&self) -> u64 {
-        if self.has_provider_breakdown() {
-            // gemini_tokens_in includes cached; subtract them out.
-            let uncached_in = self
-                .gemini_tokens_in
-                .saturating_sub(self.gemini_tokens_cached);
-            uncached_in + self.gemini_tokens_out
-        } else {
-            0
-        }
+        let stats = self.with_breakdown();
+        // gemini_tokens_in includes cached; subtract them out.
+        let uncached_in = stats
+            .gemini_tokens_in
+            .saturating_sub(stats.gemini_tokens_cached);
+        uncached_in + stats.gemini_tokens_out + stats.gemini_tokens_thoughts
     }
 
     pub fn claude_cost_usd(&self) -> f64 {
-        if !self.has_provider_breakdown() {
-            let input = self.tokens_in as f64 * CLAUDE_INPUT_USD_PER_MTOK / 1_000_000.0;
-            let output = self.tokens_out as f64 * CLAUDE_OUTPUT_USD_PER_MTOK / 1_000_000.0;
-            return input + output;
-        }
-
-        let claude_input = self.claude_tokens_in as f64 * CLAUDE_INPUT_USD_PER_MTOK / 1_000_000.0;
+        let stats = self.with_breakdown();
+        let claude_input = stats.claude_tokens_in as f64 * CLAUDE_INPUT_USD_PER_MTOK / 1_000_000.0;
         let claude_output =
-            self.claude_tokens_out as f64 * CLAUDE_OUTPUT_USD_PER_MTOK / 1_000_000.0;
+            stats.claude_tokens_out as f64 * CLAUDE_OUTPUT_USD_PER_MTOK / 1_000_000.0;
+        let claude_cache_write = claude_cache_write_cost_usd(stats.claude_tokens_cache_write_1h);
 
-        claude_input + claude_output
+        claude_input + claude_output + claude_cache_write
     }
 
     pub fn codex_cost_usd(&self) -> f64 {
-        if !self.has_provider_breakdown() {
-            return 0.0;
-        }
-
+        let stats = self.with_breakdown();
         // codex_tokens_in includes cached; subtract them out.
-        let uncached_input = self
+        let uncached_input = stats
             .codex_tokens_in
-            .saturating_sub(self.codex_tokens_cache_read);
+            .saturating_sub(stats.codex_tokens_cache_read);
         let codex_input = uncached_input as f64 * CODEX_INPUT_USD_PER_MTOK / 1_000_000.0;
-        let codex_output = self.codex_tokens_out as f64 * CODEX_OUTPUT_USD_PER_MTOK / 1_000_000.0;
+        let codex_output = stats.codex_tokens_out as f64 * CODEX_OUTPUT_USD_PER_MTOK / 1_000_000.0;
 
         codex_input + codex_output
     }
 
     pub fn gemini_cost_usd(&self) -> f64 {
-        if !self.has_provider_breakdown() {
-            return 0.0;
-        }
-
+        let stats = self.with_breakdown();
         // gemini_tokens_in includes cached; subtract them out.
-        let uncached_input = self
+        let uncached_input = stats
             .gemini_tokens_in
-            .saturating_sub(self.gemini_tokens_cached);
+            .saturating_sub(stats.gemini_tokens_cached);
         let gemini_input = uncached_input as f64 * GEMINI_INPUT_USD_PER_MTOK / 1_000_000.0;
-        let gemini_output =
-            self.gemini_tokens_out as f64 * GEMINI_OUTPUT_USD_PER_MTOK / 1_000_000.0;
+        // Thoughts are billed as output tokens.
+        let gemini_output = (stats.gemini_tokens_out + stats.gemini_tokens_thoughts) as f64
+            * GEMINI_OUTPUT_USD_PER_MTOK
+            / 1_000_000.0;
 
         gemini_input + gemini_output
     }
 
     /// Estimated cost in USD using provider-specific pricing.
-    /// Cached tokens are excluded — they are not charged to the user.
+    /// Cache reads are excluded — they are not charged to the user.
     pub fn cost_usd(&self) -> f64 {
         self.claude_cost_usd() + self.codex_cost_usd() + self.gemini_cost_usd()
     }
+
+    /// Date (YYYY-MM-DD) of the last refresh; empty before the first one.
+    pub fn date(&self) -> &str {
+        &self.date
+    }
+
+    /// `(provider, tokens_in, tokens_out, cost_usd)` for Claude, Codex and
+    /// Gemini, in that order. Input counts are as logged (Codex and Gemini
+    /// include cached input); Gemini thoughts count as output. Without a
+    /// provider breakdown all usage goes to the provider `last_model`
+    /// names, or Claude.
+    pub fn provider_usage(&self) -> [(&'static str, u64, u64, f64); 3] {
+        let stats = self.with_breakdown();
+        [
+            (
+                "claude",
+                stats.claude_tokens_in,
+                stats.claude_tokens_out,
+                stats.claude_cost_usd(),
+            ),
+            (
+                "codex",
+                stats.codex_tokens_in,
+                stats.codex_tokens_out,
+                stats.codex_cost_usd(),
+            ),
+            (
+                "gemini",
+                stats.gemini_tokens_in,
+                stats.gemini_tokens_out + stats.gemini_tokens_thoughts,
+                stats.gemini_cost_usd(),
+            ),
+        ]
+    }
 }
 
 /// Scan Claude + Codex logs and sum today's token usage.
@@ -525,14 +1563,18 @@ pub fn update_global_stats(stats: &mut GlobalStats) {
         stats.claude_tokens_out = 0;
         stats.claude_tokens_cache_read = 0;
         stats.claude_tokens_cache_write = 0;
+        stats.claude_tokens_cache_write_1h = 0;
         stats.codex_tokens_in = 0;
         stats.codex_tokens_out = 0;
         stats.codex_tokens_cache_read = 0;
         stats.gemini_tokens_in = 0;
         stats.gemini_tokens_out = 0;
         stats.gemini_tokens_cached = 0;
+        stats.gemini_tokens_thoughts = 0;
+        stats.last_model = None;
         stats.file_offsets.clear();
         stats.codex_file_states.clear();
+        stats.codex_session_totals.clear();
         stats.gemini_file_sizes.clear();
         stats.gemini_file_tokens.clear();
         stats.known_claude_files.clear();
@@ -542,6 +1584,8 @@ pub fn update_global_stats(stats: &mut GlobalStats) {
         stats.date = today.clone();
     }
 
+    stats.paused =
+        crate::config::stats_paused_since(&crate::manifest::default_base_dir()).is_some();
     update_global_stats_inner(stats, &today, None);
 }
 
@@ -551,6 +1595,62 @@ fn update_global_stats_inner(
     today: &str,
     base_dir: Option<&std::path::Path>,
 ) {
+    scan_global_usage(stats, UsageDays::day(today), base_dir);
+}
+
+/// Dates (YYYY-MM-DD, inclusive) whose usage counts toward a scan. A log
+/// entry belongs to the date its timestamp starts with.
+#[derive(Debug, Clone, Copy)]
+struct UsageDays<'a> {
+    from: &'a str,
+    to: &'a str,
+}
+
+impl<'a> UsageDays<'a> {
+    fn day(date: &'a str) -> Self {
+        Self {
+            from: date,
+            to: date,
+        }
+    }
+
+    fn includes(&self, timestamp: &str) -> bool {
+        timestamp
+            .get(..10)
+            .is_some_and(|date| self.from <= date && date <= self.to)
+    }
+
+    /// Cheap pre-parse check: a single day's lines must mention it.
+    fn may_include_line(&self, line: &str) -> bool {
+        self.from != self.to || line.contains(self.from)
+    }
+}
+
+/// Sum the usage logged from `from` through `to` by reading every log file
+/// from the start. Unlike `update_global_stats` nothing carries over between
+/// calls, so this is a one-shot report rather than a refresh path. `stats`
+/// supplies the exclusions and extra globs and receives the totals.
+pub fn scan_range_stats(stats: &mut GlobalStats, from: chrono::NaiveDate, to: chrono::NaiveDate) {
+    let from = from.format("%Y-%m-%d").to_string();
+    let to = to.format("%Y-%m-%d").to_string();
+    scan_range_stats_inner(stats, &from, &to, None);
+}
+
+fn scan_range_stats_inner(
+    stats: &mut GlobalStats,
+    from: &str,
+    to: &str,
+    base_dir: Option<&std::path::Path>,
+) {
+    *stats = GlobalStats {
+        excluded_claude_projects: std::mem::take(&mut stats.excluded_claude_projects),
+        extra_log_globs: std::mem::take(&mut stats.extra_log_globs),
+        ..Default::default()
+    };
+    scan_global_usage(stats, UsageDays { from, to }, base_dir);
+}
+
+fn scan_global_usage(stats: &mut GlobalStats, days: UsageDays, base_dir: Option<&std::path::Path>) {
     let (claude_projects_dir, codex_sessions_dir, gemini_tmp_dir) = match base_dir {
         Some(dir) => (
             dir.to_path_buf(),
@@ -577,10 +1677,16 @@ fn update_global_stats_inner(
     if needs_discovery {
         let mut claude_files = Vec::new();
         collect_jsonl_files(&claude_projects_dir, &mut claude_files, 0);
+        claude_files.retain(|p| !stats.is_excluded_claude_file(&claude_projects_dir, p));
+        add_glob_files(&stats.extra_log_globs.claude, &mut claude_files);
         stats.known_claude_files = claude_files;
 
         let mut codex_files = Vec::new();
         collect_jsonl_files(&codex_sessions_dir, &mut codex_files, 0);
+        add_glob_files(&stats.extra_log_globs.codex, &mut codex_files);
+        // Rollouts live under YYYY/MM/DD and are named by start time, so path
+        // order is chronological and a resumed session's files read in order.
+        codex_files.sort();
         stats.known_codex_files = codex_files;
 
         let claude_file_set: HashSet<PathBuf> = stats.known_claude_files.iter().cloned().collect();
@@ -592,9 +1698,18 @@ fn update_global_stats_inner(
         stats
             .codex_file_states
             .retain(|p, _| codex_file_set.contains(p));
+        let live_sessions: HashSet<&String> = stats
+            .codex_file_states
+            .values()
+            .filter_map(|s| s.session_id.as_ref())
+            .collect();
+        stats
+            .codex_session_totals
+            .retain(|id, _| live_sessions.contains(id));
 
         let mut gemini_files = Vec::new();
         collect_gemini_session_files(&gemini_tmp_dir, &mut gemini_files);
+        add_glob_files(&stats.extra_log_globs.gemini, &mut gemini_files);
         stats.known_gemini_files = gemini_files;
 
         let gemini_file_set: HashSet<PathBuf> = stats.known_gemini_files.iter().cloned().collect();
@@ -608,24 +1723,39 @@ fn update_global_stats_inner(
         stats.last_file_discovery_ts = now_ts;
     }
 
+    // While paused, parse as usual so offsets and per-file state move past
+    // the paused interval, then put the totals back.
+    let before_pause = stats.paused.then(|| stats.clone());
+
     // Process Claude files incrementally.
     // Index-based iteration avoids cloning the entire Vec<PathBuf> — we can't
     // iterate by reference because process_*_global_file takes &mut stats.
     for i in 0..stats.known_claude_files.len() {
         let path = stats.known_claude_files[i].clone();
-        process_claude_global_file(&path, stats, today);
+        process_claude_global_file(&path, stats, days);
     }
 
     // Process Codex files incrementally.
     for i in 0..stats.known_codex_files.len() {
         let path = stats.known_codex_files[i].clone();
-        process_codex_global_file(&path, stats, today);
+        process_codex_global_file(&path, stats, days);
     }
 
     // Process Gemini session files.
     for i in 0..stats.known_gemini_files.len() {
         let path = stats.known_gemini_files[i].clone();
-        process_gemini_global_file(&path, stats, today);
+        process_gemini_global_file(&path, stats, days);
+    }
+
+    if let Some(before) = before_pause {
+        *stats = GlobalStats {
+            file_offsets: std::mem::take(&mut stats.file_offsets),
+            codex_file_states: std::mem::take(&mut stats.codex_file_states),
+            codex_session_totals: std::mem::take(&mut stats.codex_session_totals),
+            gemini_file_sizes: std::mem::take(&mut stats.gemini_file_sizes),
+            gemini_file_tokens: std::mem::take(&mut stats.gemini_file_tokens),
+            ..before
+        };
     }
 }
 
@@ -635,6 +1765,7 @@ fn add_claude_usage(
     output_tokens: u64,
     cache_read_tokens: u64,
     cache_write_tokens: u64,
+    cache_write_1h_tokens: u64,
 ) {
     stats.tokens_in += input_tokens;
     stats.tokens_out += output_tokens;
@@ -645,6 +1776,7 @@ fn add_claude_usage(
     stats.claude_tokens_out += output_tokens;
     stats.claude_tokens_cache_read += cache_read_tokens;
     stats.claude_tokens_cache_write += cache_write_tokens;
+    stats.claude_tokens_cache_write_1h += cache_write_1h_tokens;
 }
 
 fn add_codex_usage(
@@ -662,7 +1794,7 @@ fn add_codex_usage(
     stats.codex_tokens_cache_read += cache_read_tokens;
 }
 
-fn process_claude_global_file(path: &PathBuf, stats: &mut GlobalStats, today: &str) {
+fn process_claude_global_file(path: &PathBuf, stats: &mut GlobalStats, days: UsageDays) {
     let mut file = match std::fs::File::open(path) {
         Ok(f) => f,
         Err(_) => return,
@@ -687,36 +1819,47 @@ fn process_claude_global_file(path: &PathBuf, stats: &mut GlobalStats, today: &s
     }
     let text = String::from_utf8_lossy(&buf);
 
-    for line in text.lines() {
+    for line in log_lines(&text) {
         if line.len() < 10 {
             continue;
         }
-        if !line.contains(today) || !line.contains("\"assistant\"") || !line.contains("\"usage\"") {
+        if is_oversized_line(line)
+            || !days.may_include_line(line)
+            || !line.contains("\"assistant\"")
+            || !line.contains("\"usage\"")
+        {
             continue;
         }
         if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
             if v.get("type").and_then(|t| t.as_str()) != Some("assistant") {
                 continue;
             }
+            let in_days = v
+                .get("timestamp")
+                .and_then(|t| t.as_str())
+                .is_some_and(|ts| days.includes(ts));
+            if !in_days {
+                continue;
+            }
+            let model = v.get("message").and_then(|m| m.get("model"));
+            if let Some(model) = real_model_name(model.and_then(|m| m.as_str())) {
+                stats.last_model = Some(model);
+            }
             if let Some(usage) = v.get("message").and_then(|m| m.get("usage")) {
+                let (cache_write, cache_write_1h) = claude_cache_write_tokens(usage);
                 add_claude_usage(
                     stats,
                     usage
                         .get("input_tokens")
                         .and_then(|t| t.as_u64())
                         .unwrap_or(0),
-                    usage
-                        .get("output_tokens")
-                        .and_then(|t| t.as_u64())
-                        .unwrap_or(0),
+                    claude_output_tokens(usage),
                     usage
                         .get("cache_read_input_tokens")
                         .and_then(|t| t.as_u64())
                         .unwrap_or(0),
-                    usage
-                        .get("cache_creation_input_tokens")
-                        .and_then(|t| t.as_u64())
-                        .unwrap_or(0),
+                    cache_write,
+                    cache_write_1h,
                 );
             }
         }
@@ -725,7 +1868,7 @@ fn process_claude_global_file(path: &PathBuf, stats: &mut GlobalStats, today: &s
     stats.file_offsets.insert(path.clone(), file_len);
 }
 
-fn process_codex_global_file(path: &PathBuf, stats: &mut GlobalStats, today: &str) {
+fn process_codex_global_file(path: &PathBuf, stats: &mut GlobalStats, days: UsageDays) {
     let mut file = match std::fs::File::open(path) {
         Ok(f) => f,
         Err(_) => return,
@@ -760,6 +1903,10 @@ fn process_codex_global_file(path: &PathBuf, stats: &mut GlobalStats, today: &st
         .get(path)
         .map(|s| s.read_offset)
         .unwrap_or(0);
+    let mut session_id = stats
+        .codex_file_states
+        .get(path)
+        .and_then(|s| s.session_id.clone());
 
     if file_len <= offset {
         return;
@@ -775,10 +1922,14 @@ fn process_codex_global_file(path: &PathBuf, stats: &mut GlobalStats, today: &st
     }
     let text = String::from_utf8_lossy(&buf);
 
-    for line in text.lines() {
+    for line in log_lines(&text) {
         if line.len() < 20 {
             continue;
         }
+        if session_id.is_none() && line.contains("\"session_meta\"") {
+            session_id = codex_session_meta_id(line);
+            continue;
+        }
         if !line.contains("\"token_count\"") || !line.contains("\"total_token_usage\"") {
             continue;
         }
@@ -821,12 +1972,27 @@ fn process_codex_global_file(path: &PathBuf, stats: &mut GlobalStats, today: &st
             .and_then(|t| t.as_u64())
             .unwrap_or(total_input_tokens.saturating_add(total_output_tokens));
 
-        let is_today = v
+        let in_days = v
             .get("timestamp")
             .and_then(|t| t.as_str())
-            .is_some_and(|ts| ts.starts_with(today));
+            .is_some_and(|ts| days.includes(ts));
+
+        // A resumed session's new rollout file carries on from the totals of
+        // the previous one; measure its first snapshot against those rather
+        // than zero. A lower total means the counters restarted.
+        let session_totals = session_id
+            .as_ref()
+            .and_then(|id| stats.codex_session_totals.get(id).copied());
+        if last_total_tokens == 0 {
+            if let Some(prev) = session_totals.filter(|p| p.total_tokens <= total_tokens) {
+                last_total_tokens = prev.total_tokens;
+                last_input_tokens = prev.input_tokens;
+                last_output_tokens = prev.output_tokens;
+                last_cached_input_tokens = prev.cached_input_tokens;
+            }
+        }
 
-        if total_tokens > last_total_tokens && is_today {
+        if total_tokens > last_total_tokens && in_days {
             let delta_input = total_input_tokens.saturating_sub(last_input_tokens);
             let delta_output = total_output_tokens.saturating_sub(last_output_tokens);
             let delta_cache_read =
@@ -839,6 +2005,20 @@ fn process_codex_global_file(path: &PathBuf, stats: &mut GlobalStats, today: &st
         last_input_tokens = total_input_tokens;
         last_output_tokens = total_output_tokens;
         last_cached_input_tokens = total_cached_input_tokens;
+
+        if let Some(id) = &session_id {
+            if session_totals.is_none_or(|p| total_tokens >= p.total_tokens) {
+                stats.codex_session_totals.insert(
+                    id.clone(),
+                    CodexSessionTotals {
+                        total_tokens,
+                        input_tokens: total_input_tokens,
+                        output_tokens: total_output_tokens,
+                        cached_input_tokens: total_cached_input_tokens,
+                    },
+                );
+            }
+        }
     }
 
     stats.codex_file_states.insert(
@@ -849,10 +2029,24 @@ fn process_codex_global_file(path: &PathBuf, stats: &mut GlobalStats, today: &st
             last_input_tokens,
             last_output_tokens,
             last_cached_input_tokens,
+            session_id,
         },
     );
 }
 
+/// Session id from a Codex rollout's `session_meta` line.
+fn codex_session_meta_id(line: &str) -> Option<String> {
+    let v = serde_json::from_str::<serde_json::Value>(line).ok()?;
+    if v.get("type").and_then(|t| t.as_str()) != Some("session_meta") {
+        return None;
+    }
+    v.get("payload")?
+        .get("id")?
+        .as_str()
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+}
+
 /// Recursively collect all `.jsonl` files under a directory.
 /// Bounded to 4 levels deep to avoid runaway walks.
 fn collect_jsonl_files(dir: &std::path::Path, out: &mut Vec<PathBuf>, depth: usize) {
@@ -873,8 +2067,82 @@ fn collect_jsonl_files(dir: &std::path::Path, out: &mut Vec<PathBuf>, depth: usi
     }
 }
 
-/// Get the pane PID for a tmux session.
+/// Deepest directory level below a glob's fixed prefix that is walked when
+/// a `*` lets the match span directories.
+const MAX_GLOB_DEPTH: usize = 8;
+
+/// Append files matching any of `globs` to `out`, skipping ones already in
+/// it, including the same file reached through a symlink.
+fn add_glob_files(globs: &[String], out: &mut Vec<PathBuf>) {
+    if globs.is_empty() {
+        return;
+    }
+    let mut seen: HashSet<PathBuf> = out
+        .iter()
+        .map(|p| std::fs::canonicalize(p).unwrap_or_else(|_| p.clone()))
+        .collect();
+    for glob in globs {
+        let mut matches = Vec::new();
+        collect_glob_files(glob, &mut matches);
+        for path in matches {
+            let canonical = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if seen.insert(canonical) {
+                out.push(path);
+            }
+        }
+    }
+}
+
+/// Collect files whose path matches `glob`, walking from the longest
+/// leading path without wildcards. Only directories a match can lie under
+/// are entered, no deeper than the glob reaches: one level per `/` without
+/// a `*`, `MAX_GLOB_DEPTH` with one. Symlinked directories are followed.
+fn collect_glob_files(glob: &str, out: &mut Vec<PathBuf>) {
+    let wildcard = glob.find(['*', '?']);
+    let Some(wildcard) = wildcard else {
+        let path = PathBuf::from(glob);
+        if path.is_file() {
+            out.push(path);
+        }
+        return;
+    };
+    let slash = glob[..wildcard].rfind('/');
+    let root = match slash {
+        Some(0) => "/",
+        Some(slash) => &glob[..slash],
+        None => ".",
+    };
+    let max_depth = if glob.contains('*') {
+        MAX_GLOB_DEPTH
+    } else {
+        glob[slash.map_or(0, |s| s + 1)..].matches('/').count()
+    };
+    walk_glob(std::path::Path::new(root), glob, out, max_depth);
+}
+
+fn walk_glob(dir: &std::path::Path, glob: &str, out: &mut Vec<PathBuf>, depth_left: usize) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let text = path.to_string_lossy();
+        if path.is_dir() {
+            if depth_left > 0 && crate::glob::glob_match_prefix(glob, &format!("{text}/")) {
+                walk_glob(&path, glob, out, depth_left - 1);
+            }
+        } else if crate::glob::glob_match(glob, &text) {
+            out.push(path);
+        }
+    }
+}
+
+/// Get the pane PID for a tmux session. `None` for remote tmux, whose PIDs
+/// can't be looked up locally; log resolution then falls back to mtime.
 pub async fn get_pane_pid(tmux_name: &str) -> Option<u32> {
+    if crate::tmux::ssh_target().is_some() {
+        return None;
+    }
     let output = run_cmd_timeout(Command::new("tmux").args([
         "list-panes",
         "-t",
@@ -894,7 +2162,7 @@ pub async fn get_pane_pid(tmux_name: &str) -> Option<u32> {
 
 /// Extract --session-id UUID from a command line string.
 /// Handles both `--session-id <uuid>` and `--session-id=<uuid>` forms.
-fn parse_session_id_from_cmdline(cmdline: &str) -> Option<String> {
+pub fn parse_session_id_from_cmdline(cmdline: &str) -> Option<String> {
     let mut args = cmdline.split_whitespace();
     while let Some(arg) = args.next() {
         if arg == "--session-id" {
@@ -932,8 +2200,9 @@ async fn resolve_uuid_from_cmdline(pid: u32) -> Option<String> {
 
 /// Collect all descendant PIDs of a process (children, grandchildren, etc.).
 /// Bounded by `MAX_TREE_DEPTH` levels and `MAX_TREE_PIDS` total to prevent
-/// runaway walks on pathological process trees.
-async fn collect_descendant_pids(pid: u32) -> Vec<u32> {
+/// runaway walks on pathological process trees. Breadth-first, so `pid`
+/// comes first and every process comes after its parent.
+pub async fn collect_descendant_pids(pid: u32) -> Vec<u32> {
     let mut all_pids = vec![pid];
     // Process level-by-level for depth tracking
     let mut current_level = vec![pid];
@@ -989,21 +2258,7 @@ fn parse_uuid_from_lsof_output(output: &str) -> Option<String> {
 /// Use lsof to find the Claude tasks UUID from a set of PIDs.
 /// Fallback method — checks all provided PIDs for open .claude/tasks/ file descriptors.
 async fn resolve_uuid_from_lsof_pids(pids: &[u32]) -> Option<String> {
-    if pids.is_empty() {
-        return None;
-    }
-
-    let pid_list = pids
-        .iter()
-        .map(|p| p.to_string())
-        .collect::<Vec<_>>()
-        .join(",");
-
-    let output = run_cmd_timeout(Command::new("lsof").args(["-p", &pid_list]))
-        .await
-        .ok()?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stdout = lsof_output(pids).await?;
     parse_uuid_from_lsof_output(&stdout)
 }
 
@@ -1023,16 +2278,29 @@ fn is_uuid(s: &str) -> bool {
 pub async fn resolve_session_uuid(tmux_name: &str) -> Option<String> {
     let pid = get_pane_pid(tmux_name).await?;
 
-    // Try command line --session-id on pane PID and all descendants
     let all_pids = collect_descendant_pids(pid).await;
-    for &p in &all_pids {
-        if let Some(uuid) = resolve_uuid_from_cmdline(p).await {
-            return Some(uuid);
+    for strategy in resolve_plan(CLAUDE_RESOLVE_ORDER, forced_resolve_strategy()) {
+        let uuid = match strategy {
+            // Command line --session-id on pane PID and all descendants
+            ResolveStrategy::Cmdline => {
+                let mut found = None;
+                for &p in &all_pids {
+                    found = resolve_uuid_from_cmdline(p).await;
+                    if found.is_some() {
+                        break;
+                    }
+                }
+                found
+            }
+            // lsof on the full process tree
+            ResolveStrategy::Lsof => resolve_uuid_from_lsof_pids(&all_pids).await,
+            ResolveStrategy::Mtime => None,
+        };
+        if uuid.is_some() {
+            return uuid;
         }
     }
-
-    // Fall back to lsof on the full process tree
-    resolve_uuid_from_lsof_pids(&all_pids).await
+    None
 }
 
 /// Convert a CWD path to the Claude projects directory escape format.
@@ -1079,6 +2347,9 @@ pub enum ConversationEntry {
     ToolResult {
         filenames: Vec<String>,
         summary: Option<String>,
+        /// Id of the tool call this answers, for re-reading the full result
+        /// from the log (see `read_full_tool_result`).
+        tool_use_id: Option<String>,
     },
     QueueOperation {
         operation: String,
@@ -1101,17 +2372,176 @@ pub enum ConversationEntry {
         reason: String,
         raw: String,
     },
+    /// Stands in for the `dropped` oldest entries evicted from a bounded
+    /// conversation buffer.
+    HistoryTruncated {
+        dropped: usize,
+    },
+    /// Stands in for the first `bytes` of a log that a tail-seeded read
+    /// skipped.
+    HistoryOmitted {
+        bytes: u64,
+    },
+    /// An entry from a subagent's own log, merged into the main transcript.
+    Sidechain {
+        agent_id: String,
+        entry: Box<ConversationEntry>,
+    },
+    /// The agent rewrote its TODO list; `todos` is the full new list.
+    TodoUpdate {
+        todos: Vec<TodoItem>,
+    },
+    /// Claude's summary of a completed run, from a `result` entry.
+    RunResult {
+        cost_usd: f64,
+        duration_ms: u64,
+        turns: u32,
+    },
+}
+
+/// Progress of one TODO item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoStatus {
+    Pending,
+    InProgress,
+    Completed,
+}
+
+/// One item of the plan an agent keeps with its TODO tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoItem {
+    pub content: String,
+    pub status: TodoStatus,
+}
+
+/// Totals Claude logs in a `result` entry at the end of a run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunResult {
+    pub cost_usd: f64,
+    pub duration_ms: u64,
+    pub turns: u32,
+}
+
+/// The totals of a `result` entry, or `None` for any other entry. Older
+/// logs name the cost `cost_usd` rather than `total_cost_usd`; a missing
+/// duration or turn count reads as 0.
+pub fn parse_run_result(value: &serde_json::Value) -> Option<RunResult> {
+    if value.get("type").and_then(|t| t.as_str()) != Some("result") {
+        return None;
+    }
+    let cost_usd = value
+        .get("total_cost_usd")
+        .or_else(|| value.get("cost_usd"))?
+        .as_f64()?;
+    let count = |key: &str| value.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    Some(RunResult {
+        cost_usd,
+        duration_ms: count("duration_ms"),
+        turns: count("num_turns") as u32,
+    })
+}
+
+/// One-line run summary, e.g. `$0.42 · 3 turns · 1m 04s`.
+pub fn format_run_result(
+    cost_usd: f64,
+    duration_ms: u64,
+    turns: u32,
+    currency: &Currency,
+) -> String {
+    let duration = crate::session::format_duration(Duration::from_millis(duration_ms));
+    let turns = match turns {
+        1 => "1 turn".to_string(),
+        n => format!("{n} turns"),
+    };
+    format!("{} · {turns} · {duration}", format_cost(cost_usd, currency))
+}
+
+/// Claude's TODO tool; each call's input carries the whole current list.
+const TODO_TOOL: &str = "TodoWrite";
+
+/// The TODO list carried by a `TodoWrite` tool_use item, or `None` for any
+/// other item. Items without text are dropped; unknown statuses count as
+/// pending.
+pub fn parse_todo_update(item: &serde_json::Value) -> Option<Vec<TodoItem>> {
+    if item.get("name").and_then(|n| n.as_str()) != Some(TODO_TOOL) {
+        return None;
+    }
+    let todos = item.get("input")?.get("todos")?.as_array()?;
+    Some(
+        todos
+            .iter()
+            .filter_map(|todo| {
+                let content = todo.get("content")?.as_str()?.trim();
+                if content.is_empty() {
+                    return None;
+                }
+                let status = match todo.get("status").and_then(|s| s.as_str()) {
+                    Some("completed") => TodoStatus::Completed,
+                    Some("in_progress") => TodoStatus::InProgress,
+                    _ => TodoStatus::Pending,
+                };
+                Some(TodoItem {
+                    content: content.to_string(),
+                    status,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// `(completed, total)` for a TODO list.
+pub fn todo_progress(todos: &[TodoItem]) -> (usize, usize) {
+    let done = todos
+        .iter()
+        .filter(|t| t.status == TodoStatus::Completed)
+        .count();
+    (done, todos.len())
 }
 
-fn summarize_jsonl_line(line: &str, max_chars: usize) -> String {
+/// Collapse whitespace and truncate to `max_width` display columns.
+fn summarize_jsonl_line(line: &str, max_width: usize) -> String {
     let compact = line.split_whitespace().collect::<Vec<_>>().join(" ");
-    if compact.chars().count() <= max_chars {
-        compact
+    truncate_to_width(&compact, max_width)
+}
+
+const TRUNCATION_ELLIPSIS: &str = "...";
+
+/// Truncate `text` to at most `max_width` terminal columns, appending an
+/// ellipsis when anything was cut. Cuts only between grapheme clusters, so
+/// wide CJK characters and multi-codepoint emoji are never split.
+pub fn truncate_to_width(text: &str, max_width: usize) -> String {
+    truncate_to_width_with(text, max_width, TRUNCATION_ELLIPSIS)
+}
+
+/// [`truncate_to_width`] with a custom ellipsis, e.g. `…` for narrow
+/// columns where three dots would crowd out the text. The ellipsis is
+/// dropped when it alone would not fit.
+pub fn truncate_to_width_with(text: &str, max_width: usize, ellipsis: &str) -> String {
+    use unicode_segmentation::UnicodeSegmentation;
+    use unicode_width::UnicodeWidthStr;
+
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    let ellipsis = if max_width >= ellipsis.width() {
+        ellipsis
     } else {
-        let mut out: String = compact.chars().take(max_chars).collect();
-        out.push_str("...");
-        out
+        ""
+    };
+    let budget = max_width - ellipsis.width();
+
+    let mut out = String::new();
+    let mut width = 0;
+    for grapheme in text.graphemes(true) {
+        let w = grapheme.width();
+        if width + w > budget {
+            break;
+        }
+        out.push_str(grapheme);
+        width += w;
     }
+    out.push_str(ellipsis);
+    out
 }
 
 fn extract_text_parts(value: &serde_json::Value) -> Vec<String> {
@@ -1481,6 +2911,85 @@ fn summarize_file_history_snapshot(
     Some((tracked_files, files, is_update))
 }
 
+/// Full text of a tool result: its first non-empty output field.
+fn tool_result_text(value: &serde_json::Value) -> Option<String> {
+    [
+        "content", "output", "message", "text", "error", "stderr", "stdout",
+    ]
+    .iter()
+    .find_map(|field| value.get(field).and_then(extract_text))
+}
+
+/// Full text of the result for tool call `tool_use_id`, re-read from the
+/// session log at `path` (and, for Claude, its subagent logs). Conversation
+/// entries only keep a short summary.
+pub fn read_full_tool_result(path: &std::path::Path, tool_use_id: &str) -> Option<String> {
+    if path.extension().is_some_and(|ext| ext == "json") {
+        let data = std::fs::read_to_string(path).ok()?;
+        let v = parse_gemini_json(&data)?;
+        return v
+            .get("messages")?
+            .as_array()?
+            .iter()
+            .filter_map(|msg| msg.get("toolCalls").and_then(|t| t.as_array()))
+            .flatten()
+            .find(|tc| tc.get("id").and_then(|id| id.as_str()) == Some(tool_use_id))
+            .and_then(gemini_tool_result_text);
+    }
+    std::iter::once(path.to_path_buf())
+        .chain(subagent_log_paths(path).into_iter().map(|(_, p)| p))
+        .find_map(|log| claude_tool_result_text(&log, tool_use_id))
+}
+
+fn claude_tool_result_text(path: &std::path::Path, tool_use_id: &str) -> Option<String> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let found = log_lines(&text)
+        .filter(|line| line.contains(tool_use_id))
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find_map(|v| {
+            let item = v
+                .get("message")
+                .and_then(|m| m.get("content"))
+                .and_then(|c| c.as_array())?
+                .iter()
+                .find(|item| {
+                    item.get("type").and_then(|t| t.as_str()) == Some("tool_result")
+                        && item.get("tool_use_id").and_then(|id| id.as_str()) == Some(tool_use_id)
+                })?;
+            // The summary came from `toolUseResult` when the line has one.
+            v.get("toolUseResult")
+                .and_then(tool_result_text)
+                .or_else(|| tool_result_text(item))
+        });
+    found
+}
+
+/// Full text of a Gemini tool call's result, mirroring
+/// `extract_gemini_tool_result_parts` without the summarizing.
+fn gemini_tool_result_text(tool_call: &serde_json::Value) -> Option<String> {
+    if let Some(text) = tool_call.get("resultDisplay").and_then(extract_text) {
+        return Some(text);
+    }
+    tool_call
+        .get("result")?
+        .as_array()?
+        .iter()
+        .find_map(|result| {
+            result
+                .get("functionResponse")
+                .and_then(|f| f.get("response"))
+                .and_then(tool_result_text)
+                .or_else(|| tool_result_text(result))
+        })
+}
+
+/// Id of the tool call a Claude tool result answers.
+fn tool_use_id_of(item: &serde_json::Value) -> Option<String> {
+    item.get("tool_use_id")
+        .and_then(|id| id.as_str())
+        .map(str::to_string)
+}
+
 fn extract_tool_result_parts(value: &serde_json::Value) -> (Vec<String>, Option<String>) {
     let filenames = value
         .get("filenames")
@@ -1492,18 +3001,7 @@ fn extract_tool_result_parts(value: &serde_json::Value) -> (Vec<String>, Option<
         })
         .unwrap_or_default();
 
-    let summary_fields = [
-        "content", "output", "message", "text", "error", "stderr", "stdout",
-    ];
-    let mut summary: Option<String> = None;
-    for field in summary_fields {
-        if let Some(v) = value.get(field) {
-            if let Some(text) = extract_text(v) {
-                summary = Some(summarize_jsonl_line(&text, 180));
-                break;
-            }
-        }
-    }
+    let mut summary = tool_result_text(value).map(|text| summarize_jsonl_line(&text, 180));
 
     if summary.is_none() {
         if let Some(ok) = value.get("success").and_then(|v| v.as_bool()) {
@@ -1518,12 +3016,63 @@ fn extract_tool_result_parts(value: &serde_json::Value) -> (Vec<String>, Option<
     (filenames, summary)
 }
 
+/// How much of a large Claude log the preview parses when a session is first
+/// selected, unless `preview_tail_kb` says otherwise. Older history is
+/// skipped; the preview keeps at most a few hundred entries anyway.
+pub const DEFAULT_CONVERSATION_TAIL_SEED_BYTES: u64 = 512 * 1024;
+
+/// Current tail seed in bytes, 0 for none; see `set_conversation_tail_seed`.
+static CONVERSATION_TAIL_SEED: std::sync::atomic::AtomicU64 =
+    std::sync::atomic::AtomicU64::new(DEFAULT_CONVERSATION_TAIL_SEED_BYTES);
+
+/// Override the tail seed (from `Config::preview_tail_seed`); `None` parses
+/// whole logs.
+pub fn set_conversation_tail_seed(bytes: Option<u64>) {
+    CONVERSATION_TAIL_SEED.store(bytes.unwrap_or(0), std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn conversation_tail_seed() -> Option<u64> {
+    let bytes = CONVERSATION_TAIL_SEED.load(std::sync::atomic::Ordering::Relaxed);
+    (bytes > 0).then_some(bytes)
+}
+
 /// Parse conversation entries from a Claude JSONL log file.
 /// Reads incrementally from `read_offset`; returns new entries + updated offset.
 pub fn parse_conversation_entries(
     path: &std::path::Path,
     read_offset: u64,
 ) -> (Vec<ConversationEntry>, u64) {
+    parse_conversation_entries_seeded(path, read_offset, None)
+}
+
+/// Like [`parse_conversation_entries`], but the first read (`read_offset == 0`)
+/// of a file larger than `tail_seed_bytes` starts near the end instead of
+/// parsing the whole history. The partial line at the seek point is dropped,
+/// a `HistoryOmitted` entry stands in for the skipped bytes, and the returned
+/// offset lets later reads continue incrementally.
+pub fn parse_conversation_entries_seeded(
+    path: &std::path::Path,
+    read_offset: u64,
+    tail_seed_bytes: Option<u64>,
+) -> (Vec<ConversationEntry>, u64) {
+    let (entries, new_offset) =
+        parse_timed_conversation_entries(path, read_offset, tail_seed_bytes);
+    (
+        entries.into_iter().map(|(_, entry)| entry).collect(),
+        new_offset,
+    )
+}
+
+/// A conversation entry stamped with its log line's `timestamp`, if any.
+pub type TimedEntry = (Option<chrono::DateTime<chrono::Utc>>, ConversationEntry);
+
+/// Like [`parse_conversation_entries_seeded`], but keeps each entry's
+/// timestamp so entries from several logs can be interleaved.
+pub fn parse_timed_conversation_entries(
+    path: &std::path::Path,
+    read_offset: u64,
+    tail_seed_bytes: Option<u64>,
+) -> (Vec<TimedEntry>, u64) {
     let mut file = match std::fs::File::open(path) {
         Ok(f) => f,
         Err(_) => return (vec![], read_offset),
@@ -1537,7 +3086,16 @@ pub fn parse_conversation_entries(
         return (vec![], read_offset);
     }
 
-    if read_offset > 0 && file.seek(SeekFrom::Start(read_offset)).is_err() {
+    // Seek one byte before the tail window so a window that happens to start
+    // on a line boundary keeps that line: the first newline found is then the
+    // one just before it.
+    let seed_start = match tail_seed_bytes {
+        Some(seed) if read_offset == 0 && file_len > seed => Some(file_len - seed - 1),
+        _ => None,
+    };
+    let mut start = seed_start.unwrap_or(read_offset);
+
+    if start > 0 && file.seek(SeekFrom::Start(start)).is_err() {
         return (vec![], read_offset);
     }
 
@@ -1546,20 +3104,38 @@ pub fn parse_conversation_entries(
         return (vec![], read_offset);
     }
 
+    if seed_start.is_some() {
+        let Some(first_newline) = buf.iter().position(|&b| b == b'\n') else {
+            return (vec![], read_offset);
+        };
+        buf.drain(..=first_newline);
+        start += first_newline as u64 + 1;
+    }
+
     let last_newline = buf.iter().rposition(|&b| b == b'\n');
     let (valid_buf, new_offset) = match last_newline {
-        Some(idx) => (&buf[..idx], read_offset + idx as u64 + 1),
+        Some(idx) => (&buf[..idx], start + idx as u64 + 1),
         None => return (vec![], read_offset),
     };
 
     let text = String::from_utf8_lossy(valid_buf);
     let mut entries = Vec::new();
+    let mut stamps = Vec::new();
+    if seed_start.is_some() {
+        entries.push(ConversationEntry::HistoryOmitted { bytes: start });
+        stamps.push(None);
+    }
 
-    for line in text.lines() {
+    for line in log_lines(&text) {
         let line = line.trim();
         if line.is_empty() {
             continue;
         }
+        if is_oversized_line(line) {
+            entries.push(oversized_line_entry(line));
+            stamps.push(None);
+            continue;
+        }
 
         let value = match serde_json::from_str::<serde_json::Value>(line) {
             Ok(v) => v,
@@ -1568,9 +3144,14 @@ pub fn parse_conversation_entries(
                     reason: "Malformed JSONL".to_string(),
                     raw: summarize_jsonl_line(line, 220),
                 });
+                stamps.push(None);
                 continue;
             }
         };
+        let timestamp = value
+            .get("timestamp")
+            .and_then(|t| t.as_str())
+            .and_then(parse_iso_timestamp);
 
         let mut parsed = false;
         let mut handled = false;
@@ -1580,7 +3161,16 @@ pub fn parse_conversation_entries(
             handled = true;
             let (filenames, summary) = extract_tool_result_parts(tool_result);
             if !filenames.is_empty() || summary.is_some() {
-                entries.push(ConversationEntry::ToolResult { filenames, summary });
+                let tool_use_id = value
+                    .get("message")
+                    .and_then(|m| m.get("content"))
+                    .and_then(|c| c.as_array())
+                    .and_then(|items| items.iter().find_map(tool_use_id_of));
+                entries.push(ConversationEntry::ToolResult {
+                    filenames,
+                    summary,
+                    tool_use_id,
+                });
                 parsed = true;
             }
         }
@@ -1602,7 +3192,11 @@ pub fn parse_conversation_entries(
                                 }
                             }
                             Some("tool_use") => {
-                                if let Some(name) = item.get("name").and_then(|n| n.as_str()) {
+                                if let Some(todos) = parse_todo_update(item) {
+                                    entries.push(ConversationEntry::TodoUpdate { todos });
+                                    parsed = true;
+                                } else if let Some(name) = item.get("name").and_then(|n| n.as_str())
+                                {
                                     entries.push(ConversationEntry::ToolUse {
                                         tool_name: name.to_string(),
                                         details: summarize_tool_use_details(item),
@@ -1613,8 +3207,11 @@ pub fn parse_conversation_entries(
                             Some("tool_result") => {
                                 let (filenames, summary) = extract_tool_result_parts(item);
                                 if !filenames.is_empty() || summary.is_some() {
-                                    entries
-                                        .push(ConversationEntry::ToolResult { filenames, summary });
+                                    entries.push(ConversationEntry::ToolResult {
+                                        filenames,
+                                        summary,
+                                        tool_use_id: tool_use_id_of(item),
+                                    });
                                     parsed = true;
                                 }
                             }
@@ -1668,9 +3265,20 @@ pub fn parse_conversation_entries(
                     parsed = true;
                 }
             }
-            Some("file-history-snapshot") => {
+            Some("result") => {
                 handled = true;
-                if let Some((tracked_files, files, is_update)) =
+                if let Some(run) = parse_run_result(&value) {
+                    entries.push(ConversationEntry::RunResult {
+                        cost_usd: run.cost_usd,
+                        duration_ms: run.duration_ms,
+                        turns: run.turns,
+                    });
+                    parsed = true;
+                }
+            }
+            Some("file-history-snapshot") => {
+                handled = true;
+                if let Some((tracked_files, files, is_update)) =
                     summarize_file_history_snapshot(&value)
                 {
                     entries.push(ConversationEntry::FileHistorySnapshot {
@@ -1694,9 +3302,62 @@ pub fn parse_conversation_entries(
                 raw: summarize_jsonl_line(line, 220),
             });
         }
+        stamps.resize(entries.len(), timestamp);
     }
 
-    (entries, new_offset)
+    (stamps.into_iter().zip(entries).collect(), new_offset)
+}
+
+/// Subagent logs written alongside a Claude session log
+/// (`<uuid>/subagents/<agent_id>.jsonl`), as `(agent_id, path)` sorted by id.
+pub fn subagent_log_paths(session_path: &std::path::Path) -> Vec<(String, PathBuf)> {
+    let dir = session_path.with_extension("").join("subagents");
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<(String, PathBuf)> = read_dir
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .filter_map(|path| {
+            let id = path.file_stem()?.to_str()?.to_string();
+            Some((id, path))
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Interleave main-log entries with subagent entries by timestamp, wrapping
+/// the latter in `Sidechain`. Untimed entries inherit the timestamp of the
+/// entry before them in their own log, so a batch never splits; ties keep
+/// main-log entries first.
+pub fn merge_sidechain_entries(
+    main: Vec<TimedEntry>,
+    sidechains: Vec<(String, Vec<TimedEntry>)>,
+) -> Vec<TimedEntry> {
+    fn fill_forward(
+        entries: Vec<TimedEntry>,
+    ) -> impl Iterator<Item = (Option<chrono::DateTime<chrono::Utc>>, ConversationEntry)> {
+        let mut last = None;
+        entries.into_iter().map(move |(ts, entry)| {
+            last = ts.or(last);
+            (last, entry)
+        })
+    }
+
+    let mut merged: Vec<TimedEntry> = fill_forward(main).collect();
+    for (agent_id, entries) in sidechains {
+        merged.extend(fill_forward(entries).map(|(ts, entry)| {
+            let entry = ConversationEntry::Sidechain {
+                agent_id: agent_id.clone(),
+                entry: Box::new(entry),
+            };
+            (ts, entry)
+        }));
+    }
+    // Stable: equal timestamps keep main-log order, then subagents by id.
+    merged.sort_by_key(|(ts, _)| *ts);
+    merged
 }
 
 /// Build the JSONL log file path for a Claude Code session.
@@ -1741,21 +3402,10 @@ pub async fn resolve_codex_rollout_path(tmux_name: &str) -> Option<PathBuf> {
     let pid = get_pane_pid(tmux_name).await?;
     let all_pids = collect_descendant_pids(pid).await;
 
-    if all_pids.is_empty() {
+    if resolve_plan(CODEX_RESOLVE_ORDER, forced_resolve_strategy()).is_empty() {
         return None;
     }
-
-    let pid_list = all_pids
-        .iter()
-        .map(|p| p.to_string())
-        .collect::<Vec<_>>()
-        .join(",");
-
-    let output = run_cmd_timeout(Command::new("lsof").args(["-p", &pid_list]))
-        .await
-        .ok()?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stdout = lsof_output(&all_pids).await?;
     parse_codex_rollout_from_lsof(&stdout)
 }
 
@@ -1765,38 +3415,49 @@ pub fn parse_codex_conversation_entries(
     path: &std::path::Path,
     read_offset: u64,
 ) -> (Vec<ConversationEntry>, u64) {
+    let (entries, new_offset, _) = parse_codex_log(path, read_offset);
+    (entries, new_offset)
+}
+
+/// Like [`parse_codex_conversation_entries`], also returning the model from
+/// the newest `turn_context` line read.
+pub fn parse_codex_log(
+    path: &std::path::Path,
+    read_offset: u64,
+) -> (Vec<ConversationEntry>, u64, Option<String>) {
     let mut file = match std::fs::File::open(path) {
         Ok(f) => f,
-        Err(_) => return (vec![], read_offset),
+        Err(_) => return (vec![], read_offset, None),
     };
     let file_len = match file.metadata() {
         Ok(m) => m.len(),
-        Err(_) => return (vec![], read_offset),
+        Err(_) => return (vec![], read_offset, None),
     };
 
     if file_len <= read_offset {
-        return (vec![], read_offset);
+        return (vec![], read_offset, None);
     }
 
     if read_offset > 0 && file.seek(SeekFrom::Start(read_offset)).is_err() {
-        return (vec![], read_offset);
+        return (vec![], read_offset, None);
     }
 
     let mut buf = Vec::new();
     if file.read_to_end(&mut buf).is_err() {
-        return (vec![], read_offset);
+        return (vec![], read_offset, None);
     }
 
     let last_newline = buf.iter().rposition(|&b| b == b'\n');
     let (valid_buf, new_offset) = match last_newline {
         Some(idx) => (&buf[..idx], read_offset + idx as u64 + 1),
-        None => return (vec![], read_offset),
+        None => return (vec![], read_offset, None),
     };
 
     let text = String::from_utf8_lossy(valid_buf);
     let mut entries = Vec::new();
+    let mut model = None;
 
-    for line in text.lines() {
+    for line in log_lines(&text) {
         let line = line.trim();
         if line.is_empty() {
             continue;
@@ -1855,11 +3516,38 @@ pub fn parse_codex_conversation_entries(
             continue;
         }
 
-        // Skip all other line types (session_meta, turn_context, reasoning,
-        // token_count, task_started, task_complete, function_call_output)
+        if line.contains("\"web_search_call\"") {
+            if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
+                if let Some(payload) = v.get("payload") {
+                    let details = payload
+                        .get("action")
+                        .and_then(|a| a.get("query"))
+                        .and_then(|q| q.as_str())
+                        .map(|q| summarize_jsonl_line(q, 120));
+                    entries.push(ConversationEntry::ToolUse {
+                        tool_name: "web_search".to_string(),
+                        details,
+                    });
+                }
+            }
+            continue;
+        }
+
+        if line.contains("\"turn_context\"") {
+            if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
+                let logged = v.get("payload").and_then(|p| p.get("model"));
+                if let Some(name) = real_model_name(logged.and_then(|m| m.as_str())) {
+                    model = Some(name);
+                }
+            }
+            continue;
+        }
+
+        // Skip all other line types (session_meta, reasoning, token_count,
+        // task_started, task_complete, function_call_output)
     }
 
-    (entries, new_offset)
+    (entries, new_offset, model)
 }
 
 // ── Gemini conversation support ──────────────────────────────────────
@@ -1939,7 +3627,7 @@ async fn get_process_start_time(pid: u32) -> Option<std::time::SystemTime> {
     if lstart_str.is_empty() {
         return None;
     }
-    
+
     // Fix for single-digit days: `ps` pads with an extra space (e.g., "Feb  5"),
     // which breaks chrono's exact space matching.
     let lstart_str = lstart_str.replace("  ", " ");
@@ -1982,7 +3670,7 @@ fn find_latest_gemini_session(
     // Track both a creation-time match (strong) and an mtime match (fallback).
     // Files created within 120s of pane start almost certainly belong to this pane.
     let mut best_by_creation: Option<(PathBuf, std::time::Duration)> = None;
-    let mut best_by_mtime: Option<(PathBuf, std::time::SystemTime)> = None;
+    let mut candidates: Vec<(PathBuf, std::time::SystemTime)> = Vec::new();
     for entry in entries.flatten() {
         let path = entry.path();
         if path.extension().and_then(|e| e.to_str()) != Some("json") {
@@ -2026,28 +3714,110 @@ fn find_latest_gemini_session(
                     // the Gemini CLI creates its session file shortly after launch.
                     if let Ok(created) = meta.created() {
                         if let Ok(diff) = created.duration_since(start_time) {
-                            if diff.as_secs() < 120 {
-                                if best_by_creation
-                                    .as_ref()
-                                    .is_none_or(|(_, d)| diff < *d)
-                                {
-                                    best_by_creation = Some((path.clone(), diff));
-                                }
+                            if diff.as_secs() < 120
+                                && best_by_creation.as_ref().is_none_or(|(_, d)| diff < *d)
+                            {
+                                best_by_creation = Some((path.clone(), diff));
                             }
                         }
                     }
                 }
 
-                if best_by_mtime.as_ref().is_none_or(|(_, t)| modified > *t) {
-                    best_by_mtime = Some((path, modified));
+                candidates.push((path, modified));
+            }
+        }
+    }
+    // Prefer creation-time match (precise) over recency (heuristic).
+    if let Some((path, _)) = best_by_creation {
+        return Some(path);
+    }
+    // mtime lies after clock adjustments or copies, so rank by the session's
+    // own last timestamp and only fall back to mtime when the file has none.
+    candidates
+        .into_iter()
+        .map(|(path, modified)| {
+            let last = gemini_session_last_timestamp(&path).unwrap_or(modified);
+            (path, last)
+        })
+        .max_by_key(|(_, last)| *last)
+        .map(|(path, _)| path)
+}
+
+/// Parse a Gemini session file. Gemini rewrites the whole file on every
+/// update, so a read can catch it mid-write; a truncated file is salvaged
+/// by keeping the `messages` entries that were written in full.
+fn parse_gemini_json(data: &str) -> Option<serde_json::Value> {
+    serde_json::from_str(data)
+        .ok()
+        .or_else(|| salvage_truncated_gemini_json(data))
+}
+
+/// Cut a truncated session file after its last complete top-level
+/// `messages` element and close the array and object. `None` when no
+/// element survived.
+fn salvage_truncated_gemini_json(data: &str) -> Option<serde_json::Value> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut string_start = 0;
+    let mut last_key = "";
+    let mut in_messages = false;
+    let mut cut = None;
+    for (i, b) in data.bytes().enumerate() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+                if depth == 1 {
+                    last_key = &data[string_start..i];
+                }
+            }
+            continue;
+        }
+        match b {
+            b'"' => {
+                in_string = true;
+                string_start = i + 1;
+            }
+            b'{' | b'[' => {
+                if depth == 1 && b == b'[' && last_key == "messages" {
+                    in_messages = true;
                 }
+                depth += 1;
             }
+            b'}' | b']' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 1 {
+                    in_messages = false;
+                } else if depth == 2 && in_messages {
+                    cut = Some(i + 1);
+                }
+            }
+            _ => {}
         }
     }
-    // Prefer creation-time match (precise) over mtime match (heuristic).
-    best_by_creation
-        .map(|(p, _)| p)
-        .or(best_by_mtime.map(|(p, _)| p))
+    let mut salvaged = data[..cut?].to_string();
+    salvaged.push_str("]}");
+    serde_json::from_str(&salvaged).ok()
+}
+
+/// The newest timestamp recorded inside a Gemini session file: its
+/// `lastUpdated` field, or else the last message's `timestamp`.
+fn gemini_session_last_timestamp(path: &std::path::Path) -> Option<std::time::SystemTime> {
+    let data = std::fs::read_to_string(path).ok()?;
+    let v = parse_gemini_json(&data)?;
+    let ts = v.get("lastUpdated").and_then(|t| t.as_str()).or_else(|| {
+        v.get("messages")?
+            .as_array()?
+            .iter()
+            .rev()
+            .find_map(|m| m.get("timestamp")?.as_str())
+    })?;
+    let dt = chrono::DateTime::parse_from_rfc3339(ts).ok()?;
+    Some(std::time::SystemTime::from(dt))
 }
 
 /// Resolve the Gemini session JSON file path for a tmux session.
@@ -2061,24 +3831,21 @@ pub async fn resolve_gemini_session_path(
     let pid = get_pane_pid(tmux_name).await?;
     let all_pids = collect_descendant_pids(pid).await;
 
-    if !all_pids.is_empty() {
-        let pid_list = all_pids
-            .iter()
-            .map(|p| p.to_string())
-            .collect::<Vec<_>>()
-            .join(",");
-
-        if let Ok(output) = run_cmd_timeout(Command::new("lsof").args(["-p", &pid_list])).await {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            if let Some(path) = parse_gemini_session_from_lsof(&stdout) {
-                return Some(path.to_string_lossy().to_string());
-            }
-        }
-    }
-
-    if let Some(chats_dir) = gemini_chats_dir(cwd) {
-        let pane_start = get_process_start_time(pid).await;
-        if let Some(path) = find_latest_gemini_session(&chats_dir, claimed_paths, pane_start) {
+    for strategy in resolve_plan(GEMINI_RESOLVE_ORDER, forced_resolve_strategy()) {
+        let path = match strategy {
+            ResolveStrategy::Lsof => lsof_output(&all_pids)
+                .await
+                .and_then(|stdout| parse_gemini_session_from_lsof(&stdout)),
+            ResolveStrategy::Mtime => match gemini_chats_dir(cwd) {
+                Some(chats_dir) => {
+                    let pane_start = get_process_start_time(pid).await;
+                    find_latest_gemini_session(&chats_dir, claimed_paths, pane_start)
+                }
+                None => None,
+            },
+            ResolveStrategy::Cmdline => None,
+        };
+        if let Some(path) = path {
             return Some(path.to_string_lossy().to_string());
         }
     }
@@ -2091,17 +3858,20 @@ pub async fn resolve_gemini_session_path(
 /// Returns (entries, last_assistant_message, stats_update).
 pub fn parse_gemini_session(
     path: &std::path::Path,
+    categories: &ToolCategories,
 ) -> (Vec<ConversationEntry>, Option<String>, GeminiStatsUpdate) {
-    let (entries, _, last_message, stats) = parse_gemini_session_entries(path, 0);
+    let (entries, _, last_message, stats) = parse_gemini_session_entries(path, 0, categories);
     (entries, last_message, stats)
 }
 
 /// Parse new conversation entries from a Gemini session JSON file.
 /// `message_offset` is the previously-seen message index (not byte offset).
+/// `categories` maps tool calls to the edit/bash/web counters.
 /// Returns (new_entries, new_message_offset, last_assistant_message, stats_update).
 pub fn parse_gemini_session_entries(
     path: &std::path::Path,
     message_offset: u64,
+    categories: &ToolCategories,
 ) -> (
     Vec<ConversationEntry>,
     u64,
@@ -2112,11 +3882,10 @@ pub fn parse_gemini_session_entries(
         Ok(d) => d,
         Err(_) => return (vec![], message_offset, None, GeminiStatsUpdate::default()),
     };
-    let v: serde_json::Value = match serde_json::from_str(&data) {
-        Ok(v) => v,
-        Err(_) => return (vec![], message_offset, None, GeminiStatsUpdate::default()),
+    let Some(v) = parse_gemini_json(&data) else {
+        return (vec![], message_offset, None, GeminiStatsUpdate::default());
     };
-    parse_gemini_session_value(&v, message_offset as usize)
+    parse_gemini_session_value(&v, message_offset as usize, categories)
 }
 
 /// Stats extracted from a Gemini session file.
@@ -2126,11 +3895,22 @@ pub struct GeminiStatsUpdate {
     pub tokens_in: u64,
     pub tokens_out: u64,
     pub tokens_cached: u64,
+    /// Reasoning tokens from the `thoughts` count, billed as output.
+    pub tokens_thoughts: u64,
     pub edits: u16,
     pub bash_cmds: u16,
+    pub web_tools: u16,
     pub files: Vec<String>,
+    /// The subset of `files` touched by editing tools.
+    pub modified_files: Vec<String>,
+    pub first_user_ts: Option<String>,
     pub last_user_ts: Option<String>,
     pub last_assistant_ts: Option<String>,
+    /// Model of the newest `gemini` message that names one.
+    pub model: Option<String>,
+    /// Per-turn token counts with their message timestamps.
+    pub token_samples: Vec<(chrono::DateTime<chrono::Utc>, u64)>,
+    pub ttft: Ttft,
 }
 
 fn summarize_gemini_tool_use_details(tool_call: &serde_json::Value) -> Option<String> {
@@ -2220,6 +4000,7 @@ fn extract_gemini_tool_result_parts(
 fn parse_gemini_session_value(
     v: &serde_json::Value,
     message_offset: usize,
+    categories: &ToolCategories,
 ) -> (
     Vec<ConversationEntry>,
     u64,
@@ -2263,7 +4044,11 @@ fn parse_gemini_session_value(
         match msg_type {
             "user" => {
                 if let Some(ts) = timestamp {
+                    stats.first_user_ts.get_or_insert_with(|| ts.to_string());
                     stats.last_user_ts = Some(ts.to_string());
+                    if let Some(at) = parse_iso_timestamp(ts) {
+                        stats.ttft.prompt(at);
+                    }
                 }
                 // content is either a string or an array of {text: "..."}
                 let text = extract_gemini_message_text(msg);
@@ -2276,14 +4061,27 @@ fn parse_gemini_session_value(
             "gemini" => {
                 if let Some(ts) = timestamp {
                     stats.last_assistant_ts = Some(ts.to_string());
+                    if let Some(at) = parse_iso_timestamp(ts) {
+                        stats.ttft.reply(at);
+                    }
+                }
+                if let Some(model) = real_model_name(msg.get("model").and_then(|m| m.as_str())) {
+                    stats.model = Some(model);
                 }
                 // Extract token usage
                 if let Some(tokens) = msg.get("tokens") {
                     stats.turns += 1;
-                    stats.tokens_in += tokens.get("input").and_then(|t| t.as_u64()).unwrap_or(0);
-                    stats.tokens_out += tokens.get("output").and_then(|t| t.as_u64()).unwrap_or(0);
+                    let count = |key: &str| tokens.get(key).and_then(|t| t.as_u64()).unwrap_or(0);
+                    stats.tokens_in += count("input");
+                    stats.tokens_out += count("output");
+                    if let Some(ts) = timestamp.and_then(parse_iso_timestamp) {
+                        let turn = count("input") + count("output") + count("thoughts");
+                        stats.token_samples.push((ts, turn));
+                    }
                     stats.tokens_cached +=
                         tokens.get("cached").and_then(|t| t.as_u64()).unwrap_or(0);
+                    stats.tokens_thoughts +=
+                        tokens.get("thoughts").and_then(|t| t.as_u64()).unwrap_or(0);
                 }
 
                 // Process tool calls
@@ -2292,22 +4090,26 @@ fn parse_gemini_session_value(
                         let name = tc.get("name").and_then(|n| n.as_str()).unwrap_or("unknown");
                         let paths = extract_gemini_tool_paths(tc.get("args"));
                         // Track edits and bash commands
-                        match name {
-                            "write_file" | "edit_file" | "replace_in_file" => {
+                        match categories.category(name) {
+                            Some(ToolCategory::Edit) => {
                                 stats.edits += 1;
                                 for path in &paths {
                                     stats.files.push(path.to_string());
+                                    stats.modified_files.push(path.to_string());
                                 }
                             }
-                            "run_shell_command" | "shell" => {
+                            Some(ToolCategory::Bash) => {
                                 stats.bash_cmds += 1;
                             }
-                            "read_file" => {
+                            Some(ToolCategory::Web) => {
+                                stats.web_tools += 1;
+                            }
+                            None if name == "read_file" => {
                                 for path in &paths {
                                     stats.files.push(path.to_string());
                                 }
                             }
-                            _ => {}
+                            None => {}
                         }
 
                         if emit_entry {
@@ -2318,7 +4120,14 @@ fn parse_gemini_session_value(
 
                             let (filenames, summary) = extract_gemini_tool_result_parts(tc);
                             if !filenames.is_empty() || summary.is_some() {
-                                entries.push(ConversationEntry::ToolResult { filenames, summary });
+                                entries.push(ConversationEntry::ToolResult {
+                                    filenames,
+                                    summary,
+                                    tool_use_id: tc
+                                        .get("id")
+                                        .and_then(|id| id.as_str())
+                                        .map(str::to_string),
+                                });
                             }
                         }
                     }
@@ -2410,18 +4219,34 @@ fn extract_gemini_content_text(msg: &serde_json::Value) -> Option<String> {
 pub fn apply_gemini_stats(stats: &mut SessionStats, update: &GeminiStatsUpdate) {
     stats.turns = update.turns;
     stats.tokens_in = update.tokens_in;
-    stats.tokens_out = update.tokens_out;
+    // Session stats have no reasoning bucket; thoughts are billed as output.
+    stats.tokens_out = update.tokens_out + update.tokens_thoughts;
     stats.tokens_cache_read = update.tokens_cached;
     stats.tokens_cache_write = 0; // Gemini doesn't distinguish cache write
     stats.edits = update.edits;
     stats.bash_cmds = update.bash_cmds;
+    stats.web_tools = update.web_tools;
+    stats.first_user_ts = update.first_user_ts.clone();
     stats.last_user_ts = update.last_user_ts.clone();
     stats.last_assistant_ts = update.last_assistant_ts.clone();
+    stats.ttft = update.ttft.clone();
+    if update.model.is_some() {
+        stats.last_model = update.model.clone();
+    }
     stats.active_subagents = 0;
+    stats.token_samples.clear();
+    for &(at, tokens) in &update.token_samples {
+        stats.record_token_sample(at, tokens);
+    }
     stats.files.clear();
+    stats.modified_files.clear();
     stats.recent_files.clear();
     for f in &update.files {
-        stats.touch_file(f.clone());
+        if update.modified_files.contains(f) {
+            stats.touch_modified_file(f.clone());
+        } else {
+            stats.touch_file(f.clone());
+        }
     }
 }
 
@@ -2456,6 +4281,7 @@ fn add_gemini_usage(
     input_tokens: u64,
     output_tokens: u64,
     cached_tokens: u64,
+    thoughts_tokens: u64,
 ) {
     stats.tokens_in += input_tokens;
     stats.tokens_out += output_tokens;
@@ -2464,12 +4290,13 @@ fn add_gemini_usage(
     stats.gemini_tokens_in += input_tokens;
     stats.gemini_tokens_out += output_tokens;
     stats.gemini_tokens_cached += cached_tokens;
+    stats.gemini_tokens_thoughts += thoughts_tokens;
 }
 
 /// Process a single Gemini session JSON file for global stats.
 /// Since Gemini rewrites the entire file, we re-parse fully but track
 /// the file size to skip unchanged files.
-fn process_gemini_global_file(path: &PathBuf, stats: &mut GlobalStats, today: &str) {
+fn process_gemini_global_file(path: &PathBuf, stats: &mut GlobalStats, days: UsageDays) {
     let file_len = match std::fs::metadata(path) {
         Ok(m) => m.len(),
         Err(_) => return,
@@ -2485,9 +4312,8 @@ fn process_gemini_global_file(path: &PathBuf, stats: &mut GlobalStats, today: &s
         Ok(d) => d,
         Err(_) => return,
     };
-    let v: serde_json::Value = match serde_json::from_str(&data) {
-        Ok(v) => v,
-        Err(_) => return,
+    let Some(v) = parse_gemini_json(&data) else {
+        return;
     };
 
     let messages = match v.get("messages").and_then(|m| m.as_array()) {
@@ -2495,44 +4321,58 @@ fn process_gemini_global_file(path: &PathBuf, stats: &mut GlobalStats, today: &s
         None => return,
     };
 
-    // Sum tokens from all gemini messages that have today's date
+    // Sum tokens from all gemini messages in the counted days
     let mut total_input = 0u64;
     let mut total_output = 0u64;
     let mut total_cached = 0u64;
+    let mut total_thoughts = 0u64;
 
     for msg in messages {
         if msg.get("type").and_then(|t| t.as_str()) != Some("gemini") {
             continue;
         }
-        // Check if the message is from today
-        let is_today = msg
+        let in_days = msg
             .get("timestamp")
             .and_then(|t| t.as_str())
-            .is_some_and(|ts| ts.starts_with(today));
-        if !is_today {
+            .is_some_and(|ts| days.includes(ts));
+        if !in_days {
             continue;
         }
+        if let Some(model) = real_model_name(msg.get("model").and_then(|m| m.as_str())) {
+            stats.last_model = Some(model);
+        }
         if let Some(tokens) = msg.get("tokens") {
             total_input += tokens.get("input").and_then(|t| t.as_u64()).unwrap_or(0);
             total_output += tokens.get("output").and_then(|t| t.as_u64()).unwrap_or(0);
             total_cached += tokens.get("cached").and_then(|t| t.as_u64()).unwrap_or(0);
+            total_thoughts += tokens.get("thoughts").and_then(|t| t.as_u64()).unwrap_or(0);
         }
     }
 
     // Subtract previous contribution from this file, then add new
-    if let Some(&(prev_in, prev_out, prev_cached)) = stats.gemini_file_tokens.get(path) {
+    if let Some(&(prev_in, prev_out, prev_cached, prev_thoughts)) =
+        stats.gemini_file_tokens.get(path)
+    {
         stats.tokens_in -= prev_in;
         stats.tokens_out -= prev_out;
         stats.tokens_cache_read -= prev_cached;
         stats.gemini_tokens_in -= prev_in;
         stats.gemini_tokens_out -= prev_out;
         stats.gemini_tokens_cached -= prev_cached;
+        stats.gemini_tokens_thoughts -= prev_thoughts;
     }
 
-    add_gemini_usage(stats, total_input, total_output, total_cached);
-    stats
-        .gemini_file_tokens
-        .insert(path.clone(), (total_input, total_output, total_cached));
+    add_gemini_usage(
+        stats,
+        total_input,
+        total_output,
+        total_cached,
+        total_thoughts,
+    );
+    stats.gemini_file_tokens.insert(
+        path.clone(),
+        (total_input, total_output, total_cached, total_thoughts),
+    );
     stats.gemini_file_sizes.insert(path.clone(), file_len);
 }
 
@@ -2562,7 +4402,7 @@ pub fn read_last_assistant_message(cwd: &str, uuid: &str) -> Option<String> {
 
     let mut last_text: Option<String> = None;
 
-    for line in text.lines() {
+    for line in log_lines(&text) {
         // Quick filter before JSON parse
         if !line.contains("\"assistant\"") {
             continue;
@@ -2646,23 +4486,95 @@ mod tests {
         assert_eq!(format_tokens(1_234_567), "1.2M");
     }
 
+    #[test]
+    fn format_tokens_as_whole_numbers() {
+        let format = TokenFormat::new(TokenStyle::Compact, 0);
+        assert_eq!(format_tokens_as(999, format), "999");
+        assert_eq!(format_tokens_as(1_234, format), "1k");
+        assert_eq!(format_tokens_as(45_312, format), "45k");
+        assert_eq!(format_tokens_as(1_734_567, format), "2M");
+    }
+
+    #[test]
+    fn format_tokens_as_two_decimals() {
+        let format = TokenFormat::new(TokenStyle::Compact, 2);
+        assert_eq!(format_tokens_as(42, format), "42");
+        assert_eq!(format_tokens_as(1_234, format), "1.23k");
+        assert_eq!(format_tokens_as(45_312, format), "45.31k");
+        assert_eq!(format_tokens_as(1_234_567, format), "1.23M");
+    }
+
+    #[test]
+    fn format_tokens_as_default_matches_format_tokens() {
+        for n in [0, 999, 1_000, 45_300, 1_234_567] {
+            assert_eq!(
+                format_tokens_as(n, TokenFormat::default()),
+                format_tokens(n)
+            );
+        }
+    }
+
+    #[test]
+    fn format_tokens_as_exact_groups_thousands() {
+        let format = TokenFormat::new(TokenStyle::Exact, 2);
+        assert_eq!(format_tokens_as(0, format), "0");
+        assert_eq!(format_tokens_as(999, format), "999");
+        assert_eq!(format_tokens_as(1_000, format), "1,000");
+        assert_eq!(format_tokens_as(45_312, format), "45,312");
+        assert_eq!(format_tokens_as(123_456_789, format), "123,456,789");
+        assert_eq!(
+            format_tokens_as(u64::MAX, format),
+            "18,446,744,073,709,551,615"
+        );
+    }
+
+    #[test]
+    fn token_format_caps_precision() {
+        assert_eq!(TokenFormat::new(TokenStyle::Compact, 9).precision, 3);
+    }
+
+    #[test]
+    fn format_activity_counts() {
+        assert_eq!(format_activity(0, 0, 0), "");
+        assert_eq!(format_activity(12, 4, 0), "✎12 ⌘4");
+        assert_eq!(format_activity(3, 0, 0), "✎3");
+        assert_eq!(format_activity(0, 7, 0), "⌘7");
+        assert_eq!(format_activity(1, 0, 2), "✎1 ⌕2");
+    }
+
     // ── format_cost tests ────────────────────────────────────────────
 
     #[test]
     fn format_cost_zero() {
-        assert_eq!(format_cost(0.0), "$0.00");
-        assert_eq!(format_cost(0.004), "$0.00");
+        assert_eq!(format_cost(0.0, &Currency::usd()), "$0.00");
+        assert_eq!(format_cost(0.004, &Currency::usd()), "$0.00");
     }
 
     #[test]
     fn format_cost_normal() {
-        assert_eq!(format_cost(0.42), "$0.42");
-        assert_eq!(format_cost(1.23), "$1.23");
+        assert_eq!(format_cost(0.42, &Currency::usd()), "$0.42");
+        assert_eq!(format_cost(1.23, &Currency::usd()), "$1.23");
     }
 
     #[test]
     fn format_cost_large() {
-        assert_eq!(format_cost(12.5), "$12");
+        assert_eq!(format_cost(12.5, &Currency::usd()), "$12");
+    }
+
+    #[test]
+    fn format_cost_converts_at_the_currency_rate() {
+        let eur = Currency {
+            symbol: "€".to_string(),
+            rate: 0.92,
+        };
+        assert_eq!(format_cost(1.0, &eur), "€0.92");
+        assert_eq!(format_cost(12.5, &eur), "€12");
+        assert_eq!(format_cost(0.005, &eur), "€0.00");
+        let yen = Currency {
+            symbol: "¥".to_string(),
+            rate: 150.0,
+        };
+        assert_eq!(format_cost(0.42, &yen), "¥63");
     }
 
     // ── SessionStats cost tests ──────────────────────────────────────
@@ -2679,7 +4591,7 @@ mod tests {
             tokens_in: 1_000_000,        // $3.00
             tokens_out: 100_000,         // $1.50
             tokens_cache_read: 500_000,  // free
-            tokens_cache_write: 200_000, // free
+            tokens_cache_write: 200_000, // free (standard TTL)
             ..Default::default()
         };
         let cost = stats.cost_usd();
@@ -2689,6 +4601,118 @@ mod tests {
         );
     }
 
+    #[test]
+    fn session_stats_cost_prices_1h_cache_writes_higher() {
+        let stats = SessionStats {
+            tokens_cache_write: 1_000_000, // 500k free + 500k @ $2.25 surcharge
+            tokens_cache_write_1h: 500_000,
+            ..Default::default()
+        };
+        let cost = stats.cost_usd();
+        assert!(
+            (cost - 1.125).abs() < 0.001,
+            "expected ~$1.13, got ${cost:.3}"
+        );
+    }
+
+    #[test]
+    fn estimated_cost_uses_the_agent_pricing() {
+        use crate::session::AgentType;
+        let stats = SessionStats {
+            tokens_in: 1_000_000,
+            tokens_out: 100_000,
+            tokens_cache_read: 400_000,
+            ..Default::default()
+        };
+        // Claude: $3.00 in + $1.50 out; cache reads are free.
+        let claude = stats.estimated_cost_usd(&AgentType::Claude);
+        assert!((claude - 4.5).abs() < 0.001, "got ${claude:.3}");
+        assert!((claude - stats.cost_usd()).abs() < f64::EPSILON);
+        // Codex: 600k uncached @ $1.25 + 100k @ $10.00.
+        let codex = stats.estimated_cost_usd(&AgentType::Codex);
+        assert!((codex - 1.75).abs() < 0.001, "got ${codex:.3}");
+        let gemini = stats.estimated_cost_usd(&AgentType::Gemini);
+        assert!((gemini - 1.75).abs() < 0.001, "got ${gemini:.3}");
+        assert_eq!(
+            SessionStats::default().estimated_cost_usd(&AgentType::Codex),
+            0.0
+        );
+    }
+
+    #[test]
+    fn claude_cache_write_tokens_reads_ttl_breakdown() {
+        let usage: serde_json::Value = serde_json::from_str(
+            r#"{"cache_creation_input_tokens":300,"cache_creation":{"ephemeral_5m_input_tokens":100,"ephemeral_1h_input_tokens":200}}"#,
+        )
+        .unwrap();
+        assert_eq!(claude_cache_write_tokens(&usage), (300, 200));
+
+        // Breakdown without the legacy total: sum the TTL buckets.
+        let usage: serde_json::Value = serde_json::from_str(
+            r#"{"cache_creation":{"ephemeral_5m_input_tokens":40,"ephemeral_1h_input_tokens":60}}"#,
+        )
+        .unwrap();
+        assert_eq!(claude_cache_write_tokens(&usage), (100, 60));
+    }
+
+    #[test]
+    fn claude_cache_write_tokens_legacy_shape() {
+        let usage: serde_json::Value =
+            serde_json::from_str(r#"{"cache_creation_input_tokens":300}"#).unwrap();
+        assert_eq!(claude_cache_write_tokens(&usage), (300, 0));
+
+        let usage: serde_json::Value = serde_json::from_str(r#"{"input_tokens":1}"#).unwrap();
+        assert_eq!(claude_cache_write_tokens(&usage), (0, 0));
+    }
+
+    #[test]
+    fn claude_output_tokens_adds_thinking_field() {
+        let usage: serde_json::Value =
+            serde_json::from_str(r#"{"output_tokens":100,"thinking_tokens":400}"#).unwrap();
+        assert_eq!(claude_output_tokens(&usage), 500);
+
+        let usage: serde_json::Value =
+            serde_json::from_str(r#"{"output_tokens":100,"reasoning_tokens":50}"#).unwrap();
+        assert_eq!(claude_output_tokens(&usage), 150);
+
+        let usage: serde_json::Value = serde_json::from_str(r#"{"output_tokens":100}"#).unwrap();
+        assert_eq!(claude_output_tokens(&usage), 100);
+    }
+
+    #[test]
+    fn update_session_stats_counts_thinking_as_output() {
+        let path = write_tmp_jsonl(
+            "stats_thinking",
+            &[
+                r#"{"type":"assistant","message":{"usage":{"input_tokens":10,"output_tokens":5,"thinking_tokens":300},"content":[]}}"#,
+                r#"{"type":"assistant","message":{"usage":{"input_tokens":10,"output_tokens":7},"content":[]}}"#,
+            ],
+        );
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        assert_eq!(stats.tokens_out, 312);
+        assert_eq!(stats.tokens_in, 20);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn update_session_stats_tracks_1h_cache_writes() {
+        let path = write_tmp_jsonl(
+            "stats_cache_ttl",
+            &[
+                r#"{"type":"assistant","message":{"usage":{"input_tokens":10,"output_tokens":5,"cache_creation_input_tokens":300,"cache_creation":{"ephemeral_5m_input_tokens":100,"ephemeral_1h_input_tokens":200}},"content":[]}}"#,
+                r#"{"type":"assistant","message":{"usage":{"input_tokens":10,"output_tokens":5,"cache_creation_input_tokens":50},"content":[]}}"#,
+            ],
+        );
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        assert_eq!(stats.tokens_cache_write, 350);
+        assert_eq!(stats.tokens_cache_write_1h, 200);
+        let _ = std::fs::remove_file(&path);
+    }
+
     // ── update_session_stats tests ───────────────────────────────────
     // Tests use update_session_stats_from_path() directly to avoid
     // HOME env var races when tests run in parallel.
@@ -2724,6 +4748,219 @@ mod tests {
         let _ = std::fs::remove_file(&path);
     }
 
+    /// A user tool-result line padded with base64-like data to `len` bytes.
+    fn oversized_tool_result(len: usize) -> String {
+        let head = r#"{"type":"user","timestamp":"2026-10-16T09:00:01Z","toolUseResult":{"filePath":"/shot.png","data":""#;
+        let tail = r#""}}"#;
+        format!("{head}{}{tail}", "A".repeat(len - head.len() - tail.len()))
+    }
+
+    #[test]
+    fn update_session_stats_skips_oversized_lines() {
+        let assistant = r#"{"type":"assistant","message":{"usage":{"input_tokens":10,"output_tokens":5},"content":[]}}"#;
+        let huge = oversized_tool_result(MAX_LOG_LINE_BYTES + 1);
+        let path = write_tmp_jsonl("stats_oversized", &[assistant, &huge, assistant]);
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        assert_eq!(stats.skipped_lines, 1);
+        assert_eq!(stats.turns, 2);
+        assert!(stats.files.is_empty());
+        assert!(!stats.skipping_line);
+        assert_eq!(stats.read_offset, std::fs::metadata(&path).unwrap().len());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn update_session_stats_skips_an_oversized_line_still_being_written() {
+        use std::io::Write;
+        let assistant = r#"{"type":"assistant","message":{"usage":{"input_tokens":10,"output_tokens":5},"content":[]}}"#;
+        let huge = oversized_tool_result(MAX_LOG_LINE_BYTES + 1024);
+        let path = write_tmp_jsonl("stats_oversized_partial", &[assistant]);
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        let (first, rest) = huge.split_at(MAX_LOG_LINE_BYTES + 10);
+        file.write_all(first.as_bytes()).unwrap();
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        assert_eq!(stats.turns, 1);
+        assert_eq!(stats.skipped_lines, 1);
+        assert!(stats.skipping_line);
+        // The unfinished line isn't kept around to be read again.
+        assert_eq!(stats.read_offset, std::fs::metadata(&path).unwrap().len());
+
+        write!(file, "{rest}\n{assistant}\n").unwrap();
+        update_session_stats_from_path(&path, &mut stats);
+        assert_eq!(stats.turns, 2);
+        assert_eq!(stats.skipped_lines, 1);
+        assert!(!stats.skipping_line);
+        assert!(stats.files.is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn update_session_stats_waits_for_a_partial_line_under_the_limit() {
+        use std::io::Write;
+        let assistant = r#"{"type":"assistant","message":{"usage":{"input_tokens":10,"output_tokens":5},"content":[]}}"#;
+        let path = write_tmp_jsonl("stats_partial_small", &[]);
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(&assistant.as_bytes()[..40]).unwrap();
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        assert_eq!(stats.read_offset, 0);
+        writeln!(file, "{}", &assistant[40..]).unwrap();
+        update_session_stats_from_path(&path, &mut stats);
+        assert_eq!(stats.turns, 1);
+        assert_eq!(stats.skipped_lines, 0);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn update_session_stats_tracks_claude_model() {
+        let path = write_tmp_jsonl(
+            "stats_model",
+            &[
+                r#"{"type":"assistant","message":{"model":"claude-opus-4-1-20250805","content":[{"type":"text","text":"a"}]}}"#,
+                r#"{"type":"assistant","message":{"model":"claude-sonnet-4-20250514","content":[{"type":"text","text":"b"}]}}"#,
+                r#"{"type":"assistant","message":{"model":"<synthetic>","content":[{"type":"text","text":"c"}]}}"#,
+            ],
+        );
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        assert_eq!(
+            stats.last_model.as_deref(),
+            Some("claude-sonnet-4-20250514")
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn update_session_stats_flags_waiting_for_task_as_awaiting_input() {
+        let user = r#"{"type":"user","timestamp":"2026-10-16T09:00:00Z","message":{"content":"run the suite"}}"#;
+        let assistant = r#"{"type":"assistant","timestamp":"2026-10-16T09:00:05Z","message":{"content":[{"type":"text","text":"Running it"}]}}"#;
+        let waiting = r#"{"type":"progress","data":{"type":"waiting_for_task","taskDescription":"Run integration suite","taskType":"local_bash"}}"#;
+        let path = write_tmp_jsonl("stats_waiting", &[user, assistant, waiting]);
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        assert!(stats.awaiting_input);
+
+        // Any later message means the agent moved on.
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        std::io::Write::write_all(&mut file, format!("{assistant}\n").as_bytes()).unwrap();
+        update_session_stats_from_path(&path, &mut stats);
+        assert!(!stats.awaiting_input);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    fn bash_progress_line(id: &str, ts: &str, elapsed: u64) -> String {
+        serde_json::json!({
+            "type": "progress",
+            "toolUseID": id,
+            "timestamp": ts,
+            "data": {"type": "bash_progress", "output": "compiling", "elapsedTimeSeconds": elapsed, "totalLines": 3}
+        })
+        .to_string()
+    }
+
+    fn tool_result_line(id: &str) -> String {
+        serde_json::json!({
+            "type": "user",
+            "timestamp": "2026-10-16T10:10:00Z",
+            "message": {"role": "user", "content": [
+                {"type": "tool_result", "tool_use_id": id, "content": "ok"}
+            ]},
+            "toolUseResult": {"stdout": "ok"}
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn update_session_stats_tracks_long_running_bash() {
+        let lines = [
+            bash_progress_line("toolu_build", "2026-10-16T10:00:30Z", 30),
+            bash_progress_line("toolu_build", "2026-10-16T10:04:00Z", 240),
+        ];
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let path = write_tmp_jsonl("stats_long_bash", &lines);
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        let _ = std::fs::remove_file(&path);
+
+        let running = stats.running_bash.as_ref().unwrap();
+        assert_eq!(running.tool_use_id.as_deref(), Some("toolu_build"));
+        assert_eq!(running.elapsed_secs, 240);
+        let at = |ts: &str| ts.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+        let threshold = std::time::Duration::from_secs(5 * 60);
+        assert_eq!(
+            stats.long_running_bash(at("2026-10-16T10:04:30Z"), threshold),
+            None
+        );
+        // Started at 10:00:00, so it crosses five minutes at 10:05 even
+        // without another progress entry.
+        assert_eq!(
+            stats.long_running_bash(at("2026-10-16T10:06:00Z"), threshold),
+            Some(std::time::Duration::from_secs(6 * 60))
+        );
+    }
+
+    #[test]
+    fn update_session_stats_clears_bash_on_its_tool_result() {
+        let lines = [
+            bash_progress_line("toolu_a", "2026-10-16T10:00:10Z", 10),
+            tool_result_line("toolu_other"),
+        ];
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let path = write_tmp_jsonl("stats_bash_other_result", &lines);
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        let _ = std::fs::remove_file(&path);
+        assert!(stats.running_bash.is_some());
+
+        let lines = [
+            bash_progress_line("toolu_a", "2026-10-16T10:00:10Z", 10),
+            bash_progress_line("toolu_b", "2026-10-16T10:01:05Z", 5),
+            tool_result_line("toolu_b"),
+        ];
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let path = write_tmp_jsonl("stats_bash_result", &lines);
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(stats.running_bash, None);
+    }
+
+    #[test]
+    fn update_session_stats_other_progress_clears_awaiting_input() {
+        let waiting = r#"{"type":"progress","data":{"type":"waiting_for_task","taskDescription":"Approve edit"}}"#;
+        let search = r#"{"type":"progress","data":{"type":"query_update","query":"tmux docs"}}"#;
+        let path = write_tmp_jsonl("stats_progress", &[waiting, search]);
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        assert!(!stats.awaiting_input);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn short_model_name_drops_vendor_prefix_and_date() {
+        assert_eq!(short_model_name("claude-sonnet-4-20250514"), "sonnet-4");
+        assert_eq!(short_model_name("claude-opus-4-1-20250805"), "opus-4-1");
+        assert_eq!(short_model_name("gpt-5-codex"), "gpt-5-codex");
+        assert_eq!(short_model_name("gemini-2.5-pro"), "gemini-2.5-pro");
+    }
+
     #[test]
     fn update_session_stats_counts_tools() {
         let path = write_tmp_jsonl(
@@ -2738,6 +4975,138 @@ mod tests {
 
         assert_eq!(stats.edits, 2, "Edit + Write = 2 edits");
         assert_eq!(stats.bash_cmds, 1);
+        assert_eq!(stats.web_tools, 0);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn update_session_stats_counts_web_tools_separately() {
+        let path = write_tmp_jsonl(
+            "stats_web_tools",
+            &[
+                r#"{"type":"assistant","message":{"usage":{"input_tokens":10,"output_tokens":5},"content":[{"type":"tool_use","name":"Edit","id":"t1","input":{}},{"type":"tool_use","name":"WebSearch","id":"t2","input":{"query":"rust"}},{"type":"tool_use","name":"Bash","id":"t3","input":{}}]}}"#,
+                r#"{"type":"assistant","message":{"usage":{"input_tokens":10,"output_tokens":5},"content":[{"type":"tool_use","name":"WebFetch","id":"t4","input":{}},{"type":"tool_use","name":"Read","id":"t5","input":{}}]}}"#,
+            ],
+        );
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+
+        assert_eq!(stats.web_tools, 2, "WebSearch + WebFetch = 2");
+        assert_eq!(stats.edits, 1);
+        assert_eq!(stats.bash_cmds, 1);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn is_web_tool_matches_each_agent_vocabulary() {
+        for name in [
+            "WebSearch",
+            "WebFetch",
+            "web_search",
+            "google_web_search",
+            "web_fetch",
+        ] {
+            assert!(is_web_tool(name), "{name}");
+        }
+        for name in ["Edit", "Bash", "Read", "run_shell_command", "exec_command"] {
+            assert!(!is_web_tool(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn tool_category_defaults_cover_each_agent() {
+        let defaults = ToolCategories::default();
+        for name in [
+            "Edit",
+            "Write",
+            "write_file",
+            "replace_in_file",
+            "apply_patch",
+        ] {
+            assert_eq!(defaults.category(name), Some(ToolCategory::Edit), "{name}");
+        }
+        for name in ["Bash", "run_shell_command", "shell", "exec_command"] {
+            assert_eq!(defaults.category(name), Some(ToolCategory::Bash), "{name}");
+        }
+        assert_eq!(
+            defaults.category("google_web_search"),
+            Some(ToolCategory::Web)
+        );
+        assert_eq!(defaults.category("Read"), None);
+    }
+
+    #[test]
+    fn custom_tool_categories_increment_the_mapped_counters() {
+        let categories = ToolCategories::new(HashMap::from([
+            ("str_replace_editor".to_string(), ToolCategory::Edit),
+            ("execute_command".to_string(), ToolCategory::Bash),
+            ("tavily_search".to_string(), ToolCategory::Web),
+        ]));
+        let path = write_tmp_jsonl(
+            "stats_custom_tool_categories",
+            &[
+                r#"{"type":"assistant","message":{"usage":{"input_tokens":10,"output_tokens":5},"content":[{"type":"tool_use","name":"str_replace_editor","id":"t1","input":{}},{"type":"tool_use","name":"str_replace_editor","id":"t2","input":{}},{"type":"tool_use","name":"execute_command","id":"t3","input":{}},{"type":"tool_use","name":"tavily_search","id":"t4","input":{}},{"type":"tool_use","name":"Bash","id":"t5","input":{}}]}}"#,
+            ],
+        );
+
+        let mut stats = SessionStats {
+            tool_categories: categories.clone(),
+            ..Default::default()
+        };
+        update_session_stats_from_path(&path, &mut stats);
+        assert_eq!(stats.edits, 2);
+        assert_eq!(
+            stats.bash_cmds, 2,
+            "defaults still apply alongside the mapping"
+        );
+        assert_eq!(stats.web_tools, 1);
+
+        let mut codex = SessionStats {
+            tool_categories: categories,
+            ..Default::default()
+        };
+        codex.count_tool("execute_command");
+        assert_eq!(codex.bash_cmds, 1);
+        // Stats without the mapping only count the built-in names.
+        let mut plain = SessionStats::default();
+        plain.count_tool("execute_command");
+        assert_eq!(plain.bash_cmds, 0);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn log_lines_strips_carriage_returns() {
+        // The chunk ends where the final `\n` was cut off, leaving its `\r`.
+        let lines: Vec<&str> = log_lines("{\"a\":1}\r\n{\"b\":2}\r").collect();
+        assert_eq!(lines, [r#"{"a":1}"#, r#"{"b":2}"#]);
+    }
+
+    #[test]
+    fn update_session_stats_parses_crlf_logs() {
+        let path = std::env::temp_dir().join("hydra_test_stats_crlf.jsonl");
+        let lines = [
+            r#"{"type":"user","timestamp":"2026-01-15T10:00:00.000Z","message":{"role":"user","content":"go"}}"#,
+            r#"{"type":"assistant","timestamp":"2026-01-15T10:00:30.000Z","message":{"usage":{"input_tokens":100,"output_tokens":50},"content":[{"type":"text","text":"done"},{"type":"tool_use","name":"Edit","id":"t1","input":{}},{"type":"tool_use","name":"Bash","id":"t2","input":{}}]}}"#,
+        ];
+        std::fs::write(&path, format!("{}\r\n", lines.join("\r\n"))).unwrap();
+
+        let mut stats = SessionStats::default();
+        let last = update_session_stats_from_path_and_last_message(&path, &mut stats);
+        assert_eq!(last.as_deref(), Some("done"));
+        assert_eq!(stats.turns, 1);
+        assert_eq!(stats.tokens_in, 100);
+        assert_eq!(stats.tokens_out, 50);
+        assert_eq!(stats.edits, 1);
+        assert_eq!(stats.bash_cmds, 1);
+        assert_eq!(
+            stats.last_user_ts.as_deref(),
+            Some("2026-01-15T10:00:00.000Z")
+        );
+        assert_eq!(
+            stats.last_assistant_ts.as_deref(),
+            Some("2026-01-15T10:00:30.000Z")
+        );
         let _ = std::fs::remove_file(&path);
     }
 
@@ -2933,6 +5302,102 @@ mod tests {
         let _ = std::fs::remove_file(&path);
     }
 
+    #[test]
+    fn update_session_stats_separates_modified_from_read_files() {
+        let path = write_tmp_jsonl(
+            "stats_modified",
+            &[
+                r#"{"type":"user","toolUseResult":{"type":"text","file":{"filePath":"/src/app.rs","content":"..."}}}"#,
+                r#"{"type":"user","toolUseResult":{"filenames":["/src/ui.rs","/src/main.rs"]}}"#,
+                r#"{"type":"user","toolUseResult":{"filePath":"/src/main.rs","oldString":"a","newString":"b"}}"#,
+                r#"{"type":"user","toolUseResult":{"type":"create","filePath":"/src/new.rs","content":"fn f() {}"}}"#,
+                r#"{"type":"user","toolUseResult":{"type":"text","file":{"filePath":"/src/new.rs","content":"..."}}}"#,
+            ],
+        );
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+
+        assert_eq!(
+            stats.modified_files,
+            HashSet::from(["/src/main.rs".to_string(), "/src/new.rs".to_string()])
+        );
+        assert_eq!(stats.read_only_files(), ["/src/app.rs", "/src/ui.rs"]);
+        assert_eq!(stats.files.len(), 4);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    fn todo_write_line(todos: serde_json::Value) -> String {
+        serde_json::json!({
+            "type": "assistant",
+            "message": {
+                "usage": {"input_tokens": 10, "output_tokens": 5},
+                "content": [{"type": "tool_use", "name": "TodoWrite", "id": "t1", "input": {"todos": todos}}]
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn update_session_stats_keeps_the_latest_todo_list() {
+        let first = todo_write_line(serde_json::json!([
+            {"content": "Write parser", "status": "in_progress", "activeForm": "Writing parser"},
+            {"content": "Add tests", "status": "pending", "activeForm": "Adding tests"},
+        ]));
+        let second = todo_write_line(serde_json::json!([
+            {"content": "Write parser", "status": "completed", "activeForm": "Writing parser"},
+            {"content": "Add tests", "status": "in_progress", "activeForm": "Adding tests"},
+        ]));
+        let path = write_tmp_jsonl("stats_todos", &[&first, &second]);
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+
+        assert_eq!(
+            stats.todos,
+            [
+                TodoItem {
+                    content: "Write parser".to_string(),
+                    status: TodoStatus::Completed,
+                },
+                TodoItem {
+                    content: "Add tests".to_string(),
+                    status: TodoStatus::InProgress,
+                },
+            ]
+        );
+        assert_eq!(todo_progress(&stats.todos), (1, 2));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn parse_todo_update_ignores_other_tools_and_blank_items() {
+        let edit = serde_json::json!({"type": "tool_use", "name": "Edit", "input": {"todos": []}});
+        assert_eq!(parse_todo_update(&edit), None);
+
+        let todo = serde_json::json!({"type": "tool_use", "name": "TodoWrite", "input": {"todos": [
+            {"content": "  ", "status": "pending"},
+            {"content": "Ship it", "status": "someday"},
+        ]}});
+        assert_eq!(
+            parse_todo_update(&todo),
+            Some(vec![TodoItem {
+                content: "Ship it".to_string(),
+                status: TodoStatus::Pending,
+            }])
+        );
+    }
+
+    #[test]
+    fn evicted_files_leave_the_modified_set() {
+        let mut stats = SessionStats::default();
+        stats.touch_modified_file("/first.rs".to_string());
+        for i in 0..MAX_SESSION_TRACKED_FILES {
+            stats.touch_file(format!("/file-{i}.rs"));
+        }
+        assert!(!stats.modified_files.contains("/first.rs"));
+    }
+
     // ── task_elapsed tests ────────────────────────────────────────
 
     #[test]
@@ -2966,54 +5431,228 @@ mod tests {
                 .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
         );
 
-        assert!(
-            stats.task_elapsed().is_none(),
-            "assistant replied = task done"
+        assert!(
+            stats.task_elapsed().is_none(),
+            "assistant replied = task done"
+        );
+    }
+
+    #[test]
+    fn is_working_with_active_subagents_ignores_timestamps() {
+        let now = chrono::Utc::now();
+        let ts = |secs_ago| {
+            Some(
+                (now - chrono::Duration::seconds(secs_ago))
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            )
+        };
+        // Assistant replied after the user (idle by timestamps), no timestamps
+        // at all, and user after assistant (working by timestamps).
+        for (user, assistant) in [(ts(60), ts(30)), (None, None), (ts(10), ts(60))] {
+            let stats = SessionStats {
+                last_user_ts: user,
+                last_assistant_ts: assistant,
+                active_subagents: 3,
+                ..Default::default()
+            };
+            assert!(stats.is_working());
+        }
+    }
+
+    #[test]
+    fn is_working_without_subagents_follows_timestamps() {
+        let now = chrono::Utc::now();
+        let ts = |secs_ago| {
+            Some(
+                (now - chrono::Duration::seconds(secs_ago))
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            )
+        };
+        let idle = SessionStats {
+            last_user_ts: ts(60),
+            last_assistant_ts: ts(30),
+            ..Default::default()
+        };
+        assert!(!idle.is_working());
+        let working = SessionStats {
+            last_user_ts: ts(10),
+            last_assistant_ts: ts(60),
+            ..Default::default()
+        };
+        assert!(working.is_working());
+    }
+
+    #[test]
+    fn format_subagents_pluralizes() {
+        assert_eq!(format_subagents(0), None);
+        assert_eq!(format_subagents(1).as_deref(), Some("1 subagent"));
+        assert_eq!(format_subagents(3).as_deref(), Some("3 subagents"));
+    }
+
+    #[test]
+    fn task_elapsed_new_user_msg_after_assistant() {
+        let mut stats = SessionStats::default();
+        let now = chrono::Utc::now();
+        // Assistant replied 60s ago, user sent new msg 10s ago
+        stats.last_assistant_ts = Some(
+            (now - chrono::Duration::seconds(60))
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        );
+        stats.last_user_ts = Some(
+            (now - chrono::Duration::seconds(10))
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        );
+
+        let elapsed = stats.task_elapsed().expect("new user msg = working");
+        assert!(elapsed.as_secs() >= 9 && elapsed.as_secs() <= 11);
+    }
+
+    #[test]
+    fn task_elapsed_from_jsonl_parsing() {
+        let path = write_tmp_jsonl(
+            "stats_timestamps",
+            &[
+                r#"{"type":"user","timestamp":"2026-01-15T10:00:00.000Z","message":{"role":"user","content":"do something"}}"#,
+                r#"{"type":"assistant","timestamp":"2026-01-15T10:00:30.000Z","message":{"role":"assistant","usage":{"input_tokens":100,"output_tokens":50,"cache_read_input_tokens":0,"cache_creation_input_tokens":0},"content":[{"type":"text","text":"done"}]}}"#,
+                r#"{"type":"user","timestamp":"2026-01-15T10:01:00.000Z","message":{"role":"user","content":"now do this"}}"#,
+            ],
+        );
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+
+        assert_eq!(
+            stats.last_user_ts.as_deref(),
+            Some("2026-01-15T10:01:00.000Z")
+        );
+        assert_eq!(
+            stats.last_assistant_ts.as_deref(),
+            Some("2026-01-15T10:00:30.000Z")
+        );
+        // User message is after assistant → agent should be working
+        assert!(stats.task_elapsed().is_some());
+        assert_eq!(
+            stats.first_user_ts.as_deref(),
+            Some("2026-01-15T10:00:00.000Z")
+        );
+        assert_eq!(
+            stats.session_duration(),
+            Some(std::time::Duration::from_secs(60))
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn ttft_measures_from_the_first_unanswered_prompt() {
+        let at = |s: &str| parse_iso_timestamp(s).unwrap();
+        let mut ttft = Ttft::default();
+        assert_eq!(ttft.average(), None);
+        ttft.reply(at("2026-01-15T10:00:00Z"));
+        assert_eq!(ttft.count, 0);
+
+        ttft.prompt(at("2026-01-15T10:00:00Z"));
+        ttft.prompt(at("2026-01-15T10:00:03Z"));
+        ttft.reply(at("2026-01-15T10:00:04Z"));
+        ttft.reply(at("2026-01-15T10:00:09Z"));
+        ttft.prompt(at("2026-01-15T10:01:00Z"));
+        ttft.reply(at("2026-01-15T10:01:02Z"));
+
+        assert_eq!(ttft.count, 2);
+        assert_eq!(ttft.average(), Some(std::time::Duration::from_secs(3)));
+        assert_eq!(ttft.last(), Some(std::time::Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn ttft_from_jsonl_ignores_tool_results_and_meta_entries() {
+        let path = write_tmp_jsonl(
+            "stats_ttft",
+            &[
+                r#"{"type":"user","timestamp":"2026-01-15T10:00:00.000Z","message":{"role":"user","content":"do something"}}"#,
+                r#"{"type":"assistant","timestamp":"2026-01-15T10:00:01.500Z","message":{"role":"assistant","content":[{"type":"tool_use","id":"t1","name":"Bash","input":{}}]}}"#,
+                r#"{"type":"user","timestamp":"2026-01-15T10:00:20.000Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"ok"}]}}"#,
+                r#"{"type":"user","isMeta":true,"timestamp":"2026-01-15T10:00:21.000Z","message":{"role":"user","content":"caveat"}}"#,
+                r#"{"type":"assistant","timestamp":"2026-01-15T10:00:30.000Z","message":{"role":"assistant","content":[{"type":"text","text":"done"}]}}"#,
+                r#"{"type":"user","timestamp":"2026-01-15T10:01:00.000Z","message":{"role":"user","content":"now do this"}}"#,
+                r#"{"type":"assistant","timestamp":"2026-01-15T10:01:02.500Z","message":{"role":"assistant","content":[{"type":"text","text":"ok"}]}}"#,
+            ],
+        );
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+
+        assert_eq!(stats.ttft.count, 2);
+        assert_eq!(stats.ttft.last_ms, Some(2500));
+        assert_eq!(
+            stats.ttft.average(),
+            Some(std::time::Duration::from_millis(2000))
         );
+        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
-    fn task_elapsed_new_user_msg_after_assistant() {
-        let mut stats = SessionStats::default();
-        let now = chrono::Utc::now();
-        // Assistant replied 60s ago, user sent new msg 10s ago
-        stats.last_assistant_ts = Some(
-            (now - chrono::Duration::seconds(60))
-                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
-        );
-        stats.last_user_ts = Some(
-            (now - chrono::Duration::seconds(10))
-                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+    fn session_duration_spans_first_user_to_last_activity() {
+        let stats = SessionStats {
+            first_user_ts: Some("2026-01-15T08:00:00Z".to_string()),
+            last_user_ts: Some("2026-01-15T09:30:00Z".to_string()),
+            last_assistant_ts: Some("2026-01-15T10:00:00Z".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            stats.session_duration(),
+            Some(std::time::Duration::from_secs(2 * 3600))
         );
+    }
 
-        let elapsed = stats.task_elapsed().expect("new user msg = working");
-        assert!(elapsed.as_secs() >= 9 && elapsed.as_secs() <= 11);
+    #[test]
+    fn session_duration_needs_a_first_user_message() {
+        let stats = SessionStats {
+            last_assistant_ts: Some("2026-01-15T10:00:00Z".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(stats.session_duration(), None);
+
+        let just_started = SessionStats {
+            first_user_ts: Some("2026-01-15T10:00:00Z".to_string()),
+            last_user_ts: Some("2026-01-15T10:00:00Z".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            just_started.session_duration(),
+            Some(std::time::Duration::ZERO)
+        );
     }
 
     #[test]
-    fn task_elapsed_from_jsonl_parsing() {
+    fn first_user_ts_survives_incremental_reads() {
         let path = write_tmp_jsonl(
-            "stats_timestamps",
+            "stats_first_user_ts",
             &[
-                r#"{"type":"user","timestamp":"2026-01-15T10:00:00.000Z","message":{"role":"user","content":"do something"}}"#,
-                r#"{"type":"assistant","timestamp":"2026-01-15T10:00:30.000Z","message":{"role":"assistant","usage":{"input_tokens":100,"output_tokens":50,"cache_read_input_tokens":0,"cache_creation_input_tokens":0},"content":[{"type":"text","text":"done"}]}}"#,
-                r#"{"type":"user","timestamp":"2026-01-15T10:01:00.000Z","message":{"role":"user","content":"now do this"}}"#,
+                r#"{"type":"user","timestamp":"2026-01-15T10:00:00.000Z","message":{"role":"user","content":"start"}}"#,
             ],
         );
-
         let mut stats = SessionStats::default();
         update_session_stats_from_path(&path, &mut stats);
 
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        use std::io::Write;
+        writeln!(
+            file,
+            r#"{{"type":"user","timestamp":"2026-01-15T11:00:00.000Z","message":{{"role":"user","content":"later"}}}}"#
+        )
+        .unwrap();
+        update_session_stats_from_path(&path, &mut stats);
+
         assert_eq!(
-            stats.last_user_ts.as_deref(),
-            Some("2026-01-15T10:01:00.000Z")
+            stats.first_user_ts.as_deref(),
+            Some("2026-01-15T10:00:00.000Z")
         );
         assert_eq!(
-            stats.last_assistant_ts.as_deref(),
-            Some("2026-01-15T10:00:30.000Z")
+            stats.last_user_ts.as_deref(),
+            Some("2026-01-15T11:00:00.000Z")
         );
-        // User message is after assistant → agent should be working
-        assert!(stats.task_elapsed().is_some());
         let _ = std::fs::remove_file(&path);
     }
 
@@ -3060,6 +5699,110 @@ mod tests {
         assert_eq!(parse_session_id_from_cmdline(cmdline), None);
     }
 
+    // ── Forced resolution strategy ───────────────────────────────────
+
+    #[test]
+    fn resolve_strategy_parses_env_values() {
+        assert_eq!(
+            ResolveStrategy::parse("cmdline"),
+            Some(ResolveStrategy::Cmdline)
+        );
+        assert_eq!(
+            ResolveStrategy::parse(" LSOF "),
+            Some(ResolveStrategy::Lsof)
+        );
+        assert_eq!(
+            ResolveStrategy::parse("mtime"),
+            Some(ResolveStrategy::Mtime)
+        );
+        assert_eq!(ResolveStrategy::parse(""), None);
+        assert_eq!(ResolveStrategy::parse("ps"), None);
+    }
+
+    #[test]
+    fn resolve_plan_tries_every_strategy_in_order_by_default() {
+        assert_eq!(
+            resolve_plan(CLAUDE_RESOLVE_ORDER, None),
+            [ResolveStrategy::Cmdline, ResolveStrategy::Lsof]
+        );
+        assert_eq!(
+            resolve_plan(GEMINI_RESOLVE_ORDER, None),
+            [ResolveStrategy::Lsof, ResolveStrategy::Mtime]
+        );
+    }
+
+    #[test]
+    fn forced_strategy_runs_only_that_path() {
+        use ResolveStrategy::*;
+        assert_eq!(resolve_plan(CLAUDE_RESOLVE_ORDER, Some(Cmdline)), [Cmdline]);
+        assert_eq!(resolve_plan(CLAUDE_RESOLVE_ORDER, Some(Lsof)), [Lsof]);
+        assert_eq!(resolve_plan(GEMINI_RESOLVE_ORDER, Some(Mtime)), [Mtime]);
+        assert_eq!(resolve_plan(GEMINI_RESOLVE_ORDER, Some(Lsof)), [Lsof]);
+        assert_eq!(resolve_plan(CODEX_RESOLVE_ORDER, Some(Lsof)), [Lsof]);
+    }
+
+    #[test]
+    fn forced_strategy_a_provider_lacks_resolves_nothing() {
+        use ResolveStrategy::*;
+        assert!(resolve_plan(CLAUDE_RESOLVE_ORDER, Some(Mtime)).is_empty());
+        assert!(resolve_plan(CODEX_RESOLVE_ORDER, Some(Cmdline)).is_empty());
+        assert!(resolve_plan(GEMINI_RESOLVE_ORDER, Some(Cmdline)).is_empty());
+    }
+
+    // ── lsof timeout fallback ────────────────────────────────────────
+
+    #[test]
+    fn lsof_timeout_falls_back_to_cached_output() {
+        let mut cache = HashMap::new();
+        let first = apply_lsof_outcome(
+            &mut cache,
+            "10,11",
+            LsofOutcome::Completed("claude 10 /x/.claude/tasks/abc".to_string()),
+        );
+        assert_eq!(first.as_deref(), Some("claude 10 /x/.claude/tasks/abc"));
+
+        let on_timeout = apply_lsof_outcome(&mut cache, "10,11", LsofOutcome::TimedOut);
+        assert_eq!(on_timeout, first);
+    }
+
+    #[test]
+    fn lsof_timeout_without_cache_is_none() {
+        let mut cache = HashMap::new();
+        assert_eq!(
+            apply_lsof_outcome(&mut cache, "10", LsofOutcome::TimedOut),
+            None
+        );
+        // A different PID list never borrows another session's output.
+        apply_lsof_outcome(&mut cache, "20", LsofOutcome::Completed("x".to_string()));
+        assert_eq!(
+            apply_lsof_outcome(&mut cache, "10", LsofOutcome::TimedOut),
+            None
+        );
+    }
+
+    #[test]
+    fn lsof_failure_does_not_use_cache() {
+        let mut cache = HashMap::new();
+        apply_lsof_outcome(&mut cache, "10", LsofOutcome::Completed("x".to_string()));
+        assert_eq!(
+            apply_lsof_outcome(&mut cache, "10", LsofOutcome::Failed),
+            None
+        );
+    }
+
+    #[test]
+    fn lsof_cache_is_bounded() {
+        let mut cache = HashMap::new();
+        for i in 0..MAX_LSOF_CACHE_ENTRIES + 5 {
+            apply_lsof_outcome(
+                &mut cache,
+                &i.to_string(),
+                LsofOutcome::Completed(String::new()),
+            );
+        }
+        assert!(cache.len() <= MAX_LSOF_CACHE_ENTRIES);
+    }
+
     // ── parse_uuid_from_lsof_output tests ───────────────────────────
 
     #[test]
@@ -3421,6 +6164,93 @@ mod tests {
         assert!(parse_iso_timestamp("2026-13-45T99:99:99Z").is_none());
     }
 
+    // ── truncate_to_width ────────────────────────────────────────
+
+    fn display_width(s: &str) -> usize {
+        unicode_width::UnicodeWidthStr::width(s)
+    }
+
+    #[test]
+    fn truncate_to_width_leaves_short_text_alone() {
+        assert_eq!(truncate_to_width("hello", 5), "hello");
+        assert_eq!(truncate_to_width("日本語", 6), "日本語");
+    }
+
+    #[test]
+    fn truncate_to_width_ascii_includes_ellipsis_in_budget() {
+        let out = truncate_to_width("abcdefghij", 8);
+        assert_eq!(out, "abcde...");
+        assert_eq!(display_width(&out), 8);
+    }
+
+    #[test]
+    fn truncate_to_width_cjk_never_exceeds_target() {
+        // Each CJK char is 2 columns; 9 columns leaves room for 3 chars + "...".
+        let out = truncate_to_width("日本語のテキストです", 9);
+        assert_eq!(out, "日本語...");
+        assert_eq!(display_width(&out), 9);
+
+        // An odd budget can't fit half a wide char.
+        let out = truncate_to_width("日本語のテキストです", 8);
+        assert_eq!(out, "日本...");
+        assert!(display_width(&out) <= 8);
+    }
+
+    #[test]
+    fn truncate_to_width_keeps_emoji_clusters_whole() {
+        // Family emoji is one grapheme made of several codepoints joined by ZWJ.
+        let family = "👨‍👩‍👧";
+        let text = format!("ok {family}{family}{family} done");
+        let out = truncate_to_width(&text, 10);
+        assert!(display_width(&out) <= 10, "{out:?}");
+        assert!(out.ends_with("..."));
+        let kept = out.trim_end_matches("...").trim_start_matches("ok ");
+        assert_eq!(kept.replace(family, ""), "", "split a cluster: {out:?}");
+    }
+
+    #[test]
+    fn truncate_to_width_tiny_budget_omits_ellipsis() {
+        assert_eq!(truncate_to_width("abcdef", 2), "ab");
+        assert_eq!(truncate_to_width("日本", 1), "");
+    }
+
+    #[test]
+    fn truncate_to_width_with_custom_ellipsis() {
+        let out = truncate_to_width_with("README.md", 7, "…");
+        assert_eq!(out, "README…");
+        let out = truncate_to_width_with("日本語テスト", 6, "…");
+        assert_eq!(out, "日本…");
+        assert_eq!(display_width(&out), 5);
+    }
+
+    #[test]
+    fn summarize_jsonl_line_respects_display_width() {
+        let line = format!("{{\"text\": \"{}\"}}", "漢字".repeat(100));
+        let out = summarize_jsonl_line(&line, 40);
+        assert!(display_width(&out) <= 40);
+        assert!(out.ends_with("..."));
+    }
+
+    #[test]
+    fn latest_timestamp_picks_newer() {
+        let older = "2026-01-15T10:00:00.000Z";
+        let newer = "2026-01-15T10:05:00.000Z";
+        let expected = parse_iso_timestamp(newer);
+        assert_eq!(latest_timestamp(Some(older), Some(newer)), expected);
+        assert_eq!(latest_timestamp(Some(newer), Some(older)), expected);
+    }
+
+    #[test]
+    fn latest_timestamp_with_one_side_missing_or_invalid() {
+        let ts = "2026-01-15T10:00:00.000Z";
+        let expected = parse_iso_timestamp(ts);
+        assert_eq!(latest_timestamp(Some(ts), None), expected);
+        assert_eq!(latest_timestamp(None, Some(ts)), expected);
+        assert_eq!(latest_timestamp(Some("garbage"), Some(ts)), expected);
+        assert_eq!(latest_timestamp(None, None), None);
+        assert_eq!(latest_timestamp(Some("garbage"), None), None);
+    }
+
     // ── JSONL timestamp branch coverage ──────────────────────────
 
     #[test]
@@ -3518,6 +6348,46 @@ mod tests {
         let _ = std::fs::remove_file(&path);
     }
 
+    #[test]
+    fn stats_tool_result_durations_aggregate() {
+        let path = write_tmp_jsonl(
+            "stats_tool_durations",
+            &[
+                r#"{"type":"user","timestamp":"2026-02-24T10:00:00Z","toolUseResult":{"filenames":["/a.rs"],"durationMs":1500}}"#,
+                r#"{"type":"user","toolUseResult":{"content":"done","totalDurationMs":40250}}"#,
+                r#"{"type":"user","toolUseResult":{"stdout":"ok","interrupted":false}}"#,
+            ],
+        );
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        assert_eq!(stats.tool_time_ms, 41_750);
+        assert_eq!(
+            stats.tool_time(),
+            Some(std::time::Duration::from_millis(41_750))
+        );
+        assert_eq!(
+            stats.file_count(),
+            1,
+            "timestamped tool results still track files"
+        );
+        assert_eq!(stats.last_user_ts.as_deref(), Some("2026-02-24T10:00:00Z"));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn stats_tool_time_absent_without_duration_fields() {
+        let path = write_tmp_jsonl(
+            "stats_tool_no_duration",
+            &[r#"{"type":"user","toolUseResult":{"filenames":["/a.rs"]}}"#],
+        );
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        assert_eq!(stats.tool_time(), None);
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn stats_malformed_json_line_skipped() {
         // Valid assistant + malformed line — should not interfere
@@ -3604,6 +6474,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn global_stats_claude_cost_prices_1h_cache_writes() {
+        let mut stats = GlobalStats::default();
+        add_claude_usage(&mut stats, 0, 0, 0, 1_000_000, 1_000_000);
+        assert!((stats.claude_cost_usd() - 2.25).abs() < 0.001);
+
+        let mut legacy = GlobalStats::default();
+        add_claude_usage(&mut legacy, 0, 0, 0, 1_000_000, 0);
+        assert!(legacy.claude_cost_usd().abs() < 0.001);
+    }
+
     #[test]
     fn global_stats_default_is_zero() {
         let stats = GlobalStats::default();
@@ -3690,6 +6571,66 @@ mod tests {
         assert!((stats.claude_cost_usd() - stats.cost_usd()).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn provider_for_model_recognizes_each_provider() {
+        use crate::session::AgentType;
+        for (model, agent) in [
+            ("claude-sonnet-4-20250514", Some(AgentType::Claude)),
+            ("gpt-5-codex", Some(AgentType::Codex)),
+            ("o3", Some(AgentType::Codex)),
+            ("codex-mini-latest", Some(AgentType::Codex)),
+            ("gemini-2.5-pro", Some(AgentType::Gemini)),
+            ("opus", None),
+            ("llama-3", None),
+        ] {
+            assert_eq!(provider_for_model(model), agent, "{model}");
+        }
+    }
+
+    #[test]
+    fn global_stats_aggregate_only_totals_use_the_models_provider() {
+        let aggregate = |model: &str| GlobalStats {
+            tokens_in: 1_000_000, // includes 200k cached
+            tokens_out: 100_000,
+            tokens_cache_read: 200_000,
+            last_model: Some(model.to_string()),
+            ..Default::default()
+        };
+
+        // Uncached input (800k * $1.25/M = $1.00) + output (100k * $10/M = $1.00)
+        let gemini = aggregate("gemini-2.5-pro");
+        assert!((gemini.gemini_cost_usd() - 2.0).abs() < 0.01);
+        assert!((gemini.claude_cost_usd() - 0.0).abs() < f64::EPSILON);
+        assert!((gemini.cost_usd() - 2.0).abs() < 0.01);
+        assert_eq!(gemini.gemini_display_tokens(), 900_000);
+        assert_eq!(gemini.claude_display_tokens(), 0);
+
+        let codex = aggregate("gpt-5-codex");
+        assert!((codex.codex_cost_usd() - 2.0).abs() < 0.01);
+        assert!((codex.cost_usd() - 2.0).abs() < 0.01);
+        assert_eq!(codex.codex_display_tokens(), 900_000);
+        let usage = codex.provider_usage();
+        assert_eq!(usage[0].1, 0);
+        assert_eq!((usage[1].1, usage[1].2), (1_000_000, 100_000));
+
+        // Unknown models keep the Claude pricing.
+        let unknown = aggregate("mystery-1");
+        assert!((unknown.claude_cost_usd() - unknown.cost_usd()).abs() < f64::EPSILON);
+        assert!((unknown.cost_usd() - 4.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn global_stats_breakdown_ignores_last_model() {
+        let stats = GlobalStats {
+            claude_tokens_in: 1_000_000,
+            tokens_in: 1_000_000,
+            last_model: Some("gemini-2.5-pro".to_string()),
+            ..Default::default()
+        };
+        assert!((stats.claude_cost_usd() - 3.0).abs() < 0.01);
+        assert!((stats.gemini_cost_usd() - 0.0).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn global_stats_helper_methods_use_provider_breakdown_when_present() {
         let stats = GlobalStats {
@@ -3746,10 +6687,199 @@ mod tests {
         };
         update_global_stats_inner(&mut stats, &today, Some(tmp.path()));
 
-        assert_eq!(stats.tokens_in, 3000);
-        assert_eq!(stats.tokens_out, 500);
-        assert_eq!(stats.tokens_cache_read, 50);
-        assert_eq!(stats.tokens_cache_write, 10);
+        assert_eq!(stats.tokens_in, 3000);
+        assert_eq!(stats.tokens_out, 500);
+        assert_eq!(stats.tokens_cache_read, 50);
+        assert_eq!(stats.tokens_cache_write, 10);
+    }
+
+    fn write_claude_usage(path: &std::path::Path, today: &str, input_tokens: u64) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            path,
+            format!(
+                r#"{{"type":"assistant","timestamp":"{today}T10:00:00.000Z","message":{{"usage":{{"input_tokens":{input_tokens},"output_tokens":1}},"content":[]}}}}"#
+            ) + "\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn update_global_stats_scans_extra_log_globs() {
+        let projects = tempfile::tempdir().unwrap();
+        let elsewhere = tempfile::tempdir().unwrap();
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        write_claude_usage(&projects.path().join("proj/a.jsonl"), &today, 100);
+        write_claude_usage(&elsewhere.path().join("x/y/b.jsonl"), &today, 20);
+        write_claude_usage(&elsewhere.path().join("c.jsonl"), &today, 3);
+        std::fs::write(elsewhere.path().join("x/notes.txt"), "not a log").unwrap();
+        // A link back into the standard directory is not counted twice.
+        std::os::unix::fs::symlink(
+            projects.path().join("proj/a.jsonl"),
+            elsewhere.path().join("x/link.jsonl"),
+        )
+        .unwrap();
+
+        let mut stats = GlobalStats::default();
+        stats.set_extra_log_globs(&ExtraLogGlobs {
+            claude: vec![format!("{}/x/*.jsonl", elsewhere.path().display())],
+            ..Default::default()
+        });
+        stats.date = today.clone();
+        update_global_stats_inner(&mut stats, &today, Some(projects.path()));
+        assert_eq!(stats.claude_tokens_in, 120);
+        assert_eq!(stats.known_claude_files.len(), 2);
+    }
+
+    #[test]
+    fn update_global_stats_scans_extra_gemini_globs() {
+        let base = tempfile::tempdir().unwrap();
+        let elsewhere = tempfile::tempdir().unwrap();
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let content = serde_json::json!({
+            "messages": [gemini_turn(&format!("{today}T09:00:00Z"), "hi", 40)]
+        });
+        std::fs::write(elsewhere.path().join("chat.json"), content.to_string()).unwrap();
+
+        let mut stats = GlobalStats::default();
+        stats.set_extra_log_globs(&ExtraLogGlobs {
+            gemini: vec![format!("{}/*.json", elsewhere.path().display())],
+            ..Default::default()
+        });
+        stats.date = today.clone();
+        update_global_stats_inner(&mut stats, &today, Some(base.path()));
+        assert_eq!(stats.gemini_tokens_in, 40);
+    }
+
+    #[test]
+    fn collect_glob_files_matches_from_the_fixed_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().display().to_string();
+        for file in ["a/1.jsonl", "a/b/2.jsonl", "a/b/3.log", "c/4.jsonl"] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        let found = |glob: String| {
+            let mut out = Vec::new();
+            collect_glob_files(&glob, &mut out);
+            let mut names: Vec<String> = out
+                .iter()
+                .map(|p| p.strip_prefix(dir.path()).unwrap().display().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(
+            found(format!("{root}/a/*.jsonl")),
+            ["a/1.jsonl", "a/b/2.jsonl"]
+        );
+        assert_eq!(found(format!("{root}/*/?.jsonl")).len(), 3);
+        // Without a `*`, each `/` is one directory level.
+        assert_eq!(
+            found(format!("{root}/?/?.jsonl")),
+            ["a/1.jsonl", "c/4.jsonl"]
+        );
+        assert_eq!(found(format!("{root}/?/b/?.log")), ["a/b/3.log"]);
+        assert_eq!(found(format!("{root}/c/4.jsonl")), ["c/4.jsonl"]);
+        assert!(found(format!("{root}/missing/*")).is_empty());
+    }
+
+    #[test]
+    fn set_extra_log_globs_expands_home_and_forces_reset() {
+        let globs = ExtraLogGlobs {
+            codex: vec!["~/logs/*.jsonl".to_string(), " ".to_string()],
+            ..Default::default()
+        };
+        let expanded = globs.expand_home(Some(std::path::Path::new("/home/me")));
+        assert_eq!(expanded.codex, ["/home/me/logs/*.jsonl"]);
+
+        let mut stats = GlobalStats {
+            date: "2026-10-16".to_string(),
+            ..Default::default()
+        };
+        stats.set_extra_log_globs(&ExtraLogGlobs::default());
+        assert_eq!(stats.date, "2026-10-16");
+        stats.set_extra_log_globs(&globs);
+        assert!(stats.date.is_empty());
+    }
+
+    #[test]
+    fn update_global_stats_bills_claude_thinking_as_output() {
+        use std::io::Write;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let projects = tmp.path().join("proj-a");
+        std::fs::create_dir_all(&projects).unwrap();
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+        let mut f = std::fs::File::create(projects.join("session1.jsonl")).unwrap();
+        writeln!(f,
+            r#"{{"type":"assistant","timestamp":"{today}T10:00:00.000Z","message":{{"usage":{{"input_tokens":1000,"output_tokens":200,"thinking_tokens":800}},"content":[]}}}}"#,
+        ).unwrap();
+        drop(f);
+
+        let mut stats = crate::logs::GlobalStats {
+            date: today.clone(),
+            ..Default::default()
+        };
+        update_global_stats_inner(&mut stats, &today, Some(tmp.path()));
+
+        assert_eq!(stats.claude_tokens_out, 1000);
+        assert_eq!(stats.tokens_out, 1000);
+    }
+
+    #[test]
+    fn update_global_stats_skips_excluded_projects() {
+        let tmp = tempfile::tempdir().unwrap();
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let line = |tokens: u64| {
+            format!(
+                r#"{{"type":"assistant","timestamp":"{today}T10:00:00.000Z","message":{{"usage":{{"input_tokens":{tokens},"output_tokens":0}},"content":[]}}}}"#
+            )
+        };
+        for (dir, tokens) in [
+            ("-home-me-work-app", 100),
+            ("-home-me-experiments", 1000),
+            ("-home-me-experiments-llm", 10_000),
+        ] {
+            let project = tmp.path().join(dir);
+            std::fs::create_dir_all(&project).unwrap();
+            std::fs::write(project.join("s.jsonl"), format!("{}\n", line(tokens))).unwrap();
+        }
+
+        let mut stats = crate::logs::GlobalStats::default();
+        stats.set_excluded_projects(&["/home/me/experiments/".to_string()]);
+        stats.date = today.clone();
+        update_global_stats_inner(&mut stats, &today, Some(tmp.path()));
+
+        assert_eq!(stats.claude_tokens_in, 100);
+        assert_eq!(stats.known_claude_files.len(), 1);
+    }
+
+    #[test]
+    fn excluded_project_prefix_respects_path_boundary() {
+        let mut stats = crate::logs::GlobalStats::default();
+        stats.set_excluded_projects(&["/home/me/exp".to_string()]);
+        let root = std::path::Path::new("/p");
+        let excluded =
+            |dir: &str| stats.is_excluded_claude_file(root, &root.join(dir).join("s.jsonl"));
+        assert!(excluded("-home-me-exp"));
+        assert!(excluded("-home-me-exp-sub"));
+        assert!(!excluded("-home-me-experiments"));
+        assert!(!excluded("-home-me-work"));
+    }
+
+    #[test]
+    fn set_excluded_projects_change_forces_reset() {
+        let mut stats = crate::logs::GlobalStats {
+            date: "2026-02-24".to_string(),
+            ..Default::default()
+        };
+        stats.set_excluded_projects(&[]);
+        assert_eq!(stats.date, "2026-02-24");
+        stats.set_excluded_projects(&["/tmp/x".to_string()]);
+        assert!(stats.date.is_empty());
     }
 
     #[test]
@@ -3790,6 +6920,48 @@ mod tests {
         assert_eq!(stats.tokens_out, 150);
     }
 
+    #[test]
+    fn update_global_stats_paused_advances_offsets_without_counting() {
+        use std::io::Write;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let projects = tmp.path().join("proj-p");
+        std::fs::create_dir_all(&projects).unwrap();
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let log = projects.join("session.jsonl");
+        let append = |input: u64| {
+            let mut f = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&log)
+                .unwrap();
+            writeln!(f,
+                r#"{{"type":"assistant","timestamp":"{today}T10:00:00.000Z","message":{{"usage":{{"input_tokens":{input},"output_tokens":1}}}}}}"#
+            ).unwrap();
+        };
+
+        let mut stats = GlobalStats {
+            date: today.clone(),
+            ..Default::default()
+        };
+        append(100);
+        update_global_stats_inner(&mut stats, &today, Some(tmp.path()));
+        assert_eq!(stats.tokens_in, 100);
+
+        stats.paused = true;
+        append(5_000);
+        update_global_stats_inner(&mut stats, &today, Some(tmp.path()));
+        assert_eq!(stats.tokens_in, 100, "paused usage is not counted");
+        assert_eq!(stats.claude_tokens_in, 100);
+        let len = std::fs::metadata(&log).unwrap().len();
+        assert_eq!(stats.file_offsets.get(&log), Some(&len), "offset advanced");
+
+        stats.paused = false;
+        append(20);
+        update_global_stats_inner(&mut stats, &today, Some(tmp.path()));
+        assert_eq!(stats.tokens_in, 120, "resuming only counts new usage");
+    }
+
     #[test]
     fn update_global_stats_skips_other_dates() {
         use std::io::Write;
@@ -4866,8 +8038,8 @@ mod tests {
     #[test]
     fn add_claude_usage_accumulates() {
         let mut stats = GlobalStats::default();
-        add_claude_usage(&mut stats, 100, 50, 20, 10);
-        add_claude_usage(&mut stats, 200, 100, 30, 20);
+        add_claude_usage(&mut stats, 100, 50, 20, 10, 0);
+        add_claude_usage(&mut stats, 200, 100, 30, 20, 0);
         assert_eq!(stats.tokens_in, 300);
         assert_eq!(stats.tokens_out, 150);
         assert_eq!(stats.tokens_cache_read, 50);
@@ -4894,7 +8066,7 @@ mod tests {
     #[test]
     fn add_mixed_usage_separates_providers() {
         let mut stats = GlobalStats::default();
-        add_claude_usage(&mut stats, 100, 50, 20, 10);
+        add_claude_usage(&mut stats, 100, 50, 20, 10, 0);
         add_codex_usage(&mut stats, 200, 100, 30);
         // Combined totals
         assert_eq!(stats.tokens_in, 300);
@@ -4923,7 +8095,7 @@ mod tests {
             ..Default::default()
         };
         let pb = std::path::PathBuf::from(&path);
-        process_claude_global_file(&pb, &mut stats, &today);
+        process_claude_global_file(&pb, &mut stats, UsageDays::day(&today));
         assert_eq!(stats.tokens_in, 100);
         assert_eq!(stats.tokens_out, 50);
         let offset1 = stats.file_offsets[&pb];
@@ -4940,12 +8112,40 @@ mod tests {
         writeln!(file, "{line2}").unwrap();
 
         // Second call reads only new bytes
-        process_claude_global_file(&pb, &mut stats, &today);
+        process_claude_global_file(&pb, &mut stats, UsageDays::day(&today));
         assert_eq!(stats.tokens_in, 300); // 100 + 200
         assert_eq!(stats.tokens_out, 150); // 50 + 100
         assert!(stats.file_offsets[&pb] > offset1);
     }
 
+    #[test]
+    fn process_claude_global_file_records_the_newest_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.jsonl");
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let line = |model: &str| {
+            format!(
+                r#"{{"type":"assistant","timestamp":"{today}T10:00:00Z","message":{{"model":"{model}","usage":{{"input_tokens":100,"output_tokens":50}},"content":[]}}}}"#
+            )
+        };
+        std::fs::write(
+            &path,
+            format!(
+                "{}\n{}\n",
+                line("claude-opus-4-1-20250805"),
+                line("<synthetic>")
+            ),
+        )
+        .unwrap();
+
+        let mut stats = GlobalStats::default();
+        process_claude_global_file(&path, &mut stats, UsageDays::day(&today));
+        assert_eq!(
+            stats.last_model.as_deref(),
+            Some("claude-opus-4-1-20250805")
+        );
+    }
+
     #[test]
     fn process_claude_global_file_skips_non_assistant_lines() {
         let dir = tempfile::tempdir().unwrap();
@@ -4961,7 +8161,11 @@ mod tests {
             date: today.clone(),
             ..Default::default()
         };
-        process_claude_global_file(&std::path::PathBuf::from(&path), &mut stats, &today);
+        process_claude_global_file(
+            &std::path::PathBuf::from(&path),
+            &mut stats,
+            UsageDays::day(&today),
+        );
         assert_eq!(stats.tokens_in, 0, "non-assistant lines should be skipped");
     }
 
@@ -4979,7 +8183,11 @@ mod tests {
             date: today.clone(),
             ..Default::default()
         };
-        process_claude_global_file(&std::path::PathBuf::from(&path), &mut stats, &today);
+        process_claude_global_file(
+            &std::path::PathBuf::from(&path),
+            &mut stats,
+            UsageDays::day(&today),
+        );
         assert_eq!(stats.tokens_in, 0, "other dates should be skipped");
     }
 
@@ -5000,7 +8208,11 @@ mod tests {
             date: today.clone(),
             ..Default::default()
         };
-        process_codex_global_file(&std::path::PathBuf::from(&path), &mut stats, &today);
+        process_codex_global_file(
+            &std::path::PathBuf::from(&path),
+            &mut stats,
+            UsageDays::day(&today),
+        );
         assert_eq!(stats.codex_tokens_in, 100);
         assert_eq!(stats.codex_tokens_out, 50);
         assert_eq!(stats.codex_tokens_cache_read, 10);
@@ -5021,7 +8233,11 @@ mod tests {
             date: today.clone(),
             ..Default::default()
         };
-        process_codex_global_file(&std::path::PathBuf::from(&path), &mut stats, &today);
+        process_codex_global_file(
+            &std::path::PathBuf::from(&path),
+            &mut stats,
+            UsageDays::day(&today),
+        );
         assert_eq!(stats.codex_tokens_in, 0, "non-event_msg should be skipped");
     }
 
@@ -5041,7 +8257,11 @@ mod tests {
             date: today.clone(),
             ..Default::default()
         };
-        process_codex_global_file(&std::path::PathBuf::from(&path), &mut stats, &today);
+        process_codex_global_file(
+            &std::path::PathBuf::from(&path),
+            &mut stats,
+            UsageDays::day(&today),
+        );
         assert_eq!(stats.codex_tokens_in, 0);
     }
 
@@ -5061,7 +8281,11 @@ mod tests {
             date: today.clone(),
             ..Default::default()
         };
-        process_codex_global_file(&std::path::PathBuf::from(&path), &mut stats, &today);
+        process_codex_global_file(
+            &std::path::PathBuf::from(&path),
+            &mut stats,
+            UsageDays::day(&today),
+        );
         assert_eq!(
             stats.codex_tokens_in, 0,
             "non-token_count payload should be skipped"
@@ -5082,13 +8306,190 @@ mod tests {
             date: today.clone(),
             ..Default::default()
         };
-        process_codex_global_file(&std::path::PathBuf::from(&path), &mut stats, &today);
+        process_codex_global_file(
+            &std::path::PathBuf::from(&path),
+            &mut stats,
+            UsageDays::day(&today),
+        );
         assert_eq!(
             stats.codex_tokens_in, 0,
             "yesterday's tokens should not count for today"
         );
     }
 
+    fn write_codex_rollout(path: &std::path::Path, session_id: &str, totals: &[(u64, u64)]) {
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let mut lines = vec![format!(
+            r#"{{"timestamp":"{today}T09:00:00Z","type":"session_meta","payload":{{"id":"{session_id}","cwd":"/work"}}}}"#
+        )];
+        for (input, output) in totals {
+            let total = input + output;
+            lines.push(format!(
+                r#"{{"type":"event_msg","timestamp":"{today}T10:00:00Z","payload":{{"type":"token_count","info":{{"total_token_usage":{{"input_tokens":{input},"output_tokens":{output},"cached_input_tokens":0,"total_tokens":{total}}}}}}}}}"#
+            ));
+        }
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+
+    /// Rollout file name, session id and (input, output) totals per token_count.
+    type Rollout<'a> = (&'a str, &'a str, &'a [(u64, u64)]);
+
+    fn codex_stats_for(rollouts: &[Rollout]) -> GlobalStats {
+        let tmp = tempfile::tempdir().unwrap();
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let day_dir = tmp.path().join(".codex/sessions/2026/10/16");
+        for (file, session_id, totals) in rollouts {
+            write_codex_rollout(&day_dir.join(file), session_id, totals);
+        }
+        let mut stats = GlobalStats {
+            date: today.clone(),
+            ..Default::default()
+        };
+        update_global_stats_inner(&mut stats, &today, Some(tmp.path()));
+        stats
+    }
+
+    /// Claude, Codex and Gemini logs with usage on 2026-10-04 through
+    /// 2026-10-07.
+    fn write_multi_day_logs(base: &std::path::Path) {
+        let claude = ["04", "05", "06", "07"]
+            .iter()
+            .zip([1u64, 10, 100, 1000])
+            .map(|(day, input)| {
+                format!(
+                    r#"{{"type":"assistant","timestamp":"2026-10-{day}T10:00:00.000Z","message":{{"usage":{{"input_tokens":{input},"output_tokens":1}},"content":[]}}}}"#
+                )
+            })
+            .collect::<Vec<_>>();
+        std::fs::create_dir_all(base.join("proj")).unwrap();
+        std::fs::write(base.join("proj/a.jsonl"), claude.join("\n") + "\n").unwrap();
+
+        // Cumulative totals: 100, then +200 on the 5th, then +400 on the 7th.
+        let codex = [("04", 100u64), ("05", 300), ("07", 700)]
+            .iter()
+            .map(|(day, total)| {
+                format!(
+                    r#"{{"type":"event_msg","timestamp":"2026-10-{day}T10:00:00Z","payload":{{"type":"token_count","info":{{"total_token_usage":{{"input_tokens":{total},"output_tokens":0,"cached_input_tokens":0,"total_tokens":{total}}}}}}}}}"#
+                )
+            })
+            .collect::<Vec<_>>();
+        let rollout = base.join(".codex/sessions/2026/10/04/rollout-2026-10-04T10-00-00-s1.jsonl");
+        std::fs::create_dir_all(rollout.parent().unwrap()).unwrap();
+        std::fs::write(rollout, codex.join("\n") + "\n").unwrap();
+
+        let chats = base.join(".gemini/tmp/abc/chats");
+        std::fs::create_dir_all(&chats).unwrap();
+        let content = serde_json::json!({
+            "messages": [
+                gemini_turn("2026-10-04T09:00:00Z", "a", 3),
+                gemini_turn("2026-10-06T09:00:00Z", "b", 30),
+            ]
+        });
+        std::fs::write(chats.join("session-1.json"), content.to_string()).unwrap();
+    }
+
+    #[test]
+    fn scan_range_stats_sums_only_days_in_range() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_multi_day_logs(tmp.path());
+
+        let mut stats = GlobalStats::default();
+        scan_range_stats_inner(&mut stats, "2026-10-05", "2026-10-06", Some(tmp.path()));
+        assert_eq!(stats.claude_tokens_in, 110);
+        assert_eq!(stats.claude_tokens_out, 2);
+        // Only the 5th's delta; the 4th's snapshot is read but not counted.
+        assert_eq!(stats.codex_tokens_in, 200);
+        assert_eq!(stats.gemini_tokens_in, 30);
+        assert_eq!(stats.tokens_in, 340);
+
+        scan_range_stats_inner(&mut stats, "2026-10-01", "2026-10-31", Some(tmp.path()));
+        assert_eq!(stats.claude_tokens_in, 1111);
+        assert_eq!(stats.codex_tokens_in, 700);
+        assert_eq!(stats.gemini_tokens_in, 33);
+
+        scan_range_stats_inner(&mut stats, "2026-10-07", "2026-10-07", Some(tmp.path()));
+        assert_eq!(stats.claude_tokens_in, 1000);
+        assert_eq!(stats.codex_tokens_in, 400);
+        assert_eq!(stats.gemini_tokens_in, 0);
+    }
+
+    #[test]
+    fn scan_range_stats_keeps_exclusions_and_starts_from_zero() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_multi_day_logs(tmp.path());
+        write_claude_usage(&tmp.path().join("other/b.jsonl"), "2026-10-05", 5);
+
+        let mut stats = GlobalStats {
+            excluded_claude_projects: vec!["proj".to_string()],
+            ..Default::default()
+        };
+        scan_range_stats_inner(&mut stats, "2026-10-05", "2026-10-05", Some(tmp.path()));
+        assert_eq!(stats.claude_tokens_in, 5);
+        // A second scan doesn't add onto the first.
+        scan_range_stats_inner(&mut stats, "2026-10-05", "2026-10-05", Some(tmp.path()));
+        assert_eq!(stats.claude_tokens_in, 5);
+        assert_eq!(stats.excluded_claude_projects, ["proj"]);
+    }
+
+    #[test]
+    fn codex_resumed_session_continues_totals_across_rollout_files() {
+        // The resumed file starts by repeating the earlier final totals.
+        let stats = codex_stats_for(&[
+            (
+                "rollout-2026-10-16T11-00-00-s1.jsonl",
+                "s1",
+                &[(1000, 200), (1500, 300)],
+            ),
+            (
+                "rollout-2026-10-16T09-00-00-s1.jsonl",
+                "s1",
+                &[(400, 100), (1000, 200)],
+            ),
+        ]);
+        assert_eq!(stats.codex_tokens_in, 1500);
+        assert_eq!(stats.codex_tokens_out, 300);
+    }
+
+    #[test]
+    fn codex_resumed_session_with_restarted_totals_counts_both_files() {
+        let stats = codex_stats_for(&[
+            ("rollout-2026-10-16T09-00-00-s1.jsonl", "s1", &[(1000, 200)]),
+            ("rollout-2026-10-16T11-00-00-s1.jsonl", "s1", &[(300, 50)]),
+        ]);
+        assert_eq!(stats.codex_tokens_in, 1300);
+        assert_eq!(stats.codex_tokens_out, 250);
+    }
+
+    #[test]
+    fn codex_rollouts_from_different_sessions_are_counted_separately() {
+        let stats = codex_stats_for(&[
+            ("rollout-2026-10-16T09-00-00-s1.jsonl", "s1", &[(1000, 200)]),
+            ("rollout-2026-10-16T11-00-00-s2.jsonl", "s2", &[(1000, 200)]),
+        ]);
+        assert_eq!(stats.codex_tokens_in, 2000);
+        assert_eq!(stats.codex_tokens_out, 400);
+    }
+
+    #[test]
+    fn codex_session_meta_id_reads_payload_id() {
+        assert_eq!(
+            codex_session_meta_id(
+                r#"{"timestamp":"2026-10-16T09:00:00Z","type":"session_meta","payload":{"id":"abc-123","cwd":"/w"}}"#
+            )
+            .as_deref(),
+            Some("abc-123")
+        );
+        assert_eq!(
+            codex_session_meta_id(r#"{"type":"event_msg","payload":{"id":"x"}}"#),
+            None
+        );
+        assert_eq!(
+            codex_session_meta_id(r#"{"type":"session_meta","payload":{"id":""}}"#),
+            None
+        );
+    }
+
     // ── update_global_stats date change ──
 
     #[test]
@@ -5113,6 +8514,14 @@ mod tests {
                 last_input_tokens: 30,
                 last_output_tokens: 20,
                 last_cached_input_tokens: 0,
+                session_id: Some("old".to_string()),
+            },
+        );
+        stats.codex_session_totals.insert(
+            "old".to_string(),
+            CodexSessionTotals {
+                total_tokens: 50,
+                ..Default::default()
             },
         );
         stats
@@ -5128,6 +8537,7 @@ mod tests {
         assert_eq!(stats.claude_tokens_in, 0);
         assert_eq!(stats.codex_tokens_in, 0);
         assert!(stats.codex_file_states.is_empty());
+        assert!(stats.codex_session_totals.is_empty());
         assert!(stats.file_offsets.is_empty());
     }
 
@@ -5243,6 +8653,28 @@ mod tests {
         assert_eq!(offset, 0);
     }
 
+    #[test]
+    fn conversation_entries_parse_todo_updates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("todos.jsonl");
+        let line = todo_write_line(serde_json::json!([
+            {"content": "Write parser", "status": "completed"},
+            {"content": "Add tests", "status": "pending"},
+        ]));
+        std::fs::write(&path, format!("{line}\n")).unwrap();
+
+        let (entries, _) = parse_conversation_entries(&path, 0);
+        assert_eq!(entries.len(), 1);
+        match &entries[0] {
+            ConversationEntry::TodoUpdate { todos } => {
+                assert_eq!(todos.len(), 2);
+                assert_eq!(todos[0].status, TodoStatus::Completed);
+                assert_eq!(todos[1].content, "Add tests");
+            }
+            other => panic!("expected TodoUpdate, got {other:?}"),
+        }
+    }
+
     #[test]
     fn conversation_entries_user_and_assistant() {
         let dir = tempfile::tempdir().unwrap();
@@ -5345,7 +8777,7 @@ mod tests {
         let (entries, _) = parse_conversation_entries(&path, 0);
         assert_eq!(entries.len(), 1);
         assert!(
-            matches!(&entries[0], ConversationEntry::ToolResult { filenames, summary } if filenames.len() == 2 && summary.is_none())
+            matches!(&entries[0], ConversationEntry::ToolResult { filenames, summary, .. } if filenames.len() == 2 && summary.is_none())
         );
     }
 
@@ -5390,6 +8822,174 @@ mod tests {
         assert!(offset2 > offset);
     }
 
+    fn user_line(text: &str) -> String {
+        serde_json::json!({"type": "user", "message": {"role": "user", "content": text}})
+            .to_string()
+    }
+
+    fn user_texts(entries: &[ConversationEntry]) -> Vec<&str> {
+        entries
+            .iter()
+            .map(|e| match e {
+                ConversationEntry::UserMessage { text } => text.as_str(),
+                other => panic!("unexpected entry {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn conversation_entries_tail_seed_skips_history_then_continues() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("big.jsonl");
+        let lines: Vec<String> = (0..20).map(|i| user_line(&format!("msg-{i:02}"))).collect();
+        std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+        // A window of ~3.5 lines lands mid-line; the partial line is dropped.
+        let line_len = lines[0].len() as u64 + 1;
+        let seed = line_len * 7 / 2;
+        let (entries, offset) = parse_conversation_entries_seeded(&path, 0, Some(seed));
+        assert!(matches!(
+            entries[0],
+            ConversationEntry::HistoryOmitted { bytes } if bytes == line_len * 17
+        ));
+        assert_eq!(user_texts(&entries[1..]), ["msg-17", "msg-18", "msg-19"]);
+        assert_eq!(offset, std::fs::metadata(&path).unwrap().len());
+
+        use std::io::Write;
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        writeln!(file, "{}", user_line("msg-20")).unwrap();
+        drop(file);
+
+        let (entries, _) = parse_conversation_entries_seeded(&path, offset, Some(seed));
+        assert_eq!(user_texts(&entries), ["msg-20"]);
+    }
+
+    #[test]
+    fn conversation_entries_tail_seed_on_line_boundary_keeps_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("boundary.jsonl");
+        let lines: Vec<String> = (0..5).map(|i| user_line(&format!("msg-{i}"))).collect();
+        std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+        let line_len = lines[0].len() as u64 + 1;
+        let (entries, _) = parse_conversation_entries_seeded(&path, 0, Some(line_len * 2));
+        assert!(matches!(
+            entries[0],
+            ConversationEntry::HistoryOmitted { bytes } if bytes == line_len * 3
+        ));
+        assert_eq!(user_texts(&entries[1..]), ["msg-3", "msg-4"]);
+    }
+
+    #[test]
+    fn conversation_entries_tail_seed_small_file_reads_everything() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("small.jsonl");
+        let lines: Vec<String> = (0..3).map(|i| user_line(&format!("msg-{i}"))).collect();
+        std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+        let (entries, _) = parse_conversation_entries_seeded(&path, 0, Some(1024 * 1024));
+        assert_eq!(user_texts(&entries), ["msg-0", "msg-1", "msg-2"]);
+    }
+
+    fn timed_line(ts: &str, text: &str) -> String {
+        serde_json::json!({
+            "type": "user",
+            "timestamp": ts,
+            "message": {"content": text},
+        })
+        .to_string()
+    }
+
+    /// `(agent, text)` for user messages, `agent` empty for the main log.
+    fn merged_texts(entries: &[TimedEntry]) -> Vec<(String, String)> {
+        entries
+            .iter()
+            .map(|(_, e)| match e {
+                ConversationEntry::UserMessage { text } => (String::new(), text.clone()),
+                ConversationEntry::Sidechain { agent_id, entry } => match entry.as_ref() {
+                    ConversationEntry::UserMessage { text } => (agent_id.clone(), text.clone()),
+                    other => panic!("unexpected entry {other:?}"),
+                },
+                other => panic!("unexpected entry {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn subagent_logs_merge_into_main_by_timestamp() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("sess-1.jsonl");
+        std::fs::write(
+            &main,
+            [
+                timed_line("2026-03-01T10:00:00Z", "main-1"),
+                timed_line("2026-03-01T10:00:30Z", "main-2"),
+            ]
+            .join("\n")
+                + "\n",
+        )
+        .unwrap();
+        let subagents = dir.path().join("sess-1").join("subagents");
+        std::fs::create_dir_all(&subagents).unwrap();
+        std::fs::write(
+            subagents.join("agent-b.jsonl"),
+            timed_line("2026-03-01T10:00:30Z", "b-1") + "\n",
+        )
+        .unwrap();
+        std::fs::write(
+            subagents.join("agent-a.jsonl"),
+            [
+                timed_line("2026-03-01T10:00:10Z", "a-1"),
+                // No timestamp: stays right after a-1.
+                r#"{"type":"user","message":{"content":"a-2"}}"#.to_string(),
+                timed_line("2026-03-01T10:00:40Z", "a-3"),
+            ]
+            .join("\n")
+                + "\n",
+        )
+        .unwrap();
+        std::fs::write(subagents.join("notes.txt"), "ignored").unwrap();
+
+        let paths = subagent_log_paths(&main);
+        let ids: Vec<&str> = paths.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, ["agent-a", "agent-b"]);
+
+        let (main_entries, _) = parse_timed_conversation_entries(&main, 0, None);
+        let sidechains = paths
+            .iter()
+            .map(|(id, path)| {
+                (
+                    id.clone(),
+                    parse_timed_conversation_entries(path, 0, None).0,
+                )
+            })
+            .collect();
+        let merged = merge_sidechain_entries(main_entries, sidechains);
+
+        let expected = [
+            ("", "main-1"),
+            ("agent-a", "a-1"),
+            ("agent-a", "a-2"),
+            ("", "main-2"),
+            ("agent-b", "b-1"),
+            ("agent-a", "a-3"),
+        ];
+        let expected: Vec<(String, String)> = expected
+            .iter()
+            .map(|(a, t)| (a.to_string(), t.to_string()))
+            .collect();
+        assert_eq!(merged_texts(&merged), expected);
+    }
+
+    #[test]
+    fn subagent_log_paths_missing_dir_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(subagent_log_paths(&dir.path().join("none.jsonl")).is_empty());
+    }
+
     #[test]
     fn conversation_entries_malformed_line_captured_as_unparsed() {
         let dir = tempfile::tempdir().unwrap();
@@ -5404,6 +9004,23 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn conversation_entries_oversized_line_captured_as_unparsed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("huge.jsonl");
+        let huge = oversized_tool_result(3 * 1024 * 1024);
+        std::fs::write(&path, format!("{huge}\n")).unwrap();
+
+        let (entries, _) = parse_conversation_entries(&path, 0);
+        assert_eq!(entries.len(), 1);
+        let ConversationEntry::Unparsed { reason, raw } = &entries[0] else {
+            panic!("expected unparsed, got {:?}", entries[0]);
+        };
+        assert_eq!(reason, "Line too long to parse (3.0 MB)");
+        assert!(raw.starts_with(r#"{"type":"user""#));
+        assert!(raw.len() < 300);
+    }
+
     #[test]
     fn conversation_entries_queue_operation_parsed() {
         let dir = tempfile::tempdir().unwrap();
@@ -5467,17 +9084,105 @@ mod tests {
                 "retryInMs": 536.45
             }),
         );
-        std::fs::write(&path, &content).unwrap();
+        std::fs::write(&path, &content).unwrap();
+
+        let (entries, _) = parse_conversation_entries(&path, 0);
+        assert_eq!(entries.len(), 1);
+        assert!(matches!(
+            &entries[0],
+            ConversationEntry::SystemEvent { subtype, detail }
+                if subtype == "api_error"
+                    && detail.contains("attempt 2/10")
+                    && detail.contains("retry in 536ms")
+        ));
+    }
+
+    fn result_line(cost_key: &str) -> String {
+        serde_json::json!({
+            "type": "result",
+            "subtype": "success",
+            "is_error": false,
+            "duration_ms": 64_250,
+            "duration_api_ms": 51_000,
+            "num_turns": 3,
+            "result": "Done.",
+            cost_key: 0.4213,
+            "usage": {"input_tokens": 1200, "output_tokens": 340}
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn conversation_entries_result_parsed_as_run_result() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("result.jsonl");
+        let lines = [result_line("total_cost_usd"), result_line("cost_usd")];
+        std::fs::write(&path, format!("{}\n", lines.join("\n"))).unwrap();
+
+        let (entries, _) = parse_conversation_entries(&path, 0);
+        assert_eq!(entries.len(), 2);
+        for entry in &entries {
+            match entry {
+                ConversationEntry::RunResult {
+                    cost_usd,
+                    duration_ms,
+                    turns,
+                } => {
+                    assert_eq!(*cost_usd, 0.4213);
+                    assert_eq!(*duration_ms, 64_250);
+                    assert_eq!(*turns, 3);
+                    assert_eq!(
+                        format_run_result(*cost_usd, *duration_ms, *turns, &Currency::usd()),
+                        "$0.42 · 3 turns · 1m 04s"
+                    );
+                }
+                other => panic!("expected RunResult, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_run_result_needs_a_cost() {
+        let no_cost = serde_json::json!({"type": "result", "num_turns": 2});
+        assert_eq!(parse_run_result(&no_cost), None);
+        let other = serde_json::json!({"type": "system", "total_cost_usd": 1.0});
+        assert_eq!(parse_run_result(&other), None);
+        let bare = serde_json::json!({"type": "result", "total_cost_usd": 0.5});
+        assert_eq!(
+            parse_run_result(&bare),
+            Some(RunResult {
+                cost_usd: 0.5,
+                duration_ms: 0,
+                turns: 0,
+            })
+        );
+    }
 
-        let (entries, _) = parse_conversation_entries(&path, 0);
-        assert_eq!(entries.len(), 1);
-        assert!(matches!(
-            &entries[0],
-            ConversationEntry::SystemEvent { subtype, detail }
-                if subtype == "api_error"
-                    && detail.contains("attempt 2/10")
-                    && detail.contains("retry in 536ms")
-        ));
+    #[test]
+    fn update_session_stats_keeps_the_latest_run_result() {
+        let first = result_line("total_cost_usd");
+        let second = serde_json::json!({
+            "type": "result",
+            "total_cost_usd": 1.5,
+            "duration_ms": 2_000,
+            "num_turns": 9
+        })
+        .to_string();
+        let path = write_tmp_jsonl("stats_run_result", &[&first, &second]);
+
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        assert_eq!(
+            stats.last_run,
+            Some(RunResult {
+                cost_usd: 1.5,
+                duration_ms: 2_000,
+                turns: 9,
+            })
+        );
+        // The summary's usage isn't a turn of its own.
+        assert_eq!(stats.turns, 0);
+        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
@@ -5571,12 +9276,52 @@ mod tests {
         assert_eq!(entries.len(), 1);
         assert!(matches!(
             &entries[0],
-            ConversationEntry::ToolResult { filenames, summary }
+            ConversationEntry::ToolResult { filenames, summary, .. }
                 if filenames.is_empty()
                     && summary.as_deref() == Some("command completed with warnings")
         ));
     }
 
+    #[test]
+    fn read_full_tool_result_rereads_untruncated_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("full_result.jsonl");
+        let long_output: String = (1..=40).map(|i| format!("line {i}\n")).collect();
+        let lines = [
+            serde_json::json!({
+                "type": "user",
+                "message": {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_1", "content": "ignored"}
+                ]},
+                "toolUseResult": {"stdout": long_output}
+            }),
+            serde_json::json!({
+                "type": "user",
+                "message": {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_2", "content": "only here"}
+                ]}
+            }),
+        ];
+        let content: String = lines.iter().map(|l| format!("{l}\n")).collect();
+        std::fs::write(&path, content).unwrap();
+
+        let (entries, _) = parse_conversation_entries(&path, 0);
+        assert!(matches!(
+            &entries[0],
+            ConversationEntry::ToolResult { summary: Some(summary), tool_use_id, .. }
+                if summary.chars().count() <= 180 && tool_use_id.as_deref() == Some("toolu_1")
+        ));
+        assert_eq!(
+            read_full_tool_result(&path, "toolu_1").as_deref(),
+            Some(long_output.trim_end())
+        );
+        assert_eq!(
+            read_full_tool_result(&path, "toolu_2").as_deref(),
+            Some("only here")
+        );
+        assert_eq!(read_full_tool_result(&path, "toolu_3"), None);
+    }
+
     #[test]
     fn conversation_entries_nonexistent_file() {
         let (entries, offset) =
@@ -5634,6 +9379,21 @@ mod tests {
         assert!(offset > 0);
     }
 
+    #[test]
+    fn codex_log_tracks_turn_context_model() {
+        let path = write_tmp_jsonl(
+            "codex_model",
+            &[
+                r#"{"type":"turn_context","payload":{"cwd":"/tmp","model":"gpt-5","approval_policy":"never"}}"#,
+                r#"{"type":"event_msg","payload":{"type":"agent_message","message":"ok"}}"#,
+                r#"{"type":"turn_context","payload":{"cwd":"/tmp","model":"gpt-5-codex"}}"#,
+            ],
+        );
+        let (entries, _, model) = parse_codex_log(&path, 0);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(model.as_deref(), Some("gpt-5-codex"));
+    }
+
     #[test]
     fn codex_conversation_agent_message() {
         let path = write_tmp_jsonl(
@@ -5662,6 +9422,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn codex_conversation_web_search_call() {
+        let path = write_tmp_jsonl(
+            "codex_conv_web_search",
+            &[
+                r#"{"type":"response_item","payload":{"type":"web_search_call","status":"completed","action":{"type":"search","query":"tokio timeout"}}}"#,
+            ],
+        );
+        let (entries, _) = parse_codex_conversation_entries(&path, 0);
+        assert_eq!(entries.len(), 1);
+        assert!(matches!(
+            &entries[0],
+            ConversationEntry::ToolUse { tool_name, details }
+                if tool_name == "web_search" && details.as_deref() == Some("tokio timeout")
+        ));
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn codex_conversation_skips_function_call_output() {
         let path = write_tmp_jsonl(
@@ -5771,7 +9549,8 @@ mod tests {
         });
         std::fs::write(&path, content.to_string()).unwrap();
 
-        let (entries, offset, last_msg, stats) = parse_gemini_session_entries(&path, 0);
+        let (entries, offset, last_msg, stats) =
+            parse_gemini_session_entries(&path, 0, &ToolCategories::default());
         assert_eq!(offset, 2);
         assert_eq!(last_msg.as_deref(), Some("Done."));
         assert_eq!(stats.turns, 1);
@@ -5789,12 +9568,19 @@ mod tests {
         ));
         assert!(matches!(
             &entries[2],
-            ConversationEntry::ToolResult { filenames, summary }
-                if filenames == &vec!["src/session.rs".to_string()] && summary.is_some()
+            ConversationEntry::ToolResult { filenames, summary, tool_use_id }
+                if filenames == &vec!["src/session.rs".to_string()]
+                    && summary.is_some()
+                    && tool_use_id.as_deref() == Some("read_file_1")
         ));
         assert!(
             matches!(&entries[3], ConversationEntry::AssistantText { text } if text == "Done.")
         );
+        assert_eq!(
+            read_full_tool_result(&path, "read_file_1").as_deref(),
+            Some("file contents...")
+        );
+        assert_eq!(read_full_tool_result(&path, "missing"), None);
     }
 
     #[test]
@@ -5808,7 +9594,7 @@ mod tests {
             ]
         });
         std::fs::write(&path, first.to_string()).unwrap();
-        let (_, offset1, _, _) = parse_gemini_session_entries(&path, 0);
+        let (_, offset1, _, _) = parse_gemini_session_entries(&path, 0, &ToolCategories::default());
         assert_eq!(offset1, 2);
 
         let second = serde_json::json!({
@@ -5819,7 +9605,8 @@ mod tests {
             ]
         });
         std::fs::write(&path, second.to_string()).unwrap();
-        let (new_entries, offset2, _, _) = parse_gemini_session_entries(&path, offset1);
+        let (new_entries, offset2, _, _) =
+            parse_gemini_session_entries(&path, offset1, &ToolCategories::default());
         assert_eq!(offset2, 3);
         assert_eq!(new_entries.len(), 1);
         assert!(
@@ -5833,7 +9620,8 @@ mod tests {
             ]
         });
         std::fs::write(&path, rollover.to_string()).unwrap();
-        let (rolled_entries, offset3, _, _) = parse_gemini_session_entries(&path, offset2);
+        let (rolled_entries, offset3, _, _) =
+            parse_gemini_session_entries(&path, offset2, &ToolCategories::default());
         assert_eq!(offset3, 1);
         assert_eq!(rolled_entries.len(), 1);
         assert!(matches!(
@@ -5842,6 +9630,116 @@ mod tests {
         ));
     }
 
+    fn gemini_turn(ts: &str, text: &str, input: u64) -> serde_json::Value {
+        serde_json::json!({
+            "type": "gemini",
+            "timestamp": ts,
+            "content": text,
+            "tokens": {"input": input, "output": 5}
+        })
+    }
+
+    #[test]
+    fn gemini_session_stats_track_ttft() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        let content = serde_json::json!({
+            "sessionId": "test-session",
+            "messages": [
+                {"type": "user", "timestamp": "2026-10-16T09:00:00Z", "content": [{"text": "go"}]},
+                gemini_turn("2026-10-16T09:00:04Z", "first", 100),
+                gemini_turn("2026-10-16T09:00:30Z", "still going", 100),
+                {"type": "user", "timestamp": "2026-10-16T09:01:00Z", "content": [{"text": "again"}]},
+                gemini_turn("2026-10-16T09:01:02Z", "second", 100),
+            ]
+        })
+        .to_string();
+        std::fs::write(&path, content).unwrap();
+
+        let (_, _, _, update) = parse_gemini_session_entries(&path, 0, &ToolCategories::default());
+        let mut stats = SessionStats::default();
+        apply_gemini_stats(&mut stats, &update);
+        assert_eq!(stats.ttft.count, 2);
+        assert_eq!(stats.ttft.last(), Some(std::time::Duration::from_secs(2)));
+        assert_eq!(
+            stats.ttft.average(),
+            Some(std::time::Duration::from_secs(3))
+        );
+    }
+
+    #[test]
+    fn gemini_session_entries_salvage_a_truncated_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        let content = serde_json::json!({
+            "sessionId": "test-session",
+            "messages": [
+                {"type": "user", "timestamp": "2026-10-16T09:00:00Z", "content": [{"text": "go"}]},
+                gemini_turn("2026-10-16T09:00:05Z", "first {\"brace\"] done", 100),
+                gemini_turn("2026-10-16T09:01:00Z", "second", 200),
+            ]
+        })
+        .to_string();
+        // Cut partway through the last message, as a concurrent rewrite would.
+        let cut = content.find("second").unwrap();
+        std::fs::write(&path, &content[..cut]).unwrap();
+
+        let (entries, offset, last_msg, stats) =
+            parse_gemini_session_entries(&path, 0, &ToolCategories::default());
+        assert_eq!(offset, 2);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(last_msg.as_deref(), Some("first {\"brace\"] done"));
+        assert_eq!(stats.turns, 1);
+        assert_eq!(stats.tokens_in, 100);
+
+        // Once the write completes, the full file parses as usual.
+        std::fs::write(&path, &content).unwrap();
+        let (_, offset, _, stats) =
+            parse_gemini_session_entries(&path, offset, &ToolCategories::default());
+        assert_eq!(offset, 3);
+        assert_eq!(stats.tokens_in, 300);
+    }
+
+    #[test]
+    fn salvage_truncated_gemini_json_needs_a_complete_message() {
+        assert!(salvage_truncated_gemini_json(r#"{"sessionId": "x", "messages": ["#).is_none());
+        assert!(
+            salvage_truncated_gemini_json(r#"{"sessionId": "x", "messages": [{"type": "us"#)
+                .is_none()
+        );
+        assert!(salvage_truncated_gemini_json(r#"{"other": [{"a": 1}, {"#).is_none());
+        assert!(salvage_truncated_gemini_json("").is_none());
+
+        let v = salvage_truncated_gemini_json(
+            r#"{"sessionId": "x", "messages": [{"type": "user"}, {"type": "gemini", "toolCalls": [{"#,
+        )
+        .unwrap();
+        assert_eq!(v["sessionId"], "x");
+        assert_eq!(v["messages"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn global_stats_count_complete_messages_of_a_truncated_gemini_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        let content = serde_json::json!({
+            "messages": [
+                gemini_turn("2026-10-16T09:00:05Z", "one", 100),
+                gemini_turn("2026-10-16T09:01:00Z", "two", 200),
+            ]
+        })
+        .to_string();
+        std::fs::write(&path, &content[..content.len() - 10]).unwrap();
+
+        let mut stats = GlobalStats::default();
+        process_gemini_global_file(&path, &mut stats, UsageDays::day("2026-10-16"));
+        assert_eq!(stats.gemini_tokens_in, 100);
+
+        std::fs::write(&path, &content).unwrap();
+        process_gemini_global_file(&path, &mut stats, UsageDays::day("2026-10-16"));
+        assert_eq!(stats.gemini_tokens_in, 300);
+    }
+
     #[test]
     fn gemini_session_entries_unknown_type_unparsed() {
         let dir = tempfile::tempdir().unwrap();
@@ -5853,7 +9751,7 @@ mod tests {
         });
         std::fs::write(&path, content.to_string()).unwrap();
 
-        let (entries, _, _, _) = parse_gemini_session_entries(&path, 0);
+        let (entries, _, _, _) = parse_gemini_session_entries(&path, 0, &ToolCategories::default());
         assert_eq!(entries.len(), 1);
         assert!(matches!(
             &entries[0],
@@ -5873,11 +9771,18 @@ mod tests {
             tokens_in: 20,
             tokens_out: 10,
             tokens_cached: 3,
+            tokens_thoughts: 0,
             edits: 1,
             bash_cmds: 2,
+            web_tools: 0,
             files: vec!["new_a.rs".to_string(), "new_b.rs".to_string()],
+            modified_files: vec!["new_b.rs".to_string()],
+            first_user_ts: Some("2026-02-24T15:00:00Z".to_string()),
             last_user_ts: Some("2026-02-24T16:00:00Z".to_string()),
             last_assistant_ts: Some("2026-02-24T16:01:00Z".to_string()),
+            model: None,
+            token_samples: Vec::new(),
+            ttft: Ttft::default(),
         };
         apply_gemini_stats(&mut stats, &update);
 
@@ -5890,6 +9795,10 @@ mod tests {
         assert_eq!(stats.active_subagents, 0);
         assert_eq!(stats.files.len(), 2);
         assert!(stats.files.contains("new_a.rs"));
+        assert_eq!(
+            stats.session_duration(),
+            Some(std::time::Duration::from_secs(61 * 60))
+        );
         assert!(stats.files.contains("new_b.rs"));
         assert!(!stats.files.contains("old.rs"));
     }
@@ -5915,7 +9824,8 @@ mod tests {
             ]
         }"#;
         let v: serde_json::Value = serde_json::from_str(json).unwrap();
-        let (entries, _, last_msg, stats) = parse_gemini_session_value(&v, 0);
+        let (entries, _, last_msg, stats) =
+            parse_gemini_session_value(&v, 0, &ToolCategories::default());
 
         assert_eq!(entries.len(), 2);
         assert!(matches!(&entries[0], ConversationEntry::UserMessage { text } if text == "Hello"));
@@ -5927,6 +9837,114 @@ mod tests {
         assert_eq!(stats.tokens_in, 100);
         assert_eq!(stats.tokens_out, 50);
         assert_eq!(stats.tokens_cached, 30);
+        assert_eq!(stats.tokens_thoughts, 10);
+    }
+
+    #[test]
+    fn parse_gemini_session_tracks_model() {
+        let json = r#"{
+            "messages": [
+                {"type": "gemini", "model": "gemini-2.5-flash", "content": "one"},
+                {"type": "user", "content": "next"},
+                {"type": "gemini", "model": "gemini-2.5-pro", "content": "two"},
+                {"type": "gemini", "content": "no model field"}
+            ]
+        }"#;
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        let (_, _, _, update) = parse_gemini_session_value(&v, 0, &ToolCategories::default());
+        assert_eq!(update.model.as_deref(), Some("gemini-2.5-pro"));
+
+        let mut stats = SessionStats::default();
+        apply_gemini_stats(&mut stats, &update);
+        assert_eq!(stats.last_model.as_deref(), Some("gemini-2.5-pro"));
+    }
+
+    fn ts(s: &str) -> chrono::DateTime<chrono::Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn tokens_in_window_sums_trailing_samples() {
+        let mut stats = SessionStats::default();
+        stats.record_token_sample(ts("2026-03-01T10:00:00Z"), 1_000);
+        stats.record_token_sample(ts("2026-03-01T10:05:00Z"), 200);
+        stats.record_token_sample(ts("2026-03-01T10:45:00Z"), 30);
+        stats.record_token_sample(ts("2026-03-01T10:59:59Z"), 4);
+
+        let now = ts("2026-03-01T11:00:00Z");
+        let hour = chrono::TimeDelta::hours(1);
+        // The window is half-open: 10:00 itself falls just outside.
+        assert_eq!(stats.tokens_in_window(now, hour), 234);
+        assert_eq!(
+            stats.tokens_in_window(now, chrono::TimeDelta::minutes(20)),
+            34
+        );
+        // Samples after `now` don't count yet.
+        assert_eq!(
+            stats.tokens_in_window(ts("2026-03-01T10:30:00Z"), hour),
+            1_200
+        );
+        assert_eq!(stats.tokens_in_window(ts("2026-03-02T00:00:00Z"), hour), 0);
+    }
+
+    #[test]
+    fn record_token_sample_keeps_order_and_drops_expired() {
+        let mut stats = SessionStats::default();
+        stats.record_token_sample(ts("2026-03-01T10:00:00Z"), 1);
+        stats.record_token_sample(ts("2026-03-01T10:30:00Z"), 2);
+        // Out of order (merged sidechain logs): slots in by time.
+        stats.record_token_sample(ts("2026-03-01T10:10:00Z"), 3);
+        let order: Vec<u64> = stats.token_samples.iter().map(|(_, t)| *t).collect();
+        assert_eq!(order, [1, 3, 2]);
+
+        // A sample an hour past 10:00 evicts it, but keeps 10:10 onwards.
+        stats.record_token_sample(ts("2026-03-01T11:05:00Z"), 4);
+        let order: Vec<u64> = stats.token_samples.iter().map(|(_, t)| *t).collect();
+        assert_eq!(order, [3, 2, 4]);
+    }
+
+    #[test]
+    fn record_token_sample_is_bounded() {
+        let mut stats = SessionStats::default();
+        let start = ts("2026-03-01T10:00:00Z");
+        for i in 0..(MAX_TOKEN_SAMPLES as i64 + 10) {
+            stats.record_token_sample(start + chrono::TimeDelta::milliseconds(i), 1);
+        }
+        assert_eq!(stats.token_samples.len(), MAX_TOKEN_SAMPLES);
+    }
+
+    #[test]
+    fn claude_and_gemini_turns_record_token_samples() {
+        let path = write_tmp_jsonl(
+            "stats_token_samples",
+            &[
+                r#"{"type":"assistant","timestamp":"2026-03-01T10:00:00Z","message":{"usage":{"input_tokens":100,"output_tokens":20},"content":[]}}"#,
+                r#"{"type":"assistant","message":{"usage":{"input_tokens":7,"output_tokens":1},"content":[]}}"#,
+                r#"{"type":"assistant","timestamp":"2026-03-01T10:20:00Z","message":{"usage":{"input_tokens":50,"output_tokens":5},"content":[]}}"#,
+            ],
+        );
+        let mut stats = SessionStats::default();
+        update_session_stats_from_path(&path, &mut stats);
+        // The untimed turn counts toward totals but has no sample.
+        assert_eq!(stats.tokens_in, 157);
+        assert_eq!(
+            stats.tokens_in_window(ts("2026-03-01T10:30:00Z"), chrono::TimeDelta::hours(1)),
+            175
+        );
+        let _ = std::fs::remove_file(&path);
+
+        let json = r#"{"messages": [
+            {"type": "gemini", "timestamp": "2026-03-01T09:00:00Z", "tokens": {"input": 900, "output": 9}},
+            {"type": "gemini", "timestamp": "2026-03-01T10:15:00Z", "tokens": {"input": 40, "output": 4, "thoughts": 6}}
+        ]}"#;
+        let v: serde_json::Value = serde_json::from_str(json).unwrap();
+        let (_, _, _, update) = parse_gemini_session_value(&v, 0, &ToolCategories::default());
+        let mut stats = SessionStats::default();
+        apply_gemini_stats(&mut stats, &update);
+        assert_eq!(
+            stats.tokens_in_window(ts("2026-03-01T10:30:00Z"), chrono::TimeDelta::hours(1)),
+            50
+        );
     }
 
     #[test]
@@ -5960,7 +9978,7 @@ mod tests {
             ]
         }"#;
         let v: serde_json::Value = serde_json::from_str(json).unwrap();
-        let (entries, _, _, stats) = parse_gemini_session_value(&v, 0);
+        let (entries, _, _, stats) = parse_gemini_session_value(&v, 0, &ToolCategories::default());
 
         // user + (tool_use + tool_result) x 2 (no assistant text since content is empty)
         assert_eq!(entries.len(), 5);
@@ -5976,13 +9994,15 @@ mod tests {
         assert_eq!(stats.files.len(), 2);
         assert!(stats.files.contains(&"src/main.rs".to_string()));
         assert!(stats.files.contains(&"src/new.rs".to_string()));
+        assert_eq!(stats.modified_files, ["src/new.rs"]);
     }
 
     #[test]
     fn parse_gemini_session_empty_messages() {
         let json = r#"{"sessionId": "abc", "messages": []}"#;
         let v: serde_json::Value = serde_json::from_str(json).unwrap();
-        let (entries, _, last_msg, stats) = parse_gemini_session_value(&v, 0);
+        let (entries, _, last_msg, stats) =
+            parse_gemini_session_value(&v, 0, &ToolCategories::default());
         assert!(entries.is_empty());
         assert!(last_msg.is_none());
         assert_eq!(stats.turns, 0);
@@ -5993,7 +10013,7 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("bad.json");
         std::fs::write(&path, "not valid json").unwrap();
-        let (entries, last_msg, _) = parse_gemini_session(&path);
+        let (entries, last_msg, _) = parse_gemini_session(&path, &ToolCategories::default());
         assert!(entries.is_empty());
         assert!(last_msg.is_none());
     }
@@ -6115,6 +10135,76 @@ mod tests {
         assert_eq!(resolved, first);
     }
 
+    fn write_with_mtime(
+        path: &std::path::Path,
+        contents: &str,
+        mtime: chrono::DateTime<chrono::Utc>,
+    ) {
+        std::fs::write(path, contents).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::from(mtime))
+            .unwrap();
+    }
+
+    #[test]
+    fn find_latest_gemini_session_prefers_internal_timestamp_over_mtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let chats_dir = dir.path().join("chats");
+        std::fs::create_dir_all(&chats_dir).unwrap();
+
+        let now = chrono::Utc::now();
+        // Copied file: fresh mtime, but its conversation ended an hour ago.
+        let stale = chats_dir.join("session-2026-02-24T16-20-a.json");
+        write_with_mtime(
+            &stale,
+            r#"{"lastUpdated":"2026-02-24T16:30:00Z","messages":[]}"#,
+            now,
+        );
+        // Active file whose mtime was skewed into the past.
+        let active = chats_dir.join("session-2026-02-24T16-21-b.json");
+        write_with_mtime(
+            &active,
+            r#"{"lastUpdated":"2026-02-24T17:30:00Z","messages":[]}"#,
+            now - chrono::Duration::hours(2),
+        );
+
+        let resolved = find_latest_gemini_session(&chats_dir, &HashSet::new(), None).unwrap();
+        assert_eq!(resolved, active);
+    }
+
+    #[test]
+    fn find_latest_gemini_session_uses_last_message_then_mtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let chats_dir = dir.path().join("chats");
+        std::fs::create_dir_all(&chats_dir).unwrap();
+
+        let now = chrono::Utc::now();
+        let by_message = chats_dir.join("session-2026-02-24T16-20-a.json");
+        write_with_mtime(
+            &by_message,
+            r#"{"messages":[{"type":"user","timestamp":"2026-02-24T16:20:00Z"},{"type":"gemini","timestamp":"2026-02-24T18:00:00Z"}]}"#,
+            now - chrono::Duration::hours(3),
+        );
+        // No internal timestamp: ranked by mtime, which is older than 18:00.
+        let unparsable = chats_dir.join("session-2026-02-24T16-21-b.json");
+        write_with_mtime(&unparsable, "{", "2026-02-24T17:00:00Z".parse().unwrap());
+        assert_eq!(
+            gemini_session_last_timestamp(&by_message),
+            Some(std::time::SystemTime::from(
+                "2026-02-24T18:00:00Z"
+                    .parse::<chrono::DateTime<chrono::Utc>>()
+                    .unwrap()
+            ))
+        );
+        assert_eq!(gemini_session_last_timestamp(&unparsable), None);
+
+        let resolved = find_latest_gemini_session(&chats_dir, &HashSet::new(), None).unwrap();
+        assert_eq!(resolved, by_message);
+    }
+
     #[test]
     fn find_latest_gemini_session_returns_none_when_all_claimed() {
         let dir = tempfile::tempdir().unwrap();
@@ -6136,20 +10226,29 @@ mod tests {
 
     #[test]
     fn apply_gemini_stats_replaces_values() {
-        let mut stats = SessionStats::default();
-        stats.turns = 5;
-        stats.tokens_in = 1000;
+        let mut stats = SessionStats {
+            turns: 5,
+            tokens_in: 1000,
+            ..Default::default()
+        };
 
         let update = GeminiStatsUpdate {
             turns: 10,
             tokens_in: 2000,
             tokens_out: 500,
             tokens_cached: 100,
+            tokens_thoughts: 0,
             edits: 3,
             bash_cmds: 1,
+            web_tools: 0,
             files: vec!["a.rs".to_string()],
+            modified_files: Vec::new(),
+            first_user_ts: None,
             last_user_ts: Some("2026-02-24T10:00:00Z".to_string()),
             last_assistant_ts: Some("2026-02-24T10:00:05Z".to_string()),
+            model: None,
+            token_samples: Vec::new(),
+            ttft: Ttft::default(),
         };
 
         apply_gemini_stats(&mut stats, &update);
@@ -6185,8 +10284,10 @@ mod tests {
         )
         .unwrap();
 
-        let mut stats = GlobalStats::default();
-        stats.date = "2026-02-24".to_string();
+        let mut stats = GlobalStats {
+            date: "2026-02-24".to_string(),
+            ..Default::default()
+        };
         // Manually discover files
         let mut gemini_files = Vec::new();
         collect_gemini_session_files(dir.path(), &mut gemini_files);
@@ -6194,7 +10295,7 @@ mod tests {
 
         for i in 0..stats.known_gemini_files.len() {
             let path = stats.known_gemini_files[i].clone();
-            process_gemini_global_file(&path, &mut stats, "2026-02-24");
+            process_gemini_global_file(&path, &mut stats, UsageDays::day("2026-02-24"));
         }
 
         assert_eq!(stats.gemini_tokens_in, 500);
@@ -6204,6 +10305,57 @@ mod tests {
         assert_eq!(stats.tokens_out, 100);
     }
 
+    #[test]
+    fn apply_gemini_stats_bills_thoughts_as_output() {
+        let mut stats = SessionStats::default();
+        let update = GeminiStatsUpdate {
+            tokens_out: 500,
+            tokens_thoughts: 200,
+            ..Default::default()
+        };
+        apply_gemini_stats(&mut stats, &update);
+        assert_eq!(stats.tokens_out, 700);
+    }
+
+    #[test]
+    fn gemini_global_stats_price_thoughts_as_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let session_path = dir.path().join("session-2026-02-24T10-00-abc12345.json");
+        let session_json = r#"{
+            "sessionId": "abc-123",
+            "messages": [
+                {
+                    "type": "gemini",
+                    "timestamp": "2026-02-24T10:00:05Z",
+                    "tokens": {"input": 0, "output": 100000, "cached": 0, "thoughts": 400000}
+                }
+            ]
+        }"#;
+        std::fs::write(&session_path, session_json).unwrap();
+
+        let mut stats = GlobalStats {
+            date: "2026-02-24".to_string(),
+            ..Default::default()
+        };
+        process_gemini_global_file(&session_path, &mut stats, UsageDays::day("2026-02-24"));
+
+        assert_eq!(stats.gemini_tokens_out, 100_000);
+        assert_eq!(stats.gemini_tokens_thoughts, 400_000);
+        assert_eq!(stats.gemini_display_tokens(), 500_000);
+        // (100k output + 400k thoughts) * $10/M = $5.00
+        let cost = stats.gemini_cost_usd();
+        assert!(
+            (cost - 5.00).abs() < 0.001,
+            "expected ~$5.00, got ${cost:.2}"
+        );
+
+        // Re-parse with fewer thoughts replaces the file's contribution.
+        let rewritten = session_json.replace("400000", "1000");
+        std::fs::write(&session_path, rewritten).unwrap();
+        process_gemini_global_file(&session_path, &mut stats, UsageDays::day("2026-02-24"));
+        assert_eq!(stats.gemini_tokens_thoughts, 1_000);
+    }
+
     #[test]
     fn gemini_global_stats_reparse_replaces_prior_file_totals() {
         let dir = tempfile::tempdir().unwrap();
@@ -6224,10 +10376,12 @@ mod tests {
         }"#;
         std::fs::write(&session_path, first).unwrap();
 
-        let mut stats = GlobalStats::default();
-        stats.date = "2026-02-24".to_string();
+        let mut stats = GlobalStats {
+            date: "2026-02-24".to_string(),
+            ..Default::default()
+        };
 
-        process_gemini_global_file(&session_path, &mut stats, "2026-02-24");
+        process_gemini_global_file(&session_path, &mut stats, UsageDays::day("2026-02-24"));
         assert_eq!(stats.gemini_tokens_in, 100);
         assert_eq!(stats.gemini_tokens_out, 20);
         assert_eq!(stats.gemini_tokens_cached, 10);
@@ -6252,7 +10406,7 @@ mod tests {
             ]
         }"#;
         std::fs::write(&session_path, rewritten).unwrap();
-        process_gemini_global_file(&session_path, &mut stats, "2026-02-24");
+        process_gemini_global_file(&session_path, &mut stats, UsageDays::day("2026-02-24"));
 
         assert_eq!(stats.gemini_tokens_in, 500);
         assert_eq!(stats.gemini_tokens_out, 75);
@@ -6274,7 +10428,7 @@ mod tests {
             ]
         }"#;
         std::fs::write(&session_path, old_only).unwrap();
-        process_gemini_global_file(&session_path, &mut stats, "2026-02-24");
+        process_gemini_global_file(&session_path, &mut stats, UsageDays::day("2026-02-24"));
 
         assert_eq!(stats.gemini_tokens_in, 0);
         assert_eq!(stats.gemini_tokens_out, 0);
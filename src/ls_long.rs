@@ -0,0 +1,275 @@
+//! Aligned stats table behind `hydra ls --long`.
+
+use chrono::{DateTime, Utc};
+use unicode_width::UnicodeWidthStr;
+
+use crate::logs::{format_cost, format_tokens, truncate_to_width_with, Currency, SessionStats};
+use crate::session::{format_duration, Session};
+use crate::stats_markdown::status_label;
+
+const HEADERS: [&str; 7] = [
+    "SESSION",
+    "STATUS",
+    "TOKENS",
+    "COST",
+    "EDITS/BASH",
+    "FILES",
+    "AGE",
+];
+
+/// Columns dropped, in this order, when the table is wider than the
+/// terminal. The session column always stays.
+const DROP_ORDER: [usize; 6] = [5, 4, 6, 3, 2, 1];
+
+/// Numeric columns, right-aligned.
+const RIGHT_ALIGNED: [usize; 5] = [2, 3, 4, 5, 6];
+
+const GAP: &str = "  ";
+
+/// Placeholder for cells with no data yet.
+const EMPTY_CELL: &str = "—";
+
+/// Cells of one session's row, in `HEADERS` order. Age runs from the
+/// session's first user message to `now`; cost is shown in `currency`.
+pub fn row(
+    session: &Session,
+    stats: Option<&SessionStats>,
+    now: DateTime<Utc>,
+    currency: &Currency,
+) -> Vec<String> {
+    let mut cells = vec![
+        format!("{} [{}]", session.name, session.agent_type),
+        status_label(session, stats).to_string(),
+    ];
+    match stats {
+        Some(st) => {
+            let age = st
+                .started_at()
+                .and_then(|start| (now - start).to_std().ok())
+                .map_or(EMPTY_CELL.to_string(), format_duration);
+            cells.extend([
+                format_tokens(st.tokens_in + st.tokens_out),
+                format_cost(st.estimated_cost_usd(&session.agent_type), currency),
+                format!("{}/{}", st.edits, st.bash_cmds),
+                st.files.len().to_string(),
+                age,
+            ]);
+        }
+        None => cells.extend(std::iter::repeat_n(EMPTY_CELL.to_string(), 5)),
+    }
+    cells
+}
+
+/// Lay `rows` out under a header. With `max_width`, the least important
+/// columns are dropped until the table fits, then the session column is
+/// cut to make up any difference. Widths are terminal columns, so wide
+/// characters in session names stay aligned.
+pub fn render(rows: &[Vec<String>], max_width: Option<usize>) -> String {
+    let mut widths: Vec<usize> = HEADERS.iter().map(|h| h.width()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.width());
+        }
+    }
+
+    let mut visible: Vec<usize> = (0..HEADERS.len()).collect();
+    let total = |visible: &[usize]| -> usize {
+        visible.iter().map(|&i| widths[i]).sum::<usize>() + GAP.len() * (visible.len() - 1)
+    };
+    if let Some(max_width) = max_width {
+        for col in DROP_ORDER {
+            if total(&visible) <= max_width {
+                break;
+            }
+            visible.retain(|&i| i != col);
+        }
+        let overflow = total(&visible).saturating_sub(max_width);
+        widths[0] = widths[0].saturating_sub(overflow).max(1);
+    }
+
+    let header: Vec<String> = HEADERS.iter().map(|h| h.to_string()).collect();
+    let mut out = String::new();
+    for cells in std::iter::once(&header).chain(rows) {
+        let line: Vec<String> = visible
+            .iter()
+            .map(|&i| {
+                let width = widths[i];
+                let cell = fit(cells.get(i).map_or("", String::as_str), width);
+                let pad = " ".repeat(width.saturating_sub(cell.width()));
+                if RIGHT_ALIGNED.contains(&i) {
+                    format!("{pad}{cell}")
+                } else {
+                    format!("{cell}{pad}")
+                }
+            })
+            .collect();
+        out.push_str(line.join(GAP).trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// `text` cut to `width` columns, ending in `…` when shortened.
+fn fit(text: &str, width: usize) -> String {
+    truncate_to_width_with(text, width, "…")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{AgentState, AgentType, ProcessState};
+    use std::collections::HashSet;
+    use std::time::Instant;
+
+    fn make_session(name: &str, agent_type: AgentType) -> Session {
+        Session {
+            name: name.to_string(),
+            tmux_name: format!("hydra-test-{name}"),
+            agent_type,
+            process_state: ProcessState::Alive,
+            agent_state: AgentState::Idle,
+            last_activity_at: Instant::now(),
+            task_elapsed: None,
+            _alive: true,
+        }
+    }
+
+    fn stats() -> SessionStats {
+        SessionStats {
+            tokens_in: 1_000_000,
+            tokens_out: 200_000,
+            edits: 4,
+            bash_cmds: 7,
+            files: HashSet::from(["src/a.rs".to_string(), "src/b.rs".to_string()]),
+            first_user_ts: Some("2026-10-16T09:00:00Z".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2026-10-16T10:30:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn row_fills_every_column_from_stats() {
+        let session = make_session("alpha", AgentType::Claude);
+        assert_eq!(
+            row(&session, Some(&stats()), now(), &Currency::usd()),
+            [
+                "alpha [Claude]",
+                "idle",
+                "1.2M",
+                "$6.00",
+                "4/7",
+                "2",
+                "1h 30m"
+            ]
+        );
+    }
+
+    #[test]
+    fn row_without_stats_uses_placeholders() {
+        let session = make_session("bravo", AgentType::Codex);
+        assert_eq!(
+            row(&session, None, now(), &Currency::usd()),
+            ["bravo [Codex]", "idle", "—", "—", "—", "—", "—"]
+        );
+    }
+
+    #[test]
+    fn render_aligns_columns_under_the_header() {
+        let rows = [
+            row(
+                &make_session("alpha", AgentType::Claude),
+                Some(&stats()),
+                now(),
+                &Currency::usd(),
+            ),
+            row(
+                &make_session("b", AgentType::Gemini),
+                None,
+                now(),
+                &Currency::usd(),
+            ),
+        ];
+        let table = render(&rows, None);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "SESSION         STATUS  TOKENS   COST  EDITS/BASH  FILES     AGE",
+                "alpha [Claude]  idle      1.2M  $6.00         4/7      2  1h 30m",
+                "b [Gemini]      idle         —      —           —      —       —",
+            ]
+        );
+    }
+
+    #[test]
+    fn render_drops_least_important_columns_to_fit() {
+        let rows = [row(
+            &make_session("alpha", AgentType::Claude),
+            Some(&stats()),
+            now(),
+            &Currency::usd(),
+        )];
+        let table = render(&rows, Some(40));
+        assert_eq!(
+            table.lines().collect::<Vec<_>>(),
+            [
+                "SESSION         STATUS  TOKENS   COST",
+                "alpha [Claude]  idle      1.2M  $6.00",
+            ]
+        );
+        for line in table.lines() {
+            assert!(line.width() <= 40, "{line}");
+        }
+    }
+
+    #[test]
+    fn render_aligns_wide_session_names_by_display_width() {
+        let rows = [
+            row(
+                &make_session("日本語", AgentType::Claude),
+                None,
+                now(),
+                &Currency::usd(),
+            ),
+            row(
+                &make_session("abc", AgentType::Claude),
+                None,
+                now(),
+                &Currency::usd(),
+            ),
+        ];
+        let table = render(&rows, None);
+        let status_col: Vec<usize> = table
+            .lines()
+            .map(|line| {
+                let at = line.find("STATUS").or_else(|| line.find("idle")).unwrap();
+                line[..at].width()
+            })
+            .collect();
+        assert_eq!(status_col, [17, 17, 17]);
+
+        let table = render(&rows, Some(12));
+        for line in table.lines() {
+            assert!(line.width() <= 12, "{line}");
+        }
+        assert_eq!(table.lines().nth(1), Some("日本語 [Cla…"));
+    }
+
+    #[test]
+    fn render_cuts_the_session_column_when_nothing_else_is_left() {
+        let rows = [row(
+            &make_session("a-very-long-session-name", AgentType::Claude),
+            None,
+            now(),
+            &Currency::usd(),
+        )];
+        let table = render(&rows, Some(12));
+        assert_eq!(
+            table.lines().collect::<Vec<_>>(),
+            ["SESSION", "a-very-long…"]
+        );
+    }
+}
@@ -1,5 +1,5 @@
-use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use anyhow::{bail, Context, Result};
+use clap::{CommandFactory, Parser, Subcommand};
 use crossterm::{
     event::{
         DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
@@ -10,28 +10,56 @@ use crossterm::{
 };
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
-use std::io;
-use std::time::Duration;
+use std::collections::HashSet;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 use std::sync::Arc;
 
+mod commands;
+
+use commands::diff_files::cmd_diff_files;
+use commands::ls::{cmd_ls, format_tags};
+use commands::serve::cmd_serve;
+use commands::stats::{
+    cmd_report, cmd_stats, cmd_stats_csv, cmd_stats_markdown, cmd_stats_summary,
+};
+use commands::transcript::{cmd_export, cmd_feed, cmd_open_log, cmd_replay};
 use hydra::app::{Mode, StateSnapshot, UiApp};
 use hydra::backend::Backend;
+use hydra::cli_style::{self, Paint};
 use hydra::event::{Event, EventHandler};
 use hydra::session::{self, project_id, AgentType};
+use hydra::system::process;
 use hydra::tmux::SessionManager;
 use hydra::tmux_control::{ControlModeSessionManager, TmuxControlConnection};
-use hydra::{manifest, tmux, ui};
+use hydra::{config, export, logs, manifest, migrate, proc_tree, tmux, ui, worktree};
 
 const EVENT_TICK_RATE: Duration = Duration::from_millis(50);
 
+/// How long quitting the TUI waits for the backend to finish its queue.
+const BACKEND_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
 const GITHUB_REPO_URL: &str = "https://github.com/rencryptofish/hydra.git";
 
+/// GitHub API listing of releases, newest first.
+const GITHUB_RELEASES_API: &str = "https://api.github.com/repos/rencryptofish/hydra/releases";
+
 #[derive(Parser)]
 #[command(name = "hydra", version, about = "AI Agent tmux session manager")]
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
+    /// Disable colored output (also honored via the NO_COLOR env var)
+    #[arg(long, global = true)]
+    no_color: bool,
+    /// Drive tmux on USER@HOST over ssh (also set via HYDRA_TMUX_SSH)
+    #[arg(long, global = true, value_name = "USER@HOST")]
+    host: Option<String>,
+    /// Only print errors and requested output, no progress or confirmations
+    #[arg(short, long, global = true)]
+    quiet: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -42,21 +70,212 @@ enum Commands {
         agent: String,
         /// Session name
         name: String,
+        /// Print the agent launch command and exit without creating the session
+        #[arg(long)]
+        print_command: bool,
+        /// Tag the session (repeatable)
+        #[arg(long = "tag", value_name = "TAG", value_parser = parse_tag)]
+        tags: Vec<String>,
+        /// Apply a pane layout preset from `layouts` in ~/.hydra/config.json
+        #[arg(long, value_name = "PRESET")]
+        layout: Option<String>,
+        /// Create N sessions named NAME-1 through NAME-N
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..=MAX_BATCH_SIZE))]
+        count: Option<u32>,
+        /// Type this file's contents into the agent once it starts, then Enter
+        #[arg(long, value_name = "FILE")]
+        template: Option<PathBuf>,
+        /// Start the agent in a new git worktree on BRANCH (created if missing)
+        #[arg(long, value_name = "BRANCH", conflicts_with = "count")]
+        worktree: Option<String>,
     },
     /// Kill a session
     Kill {
         /// Session name
         name: String,
+        /// Also remove the git worktree made by `hydra new --worktree`,
+        /// without asking
+        #[arg(long)]
+        remove_worktree: bool,
+    },
+    /// Kill a session's tmux session but keep its record, to resume later
+    Stop {
+        /// Session name
+        name: String,
+    },
+    /// Restart a stopped session, resuming its conversation
+    Resume {
+        /// Session name
+        name: String,
     },
     /// List sessions for the current project
-    Ls,
+    Ls {
+        /// Only show sessions with this tag
+        #[arg(long, value_parser = parse_tag)]
+        tag: Option<String>,
+        /// Show a table of status, tokens, cost, activity, files and age
+        #[arg(short, long)]
+        long: bool,
+    },
+    /// Add or remove tags on a session
+    Tag {
+        /// Session name
+        name: String,
+        /// Tags to add
+        #[arg(value_parser = parse_tag)]
+        tags: Vec<String>,
+        /// Tags to remove (repeatable)
+        #[arg(long, value_name = "TAG", value_parser = parse_tag)]
+        remove: Vec<String>,
+    },
+    /// Show, set or clear a session's note
+    Note {
+        /// Session name
+        name: String,
+        /// Note text; omit to print the current note
+        text: Vec<String>,
+        /// Remove the note
+        #[arg(long, conflicts_with = "text")]
+        clear: bool,
+    },
+    /// Print the log file hydra reads for a session
+    OpenLog {
+        /// Session name
+        name: String,
+    },
+    /// Show the process tree hydra walks to find a session's log
+    Tree {
+        /// Session name
+        name: String,
+    },
+    /// Compare the files two sessions have touched
+    DiffFiles {
+        /// First session name
+        a: String,
+        /// Second session name
+        b: String,
+    },
+    /// Print a session's conversation transcript
+    Export {
+        /// Session name
+        name: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = export::Format::Md)]
+        format: export::Format,
+        /// Replace the home directory with `~` and the project path with `<project>`
+        #[arg(long)]
+        redact: bool,
+    },
+    /// Replay a session's transcript with its original pacing
+    Replay {
+        /// Session name
+        name: String,
+        /// Playback speed multiplier (2 = twice as fast)
+        #[arg(long, default_value_t = 1.0, value_parser = parse_speed)]
+        speed: f64,
+        /// Replace the home directory with `~` and the project path with `<project>`
+        #[arg(long)]
+        redact: bool,
+    },
+    /// Follow new activity from all project sessions in one stream
+    Feed,
+    /// Serve stats as JSON over HTTP (`/stats`, `/sessions`)
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = 8787)]
+        port: u16,
+        /// Address to bind; defaults to localhost only
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+    },
+    /// Show whether daily stats collection is paused, or pause/resume it
+    Stats {
+        /// Stop adding new usage to the daily totals
+        #[arg(long, conflicts_with = "resume")]
+        pause: bool,
+        /// Start counting usage again (the paused interval stays uncounted)
+        #[arg(long)]
+        resume: bool,
+        /// Print today's usage as CSV: date,provider,tokens_in,tokens_out,cost_usd
+        #[arg(long, conflicts_with_all = ["pause", "resume"])]
+        csv: bool,
+        /// Break down today's usage by session: top sessions, averages, percentiles
+        #[arg(long, conflicts_with_all = ["pause", "resume", "csv"])]
+        summary: bool,
+        /// Print this project's sessions as a Markdown table: session, status, tokens, cost, age
+        #[arg(long, conflicts_with_all = ["pause", "resume", "csv", "summary"])]
+        markdown: bool,
+        /// How many sessions `--summary` lists
+        #[arg(long, value_name = "N", default_value_t = 5, requires = "summary")]
+        top: usize,
+    },
+    /// Sum usage per provider over a date range by scanning every log file
+    Report {
+        /// First day to count (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        from: chrono::NaiveDate,
+        /// Last day to count, inclusive (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        to: chrono::NaiveDate,
+    },
+    /// Remove abandoned temp files and manifest records that can't be revived
+    Clean {
+        /// Clean every project under ~/.hydra and delete ones left empty
+        #[arg(long)]
+        all: bool,
+    },
+    /// Adopt tmux sessions started outside hydra into this project
+    Migrate {
+        /// Also adopt sessions whose tmux name matches this glob (`*`, `?`)
+        #[arg(long, value_name = "GLOB")]
+        pattern: Option<String>,
+        /// Show what would be adopted without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Print version information
+    Version {
+        /// Include build, platform and tool versions for bug reports
+        #[arg(long)]
+        full: bool,
+    },
     /// Update hydra to the latest version from GitHub
-    Update,
+    Update {
+        /// Run even when invoked via sudo on a user-writable install
+        #[arg(long)]
+        force: bool,
+        /// Also consider prereleases when picking the release to install
+        #[arg(long)]
+        prerelease: bool,
+    },
+    /// Print a shell completion script
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    cli_style::init(cli.no_color);
+    cli_style::set_quiet(cli.quiet);
+    tmux::init_ssh_target(cli.host.as_deref());
+    let user_config = match config::try_load_config(&manifest::default_base_dir()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                cli_style::banner("warning", &format!("{e}; using default settings"))
+            );
+            config::Config::default()
+        }
+    };
+    logs::set_lsof_timeout(user_config.lsof_timeout());
+    logs::set_subagent_transcripts(user_config.subagent_transcripts);
+    logs::set_conversation_tail_seed(user_config.preview_tail_seed());
+    logs::set_token_format(user_config.token_format());
 
     let cwd = std::env::current_dir()
         .context("Failed to get current directory")?
@@ -65,62 +284,925 @@ async fn main() -> Result<()> {
     let pid = project_id(&cwd);
 
     match cli.command {
-        Some(Commands::New { agent, name }) => cmd_new(&pid, &name, &agent, &cwd).await,
-        Some(Commands::Kill { name }) => cmd_kill(&pid, &name).await,
-        Some(Commands::Ls) => cmd_ls(&pid).await,
-        Some(Commands::Update) => cmd_update().await,
-        None => run_tui(pid, cwd).await,
+        Some(Commands::New {
+            agent,
+            name,
+            print_command,
+            tags,
+            layout,
+            count,
+            template,
+            worktree,
+        }) => {
+            let opts = NewOptions {
+                print_command,
+                tags: &tags,
+                layout: layout.as_deref(),
+                count,
+                template: template.as_deref(),
+                worktree: worktree.as_deref(),
+            };
+            cmd_new(&pid, &name, &agent, &cwd, &user_config, opts).await
+        }
+        Some(Commands::Kill {
+            name,
+            remove_worktree,
+        }) => cmd_kill(&pid, &cwd, &name, remove_worktree).await,
+        Some(Commands::Stop { name }) => cmd_stop(&pid, &name).await,
+        Some(Commands::Resume { name }) => cmd_resume(&pid, &name, &user_config).await,
+        Some(Commands::Ls { tag, long }) => {
+            cmd_ls(&pid, &cwd, tag.as_deref(), long, &user_config).await
+        }
+        Some(Commands::Tag { name, tags, remove }) => cmd_tag(&pid, &name, &tags, &remove).await,
+        Some(Commands::Note { name, text, clear }) => cmd_note(&pid, &name, &text, clear).await,
+        Some(Commands::OpenLog { name }) => cmd_open_log(&pid, &cwd, &name).await,
+        Some(Commands::Tree { name }) => cmd_tree(&pid, &name).await,
+        Some(Commands::DiffFiles { a, b }) => {
+            cmd_diff_files(&pid, &cwd, &a, &b, &user_config).await
+        }
+        Some(Commands::Export {
+            name,
+            format,
+            redact,
+        }) => cmd_export(&pid, &cwd, &name, format, redact, &user_config).await,
+        Some(Commands::Replay {
+            name,
+            speed,
+            redact,
+        }) => cmd_replay(&pid, &cwd, &name, speed, redact, &user_config).await,
+        Some(Commands::Feed) => cmd_feed(&pid, &cwd, &user_config).await,
+        Some(Commands::Serve { port, bind }) => {
+            cmd_serve(&pid, &cwd, &user_config, &bind, port).await
+        }
+        Some(Commands::Stats {
+            pause,
+            resume,
+            csv,
+            summary,
+            top,
+            markdown,
+        }) => {
+            if csv {
+                cmd_stats_csv(&user_config)
+            } else if summary {
+                cmd_stats_summary(&pid, &cwd, top, &user_config).await
+            } else if markdown {
+                cmd_stats_markdown(&pid, &cwd, &user_config).await
+            } else {
+                cmd_stats(pause, resume)
+            }
+        }
+        Some(Commands::Report { from, to }) => cmd_report(&user_config, from, to),
+        Some(Commands::Clean { all }) => cmd_clean(&pid, all).await,
+        Some(Commands::Migrate { pattern, dry_run }) => {
+            cmd_migrate(&pid, pattern.as_deref(), dry_run).await
+        }
+        Some(Commands::Version { full }) => cmd_version(full).await,
+        Some(Commands::Update { force, prerelease }) => cmd_update(force, prerelease).await,
+        Some(Commands::Completions { shell }) => {
+            print!("{}", completion_script(shell));
+            Ok(())
+        }
+        None => {
+            if !confirm_not_nested(&pid).await? {
+                return Ok(());
+            }
+            run_tui(pid, cwd).await
+        }
     }
 }
 
-async fn cmd_new(project_id: &str, name: &str, agent_str: &str, cwd: &str) -> Result<()> {
+/// Warn when launched from inside one of this project's own sessions, where
+/// the TUI would preview and capture the pane it is drawing in, and ask
+/// whether to go on. Returns false if the user declines.
+async fn confirm_not_nested(project_id: &str) -> Result<bool> {
+    let tmux_env = std::env::var("TMUX").ok();
+    if tmux_env.as_deref().is_none_or(str::is_empty) {
+        return Ok(true);
+    }
+    let current = tmux::current_session_name().await;
+    let manifest = manifest::load_manifest(&manifest::default_base_dir(), project_id).await;
+    let Some(name) = manifest::enclosing_session(
+        tmux_env.as_deref(),
+        current.as_deref(),
+        project_id,
+        &manifest,
+    ) else {
+        return Ok(true);
+    };
+    eprintln!(
+        "{}",
+        cli_style::banner(
+            "warning",
+            &format!(
+                "hydra is running inside its own session '{name}'; the TUI will preview itself"
+            )
+        )
+    );
+    eprint!("Continue anyway? [y/N] ");
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(is_yes(&answer))
+}
+
+/// Whether a prompt answer is `y` or `yes`, ignoring case and whitespace.
+fn is_yes(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+struct NewOptions<'a> {
+    print_command: bool,
+    tags: &'a [String],
+    layout: Option<&'a str>,
+    count: Option<u32>,
+    template: Option<&'a Path>,
+    worktree: Option<&'a str>,
+}
+
+/// Most sessions a single `hydra new --count` may create.
+const MAX_BATCH_SIZE: i64 = 50;
+
+/// Largest prompt file `hydra new --template` will type into an agent.
+const MAX_TEMPLATE_BYTES: usize = 256 * 1024;
+
+/// How long `hydra new --template` waits for the agent's first screen.
+const TEMPLATE_READY_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Prompt text to send for a template file: line endings normalized to
+/// `\n` and trailing blank lines dropped, since Enter is sent separately.
+fn template_prompt(content: &str) -> Result<String> {
+    if content.len() > MAX_TEMPLATE_BYTES {
+        bail!(
+            "template is {} KiB; the limit is {} KiB",
+            content.len() / 1024,
+            MAX_TEMPLATE_BYTES / 1024
+        );
+    }
+    let prompt = content.replace("\r\n", "\n");
+    let prompt = prompt.trim_end();
+    if prompt.is_empty() {
+        bail!("template is empty");
+    }
+    Ok(prompt.to_string())
+}
+
+/// Read and prepare a `--template` file, returning its absolute path (kept
+/// on the session record) and the prompt to send.
+fn load_template(path: &Path) -> Result<(String, String)> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read template {}", path.display()))?;
+    let prompt =
+        template_prompt(&content).with_context(|| format!("Bad template {}", path.display()))?;
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    Ok((path.to_string_lossy().into_owned(), prompt))
+}
+
+async fn cmd_new(
+    project_id: &str,
+    name: &str,
+    agent_str: &str,
+    cwd: &str,
+    user_config: &config::Config,
+    opts: NewOptions<'_>,
+) -> Result<()> {
     let agent: AgentType = agent_str.parse()?;
-    let record = manifest::SessionRecord::for_new_session(name, &agent, cwd);
-    let cmd = record.create_command();
+    let layout = opts
+        .layout
+        .map(|preset| {
+            user_config.layouts.get(preset).with_context(|| {
+                format!(
+                    "Unknown layout preset '{preset}' (define it under \"layouts\" in config.json)"
+                )
+            })
+        })
+        .transpose()?;
+    let template = opts.template.map(load_template).transpose()?;
+    let names = match opts.count {
+        Some(count) => batch_names(name, count),
+        None => vec![name.to_string()],
+    };
+    let new_record = |name: &str, cwd: &str| {
+        let mut record = manifest::SessionRecord::for_new_session(name, &agent, cwd);
+        record.add_tags(opts.tags);
+        record.layout = opts.layout.map(str::to_string);
+        record.template = template.as_ref().map(|(path, _)| path.clone());
+        record.worktree = opts.worktree.is_some();
+        record
+    };
+    let prompt = template.as_ref().map(|(_, prompt)| prompt.as_str());
+    if opts.print_command {
+        // Dry run: no tmux session, no manifest entry, no worktree.
+        for name in &names {
+            println!("{}", new_record(name, cwd).create_command());
+        }
+        return Ok(());
+    }
     let base_dir = manifest::default_base_dir();
+    let project_cwd = cwd;
+    let worktree = match opts.worktree {
+        Some(branch) => {
+            let worktree = worktree::create(&worktree::SystemGit, Path::new(cwd), branch).await?;
+            cli_style::info(format!(
+                "Created worktree: {} (branch {branch})",
+                worktree.path.display()
+            ));
+            Some(worktree)
+        }
+        None => None,
+    };
+    let worktree_cwd = worktree
+        .as_ref()
+        .map(|w| w.path.to_string_lossy().into_owned());
+    let cwd = worktree_cwd.as_deref().unwrap_or(cwd);
+
+    let result = create_in_order(&names, |name| {
+        let record = new_record(&name, cwd);
+        let (base_dir, agent) = (&base_dir, &agent);
+        async move {
+            create_named_session(project_id, cwd, base_dir, agent, record, layout, prompt).await
+        }
+    })
+    .await;
+    let Err((err, created)) = result else {
+        report_launch_errors(
+            project_id,
+            &base_dir,
+            &names,
+            user_config.launch_check_after(),
+        )
+        .await;
+        return Ok(());
+    };
+    for name in &created {
+        let tmux_name = session::tmux_session_name(project_id, name);
+        let _ = tmux::kill_session(&tmux_name).await;
+        let _ = manifest::remove_session(&base_dir, project_id, name).await;
+    }
+    if let Some(worktree) = &worktree {
+        let project_cwd = Path::new(project_cwd);
+        if let Err(e) = worktree::roll_back(&worktree::SystemGit, project_cwd, worktree).await {
+            eprintln!(
+                "{}",
+                cli_style::banner("warning", &format!("worktree not rolled back: {e}"))
+            );
+        }
+    }
+    if names.len() == 1 {
+        return Err(err);
+    }
+    let failed = &names[created.len()];
+    let rolled_back = if created.is_empty() {
+        String::new()
+    } else {
+        format!(" (rolled back {})", created.join(", "))
+    };
+    Err(err.context(format!("Failed to create '{failed}'{rolled_back}")))
+}
+
+/// After `wait`, warn about each freshly launched session whose agent has
+/// already died, and record why on its manifest record (clearing any
+/// earlier error otherwise). One wait covers every session; `None` skips
+/// the check.
+async fn report_launch_errors(
+    project_id: &str,
+    base_dir: &Path,
+    names: &[String],
+    mut wait: Option<Duration>,
+) {
+    for name in names {
+        let error = match wait {
+            Some(delay) => {
+                let tmux_name = session::tmux_session_name(project_id, name);
+                wait = Some(Duration::ZERO);
+                tmux::check_launch(&tmux_name, delay).await
+            }
+            None => None,
+        };
+        if let Some(error) = &error {
+            eprintln!(
+                "{}",
+                cli_style::banner(
+                    "warning",
+                    &format!(
+                        "'{name}' exited at launch: {}",
+                        tmux::launch_error_summary(error)
+                    )
+                )
+            );
+        }
+        let _ = manifest::set_launch_error(base_dir, project_id, name, error).await;
+    }
+}
+
+/// `base-1` through `base-N`.
+fn batch_names(base: &str, count: u32) -> Vec<String> {
+    (1..=count).map(|i| format!("{base}-{i}")).collect()
+}
+
+/// Run `create` for each name in order, stopping at the first failure. On
+/// failure, returns the error and the names already created, newest first,
+/// so the caller can roll them back. The failed name is `names[created.len()]`.
+async fn create_in_order<F, Fut>(
+    names: &[String],
+    mut create: F,
+) -> std::result::Result<(), (anyhow::Error, Vec<String>)>
+where
+    F: FnMut(String) -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    let mut created = Vec::new();
+    for name in names {
+        if let Err(e) = create(name.clone()).await {
+            created.reverse();
+            return Err((e, created));
+        }
+        created.push(name.clone());
+    }
+    Ok(())
+}
+
+/// Create one session's tmux session and manifest record. A record that
+/// can't be saved takes its fresh tmux session down with it.
+async fn create_named_session(
+    project_id: &str,
+    cwd: &str,
+    base_dir: &std::path::Path,
+    agent: &AgentType,
+    record: manifest::SessionRecord,
+    layout: Option<&Vec<config::LayoutStep>>,
+    prompt: Option<&str>,
+) -> Result<()> {
+    let name = record.name.clone();
+    let cmd = record.create_command();
+    let tmux_name = session::tmux_session_name(project_id, &name);
+    let sessions = manifest::load_manifest(base_dir, project_id).await.sessions;
+    if let Some(other) = session::tmux_name_clash(&name, sessions.keys()) {
+        bail!("Session name '{name}' clashes with '{other}': both map to tmux session {tmux_name}");
+    }
+    let has_record = sessions.contains_key(&name);
+    let pane_dead = pane_dead(&tmux_name).await;
+    match plan_new_session(has_record, pane_dead) {
+        NewSessionPlan::Create => {}
+        NewSessionPlan::ReplaceStale => {
+            if pane_dead.is_some() {
+                tmux::kill_session(&tmux_name).await?;
+            }
+            cli_style::info(format!("Replacing stale session '{name}'"));
+        }
+        NewSessionPlan::AlreadyRunning => {
+            bail!("Session '{name}' is already running (use `hydra kill {name}` first)")
+        }
+    }
 
-    let tmux_name = tmux::create_session(project_id, name, &agent, cwd, Some(&cmd)).await?;
-    manifest::add_session(&base_dir, project_id, record).await?;
-    println!("Created session: {tmux_name}");
+    let tmux_name = tmux::create_session(project_id, &name, agent, cwd, Some(&cmd)).await?;
+    if let Err(e) = manifest::add_session(base_dir, project_id, record).await {
+        let _ = tmux::kill_session(&tmux_name).await;
+        return Err(e);
+    }
+    cli_style::info(format!("Created session: {tmux_name}"));
+    if let Some(steps) = layout {
+        if let Err(e) = tmux::apply_layout(&tmux_name, cwd, steps).await {
+            eprintln!(
+                "{}",
+                cli_style::banner("warning", &format!("layout not fully applied: {e}"))
+            );
+        }
+    }
+    if let Some(prompt) = prompt {
+        if let Err(e) = send_template(&tmux_name, prompt).await {
+            eprintln!(
+                "{}",
+                cli_style::banner("warning", &format!("template not sent: {e}"))
+            );
+        }
+    }
     Ok(())
 }
 
-async fn cmd_kill(project_id: &str, name: &str) -> Result<()> {
+/// `None` when `tmux_name` has no tmux session, otherwise whether its pane
+/// has exited.
+async fn pane_dead(tmux_name: &str) -> Option<bool> {
+    tmux::SessionManager::batch_pane_status(&tmux::TmuxSessionManager::new())
+        .await
+        .and_then(|panes| panes.get(tmux_name).map(|&(dead, _)| dead))
+}
+
+/// Type `prompt` into a fresh session once its agent has drawn a screen.
+async fn send_template(tmux_name: &str, prompt: &str) -> Result<()> {
+    if !tmux::wait_for_settled_pane(tmux_name, TEMPLATE_READY_TIMEOUT).await {
+        bail!(
+            "agent did not start within {}s",
+            TEMPLATE_READY_TIMEOUT.as_secs()
+        );
+    }
+    tmux::send_text_enter(tmux_name, prompt).await
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum NewSessionPlan {
+    /// No record and no tmux session: create from scratch.
+    Create,
+    /// A manifest record or exited pane remains from a dead session: clear
+    /// it and create a fresh one under the same name.
+    ReplaceStale,
+    /// The name belongs to a session whose agent is still running.
+    AlreadyRunning,
+}
+
+/// Decide how `hydra new` handles an existing name. `pane_dead` is `None`
+/// when no tmux session exists, otherwise whether its pane has exited.
+fn plan_new_session(has_record: bool, pane_dead: Option<bool>) -> NewSessionPlan {
+    match (has_record, pane_dead) {
+        (_, Some(false)) => NewSessionPlan::AlreadyRunning,
+        (true, _) | (false, Some(true)) => NewSessionPlan::ReplaceStale,
+        (false, None) => NewSessionPlan::Create,
+    }
+}
+
+async fn cmd_kill(project_id: &str, cwd: &str, name: &str, remove_worktree: bool) -> Result<()> {
     let tmux_name = session::tmux_session_name(project_id, name);
-    tmux::kill_session(&tmux_name).await?;
     let base_dir = manifest::default_base_dir();
+    let record = manifest::load_manifest(&base_dir, project_id)
+        .await
+        .sessions
+        .remove(name);
+    let stopped = record.as_ref().is_some_and(|r| r.stopped);
+    let worktree = record.filter(|r| r.worktree).map(|r| r.cwd);
+    if remove_worktree && worktree.is_none() {
+        bail!("Session '{name}' was not started in a worktree (`hydra new --worktree`)");
+    }
+    // A stopped session has no tmux session left; killing just drops its record.
+    if !stopped || pane_dead(&tmux_name).await.is_some() {
+        tmux::kill_session(&tmux_name).await?;
+    }
     let _ = manifest::remove_session(&base_dir, project_id, name).await;
-    println!("Killed session: {tmux_name}");
+    cli_style::info(format!("Killed session: {tmux_name}"));
+    let Some(path) = worktree else {
+        return Ok(());
+    };
+    if !remove_worktree && !confirm_remove_worktree(&path)? {
+        cli_style::info(format!(
+            "Kept worktree {path} (remove it with `git worktree remove {path}`)"
+        ));
+        return Ok(());
+    }
+    worktree::remove(&worktree::SystemGit, Path::new(cwd), Path::new(&path))
+        .await
+        .context("Session killed, but its worktree was kept")?;
+    cli_style::info(format!("Removed worktree: {path}"));
+    Ok(())
+}
+
+/// Ask whether to remove a killed session's worktree too. Without a
+/// terminal to ask on the worktree is kept; `--remove-worktree` skips the
+/// question.
+fn confirm_remove_worktree(path: &str) -> Result<bool> {
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    eprint!("Remove its worktree {path} too? The branch is kept. [y/N] ");
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(is_yes(&answer))
+}
+
+async fn cmd_stop(project_id: &str, name: &str) -> Result<()> {
+    let tmux_name = session::tmux_session_name(project_id, name);
+    let base_dir = manifest::default_base_dir();
+    // Marked first so the TUI never revives it in between; errors if unknown.
+    manifest::set_session_stopped(&base_dir, project_id, name, true).await?;
+    if pane_dead(&tmux_name).await.is_some() {
+        if let Err(e) = tmux::kill_session(&tmux_name).await {
+            let _ = manifest::set_session_stopped(&base_dir, project_id, name, false).await;
+            return Err(e);
+        }
+    }
+    cli_style::info(format!(
+        "Stopped session: {tmux_name} (resume with `hydra resume {name}`)"
+    ));
+    Ok(())
+}
+
+async fn cmd_resume(project_id: &str, name: &str, user_config: &config::Config) -> Result<()> {
+    let base_dir = manifest::default_base_dir();
+    let record = manifest::load_manifest(&base_dir, project_id)
+        .await
+        .sessions
+        .remove(name)
+        .with_context(|| format!("No session named '{name}' in this project"))?;
+    let tmux_name = session::tmux_session_name(project_id, name);
+    if pane_dead(&tmux_name).await.is_some() {
+        bail!("Session '{name}' is already running");
+    }
+    let agent: AgentType = record.agent_type.parse()?;
+    let tmux_name = tmux::create_session(
+        project_id,
+        name,
+        &agent,
+        &record.cwd,
+        Some(&record.resume_command()),
+    )
+    .await?;
+    manifest::set_session_stopped(&base_dir, project_id, name, false).await?;
+    cli_style::info(format!("Resumed session: {tmux_name}"));
+    report_launch_errors(
+        project_id,
+        &base_dir,
+        &[name.to_string()],
+        user_config.launch_check_after(),
+    )
+    .await;
+    if let Some(steps) = record
+        .layout
+        .as_ref()
+        .and_then(|l| user_config.layouts.get(l))
+    {
+        if let Err(e) = tmux::apply_layout(&tmux_name, &record.cwd, steps).await {
+            eprintln!(
+                "{}",
+                cli_style::banner("warning", &format!("layout not fully applied: {e}"))
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn cmd_tag(project_id: &str, name: &str, add: &[String], remove: &[String]) -> Result<()> {
+    let base_dir = manifest::default_base_dir();
+    let tags = manifest::update_session_tags(&base_dir, project_id, name, add, remove).await?;
+    if tags.is_empty() {
+        println!("{name}: no tags");
+    } else {
+        println!("{name}: {}", format_tags(&tags));
+    }
+    Ok(())
+}
+
+async fn cmd_note(project_id: &str, name: &str, text: &[String], clear: bool) -> Result<()> {
+    let base_dir = manifest::default_base_dir();
+    let note = if text.is_empty() && !clear {
+        let manifest = manifest::load_manifest(&base_dir, project_id).await;
+        let record = manifest
+            .sessions
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("No session named '{name}' in this project"))?;
+        record.note.clone()
+    } else {
+        manifest::set_session_note(&base_dir, project_id, name, &text.join(" ")).await?
+    };
+    match note {
+        Some(note) => println!("{name}: {note}"),
+        None => println!("{name}: no note"),
+    }
     Ok(())
 }
 
-async fn cmd_ls(project_id: &str) -> Result<()> {
+async fn cmd_tree(project_id: &str, name: &str) -> Result<()> {
+    if tmux::ssh_target().is_some() {
+        bail!("hydra tree needs local tmux: remote pane PIDs mean nothing here");
+    }
     let manager = tmux::TmuxSessionManager::new();
-    let sessions = tmux::SessionManager::list_sessions(&manager, project_id).await?;
-    if sessions.is_empty() {
-        println!("No sessions for this project.");
+    let sessions =
+        manifest::list_named_sessions(&manager, &manifest::default_base_dir(), project_id).await?;
+    let Some(session) = sessions.into_iter().find(|s| s.name == name) else {
+        bail!("No session named '{name}' in this project");
+    };
+    let Some(pane_pid) = logs::get_pane_pid(&session.tmux_name).await else {
+        bail!("No pane PID for '{name}'; is it running?");
+    };
+
+    let mut processes = Vec::new();
+    for pid in logs::collect_descendant_pids(pane_pid).await {
+        processes.push(proc_tree::Process {
+            pid,
+            ppid: process::process_parent(pid).await,
+            command: process::process_command(pid).await,
+        });
+    }
+    let source = proc_tree::session_id_source(&processes);
+    print!(
+        "{}",
+        proc_tree::render(&processes, source.as_ref().map(|(pid, _)| *pid))
+    );
+    println!(
+        "{}",
+        proc_tree::session_id_summary(&session.agent_type, source.as_ref())
+    );
+    Ok(())
+}
+
+/// Normalize a tag argument, rejecting input that can't name a tag
+/// instead of silently dropping it (or, for `ls --tag`, listing every
+/// session).
+fn parse_tag(s: &str) -> Result<String, String> {
+    manifest::normalize_tag(s).ok_or_else(|| format!("'{s}' is not a valid tag"))
+}
+
+fn parse_speed(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(speed) if speed.is_finite() && speed > 0.0 => Ok(speed),
+        _ => Err(format!("'{s}' is not a positive number")),
+    }
+}
+
+async fn cmd_clean(project_id: &str, all: bool) -> Result<()> {
+    let base_dir = manifest::default_base_dir();
+    let ids = if all {
+        manifest::project_ids(&base_dir).await
     } else {
-        for s in &sessions {
-            println!("{} [{}]", s.name, s.agent_type);
+        vec![project_id.to_string()]
+    };
+    let manager = tmux::TmuxSessionManager::new();
+    let mut projects = Vec::with_capacity(ids.len());
+    for id in ids {
+        let live: HashSet<String> = manifest::list_named_sessions(&manager, &base_dir, &id)
+            .await?
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        projects.push((id, live));
+    }
+
+    let report = manifest::clean(&base_dir, &projects, all, SystemTime::now()).await?;
+    if report.is_empty() {
+        cli_style::info("Nothing to clean.");
+        return Ok(());
+    }
+    for path in &report.files {
+        cli_style::info(format!("Removed temp file {}", path.display()));
+    }
+    for (project, name) in &report.records {
+        cli_style::info(format!("Removed stale session record {name} ({project})"));
+    }
+    for project in &report.projects {
+        cli_style::info(format!("Removed empty project {project}"));
+    }
+    for (project, err) in &report.unreadable {
+        eprintln!("Skipped {project}: {err}");
+    }
+    Ok(())
+}
+
+/// How deep `hydra migrate` looks below a pane's shell for the agent.
+const MIGRATE_PROCESS_DEPTH: usize = 4;
+const MIGRATE_MAX_PIDS: usize = 32;
+
+async fn cmd_migrate(project_id: &str, pattern: Option<&str>, dry_run: bool) -> Result<()> {
+    let panes = tmux::list_session_panes().await;
+    let live: HashSet<String> = panes.iter().map(|p| p.tmux_name.clone()).collect();
+    let mut candidates = Vec::with_capacity(panes.len());
+    for pane in panes {
+        let mut cmdlines = Vec::new();
+        // Remote panes' PIDs mean nothing locally; the process name is all we have.
+        if let (Some(pid), None) = (pane.pane_pid, tmux::ssh_target()) {
+            for pid in
+                process::collect_descendant_pids(pid, MIGRATE_PROCESS_DEPTH, MIGRATE_MAX_PIDS).await
+            {
+                cmdlines.extend(process::process_command(pid).await);
+            }
         }
+        cmdlines.push(pane.command);
+        candidates.push(migrate::Candidate {
+            tmux_name: pane.tmux_name,
+            cwd: pane.cwd,
+            cmdlines,
+        });
+    }
+
+    let base_dir = manifest::default_base_dir();
+    let known: HashSet<String> = manifest::load_manifest(&base_dir, project_id)
+        .await
+        .sessions
+        .into_keys()
+        .collect();
+    let plan = migrate::plan(&candidates, project_id, pattern, &known, &live);
+    for (tmux_name, reason) in &plan.skipped {
+        cli_style::info(cli_style::paint(
+            &format!("Skipped {tmux_name}: {reason}"),
+            Paint::Dim,
+        ));
+    }
+    if plan.adopt.is_empty() {
+        cli_style::info("No sessions to adopt.");
+        return Ok(());
+    }
+
+    for adoption in &plan.adopt {
+        let new_tmux_name = adoption.new_tmux_name(project_id);
+        let label = format!(
+            "{} as {} [{}]",
+            adoption.tmux_name, adoption.name, adoption.agent
+        );
+        if dry_run {
+            println!("Would adopt {label}");
+            continue;
+        }
+        tmux::adopt_session(&adoption.tmux_name, &new_tmux_name, &adoption.agent).await?;
+        let mut record = manifest::SessionRecord::for_new_session(
+            &adoption.name,
+            &adoption.agent,
+            &adoption.cwd,
+        );
+        record.agent_session_id = adoption.agent_session_id.clone();
+        manifest::add_session(&base_dir, project_id, record).await?;
+        cli_style::info(format!("Adopted {label}"));
     }
     Ok(())
 }
 
-async fn cmd_update() -> Result<()> {
-    println!("Updating hydra from latest commit...");
+/// Completion script for `shell` covering every subcommand and flag.
+fn completion_script(shell: clap_complete::Shell) -> String {
+    let mut out = Vec::new();
+    clap_complete::generate(shell, &mut Cli::command(), "hydra", &mut out);
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+async fn cmd_version(full: bool) -> Result<()> {
+    if !full {
+        println!("hydra {}", env!("CARGO_PKG_VERSION"));
+        return Ok(());
+    }
+    let tmux = tool_version("tmux", "-V").await;
+    let curl = tool_version("curl", "--version").await;
+    print!("{}", full_version_report(tmux.as_deref(), curl.as_deref()));
+    Ok(())
+}
+
+/// Release asset name for this platform, e.g. `hydra-linux-x86_64`.
+fn platform_asset_name() -> String {
+    format!("hydra-{}-{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+/// Copy-pasteable build and environment details for bug reports.
+fn full_version_report(tmux: Option<&str>, curl: Option<&str>) -> String {
+    let missing = "not found";
+    format!(
+        "hydra {}\n\
+         commit:   {}\n\
+         target:   {}\n\
+         platform: {}\n\
+         tmux:     {}\n\
+         curl:     {}\n",
+        env!("CARGO_PKG_VERSION"),
+        env!("HYDRA_GIT_COMMIT"),
+        env!("HYDRA_TARGET"),
+        platform_asset_name(),
+        tmux.unwrap_or(missing),
+        curl.unwrap_or(missing),
+    )
+}
+
+/// First line of `<program> <flag>`, or `None` if it can't be run.
+async fn tool_version(program: &str, flag: &str) -> Option<String> {
+    let output = tokio::time::timeout(
+        Duration::from_secs(2),
+        tokio::process::Command::new(program).arg(flag).output(),
+    )
+    .await
+    .ok()?
+    .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .lines()
+        .next()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+}
+
+async fn cmd_update(force: bool, prerelease: bool) -> Result<()> {
+    if let Some(dir) = sudo_on_user_writable_install() {
+        if !force {
+            anyhow::bail!(
+                "refusing to update as root: {} is writable by your user, and a root-owned \
+                 binary there would block future updates. Re-run without sudo, or pass --force.",
+                dir.display()
+            );
+        }
+        let message = format!("updating as root; {} may end up root-owned", dir.display());
+        eprintln!("{}", cli_style::banner("warning", &message));
+    }
+
+    let tag = match fetch_releases() {
+        Ok(releases) => select_release(&releases, prerelease)
+            .and_then(|r| r["tag_name"].as_str())
+            .map(str::to_string),
+        Err(e) => {
+            let message = format!("could not list releases ({e}); using the latest commit");
+            eprintln!("{}", cli_style::banner("warning", &message));
+            None
+        }
+    };
+    match &tag {
+        Some(tag) => cli_style::info(format!("Updating hydra to {tag}...")),
+        None => cli_style::info("No matching release; updating hydra from latest commit..."),
+    }
     let status = std::process::Command::new("cargo")
-        .args(["install", "--git", GITHUB_REPO_URL, "hydra", "--locked"])
+        .args(cargo_install_args(tag.as_deref(), cli_style::quiet()))
         .env("CARGO_NET_GIT_FETCH_WITH_CLI", "true")
         .status()
         .context("Failed to run cargo — is cargo on PATH?")?;
     if !status.success() {
         anyhow::bail!("cargo install failed");
     }
-    println!("hydra updated successfully.");
+    cli_style::info("hydra updated successfully.");
     Ok(())
 }
 
+/// Arguments for the `cargo install` that performs `hydra update`. Quiet
+/// mode silences cargo's own Updating/Downloading/Compiling progress; its
+/// errors still reach stderr.
+fn cargo_install_args(tag: Option<&str>, quiet: bool) -> Vec<&str> {
+    let mut args = vec!["install", "--git", GITHUB_REPO_URL, "hydra", "--locked"];
+    if let Some(tag) = tag {
+        args.extend(["--tag", tag]);
+    }
+    if quiet {
+        args.push("--quiet");
+    }
+    args
+}
+
+/// Fetch the release list from the GitHub API.
+fn fetch_releases() -> Result<Vec<serde_json::Value>> {
+    let output = std::process::Command::new("curl")
+        .args([
+            "-fsSL",
+            "--max-time",
+            "10",
+            "-H",
+            "Accept: application/vnd.github+json",
+            GITHUB_RELEASES_API,
+        ])
+        .output()
+        .context("Failed to run curl — is curl on PATH?")?;
+    if !output.status.success() {
+        anyhow::bail!("curl exited with {}", output.status);
+    }
+    serde_json::from_slice(&output.stdout).context("unexpected release list format")
+}
+
+/// Newest installable release by `published_at`. Drafts and releases
+/// without a tag are never picked; prereleases only with `include_prerelease`.
+fn select_release(
+    releases: &[serde_json::Value],
+    include_prerelease: bool,
+) -> Option<&serde_json::Value> {
+    releases
+        .iter()
+        .filter(|r| r["tag_name"].as_str().is_some_and(|t| !t.is_empty()))
+        .filter(|r| !r["draft"].as_bool().unwrap_or(false))
+        .filter(|r| include_prerelease || !r["prerelease"].as_bool().unwrap_or(false))
+        .max_by_key(|r| {
+            r["published_at"]
+                .as_str()
+                .and_then(|ts| ts.parse::<chrono::DateTime<chrono::Utc>>().ok())
+        })
+}
+
+/// When running under `sudo` and the directory holding the current binary is
+/// writable by the invoking user, return that directory.
+#[cfg(unix)]
+fn sudo_on_user_writable_install() -> Option<std::path::PathBuf> {
+    use std::os::unix::fs::MetadataExt;
+
+    let sudo_uid = std::env::var("SUDO_UID").ok()?.parse().ok();
+    let output = std::process::Command::new("id").arg("-u").output().ok()?;
+    let euid: u32 = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .ok()?;
+    let dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+    let meta = std::fs::metadata(&dir).ok()?;
+
+    should_refuse_root_update(euid, sudo_uid, meta.uid(), meta.mode()).then_some(dir)
+}
+
+#[cfg(not(unix))]
+fn sudo_on_user_writable_install() -> Option<std::path::PathBuf> {
+    None
+}
+
+/// Whether an update running as `euid` should be refused because the original
+/// sudo user (`sudo_uid`) could write the install directory themselves.
+/// Only owner and world write bits are considered; group membership is not
+/// resolved.
+fn should_refuse_root_update(
+    euid: u32,
+    sudo_uid: Option<u32>,
+    dir_owner_uid: u32,
+    dir_mode: u32,
+) -> bool {
+    let Some(user) = sudo_uid else {
+        return false;
+    };
+    if euid != 0 || user == 0 {
+        return false;
+    }
+    let owner_writable = dir_owner_uid == user && dir_mode & 0o200 != 0;
+    let world_writable = dir_mode & 0o002 != 0;
+    owner_writable || world_writable
+}
+
 async fn run_tui(project_id: String, cwd: String) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
@@ -165,13 +1247,16 @@ async fn run_tui(project_id: String, cwd: String) -> Result<()> {
     );
 
     // Spawn the backend actor task
-    tokio::spawn(backend.run(cmd_rx));
+    let backend_task = tokio::spawn(backend.run(cmd_rx));
 
     let mut app = UiApp::new(state_rx, preview_rx, cmd_tx);
+    app.config = config::load_config(&manifest::default_base_dir());
     let mut events = EventHandler::new(EVENT_TICK_RATE);
     let mut prev_mouse_captured = true;
 
     // Draw initial frame before entering event loop
+    let size = terminal.size()?;
+    app.handle_resize(size.width, size.height);
     terminal.draw(|frame| ui::draw(frame, &app))?;
 
     // Main loop: no .await calls — UI never blocks on I/O.
@@ -198,9 +1283,7 @@ async fn run_tui(project_id: String, cwd: String) -> Result<()> {
             }
             Some(Event::Mouse(mouse)) => {
                 if !matches!(mouse.kind, MouseEventKind::Moved) {
-                    let size = terminal.size()?;
-                    let frame_area = ratatui::layout::Rect::new(0, 0, size.width, size.height);
-                    let layout = ui::compute_layout(frame_area);
+                    let layout = app.layout;
                     app.handle_mouse(mouse, &layout);
                     app.needs_redraw = true;
                 }
@@ -208,9 +1291,19 @@ async fn run_tui(project_id: String, cwd: String) -> Result<()> {
             Some(Event::Tick) => {
                 // Poll for backend state updates (non-blocking)
                 app.poll_state();
+                if std::mem::take(&mut app.ring_bell) {
+                    let backend = terminal.backend_mut();
+                    io::Write::write_all(backend, b"\x07")?;
+                    io::Write::flush(backend)?;
+                }
             }
-            Some(Event::Resize) => {
-                app.needs_redraw = true;
+            Some(Event::Resize(width, height)) => {
+                // Redraw immediately rather than waiting for the end of the loop,
+                // so large resizes never show a stale frame.
+                app.handle_resize(width, height);
+                terminal.autoresize()?;
+                terminal.draw(|frame| ui::draw(frame, &app))?;
+                app.needs_redraw = false;
             }
             None => break,
         }
@@ -228,14 +1321,21 @@ async fn run_tui(project_id: String, cwd: String) -> Result<()> {
         // Only redraw when state has actually changed
         if app.needs_redraw {
             let size = terminal.size()?;
-            app.terminal_size = (size.width, size.height);
-            app.update_diff_tree();
+            if (size.width, size.height) != app.terminal_size {
+                app.handle_resize(size.width, size.height);
+            } else {
+                app.update_diff_tree();
+            }
 
             terminal.draw(|frame| ui::draw(frame, &app))?;
             app.needs_redraw = false;
         }
     }
 
+    // Let the backend work through commands queued before Quit, such as
+    // killing every session from the quit confirmation.
+    let _ = tokio::time::timeout(BACKEND_SHUTDOWN_TIMEOUT, backend_task).await;
+
     // Restore terminal
     disable_raw_mode()?;
     execute!(
@@ -265,33 +1365,667 @@ mod update_tests {
     fn test_cli_parsing_new_command() {
         let cli = Cli::parse_from(["hydra", "new", "claude", "alpha"]);
         match cli.command {
-            Some(Commands::New { agent, name }) => {
+            Some(Commands::New {
+                agent,
+                name,
+                print_command,
+                tags,
+                layout,
+                count,
+                template,
+                worktree,
+            }) => {
                 assert_eq!(agent, "claude");
                 assert_eq!(name, "alpha");
+                assert!(!print_command);
+                assert!(tags.is_empty());
+                assert!(layout.is_none());
+                assert!(count.is_none());
+                assert!(template.is_none());
+                assert!(worktree.is_none());
             }
             other => panic!("expected New, got {other:?}"),
         }
     }
 
+    #[test]
+    fn test_cli_parsing_new_with_layout() {
+        let cli = Cli::parse_from(["hydra", "new", "claude", "alpha", "--layout", "logs"]);
+        match cli.command {
+            Some(Commands::New { layout, .. }) => assert_eq!(layout.as_deref(), Some("logs")),
+            other => panic!("expected New, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_new_with_template() {
+        let cli = Cli::parse_from(["hydra", "new", "claude", "alpha", "--template", "p.md"]);
+        match cli.command {
+            Some(Commands::New { template, .. }) => {
+                assert_eq!(template.as_deref(), Some(Path::new("p.md")))
+            }
+            other => panic!("expected New, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_new_with_worktree() {
+        let cli = Cli::parse_from(["hydra", "new", "claude", "feat", "--worktree", "feature-x"]);
+        match cli.command {
+            Some(Commands::New { worktree, .. }) => {
+                assert_eq!(worktree.as_deref(), Some("feature-x"))
+            }
+            other => panic!("expected New, got {other:?}"),
+        }
+        // One worktree can't host a whole batch.
+        assert!(Cli::try_parse_from([
+            "hydra",
+            "new",
+            "claude",
+            "feat",
+            "--worktree",
+            "x",
+            "--count",
+            "2"
+        ])
+        .is_err());
+
+        let cli = Cli::parse_from(["hydra", "kill", "feat", "--remove-worktree"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Kill {
+                remove_worktree: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn template_prompt_normalizes_line_endings_and_trailing_blanks() {
+        assert_eq!(
+            template_prompt("Fix the build.\r\n\r\n- run `cargo test`\r\n\n").unwrap(),
+            "Fix the build.\n\n- run `cargo test`"
+        );
+        // Quotes and shell metacharacters pass through untouched: the text
+        // goes to tmux as one argument, never through a shell.
+        assert_eq!(
+            template_prompt("echo \"$HOME\"; rm -rf 'x'\n").unwrap(),
+            "echo \"$HOME\"; rm -rf 'x'"
+        );
+    }
+
+    #[test]
+    fn template_prompt_rejects_empty_and_oversized_files() {
+        assert!(template_prompt(" \n\r\n").is_err());
+        assert!(template_prompt(&"x".repeat(MAX_TEMPLATE_BYTES + 1)).is_err());
+        assert!(template_prompt(&"x".repeat(MAX_TEMPLATE_BYTES)).is_ok());
+    }
+
+    #[test]
+    fn load_template_returns_absolute_path_and_prompt() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("prompt.md");
+        std::fs::write(&path, "Review src/\n").unwrap();
+        let (stored, prompt) = load_template(&path).unwrap();
+        assert!(Path::new(&stored).is_absolute());
+        assert_eq!(prompt, "Review src/");
+        assert!(load_template(&dir.path().join("missing.md")).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_new_print_command() {
+        let cli = Cli::parse_from(["hydra", "new", "codex", "alpha", "--print-command"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::New {
+                print_command: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parsing_new_with_tags() {
+        let cli = Cli::parse_from([
+            "hydra", "new", "claude", "alpha", "--tag", "foo", "--tag", "bar",
+        ]);
+        match cli.command {
+            Some(Commands::New { tags, .. }) => assert_eq!(tags, ["foo", "bar"]),
+            other => panic!("expected New, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_new_with_count() {
+        let cli = Cli::parse_from(["hydra", "new", "claude", "fan", "--count", "3"]);
+        match cli.command {
+            Some(Commands::New { name, count, .. }) => {
+                assert_eq!(name, "fan");
+                assert_eq!(count, Some(3));
+            }
+            other => panic!("expected New, got {other:?}"),
+        }
+        assert!(Cli::try_parse_from(["hydra", "new", "claude", "fan", "--count", "0"]).is_err());
+        assert!(Cli::try_parse_from(["hydra", "new", "claude", "fan", "--count", "51"]).is_err());
+    }
+
+    #[test]
+    fn batch_names_are_numbered_from_one() {
+        assert_eq!(batch_names("fan", 3), ["fan-1", "fan-2", "fan-3"]);
+        assert_eq!(batch_names("solo", 1), ["solo-1"]);
+    }
+
+    #[tokio::test]
+    async fn create_in_order_creates_every_name() {
+        let names = batch_names("fan", 3);
+        let mut attempted = Vec::new();
+        let result = create_in_order(&names, |name| {
+            attempted.push(name);
+            async { Ok(()) }
+        })
+        .await;
+        assert!(result.is_ok());
+        assert_eq!(attempted, names);
+    }
+
+    #[tokio::test]
+    async fn create_in_order_stops_and_lists_rollback_newest_first() {
+        let names = batch_names("fan", 4);
+        let mut attempted = Vec::new();
+        let result = create_in_order(&names, |name| {
+            let fail = name == "fan-3";
+            attempted.push(name);
+            async move {
+                if fail {
+                    bail!("tmux refused");
+                }
+                Ok(())
+            }
+        })
+        .await;
+        let (err, rollback) = result.unwrap_err();
+        assert_eq!(err.to_string(), "tmux refused");
+        assert_eq!(rollback, ["fan-2", "fan-1"]);
+        assert_eq!(names[rollback.len()], "fan-3");
+        // Nothing after the failure is attempted.
+        assert_eq!(attempted, ["fan-1", "fan-2", "fan-3"]);
+    }
+
+    #[tokio::test]
+    async fn create_in_order_first_failure_has_nothing_to_roll_back() {
+        let names = batch_names("fan", 2);
+        let result = create_in_order(&names, |_| async { bail!("no tmux") }).await;
+        let (_, rollback) = result.unwrap_err();
+        assert!(rollback.is_empty());
+    }
+
+    #[test]
+    fn test_cli_parsing_tag_command() {
+        let cli = Cli::parse_from(["hydra", "tag", "alpha", "a", "b", "--remove", "c"]);
+        match cli.command {
+            Some(Commands::Tag { name, tags, remove }) => {
+                assert_eq!(name, "alpha");
+                assert_eq!(tags, ["a", "b"]);
+                assert_eq!(remove, ["c"]);
+            }
+            other => panic!("expected Tag, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_note_command() {
+        let cli = Cli::parse_from(["hydra", "note", "alpha", "waiting", "on", "review"]);
+        match cli.command {
+            Some(Commands::Note { name, text, clear }) => {
+                assert_eq!(name, "alpha");
+                assert_eq!(text, ["waiting", "on", "review"]);
+                assert!(!clear);
+            }
+            other => panic!("expected Note, got {other:?}"),
+        }
+        let cli = Cli::parse_from(["hydra", "note", "alpha", "--clear"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Note { clear: true, .. })
+        ));
+        assert!(Cli::try_parse_from(["hydra", "note", "alpha", "x", "--clear"]).is_err());
+    }
+
     #[test]
     fn test_cli_parsing_kill_command() {
         let cli = Cli::parse_from(["hydra", "kill", "alpha"]);
         match cli.command {
-            Some(Commands::Kill { name }) => assert_eq!(name, "alpha"),
+            Some(Commands::Kill {
+                name,
+                remove_worktree,
+            }) => {
+                assert_eq!(name, "alpha");
+                assert!(!remove_worktree);
+            }
             other => panic!("expected Kill, got {other:?}"),
         }
     }
 
+    #[test]
+    fn test_cli_parsing_stop_and_resume_commands() {
+        let cli = Cli::parse_from(["hydra", "stop", "alpha"]);
+        assert!(matches!(cli.command, Some(Commands::Stop { name }) if name == "alpha"));
+        let cli = Cli::parse_from(["hydra", "resume", "alpha"]);
+        assert!(matches!(cli.command, Some(Commands::Resume { name }) if name == "alpha"));
+    }
+
+    #[test]
+    fn is_yes_accepts_only_explicit_yes() {
+        for answer in ["y\n", "Y", " yes ", "YES\r\n"] {
+            assert!(is_yes(answer), "{answer:?}");
+        }
+        for answer in ["", "\n", "n", "no", "yep", "sure"] {
+            assert!(!is_yes(answer), "{answer:?}");
+        }
+    }
+
     #[test]
     fn test_cli_parsing_ls_command() {
         let cli = Cli::parse_from(["hydra", "ls"]);
-        assert!(matches!(cli.command, Some(Commands::Ls)));
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Ls {
+                tag: None,
+                long: false
+            })
+        ));
+        let cli = Cli::parse_from(["hydra", "ls", "--tag", "infra"]);
+        assert!(matches!(cli.command, Some(Commands::Ls { tag: Some(t), .. }) if t == "infra"));
+        let cli = Cli::parse_from(["hydra", "ls", "--tag", " #Infra "]);
+        assert!(matches!(cli.command, Some(Commands::Ls { tag: Some(t), .. }) if t == "infra"));
+        for bad in ["two words", "  ", "#"] {
+            assert!(Cli::try_parse_from(["hydra", "ls", "--tag", bad]).is_err());
+        }
+        let cli = Cli::parse_from(["hydra", "ls", "-l"]);
+        assert!(matches!(cli.command, Some(Commands::Ls { long: true, .. })));
+    }
+
+    #[test]
+    fn test_cli_parsing_export_command() {
+        let cli = Cli::parse_from(["hydra", "export", "s1"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Export { name, format: export::Format::Md, redact: false }) if name == "s1"
+        ));
+        let cli = Cli::parse_from(["hydra", "export", "s1", "--redact"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Export { redact: true, .. })
+        ));
+        let cli = Cli::parse_from(["hydra", "export", "s1", "--format", "html"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Export {
+                format: export::Format::Html,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["hydra", "export", "s1", "--format", "pdf"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_replay_command() {
+        let cli = Cli::parse_from(["hydra", "replay", "s1"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Replay { name, speed, redact: false }) if name == "s1" && speed == 1.0
+        ));
+        let cli = Cli::parse_from(["hydra", "replay", "s1", "--redact"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Replay { redact: true, .. })
+        ));
+        let cli = Cli::parse_from(["hydra", "replay", "s1", "--speed", "2.5"]);
+        assert!(matches!(cli.command, Some(Commands::Replay { speed, .. }) if speed == 2.5));
+        for bad in ["0", "-1", "fast", "inf"] {
+            assert!(Cli::try_parse_from(["hydra", "replay", "s1", "--speed", bad]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_completions_command() {
+        let cli = Cli::parse_from(["hydra", "completions", "zsh"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Completions {
+                shell: clap_complete::Shell::Zsh
+            })
+        ));
+        assert!(Cli::try_parse_from(["hydra", "completions", "tcsh"]).is_err());
+    }
+
+    #[test]
+    fn completions_cover_subcommands_for_each_shell() {
+        use clap_complete::Shell;
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = completion_script(shell);
+            assert!(!script.is_empty(), "{shell}");
+            for subcommand in ["export", "completions", "ls"] {
+                assert!(script.contains(subcommand), "{shell} lacks {subcommand}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_feed_command() {
+        let cli = Cli::parse_from(["hydra", "feed"]);
+        assert!(matches!(cli.command, Some(Commands::Feed)));
+    }
+
+    #[test]
+    fn test_cli_parsing_stats_command() {
+        let cli = Cli::parse_from(["hydra", "stats"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stats {
+                pause: false,
+                resume: false,
+                csv: false,
+                summary: false,
+                top: 5,
+                markdown: false,
+            })
+        ));
+        let cli = Cli::parse_from(["hydra", "stats", "--pause"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stats { pause: true, .. })
+        ));
+        assert!(Cli::try_parse_from(["hydra", "stats", "--pause", "--resume"]).is_err());
+        let cli = Cli::parse_from(["hydra", "stats", "--csv"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stats { csv: true, .. })
+        ));
+        assert!(Cli::try_parse_from(["hydra", "stats", "--csv", "--pause"]).is_err());
+        let cli = Cli::parse_from(["hydra", "stats", "--summary", "--top", "3"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stats {
+                summary: true,
+                top: 3,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["hydra", "stats", "--top", "3"]).is_err());
+        assert!(Cli::try_parse_from(["hydra", "stats", "--summary", "--csv"]).is_err());
+        let cli = Cli::parse_from(["hydra", "stats", "--markdown"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stats { markdown: true, .. })
+        ));
+        assert!(Cli::try_parse_from(["hydra", "stats", "--markdown", "--summary"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_clean_command() {
+        let cli = Cli::parse_from(["hydra", "clean"]);
+        assert!(matches!(cli.command, Some(Commands::Clean { all: false })));
+        let cli = Cli::parse_from(["hydra", "clean", "--all"]);
+        assert!(matches!(cli.command, Some(Commands::Clean { all: true })));
+    }
+
+    #[test]
+    fn test_cli_parsing_tree_command() {
+        let cli = Cli::parse_from(["hydra", "tree", "alpha"]);
+        assert!(matches!(cli.command, Some(Commands::Tree { name }) if name == "alpha"));
+    }
+
+    #[test]
+    fn test_cli_parsing_report_command() {
+        let cli = Cli::parse_from([
+            "hydra",
+            "report",
+            "--from",
+            "2026-10-05",
+            "--to",
+            "2026-10-11",
+        ]);
+        let Some(Commands::Report { from, to }) = cli.command else {
+            panic!("expected report");
+        };
+        assert_eq!(from.to_string(), "2026-10-05");
+        assert_eq!(to.to_string(), "2026-10-11");
+        assert!(
+            Cli::try_parse_from(["hydra", "report", "--from", "10/05", "--to", "2026-10-11"])
+                .is_err()
+        );
+        assert!(Cli::try_parse_from(["hydra", "report", "--from", "2026-10-05"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_migrate_command() {
+        let cli = Cli::parse_from(["hydra", "migrate"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Migrate {
+                pattern: None,
+                dry_run: false
+            })
+        ));
+        let cli = Cli::parse_from(["hydra", "migrate", "--pattern", "agent-*", "--dry-run"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Migrate { pattern: Some(p), dry_run: true }) if p == "agent-*"
+        ));
+    }
+
+    #[test]
+    fn test_cli_parsing_serve_command() {
+        let cli = Cli::parse_from(["hydra", "serve"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Serve { port: 8787, bind }) if bind == "127.0.0.1"
+        ));
+        let cli = Cli::parse_from(["hydra", "serve", "--port", "9000"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Serve { port: 9000, .. })
+        ));
     }
 
     #[test]
     fn test_cli_parsing_update_command() {
         let cli = Cli::parse_from(["hydra", "update"]);
-        assert!(matches!(cli.command, Some(Commands::Update)));
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Update {
+                force: false,
+                prerelease: false
+            })
+        ));
+        let cli = Cli::parse_from(["hydra", "update", "--force"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Update { force: true, .. })
+        ));
+        let cli = Cli::parse_from(["hydra", "update", "--prerelease"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Update {
+                prerelease: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cli_parsing_diff_files_command() {
+        let cli = Cli::parse_from(["hydra", "diff-files", "alpha", "bravo"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::DiffFiles { a, b }) if a == "alpha" && b == "bravo"
+        ));
+        assert!(Cli::try_parse_from(["hydra", "diff-files", "alpha"]).is_err());
+    }
+
+    #[test]
+    fn plan_new_session_without_record_or_tmux_creates() {
+        assert_eq!(plan_new_session(false, None), NewSessionPlan::Create);
+    }
+
+    #[test]
+    fn plan_new_session_replaces_dead_record() {
+        // Record whose tmux session is gone entirely.
+        assert_eq!(plan_new_session(true, None), NewSessionPlan::ReplaceStale);
+        // Record whose pane exited but lingers (remain-on-exit).
+        assert_eq!(
+            plan_new_session(true, Some(true)),
+            NewSessionPlan::ReplaceStale
+        );
+        // Exited pane without a record.
+        assert_eq!(
+            plan_new_session(false, Some(true)),
+            NewSessionPlan::ReplaceStale
+        );
+    }
+
+    #[test]
+    fn plan_new_session_refuses_live_session() {
+        assert_eq!(
+            plan_new_session(true, Some(false)),
+            NewSessionPlan::AlreadyRunning
+        );
+        assert_eq!(
+            plan_new_session(false, Some(false)),
+            NewSessionPlan::AlreadyRunning
+        );
+    }
+
+    #[test]
+    fn test_cli_parsing_version_command() {
+        let cli = Cli::parse_from(["hydra", "version", "--full"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Version { full: true })
+        ));
+    }
+
+    #[test]
+    fn full_version_report_includes_platform_and_tools() {
+        let report = full_version_report(Some("tmux 3.4"), None);
+        assert!(report.contains(&platform_asset_name()));
+        assert!(report.contains(env!("CARGO_PKG_VERSION")));
+        assert!(report.contains("tmux:     tmux 3.4"));
+        assert!(report.contains("curl:     not found"));
+    }
+
+    // ── Root update guard tests ──────────────────────────────────────
+
+    #[test]
+    fn root_update_refused_for_user_owned_dir() {
+        assert!(should_refuse_root_update(0, Some(501), 501, 0o40755));
+    }
+
+    #[test]
+    fn root_update_refused_for_world_writable_dir() {
+        assert!(should_refuse_root_update(0, Some(501), 0, 0o41777));
+    }
+
+    #[test]
+    fn root_update_allowed_for_root_owned_dir() {
+        assert!(!should_refuse_root_update(0, Some(501), 0, 0o40755));
+    }
+
+    #[test]
+    fn root_update_allowed_when_owner_cannot_write() {
+        assert!(!should_refuse_root_update(0, Some(501), 501, 0o40555));
+    }
+
+    fn releases() -> Vec<serde_json::Value> {
+        serde_json::from_str(
+            r#"[
+                {"tag_name": "v0.4.0-rc.1", "prerelease": true, "draft": false,
+                 "published_at": "2026-10-10T12:00:00Z"},
+                {"tag_name": "v0.5.0", "prerelease": false, "draft": true,
+                 "published_at": null},
+                {"tag_name": "v0.3.1", "prerelease": false, "draft": false,
+                 "published_at": "2026-09-20T08:00:00Z"},
+                {"tag_name": "v0.3.0", "prerelease": false, "draft": false,
+                 "published_at": "2026-09-01T08:00:00Z"}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn cargo_install_args_silence_cargo_when_quiet() {
+        assert_eq!(
+            cargo_install_args(Some("v1.2.0"), false),
+            [
+                "install",
+                "--git",
+                GITHUB_REPO_URL,
+                "hydra",
+                "--locked",
+                "--tag",
+                "v1.2.0"
+            ]
+        );
+        assert_eq!(cargo_install_args(None, true).last(), Some(&"--quiet"));
+    }
+
+    #[test]
+    fn parse_quiet_flag_anywhere() {
+        assert!(Cli::parse_from(["hydra", "-q", "kill", "alpha"]).quiet);
+        assert!(Cli::parse_from(["hydra", "update", "--quiet"]).quiet);
+        assert!(!Cli::parse_from(["hydra", "update"]).quiet);
+    }
+
+    fn selected_tag(releases: &[serde_json::Value], prerelease: bool) -> Option<&str> {
+        select_release(releases, prerelease).and_then(|r| r["tag_name"].as_str())
+    }
+
+    #[test]
+    fn select_release_picks_newest_stable() {
+        assert_eq!(selected_tag(&releases(), false), Some("v0.3.1"));
+    }
+
+    #[test]
+    fn select_release_includes_prereleases_on_opt_in() {
+        assert_eq!(selected_tag(&releases(), true), Some("v0.4.0-rc.1"));
+    }
+
+    #[test]
+    fn select_release_orders_by_publish_date_not_position() {
+        let mut list = releases();
+        list.reverse();
+        assert_eq!(selected_tag(&list, false), Some("v0.3.1"));
+    }
+
+    #[test]
+    fn select_release_skips_drafts_and_untagged() {
+        let list: Vec<serde_json::Value> = serde_json::from_str(
+            r#"[
+                {"tag_name": "v1.0.0", "draft": true, "published_at": "2026-10-12T00:00:00Z"},
+                {"tag_name": "", "published_at": "2026-10-11T00:00:00Z"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(selected_tag(&list, true), None);
+        assert_eq!(selected_tag(&[], false), None);
+        let only_pre: Vec<serde_json::Value> = releases()[..1].to_vec();
+        assert_eq!(selected_tag(&only_pre, false), None);
+    }
+
+    #[test]
+    fn update_allowed_without_sudo() {
+        assert!(!should_refuse_root_update(0, None, 501, 0o40755));
+        assert!(!should_refuse_root_update(501, Some(501), 501, 0o40755));
+        assert!(!should_refuse_root_update(0, Some(0), 0, 0o40755));
+    }
+
+    #[test]
+    fn test_cli_parsing_no_color_is_global() {
+        let cli = Cli::parse_from(["hydra", "ls", "--no-color"]);
+        assert!(cli.no_color);
+        let cli = Cli::parse_from(["hydra", "--no-color"]);
+        assert!(cli.no_color);
     }
 
     #[test]
@@ -1,13 +1,18 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 use crate::session::AgentType;
 
 /// Maximum failed revival attempts before pruning a manifest entry.
 pub const MAX_FAILED_ATTEMPTS: u32 = 3;
 
+/// Atomic-write temp files older than this are assumed abandoned by a
+/// crashed writer; younger ones may belong to a write still in flight.
+pub const STALE_TMP_AGE: Duration = Duration::from_secs(60);
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct SessionRecord {
     pub name: String,
@@ -16,6 +21,36 @@ pub struct SessionRecord {
     pub cwd: String,
     #[serde(default)]
     pub failed_attempts: u32,
+    /// User-assigned labels for grouping sessions, kept sorted and unique.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Layout preset applied at creation, reapplied when the session is revived.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<String>,
+    /// Prompt file typed into the agent at creation (`hydra new --template`).
+    /// Revival resumes the conversation, so it is kept for reference rather
+    /// than sent again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Set by `hydra stop`: the tmux session was killed on purpose, so the
+    /// record is kept but not revived until `hydra resume`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stopped: bool,
+    /// Free-text note from `hydra note` or the TUI, e.g. "waiting on review".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Last output of an agent that exited right after it was launched,
+    /// e.g. "command not found". Cleared by the next successful launch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launch_error: Option<String>,
+    /// Position in the user's manual ordering of the session list
+    /// (Shift+J/K); sessions without one sort after, by name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<u32>,
+    /// `cwd` is a git worktree made by `hydra new --worktree`, which
+    /// `hydra kill` offers to remove (`--remove-worktree` removes it unasked).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub worktree: bool,
 }
 
 #[derive(Serialize, Deserialize, Default, Debug)]
@@ -37,10 +72,21 @@ pub fn manifest_path(base_dir: &Path, project_id: &str) -> PathBuf {
 
 /// Load manifest from disk. Returns empty Manifest on missing or corrupt file.
 pub async fn load_manifest(base_dir: &Path, project_id: &str) -> Manifest {
+    try_load_manifest(base_dir, project_id)
+        .await
+        .unwrap_or_default()
+}
+
+/// Load manifest from disk, failing when the file exists but can't be read
+/// or parsed (corrupt, or written by a newer hydra). A missing file is an
+/// empty Manifest. For callers that delete based on what they loaded.
+pub async fn try_load_manifest(base_dir: &Path, project_id: &str) -> Result<Manifest> {
     let path = manifest_path(base_dir, project_id);
     match tokio::fs::read_to_string(&path).await {
-        Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
-        Err(_) => Manifest::default(),
+        Ok(contents) => serde_json::from_str(&contents)
+            .with_context(|| format!("{} is not a valid manifest", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Manifest::default()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
     }
 }
 
@@ -74,6 +120,208 @@ pub async fn add_session(base_dir: &Path, project_id: &str, record: SessionRecor
     save_manifest(base_dir, project_id, &manifest).await
 }
 
+/// Add and remove tags on a session record (load-modify-save).
+/// Returns the record's resulting tags; errors if the session isn't in the manifest.
+pub async fn update_session_tags(
+    base_dir: &Path,
+    project_id: &str,
+    name: &str,
+    add: &[String],
+    remove: &[String],
+) -> Result<Vec<String>> {
+    let mut manifest = load_manifest(base_dir, project_id).await;
+    let record = manifest
+        .sessions
+        .get_mut(name)
+        .ok_or_else(|| anyhow::anyhow!("No session named '{name}' in this project"))?;
+    record.add_tags(add);
+    record.remove_tags(remove);
+    let tags = record.tags.clone();
+    save_manifest(base_dir, project_id, &manifest).await?;
+    Ok(tags)
+}
+
+/// Mark a session record stopped or not (load-modify-save).
+/// Errors if the session isn't in the manifest.
+pub async fn set_session_stopped(
+    base_dir: &Path,
+    project_id: &str,
+    name: &str,
+    stopped: bool,
+) -> Result<()> {
+    let mut manifest = load_manifest(base_dir, project_id).await;
+    let record = manifest
+        .sessions
+        .get_mut(name)
+        .ok_or_else(|| anyhow::anyhow!("No session named '{name}' in this project"))?;
+    record.stopped = stopped;
+    if !stopped {
+        record.failed_attempts = 0;
+    }
+    save_manifest(base_dir, project_id, &manifest).await
+}
+
+/// Record why a session died at launch, or clear it with `None`.
+/// A no-op if the session isn't in the manifest.
+pub async fn set_launch_error(
+    base_dir: &Path,
+    project_id: &str,
+    name: &str,
+    error: Option<String>,
+) -> Result<()> {
+    let mut manifest = load_manifest(base_dir, project_id).await;
+    let Some(record) = manifest.sessions.get_mut(name) else {
+        return Ok(());
+    };
+    if record.launch_error == error {
+        return Ok(());
+    }
+    record.launch_error = error;
+    save_manifest(base_dir, project_id, &manifest).await
+}
+
+/// Store `names` as the manual session order: each listed session gets
+/// its position. Sessions missing from the manifest are skipped.
+pub async fn set_session_order(base_dir: &Path, project_id: &str, names: &[String]) -> Result<()> {
+    let mut manifest = load_manifest(base_dir, project_id).await;
+    for (position, name) in names.iter().enumerate() {
+        if let Some(record) = manifest.sessions.get_mut(name) {
+            record.order = Some(position as u32);
+        }
+    }
+    save_manifest(base_dir, project_id, &manifest).await
+}
+
+/// Set or clear a session's note (load-modify-save). Returns the stored
+/// note; errors if the session isn't in the manifest.
+pub async fn set_session_note(
+    base_dir: &Path,
+    project_id: &str,
+    name: &str,
+    note: &str,
+) -> Result<Option<String>> {
+    let mut manifest = load_manifest(base_dir, project_id).await;
+    let record = manifest
+        .sessions
+        .get_mut(name)
+        .ok_or_else(|| anyhow::anyhow!("No session named '{name}' in this project"))?;
+    record.set_note(note);
+    let note = record.note.clone();
+    save_manifest(base_dir, project_id, &manifest).await?;
+    Ok(note)
+}
+
+/// Notes for every session in `manifest`, keyed by session name.
+/// Sessions without a note are omitted.
+pub fn session_notes(manifest: &Manifest) -> HashMap<String, String> {
+    manifest
+        .sessions
+        .iter()
+        .filter_map(|(name, record)| Some((name.clone(), record.note.clone()?)))
+        .collect()
+}
+
+/// Launch errors for every session in `manifest`, keyed by session name.
+pub fn launch_errors(manifest: &Manifest) -> HashMap<String, String> {
+    manifest
+        .sessions
+        .iter()
+        .filter_map(|(name, record)| Some((name.clone(), record.launch_error.clone()?)))
+        .collect()
+}
+
+/// Working directory of every session in `manifest`, keyed by session name.
+/// Worktree sessions run outside the project cwd.
+pub fn session_cwds(manifest: &Manifest) -> HashMap<String, String> {
+    manifest
+        .sessions
+        .iter()
+        .map(|(name, record)| (name.clone(), record.cwd.clone()))
+        .collect()
+}
+
+/// Working directories for every session in the manifest, keyed by
+/// session name.
+pub async fn load_session_cwds(base_dir: &Path, project_id: &str) -> HashMap<String, String> {
+    session_cwds(&load_manifest(base_dir, project_id).await)
+}
+
+/// The directory session `name` runs in: its recorded `cwd`, or the
+/// project `cwd` for a session without a record.
+pub fn session_cwd<'a>(cwds: &'a HashMap<String, String>, cwd: &'a str, name: &str) -> &'a str {
+    cwds.get(name).map_or(cwd, String::as_str)
+}
+
+/// Manual positions for every ordered session in `manifest`, keyed by
+/// session name.
+pub fn session_order(manifest: &Manifest) -> HashMap<String, u32> {
+    manifest
+        .sessions
+        .iter()
+        .filter_map(|(name, record)| Some((name.clone(), record.order?)))
+        .collect()
+}
+
+/// Tags for every session in the manifest, keyed by session name.
+/// Sessions without tags are omitted.
+pub async fn load_session_tags(base_dir: &Path, project_id: &str) -> HashMap<String, Vec<String>> {
+    session_tags(&load_manifest(base_dir, project_id).await)
+}
+
+/// Tags for every session in `manifest`, keyed by session name.
+pub fn session_tags(manifest: &Manifest) -> HashMap<String, Vec<String>> {
+    manifest
+        .sessions
+        .iter()
+        .filter(|(_, record)| !record.tags.is_empty())
+        .map(|(name, record)| (name.clone(), record.tags.clone()))
+        .collect()
+}
+
+/// Load the project's sessions with their manifest display names restored
+/// (see [`crate::session::restore_display_names`]).
+pub async fn list_named_sessions(
+    manager: &impl crate::tmux::SessionManager,
+    base_dir: &Path,
+    project_id: &str,
+) -> Result<Vec<crate::session::Session>> {
+    let mut sessions = manager.list_sessions(project_id).await?;
+    let manifest = load_manifest(base_dir, project_id).await;
+    crate::session::restore_display_names(&mut sessions, project_id, manifest.sessions.keys());
+    Ok(sessions)
+}
+
+/// Display name of the managed session hydra is running inside, if any.
+/// `tmux_env` is `$TMUX`, which tmux sets only inside its panes, and
+/// `current_session` the enclosing tmux session's name.
+pub fn enclosing_session(
+    tmux_env: Option<&str>,
+    current_session: Option<&str>,
+    project_id: &str,
+    manifest: &Manifest,
+) -> Option<String> {
+    if tmux_env.is_none_or(str::is_empty) {
+        return None;
+    }
+    let current = current_session?;
+    manifest
+        .sessions
+        .keys()
+        .find(|name| crate::session::tmux_session_name(project_id, name) == current)
+        .cloned()
+}
+
+/// Normalize user input into a tag: trimmed, lowercased, leading `#` dropped.
+/// Returns `None` for input that is empty after normalization.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#').trim().to_lowercase();
+    if tag.is_empty() || tag.contains(char::is_whitespace) {
+        None
+    } else {
+        Some(tag)
+    }
+}
+
 /// Remove a session record from the manifest by name (load-modify-save).
 pub async fn remove_session(base_dir: &Path, project_id: &str, name: &str) -> Result<()> {
     let mut manifest = load_manifest(base_dir, project_id).await;
@@ -81,6 +329,127 @@ pub async fn remove_session(base_dir: &Path, project_id: &str, name: &str) -> Re
     save_manifest(base_dir, project_id, &manifest).await
 }
 
+/// What `clean` removed.
+#[derive(Debug, Default, PartialEq)]
+pub struct CleanReport {
+    /// Abandoned temp files that were deleted.
+    pub files: Vec<PathBuf>,
+    /// `(project_id, session name)` of manifest records that were dropped.
+    pub records: Vec<(String, String)>,
+    /// Project directories deleted because no sessions were left in them.
+    pub projects: Vec<String>,
+    /// `(project_id, error)` of manifests left untouched because they
+    /// could not be parsed.
+    pub unreadable: Vec<(String, String)>,
+}
+
+impl CleanReport {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+            && self.records.is_empty()
+            && self.projects.is_empty()
+            && self.unreadable.is_empty()
+    }
+}
+
+/// Project ids that have a directory under `base_dir`, sorted.
+pub async fn project_ids(base_dir: &Path) -> Vec<String> {
+    let mut ids = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir(base_dir).await else {
+        return ids;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if entry.file_type().await.is_ok_and(|t| t.is_dir()) {
+            ids.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    ids.sort();
+    ids
+}
+
+/// Remove abandoned temp files and stale manifest records for each
+/// `(project_id, live session names)` pair. Records of live sessions are
+/// always kept. With `prune_projects`, project directories whose manifest
+/// ends up empty are deleted too.
+pub async fn clean(
+    base_dir: &Path,
+    projects: &[(String, HashSet<String>)],
+    prune_projects: bool,
+    now: SystemTime,
+) -> Result<CleanReport> {
+    let mut report = CleanReport::default();
+    remove_stale_tmp_files(base_dir, now, &mut report).await;
+    for (project_id, live) in projects {
+        let dir = base_dir.join(project_id);
+        remove_stale_tmp_files(&dir, now, &mut report).await;
+
+        // Never prune from a manifest that didn't parse: it would look empty.
+        let mut manifest = match try_load_manifest(base_dir, project_id).await {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                report
+                    .unreadable
+                    .push((project_id.clone(), format!("{e:#}")));
+                continue;
+            }
+        };
+        let mut stale: Vec<String> = manifest
+            .sessions
+            .values()
+            .filter(|r| r.is_stale() && !live.contains(&r.name))
+            .map(|r| r.name.clone())
+            .collect();
+        stale.sort();
+        if !stale.is_empty() {
+            for name in &stale {
+                manifest.sessions.remove(name);
+            }
+            save_manifest(base_dir, project_id, &manifest).await?;
+            report
+                .records
+                .extend(stale.into_iter().map(|name| (project_id.clone(), name)));
+        }
+
+        if prune_projects && manifest.sessions.is_empty() && live.is_empty() {
+            let _ = tokio::fs::remove_file(manifest_path(base_dir, project_id)).await;
+            // Fails (and keeps the directory) if anything else lives there.
+            if tokio::fs::remove_dir(&dir).await.is_ok() {
+                report.projects.push(project_id.clone());
+            }
+        }
+    }
+    Ok(report)
+}
+
+async fn remove_stale_tmp_files(dir: &Path, now: SystemTime, report: &mut CleanReport) {
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return;
+    };
+    let mut removed = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let is_tmp = (name.starts_with("sessions.") || name.starts_with("config."))
+            && name.ends_with(".tmp");
+        if !is_tmp {
+            continue;
+        }
+        let old_enough = entry
+            .metadata()
+            .await
+            .and_then(|m| m.modified())
+            .is_ok_and(|modified| {
+                now.duration_since(modified)
+                    .is_ok_and(|age| age >= STALE_TMP_AGE)
+            });
+        if old_enough && tokio::fs::remove_file(entry.path()).await.is_ok() {
+            removed.push(entry.path());
+        }
+    }
+    removed.sort();
+    report.files.extend(removed);
+}
+
 impl SessionRecord {
     /// Create a new SessionRecord for a fresh session, generating a UUID for Claude.
     pub fn for_new_session(name: &str, agent: &AgentType, cwd: &str) -> Self {
@@ -95,9 +464,48 @@ impl SessionRecord {
             agent_session_id,
             cwd: cwd.to_string(),
             failed_attempts: 0,
+            tags: Vec::new(),
+            layout: None,
+            template: None,
+            stopped: false,
+            note: None,
+            launch_error: None,
+            order: None,
+            worktree: false,
         }
     }
 
+    /// Add tags, ignoring invalid and duplicate entries.
+    pub fn add_tags(&mut self, tags: &[String]) {
+        for tag in tags.iter().filter_map(|t| normalize_tag(t)) {
+            if let Err(pos) = self.tags.binary_search(&tag) {
+                self.tags.insert(pos, tag);
+            }
+        }
+    }
+
+    /// Remove tags; tags the record doesn't have are ignored.
+    pub fn remove_tags(&mut self, tags: &[String]) {
+        let remove: Vec<String> = tags.iter().filter_map(|t| normalize_tag(t)).collect();
+        self.tags.retain(|t| !remove.contains(t));
+    }
+
+    /// Replace the note with `note`, collapsed onto one line; blank clears it.
+    pub fn set_note(&mut self, note: &str) {
+        let note = note.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.note = (!note.is_empty()).then_some(note);
+    }
+
+    /// Whether revival can never bring this session back: it has used up its
+    /// retries or its working directory is gone.
+    pub fn is_stale(&self) -> bool {
+        self.failed_attempts >= MAX_FAILED_ATTEMPTS || !Path::new(&self.cwd).is_dir()
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        normalize_tag(tag).is_some_and(|tag| self.tags.contains(&tag))
+    }
+
     /// Build the command string to resume this agent session.
     pub fn resume_command(&self) -> String {
         match self.agent_type.as_str() {
@@ -146,6 +554,14 @@ mod tests {
             agent_session_id: Some("abc-123".to_string()),
             cwd: "/tmp/test".to_string(),
             failed_attempts: 0,
+            tags: Vec::new(),
+            layout: None,
+            template: None,
+            stopped: false,
+            note: None,
+            launch_error: None,
+            order: None,
+            worktree: false,
         };
         assert_eq!(
             record.resume_command(),
@@ -161,6 +577,14 @@ mod tests {
             agent_session_id: None,
             cwd: "/tmp/test".to_string(),
             failed_attempts: 0,
+            tags: Vec::new(),
+            layout: None,
+            template: None,
+            stopped: false,
+            note: None,
+            launch_error: None,
+            order: None,
+            worktree: false,
         };
         assert_eq!(
             record.resume_command(),
@@ -176,6 +600,14 @@ mod tests {
             agent_session_id: None,
             cwd: "/tmp/test".to_string(),
             failed_attempts: 0,
+            tags: Vec::new(),
+            layout: None,
+            template: None,
+            stopped: false,
+            note: None,
+            launch_error: None,
+            order: None,
+            worktree: false,
         };
         assert_eq!(
             record.resume_command(),
@@ -191,6 +623,14 @@ mod tests {
             agent_session_id: Some("abc-123".to_string()),
             cwd: "/tmp/test".to_string(),
             failed_attempts: 0,
+            tags: Vec::new(),
+            layout: None,
+            template: None,
+            stopped: false,
+            note: None,
+            launch_error: None,
+            order: None,
+            worktree: false,
         };
         assert_eq!(
             record.create_command(),
@@ -206,6 +646,14 @@ mod tests {
             agent_session_id: None,
             cwd: "/tmp/test".to_string(),
             failed_attempts: 0,
+            tags: Vec::new(),
+            layout: None,
+            template: None,
+            stopped: false,
+            note: None,
+            launch_error: None,
+            order: None,
+            worktree: false,
         };
         assert_eq!(
             record.create_command(),
@@ -221,6 +669,14 @@ mod tests {
             agent_session_id: None,
             cwd: "/tmp/test".to_string(),
             failed_attempts: 0,
+            tags: Vec::new(),
+            layout: None,
+            template: None,
+            stopped: false,
+            note: None,
+            launch_error: None,
+            order: None,
+            worktree: false,
         };
         assert_eq!(
             record.create_command(),
@@ -236,6 +692,14 @@ mod tests {
             agent_session_id: None,
             cwd: "/tmp".to_string(),
             failed_attempts: 0,
+            tags: Vec::new(),
+            layout: None,
+            template: None,
+            stopped: false,
+            note: None,
+            launch_error: None,
+            order: None,
+            worktree: false,
         };
         assert_eq!(record.resume_command(), "aider");
     }
@@ -248,6 +712,14 @@ mod tests {
             agent_session_id: None,
             cwd: "/tmp".to_string(),
             failed_attempts: 0,
+            tags: Vec::new(),
+            layout: None,
+            template: None,
+            stopped: false,
+            note: None,
+            launch_error: None,
+            order: None,
+            worktree: false,
         };
         assert_eq!(record.create_command(), "aider");
     }
@@ -267,6 +739,14 @@ mod tests {
                 agent_session_id: Some("uuid-1".to_string()),
                 cwd: "/tmp/test".to_string(),
                 failed_attempts: 0,
+                tags: Vec::new(),
+                layout: None,
+                template: None,
+                stopped: false,
+                note: None,
+                launch_error: None,
+                order: None,
+                worktree: false,
             },
         );
         manifest.sessions.insert(
@@ -277,6 +757,14 @@ mod tests {
                 agent_session_id: None,
                 cwd: "/tmp/test".to_string(),
                 failed_attempts: 0,
+                tags: Vec::new(),
+                layout: None,
+                template: None,
+                stopped: false,
+                note: None,
+                launch_error: None,
+                order: None,
+                worktree: false,
             },
         );
 
@@ -327,6 +815,14 @@ mod tests {
             agent_session_id: Some("uuid-1".to_string()),
             cwd: "/tmp/test".to_string(),
             failed_attempts: 0,
+            tags: Vec::new(),
+            layout: None,
+            template: None,
+            stopped: false,
+            note: None,
+            launch_error: None,
+            order: None,
+            worktree: false,
         };
         add_session(base, pid, record).await.unwrap();
 
@@ -378,6 +874,14 @@ mod tests {
             agent_session_id: None,
             cwd: "/tmp/test".to_string(),
             failed_attempts: 0,
+            tags: Vec::new(),
+            layout: None,
+            template: None,
+            stopped: false,
+            note: None,
+            launch_error: None,
+            order: None,
+            worktree: false,
         };
         assert_eq!(record.resume_command(), "gemini --yolo --resume");
     }
@@ -390,6 +894,14 @@ mod tests {
             agent_session_id: None,
             cwd: "/tmp/test".to_string(),
             failed_attempts: 0,
+            tags: Vec::new(),
+            layout: None,
+            template: None,
+            stopped: false,
+            note: None,
+            launch_error: None,
+            order: None,
+            worktree: false,
         };
         assert_eq!(record.create_command(), "gemini --yolo");
     }
@@ -411,6 +923,283 @@ mod tests {
         );
     }
 
+    // ── Tags ─────────────────────────────────────────────────────────
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn add_tags_normalizes_sorts_and_dedupes() {
+        let mut record = SessionRecord::for_new_session("alpha", &AgentType::Codex, "/tmp");
+        record.add_tags(&tags(&["Backend", "#infra", "backend", "  ", "two words"]));
+        assert_eq!(record.tags, tags(&["backend", "infra"]));
+        assert!(record.has_tag("#Backend"));
+        assert!(!record.has_tag("frontend"));
+    }
+
+    #[test]
+    fn remove_tags_ignores_missing() {
+        let mut record = SessionRecord::for_new_session("alpha", &AgentType::Codex, "/tmp");
+        record.add_tags(&tags(&["a", "b", "c"]));
+        record.remove_tags(&tags(&["#B", "zzz"]));
+        assert_eq!(record.tags, tags(&["a", "c"]));
+    }
+
+    #[test]
+    fn record_without_tags_deserializes_and_omits_field() {
+        let json = r#"{"name":"alpha","agent_type":"claude","agent_session_id":null,"cwd":"/tmp"}"#;
+        let record: SessionRecord = serde_json::from_str(json).unwrap();
+        assert!(record.tags.is_empty());
+        let out = serde_json::to_string(&record).unwrap();
+        assert!(!out.contains("tags"));
+    }
+
+    #[tokio::test]
+    async fn tags_roundtrip_through_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let mut record = SessionRecord::for_new_session("alpha", &AgentType::Claude, "/tmp");
+        record.add_tags(&tags(&["review"]));
+        add_session(base, "tags_test", record).await.unwrap();
+
+        let result = update_session_tags(
+            base,
+            "tags_test",
+            "alpha",
+            &tags(&["urgent", "infra"]),
+            &tags(&["review"]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result, tags(&["infra", "urgent"]));
+
+        let manifest = load_manifest(base, "tags_test").await;
+        assert_eq!(manifest.sessions["alpha"].tags, tags(&["infra", "urgent"]));
+        let by_name = load_session_tags(base, "tags_test").await;
+        assert_eq!(by_name["alpha"], tags(&["infra", "urgent"]));
+    }
+
+    #[tokio::test]
+    async fn update_session_tags_unknown_session_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = update_session_tags(dir.path(), "tags_test", "ghost", &tags(&["x"]), &[])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("ghost"));
+    }
+
+    #[test]
+    fn set_note_collapses_whitespace_and_blank_clears() {
+        let mut record = SessionRecord::for_new_session("alpha", &AgentType::Claude, "/tmp");
+        record.set_note("  waiting on\nPR   review ");
+        assert_eq!(record.note.as_deref(), Some("waiting on PR review"));
+        record.set_note("exploring approach B");
+        assert_eq!(record.note.as_deref(), Some("exploring approach B"));
+        record.set_note(" \t ");
+        assert_eq!(record.note, None);
+        assert!(!serde_json::to_string(&record).unwrap().contains("note"));
+    }
+
+    #[tokio::test]
+    async fn notes_roundtrip_through_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let pid = "note_test";
+        add_session(
+            base,
+            pid,
+            SessionRecord::for_new_session("alpha", &AgentType::Claude, "/tmp"),
+        )
+        .await
+        .unwrap();
+        add_session(
+            base,
+            pid,
+            SessionRecord::for_new_session("bravo", &AgentType::Codex, "/tmp"),
+        )
+        .await
+        .unwrap();
+
+        let note = set_session_note(base, pid, "alpha", "waiting on PR review")
+            .await
+            .unwrap();
+        assert_eq!(note.as_deref(), Some("waiting on PR review"));
+        let manifest = load_manifest(base, pid).await;
+        assert_eq!(
+            manifest.sessions["alpha"].note.as_deref(),
+            Some("waiting on PR review")
+        );
+        assert_eq!(
+            session_notes(&manifest),
+            HashMap::from([("alpha".to_string(), "waiting on PR review".to_string())])
+        );
+
+        assert_eq!(
+            set_session_note(base, pid, "alpha", "").await.unwrap(),
+            None
+        );
+        assert!(session_notes(&load_manifest(base, pid).await).is_empty());
+
+        let err = set_session_note(base, pid, "ghost", "x").await.unwrap_err();
+        assert!(err.to_string().contains("ghost"));
+    }
+
+    #[tokio::test]
+    async fn set_launch_error_records_and_clears_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let pid = "launch_test";
+        add_session(
+            base,
+            pid,
+            SessionRecord::for_new_session("alpha", &AgentType::Claude, "/tmp"),
+        )
+        .await
+        .unwrap();
+
+        let error = "exited with status 127: sh: 1: exec: claude: not found".to_string();
+        set_launch_error(base, pid, "alpha", Some(error.clone()))
+            .await
+            .unwrap();
+        assert_eq!(
+            launch_errors(&load_manifest(base, pid).await),
+            HashMap::from([("alpha".to_string(), error)])
+        );
+
+        set_launch_error(base, pid, "alpha", None).await.unwrap();
+        assert!(launch_errors(&load_manifest(base, pid).await).is_empty());
+
+        // Unknown sessions are ignored rather than created.
+        set_launch_error(base, pid, "ghost", Some("x".to_string()))
+            .await
+            .unwrap();
+        assert!(!load_manifest(base, pid)
+            .await
+            .sessions
+            .contains_key("ghost"));
+    }
+
+    #[tokio::test]
+    async fn set_session_order_round_trips_through_the_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let pid = "order_test";
+        for name in ["alpha", "bravo", "charlie"] {
+            add_session(
+                base,
+                pid,
+                SessionRecord::for_new_session(name, &AgentType::Claude, "/tmp"),
+            )
+            .await
+            .unwrap();
+        }
+        let names = |list: &[&str]| list.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        set_session_order(base, pid, &names(&["charlie", "ghost", "alpha"]))
+            .await
+            .unwrap();
+        let manifest = load_manifest(base, pid).await;
+        assert_eq!(
+            session_order(&manifest),
+            HashMap::from([("charlie".to_string(), 0), ("alpha".to_string(), 2)])
+        );
+        assert!(!manifest.sessions.contains_key("ghost"));
+
+        // A later order overwrites the listed positions.
+        set_session_order(base, pid, &names(&["alpha", "bravo", "charlie"]))
+            .await
+            .unwrap();
+        assert_eq!(
+            session_order(&load_manifest(base, pid).await),
+            HashMap::from([
+                ("alpha".to_string(), 0),
+                ("bravo".to_string(), 1),
+                ("charlie".to_string(), 2)
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn set_session_stopped_keeps_the_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let pid = "stop_test";
+        let mut record = SessionRecord::for_new_session("alpha", &AgentType::Claude, "/tmp");
+        record.failed_attempts = 2;
+        add_session(base, pid, record).await.unwrap();
+
+        set_session_stopped(base, pid, "alpha", true).await.unwrap();
+        let manifest = load_manifest(base, pid).await;
+        let record = &manifest.sessions["alpha"];
+        assert!(record.stopped);
+        assert_eq!(record.failed_attempts, 2);
+        assert!(record.agent_session_id.is_some());
+
+        set_session_stopped(base, pid, "alpha", false)
+            .await
+            .unwrap();
+        let manifest = load_manifest(base, pid).await;
+        assert!(!manifest.sessions["alpha"].stopped);
+        assert_eq!(manifest.sessions["alpha"].failed_attempts, 0);
+    }
+
+    #[tokio::test]
+    async fn set_session_stopped_unknown_session_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = set_session_stopped(dir.path(), "stop_test", "ghost", true)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("ghost"));
+    }
+
+    #[test]
+    fn enclosing_session_matches_the_current_tmux_session() {
+        let mut manifest = Manifest::default();
+        let record = SessionRecord::for_new_session("alpha", &AgentType::Claude, "/tmp");
+        manifest.sessions.insert(record.name.clone(), record);
+        let tmux = Some("/tmp/tmux-1000/default,123,0");
+
+        assert_eq!(
+            enclosing_session(tmux, Some("hydra-proj-alpha"), "proj", &manifest),
+            Some("alpha".to_string())
+        );
+        // Unmanaged session, another project's session, or no session found.
+        assert_eq!(
+            enclosing_session(tmux, Some("work"), "proj", &manifest),
+            None
+        );
+        assert_eq!(
+            enclosing_session(tmux, Some("hydra-other-alpha"), "proj", &manifest),
+            None
+        );
+        assert_eq!(enclosing_session(tmux, None, "proj", &manifest), None);
+    }
+
+    #[test]
+    fn enclosing_session_requires_tmux_env() {
+        let mut manifest = Manifest::default();
+        let record = SessionRecord::for_new_session("alpha", &AgentType::Claude, "/tmp");
+        manifest.sessions.insert(record.name.clone(), record);
+        for tmux in [None, Some("")] {
+            assert_eq!(
+                enclosing_session(tmux, Some("hydra-proj-alpha"), "proj", &manifest),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn stopped_flag_is_omitted_until_set() {
+        let mut record = SessionRecord::for_new_session("alpha", &AgentType::Codex, "/tmp");
+        assert!(!serde_json::to_string(&record).unwrap().contains("stopped"));
+        record.stopped = true;
+        let json = serde_json::to_string(&record).unwrap();
+        assert!(json.contains("\"stopped\":true"));
+        let parsed: SessionRecord = serde_json::from_str(&json).unwrap();
+        assert!(parsed.stopped);
+    }
+
     #[tokio::test]
     async fn atomic_write_no_temp_file_left_behind() {
         let dir = tempfile::tempdir().unwrap();
@@ -426,6 +1215,14 @@ mod tests {
                 agent_session_id: None,
                 cwd: "/tmp".to_string(),
                 failed_attempts: 0,
+                tags: Vec::new(),
+                layout: None,
+                template: None,
+                stopped: false,
+                note: None,
+                launch_error: None,
+                order: None,
+                worktree: false,
             },
         );
 
@@ -470,6 +1267,14 @@ mod tests {
                         agent_session_id: None,
                         cwd: "/tmp".to_string(),
                         failed_attempts: 0,
+                        tags: Vec::new(),
+                        layout: None,
+                        template: None,
+                        stopped: false,
+                        note: None,
+                        launch_error: None,
+                        order: None,
+                        worktree: false,
                     },
                 );
                 save_manifest(&base, &pid, &manifest).await.unwrap();
@@ -488,4 +1293,116 @@ mod tests {
             "manifest should contain at least one session from concurrent writes"
         );
     }
+
+    #[tokio::test]
+    async fn clean_removes_abandoned_tmp_files_and_stale_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let pid = "proj";
+        let cwd = base.to_string_lossy().to_string();
+        let record = |name: &str, cwd: &str, failed_attempts| SessionRecord {
+            failed_attempts,
+            ..SessionRecord::for_new_session(name, &AgentType::Codex, cwd)
+        };
+        let mut manifest = Manifest::default();
+        for r in [
+            record("keep", &cwd, 0),
+            record("gone-cwd", "/nonexistent/hydra-clean", 0),
+            record("retried-out", &cwd, MAX_FAILED_ATTEMPTS),
+            record("live", "/nonexistent/hydra-clean", 0),
+        ] {
+            manifest.sessions.insert(r.name.clone(), r);
+        }
+        save_manifest(base, pid, &manifest).await.unwrap();
+        std::fs::write(base.join("config.json"), "{}").unwrap();
+
+        let now = SystemTime::now();
+        let abandoned = base.join(pid).join("sessions.1.0.tmp");
+        let in_flight = base.join(pid).join("sessions.2.0.tmp");
+        for path in [&abandoned, &in_flight] {
+            std::fs::write(path, "{}").unwrap();
+        }
+        std::fs::File::options()
+            .write(true)
+            .open(&abandoned)
+            .unwrap()
+            .set_modified(now - STALE_TMP_AGE)
+            .unwrap();
+
+        let live = HashSet::from(["live".to_string()]);
+        let report = clean(base, &[(pid.to_string(), live)], true, now)
+            .await
+            .unwrap();
+
+        assert_eq!(report.files, vec![abandoned.clone()]);
+        assert_eq!(
+            report.records,
+            [
+                (pid.to_string(), "gone-cwd".to_string()),
+                (pid.to_string(), "retried-out".to_string()),
+            ]
+        );
+        assert!(report.projects.is_empty());
+        assert!(!abandoned.exists());
+        assert!(in_flight.exists());
+        assert!(base.join("config.json").exists());
+        let mut kept: Vec<_> = load_manifest(base, pid)
+            .await
+            .sessions
+            .into_keys()
+            .collect();
+        kept.sort();
+        assert_eq!(kept, ["keep", "live"]);
+    }
+
+    #[tokio::test]
+    async fn clean_leaves_a_corrupt_manifest_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let path = manifest_path(base, "proj");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{\"sessions\": [not json").unwrap();
+
+        let projects = vec![("proj".to_string(), HashSet::new())];
+        let report = clean(base, &projects, true, SystemTime::now())
+            .await
+            .unwrap();
+
+        assert_eq!(report.unreadable.len(), 1);
+        assert_eq!(report.unreadable[0].0, "proj");
+        assert!(report.unreadable[0].1.contains("not a valid manifest"));
+        assert!(report.projects.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"sessions\": [not json"
+        );
+        assert!(try_load_manifest(base, "missing")
+            .await
+            .unwrap()
+            .sessions
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn clean_prunes_emptied_projects_only_when_asked() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let mut manifest = Manifest::default();
+        let r = SessionRecord::for_new_session("old", &AgentType::Codex, "/nonexistent/x");
+        manifest.sessions.insert(r.name.clone(), r);
+        save_manifest(base, "a", &manifest).await.unwrap();
+        save_manifest(base, "b", &manifest).await.unwrap();
+        assert_eq!(project_ids(base).await, ["a", "b"]);
+
+        let projects = |id: &str| vec![(id.to_string(), HashSet::new())];
+        let now = SystemTime::now();
+        let report = clean(base, &projects("a"), false, now).await.unwrap();
+        assert!(report.projects.is_empty());
+        assert!(base.join("a").is_dir());
+
+        let report = clean(base, &projects("b"), true, now).await.unwrap();
+        assert_eq!(report.projects, ["b"]);
+        assert!(!base.join("b").exists());
+        assert_eq!(project_ids(base).await, ["a"]);
+    }
 }
@@ -0,0 +1,307 @@
+//! `hydra migrate`: adopt tmux sessions that were started outside hydra.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::glob::glob_match;
+use crate::logs::parse_session_id_from_cmdline;
+use crate::session::{parse_session_name, sanitize_tmux_name, tmux_session_name, AgentType};
+
+/// Prefix of hydra's own control-mode sessions, which are never adopted.
+const CONTROL_SESSION_PREFIX: &str = "_hydra_ctrl_";
+
+/// Interpreters that run an agent as a script argument.
+const RUNTIMES: &[&str] = &["node", "nodejs", "bun", "deno", "python", "python3"];
+
+/// A live tmux session and what is running in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub tmux_name: String,
+    pub cwd: String,
+    /// Command lines of the pane's processes, outermost first.
+    pub cmdlines: Vec<String>,
+}
+
+/// A session `migrate` will take over.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Adoption {
+    pub tmux_name: String,
+    /// Display name in hydra; the session is renamed to match when needed.
+    pub name: String,
+    pub agent: AgentType,
+    pub agent_session_id: Option<String>,
+    pub cwd: String,
+}
+
+impl Adoption {
+    pub fn new_tmux_name(&self, project_id: &str) -> String {
+        tmux_session_name(project_id, &self.name)
+    }
+}
+
+/// What `plan` decided.
+#[derive(Debug, Default, PartialEq)]
+pub struct Plan {
+    pub adopt: Vec<Adoption>,
+    /// Sessions that matched but can't be adopted, with the reason.
+    pub skipped: Vec<(String, String)>,
+}
+
+/// The agent a command line runs: its program, or the script handed to a
+/// runtime like node. `None` for anything else, e.g. a shell or an editor
+/// that merely mentions an agent in its arguments.
+pub fn agent_from_cmdline(cmdline: &str) -> Option<AgentType> {
+    let mut words = cmdline.split_whitespace();
+    let program = words.next()?;
+    if let Some(agent) = agent_from_program(program) {
+        return Some(agent);
+    }
+    if !RUNTIMES.contains(&file_stem(program)) {
+        return None;
+    }
+    let script = words.find(|w| !w.starts_with('-'))?;
+    agent_from_program(script).or_else(|| agent_from_package_path(script))
+}
+
+fn file_stem(path: &str) -> &str {
+    let base = Path::new(path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(path);
+    let base = base.trim_start_matches('-'); // login shells show as `-zsh`
+    [".js", ".mjs", ".cjs"]
+        .iter()
+        .find_map(|ext| base.strip_suffix(ext))
+        .unwrap_or(base)
+}
+
+fn agent_from_program(program: &str) -> Option<AgentType> {
+    match file_stem(program) {
+        "claude" => Some(AgentType::Claude),
+        "codex" => Some(AgentType::Codex),
+        "gemini" => Some(AgentType::Gemini),
+        _ => None,
+    }
+}
+
+/// npm installs run an entry script whose name says nothing (`cli.js`);
+/// the package directory does.
+fn agent_from_package_path(script: &str) -> Option<AgentType> {
+    if script.contains("/claude-code/") {
+        Some(AgentType::Claude)
+    } else if script.contains("/@openai/codex/") {
+        Some(AgentType::Codex)
+    } else if script.contains("/gemini-cli/") {
+        Some(AgentType::Gemini)
+    } else {
+        None
+    }
+}
+
+/// Decide which live sessions to adopt into `project_id`. Sessions already
+/// named like this project's but missing from the manifest (`known`) are
+/// always considered; others only when they match `pattern`. `live` is
+/// every tmux session name, used to avoid renaming onto one that exists.
+pub fn plan(
+    candidates: &[Candidate],
+    project_id: &str,
+    pattern: Option<&str>,
+    known: &HashSet<String>,
+    live: &HashSet<String>,
+) -> Plan {
+    let mut plan = Plan::default();
+    let mut claimed = HashSet::new();
+    for candidate in candidates {
+        let tmux_name = &candidate.tmux_name;
+        if tmux_name.starts_with(CONTROL_SESSION_PREFIX) {
+            continue;
+        }
+        let name = match parse_session_name(tmux_name, project_id) {
+            Some(name) if known.contains(&name) => continue,
+            Some(name) => name,
+            None if pattern.is_some_and(|p| glob_match(p, tmux_name)) => {
+                sanitize_tmux_name(tmux_name)
+            }
+            None => continue,
+        };
+
+        let mut skip = |reason: String| plan.skipped.push((tmux_name.clone(), reason));
+        let Some(agent) = candidate
+            .cmdlines
+            .iter()
+            .find_map(|c| agent_from_cmdline(c))
+        else {
+            skip("no claude, codex or gemini process found".to_string());
+            continue;
+        };
+        if known.contains(&name) {
+            skip(format!("hydra already has a session named {name}"));
+            continue;
+        }
+        let new_tmux_name = tmux_session_name(project_id, &name);
+        if (new_tmux_name != *tmux_name && live.contains(&new_tmux_name))
+            || !claimed.insert(new_tmux_name.clone())
+        {
+            skip(format!("{new_tmux_name} already exists"));
+            continue;
+        }
+
+        let agent_session_id = match agent {
+            AgentType::Claude => candidate
+                .cmdlines
+                .iter()
+                .find_map(|c| parse_session_id_from_cmdline(c)),
+            _ => None,
+        };
+        plan.adopt.push(Adoption {
+            tmux_name: tmux_name.clone(),
+            name,
+            agent,
+            agent_session_id,
+            cwd: candidate.cwd.clone(),
+        });
+    }
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "7dab6316-ea34-4b26-b7e8-b6b57846697a";
+
+    #[test]
+    fn agent_from_cmdline_recognizes_agent_programs() {
+        for (cmdline, agent) in [
+            (
+                "claude --dangerously-skip-permissions --session-id 7dab6316",
+                AgentType::Claude,
+            ),
+            ("/home/me/.local/bin/claude", AgentType::Claude),
+            (
+                "codex -c check_for_update_on_startup=false --yolo",
+                AgentType::Codex,
+            ),
+            ("gemini --yolo --resume", AgentType::Gemini),
+        ] {
+            assert_eq!(agent_from_cmdline(cmdline), Some(agent), "{cmdline}");
+        }
+    }
+
+    #[test]
+    fn agent_from_cmdline_looks_through_runtimes() {
+        for (cmdline, agent) in [
+            (
+                "node /usr/lib/node_modules/@anthropic-ai/claude-code/cli.js --resume",
+                AgentType::Claude,
+            ),
+            (
+                "/opt/homebrew/bin/node /opt/homebrew/lib/node_modules/@openai/codex/bin/codex.js --yolo",
+                AgentType::Codex,
+            ),
+            (
+                "node --no-warnings /usr/local/lib/node_modules/@google/gemini-cli/dist/index.js",
+                AgentType::Gemini,
+            ),
+            ("bun /usr/local/bin/gemini", AgentType::Gemini),
+        ] {
+            assert_eq!(agent_from_cmdline(cmdline), Some(agent), "{cmdline}");
+        }
+    }
+
+    #[test]
+    fn agent_from_cmdline_ignores_other_programs() {
+        for cmdline in [
+            "",
+            "-zsh",
+            "bash",
+            "vim claude.md",
+            "tail -f codex.log",
+            "node server.js",
+            "less /tmp/claude-code/notes",
+        ] {
+            assert_eq!(agent_from_cmdline(cmdline), None, "{cmdline}");
+        }
+    }
+
+    fn candidate(tmux_name: &str, cmdline: &str) -> Candidate {
+        Candidate {
+            tmux_name: tmux_name.to_string(),
+            cwd: "/work".to_string(),
+            cmdlines: vec!["-zsh".to_string(), cmdline.to_string()],
+        }
+    }
+
+    fn names(list: &[&str]) -> HashSet<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn plan_adopts_unrecorded_hydra_sessions_and_pattern_matches() {
+        let candidates = [
+            candidate("hydra-abc-lost", &format!("claude --session-id {UUID}")),
+            candidate("hydra-abc-known", "claude"),
+            candidate("hydra-zzz-other", "claude"),
+            candidate("agent.1", "codex --yolo"),
+            candidate("scratch", "gemini"),
+            candidate("_hydra_ctrl_42", "tmux"),
+        ];
+        let live: HashSet<String> = candidates.iter().map(|c| c.tmux_name.clone()).collect();
+        let plan = plan(
+            &candidates,
+            "abc",
+            Some("agent*"),
+            &names(&["known"]),
+            &live,
+        );
+
+        assert_eq!(
+            plan.adopt,
+            [
+                Adoption {
+                    tmux_name: "hydra-abc-lost".to_string(),
+                    name: "lost".to_string(),
+                    agent: AgentType::Claude,
+                    agent_session_id: Some(UUID.to_string()),
+                    cwd: "/work".to_string(),
+                },
+                Adoption {
+                    tmux_name: "agent.1".to_string(),
+                    name: "agent_1".to_string(),
+                    agent: AgentType::Codex,
+                    agent_session_id: None,
+                    cwd: "/work".to_string(),
+                },
+            ]
+        );
+        assert_eq!(plan.adopt[1].new_tmux_name("abc"), "hydra-abc-agent_1");
+        assert!(plan.skipped.is_empty());
+    }
+
+    #[test]
+    fn plan_skips_sessions_without_an_agent_or_with_a_taken_name() {
+        let candidates = [
+            candidate("agent-shell", "bash"),
+            candidate("agent-dup", "claude"),
+            candidate("agent-named", "claude"),
+        ];
+        let live = names(&[
+            "agent-shell",
+            "agent-dup",
+            "agent-named",
+            "hydra-abc-agent-dup",
+        ]);
+        let plan = plan(
+            &candidates,
+            "abc",
+            Some("agent-*"),
+            &names(&["agent-named"]),
+            &live,
+        );
+
+        assert!(plan.adopt.is_empty());
+        let skipped: Vec<&str> = plan.skipped.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(skipped, ["agent-shell", "agent-dup", "agent-named"]);
+        assert!(plan.skipped[1].1.contains("hydra-abc-agent-dup"));
+    }
+}
@@ -0,0 +1,171 @@
+//! `hydra tree`: the pane's process tree as log resolution walks it, and
+//! which process carried the session id.
+
+use crate::agent::provider_for;
+use crate::logs::parse_session_id_from_cmdline;
+use crate::session::AgentType;
+
+/// One process from the walk below a pane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Process {
+    pub pid: u32,
+    pub ppid: Option<u32>,
+    /// Full command line; `None` if the process exited mid-walk.
+    pub command: Option<String>,
+}
+
+/// The first process, in walk order, whose command line carries a
+/// `--session-id`: the one Claude's command-line resolution settles on.
+pub fn session_id_source(processes: &[Process]) -> Option<(u32, String)> {
+    processes.iter().find_map(|p| {
+        let id = parse_session_id_from_cmdline(p.command.as_deref()?)?;
+        Some((p.pid, id))
+    })
+}
+
+/// Draw `processes` as a tree under the first one (the pane PID), marking
+/// the process `source` names. Processes whose parent wasn't walked (they
+/// were reparented mid-walk) hang off the root.
+pub fn render(processes: &[Process], source: Option<u32>) -> String {
+    let Some(root) = processes.first() else {
+        return String::new();
+    };
+    let mut out = String::new();
+    push_line(&mut out, "", root, source);
+    let children = |parent: u32| -> Vec<&Process> {
+        processes[1..]
+            .iter()
+            .filter(|p| {
+                let parent_walked = p
+                    .ppid
+                    .is_some_and(|ppid| processes.iter().any(|q| q.pid == ppid));
+                match p.ppid {
+                    Some(ppid) if parent_walked => ppid == parent,
+                    _ => parent == root.pid,
+                }
+            })
+            .collect()
+    };
+    // Depth-first, children in walk order.
+    let mut stack: Vec<(&Process, String, bool)> = children(root.pid)
+        .into_iter()
+        .rev()
+        .enumerate()
+        .map(|(i, p)| (p, String::new(), i == 0))
+        .collect();
+    while let Some((process, indent, last)) = stack.pop() {
+        let branch = if last { "└─ " } else { "├─ " };
+        push_line(&mut out, &format!("{indent}{branch}"), process, source);
+        let indent = format!("{indent}{}", if last { "   " } else { "│  " });
+        for (i, child) in children(process.pid).into_iter().rev().enumerate() {
+            stack.push((child, indent.clone(), i == 0));
+        }
+    }
+    out
+}
+
+fn push_line(out: &mut String, prefix: &str, process: &Process, source: Option<u32>) {
+    let command = process.command.as_deref().unwrap_or("(exited)");
+    out.push_str(&format!("{prefix}{}  {command}", process.pid));
+    if source == Some(process.pid) {
+        out.push_str("  ← session id");
+    }
+    out.push('\n');
+}
+
+/// Closing line: where the session id came from, or how this agent's
+/// sessions are found instead.
+pub fn session_id_summary(agent: &AgentType, source: Option<&(u32, String)>) -> String {
+    match (agent, source) {
+        (AgentType::Claude, Some((pid, id))) => {
+            format!("Session id {id} from pid {pid}'s command line")
+        }
+        (AgentType::Claude, None) => {
+            "No command line carries --session-id; hydra falls back to lsof".to_string()
+        }
+        (agent, _) => format!(
+            "{agent} sessions aren't identified by command line: hydra {}",
+            provider_for(agent).log_resolution_hint()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "7dab6316-ea34-4b26-b7e8-b6b57846697a";
+
+    fn process(pid: u32, ppid: u32, command: &str) -> Process {
+        Process {
+            pid,
+            ppid: Some(ppid),
+            command: Some(command.to_string()),
+        }
+    }
+
+    /// A shell running claude, which runs two MCP servers, one with a
+    /// child of its own; in the breadth-first order of the walk.
+    fn hierarchy() -> Vec<Process> {
+        vec![
+            process(100, 1, "-zsh"),
+            process(200, 100, &format!("claude --session-id {UUID}")),
+            process(300, 200, "node mcp-a.js"),
+            process(301, 200, "python3 mcp-b.py"),
+            process(400, 300, "rg --files"),
+        ]
+    }
+
+    #[test]
+    fn render_draws_the_walked_hierarchy() {
+        let procs = hierarchy();
+        let source = session_id_source(&procs);
+        assert_eq!(source, Some((200, UUID.to_string())));
+        let expected = [
+            "100  -zsh".to_string(),
+            format!("└─ 200  claude --session-id {UUID}  ← session id"),
+            "   ├─ 300  node mcp-a.js".to_string(),
+            "   │  └─ 400  rg --files".to_string(),
+            "   └─ 301  python3 mcp-b.py".to_string(),
+        ];
+        let tree = render(&procs, source.map(|(pid, _)| pid));
+        assert_eq!(tree.lines().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn render_hangs_orphans_off_the_root_and_marks_exited_processes() {
+        let procs = vec![
+            process(100, 1, "-zsh"),
+            Process {
+                pid: 500,
+                ppid: None,
+                command: None,
+            },
+            process(600, 42, "codex --yolo"),
+        ];
+        assert_eq!(
+            render(&procs, None),
+            "100  -zsh\n├─ 500  (exited)\n└─ 600  codex --yolo\n"
+        );
+        assert_eq!(session_id_source(&procs), None);
+    }
+
+    #[test]
+    fn session_id_source_takes_the_first_in_walk_order() {
+        let mut procs = hierarchy();
+        procs[3].command = Some(format!("claude --session-id={UUID}"));
+        procs[1].command = Some("claude".to_string());
+        assert_eq!(session_id_source(&procs), Some((301, UUID.to_string())));
+    }
+
+    #[test]
+    fn session_id_summary_names_the_source_or_the_fallback() {
+        let source = (200, UUID.to_string());
+        assert_eq!(
+            session_id_summary(&AgentType::Claude, Some(&source)),
+            format!("Session id {UUID} from pid 200's command line")
+        );
+        assert!(session_id_summary(&AgentType::Claude, None).contains("lsof"));
+        assert!(session_id_summary(&AgentType::Codex, None).starts_with("Codex sessions"));
+    }
+}
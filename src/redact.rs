@@ -0,0 +1,152 @@
+//! `--redact` for transcript output: hide the home directory and project
+//! path before a transcript is shared.
+
+use std::path::Path;
+
+/// Placeholder for the project directory.
+pub const PROJECT_PLACEHOLDER: &str = "<project>";
+
+/// Replaces absolute path prefixes in rendered text with placeholders.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    /// Prefix and its placeholder, most specific first.
+    rules: Vec<(String, &'static str)>,
+}
+
+impl Redactor {
+    /// Redact each of `cwds` as `<project>` and `home` as `~`. A project
+    /// inside the home directory is matched first, so its paths become
+    /// `<project>/…`.
+    pub fn new(home: Option<&Path>, cwds: &[&str]) -> Self {
+        let mut rules = Vec::new();
+        let home = home.map(|h| h.to_string_lossy().into_owned());
+        let candidates = cwds
+            .iter()
+            .map(|cwd| (cwd.to_string(), PROJECT_PLACEHOLDER))
+            .chain(home.map(|home| (home, "~")));
+        for (prefix, placeholder) in candidates {
+            let prefix = prefix.trim_end_matches('/');
+            // A bare `/` would swallow every absolute path.
+            if prefix.is_empty() {
+                continue;
+            }
+            if !rules.iter().any(|(p, _)| p == prefix) {
+                rules.push((prefix.to_string(), placeholder));
+            }
+        }
+        rules.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        Self { rules }
+    }
+
+    /// Redactor for the current user's home directory and `cwds`, e.g. a
+    /// worktree session's own directory alongside the project's.
+    pub fn for_cwds(cwds: &[&str]) -> Self {
+        Self::new(dirs::home_dir().as_deref(), cwds)
+    }
+
+    /// `text` with every redacted prefix replaced. A prefix only matches
+    /// whole path components, so `/home/al` leaves `/home/alice` alone.
+    pub fn apply(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        'scan: while !rest.is_empty() {
+            for (prefix, placeholder) in &self.rules {
+                if rest.starts_with(prefix.as_str())
+                    && ends_component(&rest[prefix.len()..])
+                    && starts_component(&out)
+                {
+                    out.push_str(placeholder);
+                    rest = &rest[prefix.len()..];
+                    continue 'scan;
+                }
+            }
+            let ch = rest.chars().next().unwrap_or_default();
+            out.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+        out
+    }
+}
+
+fn is_path_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '~')
+}
+
+/// Whether a prefix ending right before `after` ends a path component.
+fn ends_component(after: &str) -> bool {
+    after.chars().next().is_none_or(|c| !is_path_char(c))
+}
+
+/// Whether a prefix starting right after `before` starts a path, rather
+/// than continuing one (`/srv/home/me` doesn't contain `/home/me`).
+fn starts_component(before: &str) -> bool {
+    before
+        .chars()
+        .next_back()
+        .is_none_or(|c| !is_path_char(c) && c != '/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redactor() -> Redactor {
+        Redactor::new(Some(Path::new("/home/alice/")), &["/home/alice/work/app"])
+    }
+
+    #[test]
+    fn home_and_project_prefixes_are_redacted() {
+        let r = redactor();
+        assert_eq!(
+            r.apply("Edit /home/alice/work/app/src/main.rs"),
+            "Edit <project>/src/main.rs"
+        );
+        assert_eq!(r.apply("cat /home/alice/.zshrc"), "cat ~/.zshrc");
+        assert_eq!(r.apply("cd /home/alice/work/app"), "cd <project>");
+        assert_eq!(
+            r.apply("file=\"/home/alice/notes.md\", cwd=/home/alice/work/app/"),
+            "file=\"~/notes.md\", cwd=<project>/"
+        );
+    }
+
+    #[test]
+    fn relative_and_unrelated_paths_survive() {
+        let r = redactor();
+        for text in [
+            "src/main.rs",
+            "./home/alice/file",
+            "/tmp/build.log",
+            "/home/alicex/file",
+            "/srv/home/alice/file",
+            "/home/alice/work/application/x",
+        ] {
+            let redacted = r.apply(text);
+            assert!(!redacted.contains("<project>"), "{text} -> {redacted}");
+            if text != "/home/alice/work/application/x" {
+                assert_eq!(redacted, text);
+            }
+        }
+        assert_eq!(
+            r.apply("/home/alice/work/application/x"),
+            "~/work/application/x"
+        );
+    }
+
+    #[test]
+    fn root_prefixes_are_ignored() {
+        let r = Redactor::new(Some(Path::new("/")), &["/"]);
+        assert_eq!(r.apply("/etc/hosts"), "/etc/hosts");
+    }
+
+    #[test]
+    fn every_cwd_is_redacted_as_the_project() {
+        let r = Redactor::new(
+            Some(Path::new("/home/alice")),
+            &["/home/alice/work/app", "/home/alice/work/app-feature"],
+        );
+        assert_eq!(
+            r.apply("diff /home/alice/work/app/a.rs /home/alice/work/app-feature/a.rs"),
+            "diff <project>/a.rs <project>/a.rs"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Timing for `hydra replay`, which prints a session transcript with pauses
+//! proportional to the real gaps between entries.
+
+use std::time::Duration;
+
+use crate::logs::TimedEntry;
+
+/// Longest pause between two replayed entries, whatever the real gap was.
+pub const MAX_DELAY: Duration = Duration::from_secs(3);
+
+/// Pause used when either entry has no timestamp, before speed scaling.
+pub const UNTIMED_DELAY: Duration = Duration::from_millis(400);
+
+/// Order entries by timestamp. Untimed entries inherit the timestamp of the
+/// entry before them so they stay next to it; the sort is stable, so ties
+/// keep log order.
+pub fn in_timestamp_order(entries: Vec<TimedEntry>) -> Vec<TimedEntry> {
+    let mut last = None;
+    let mut entries: Vec<TimedEntry> = entries
+        .into_iter()
+        .map(|(ts, entry)| {
+            last = ts.or(last);
+            (last, entry)
+        })
+        .collect();
+    entries.sort_by_key(|(ts, _)| *ts);
+    entries
+}
+
+/// Pause before printing an entry stamped `next` after one stamped `prev`:
+/// the real gap divided by `speed`, clamped to `max`. Out-of-order or equal
+/// timestamps replay back to back.
+pub fn replay_delay(
+    prev: Option<chrono::DateTime<chrono::Utc>>,
+    next: Option<chrono::DateTime<chrono::Utc>>,
+    speed: f64,
+    max: Duration,
+) -> Duration {
+    let gap = match (prev, next) {
+        (Some(prev), Some(next)) => (next - prev).to_std().unwrap_or(Duration::ZERO),
+        _ => UNTIMED_DELAY,
+    };
+    if speed <= 0.0 || !speed.is_finite() {
+        return max;
+    }
+    Duration::try_from_secs_f64(gap.as_secs_f64() / speed)
+        .unwrap_or(max)
+        .min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::ConversationEntry;
+    use chrono::{DateTime, Utc};
+
+    fn ts(s: &str) -> Option<DateTime<Utc>> {
+        Some(s.parse().unwrap())
+    }
+
+    fn user(text: &str) -> ConversationEntry {
+        ConversationEntry::UserMessage {
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn replay_delay_scales_real_gap_by_speed() {
+        let a = ts("2026-01-01T10:00:00Z");
+        let b = ts("2026-01-01T10:00:02Z");
+        assert_eq!(replay_delay(a, b, 1.0, MAX_DELAY), Duration::from_secs(2));
+        assert_eq!(
+            replay_delay(a, b, 4.0, MAX_DELAY),
+            Duration::from_millis(500)
+        );
+        assert_eq!(replay_delay(a, b, 0.5, MAX_DELAY), MAX_DELAY);
+    }
+
+    #[test]
+    fn replay_delay_clamps_long_and_backwards_gaps() {
+        let a = ts("2026-01-01T10:00:00Z");
+        let later = ts("2026-01-01T12:00:00Z");
+        assert_eq!(replay_delay(a, later, 1.0, MAX_DELAY), MAX_DELAY);
+        assert_eq!(replay_delay(later, a, 1.0, MAX_DELAY), Duration::ZERO);
+        assert_eq!(replay_delay(a, a, 1.0, MAX_DELAY), Duration::ZERO);
+    }
+
+    #[test]
+    fn replay_delay_uses_fixed_pause_without_timestamps() {
+        let a = ts("2026-01-01T10:00:00Z");
+        assert_eq!(replay_delay(None, a, 1.0, MAX_DELAY), UNTIMED_DELAY);
+        assert_eq!(replay_delay(a, None, 2.0, MAX_DELAY), UNTIMED_DELAY / 2);
+    }
+
+    #[test]
+    fn in_timestamp_order_keeps_untimed_entries_with_their_predecessor() {
+        let entries = vec![
+            (ts("2026-01-01T10:00:05Z"), user("late")),
+            (None, user("late-untimed")),
+            (ts("2026-01-01T10:00:01Z"), user("early")),
+        ];
+        let texts: Vec<String> = in_timestamp_order(entries)
+            .into_iter()
+            .map(|(_, e)| match e {
+                ConversationEntry::UserMessage { text } => text,
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(texts, ["early", "late", "late-untimed"]);
+    }
+}
@@ -0,0 +1,359 @@
+//! Read-only HTTP endpoint behind `hydra serve`.
+//!
+//! Stats are refreshed on a background interval into a [`ServeState`]; the
+//! request handlers only ever read that cached state, so a slow log scan
+//! never blocks a client.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::RwLock;
+
+use crate::logs::{GlobalStats, SessionStats};
+use crate::session::{ProcessState, Session};
+
+/// Longest request line or header accepted before the connection is dropped.
+const MAX_LINE_BYTES: usize = 8 * 1024;
+
+/// Most header lines read from a single request.
+const MAX_HEADERS: usize = 64;
+
+/// How long a client may take to send its request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Cached JSON documents served to clients.
+#[derive(Debug, Clone)]
+pub struct ServeState {
+    pub stats: Value,
+    pub sessions: Value,
+}
+
+impl Default for ServeState {
+    fn default() -> Self {
+        Self {
+            stats: json!({}),
+            sessions: json!({ "sessions": [] }),
+        }
+    }
+}
+
+/// A fully-formed response: status code, reason phrase and JSON body.
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub reason: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    fn json(value: &Value) -> Self {
+        Self {
+            status: 200,
+            reason: "OK",
+            body: value.to_string(),
+        }
+    }
+
+    fn error(status: u16, reason: &'static str) -> Self {
+        Self {
+            status,
+            reason,
+            body: json!({ "error": reason }).to_string(),
+        }
+    }
+
+    fn to_http(&self) -> Vec<u8> {
+        format!(
+            "HTTP/1.1 {} {}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
+             Connection: close\r\n\
+             \r\n\
+             {}",
+            self.status,
+            self.reason,
+            self.body.len(),
+            self.body
+        )
+        .into_bytes()
+    }
+}
+
+/// Today's machine-wide token totals and estimated cost.
+pub fn global_stats_json(stats: &GlobalStats) -> Value {
+    json!({
+        "tokens_in": stats.tokens_in,
+        "tokens_out": stats.tokens_out,
+        "tokens_cache_read": stats.tokens_cache_read,
+        "tokens_cache_write": stats.tokens_cache_write,
+        "paused": stats.is_paused(),
+        "cost_usd": {
+            "total": stats.cost_usd(),
+            "claude": stats.claude_cost_usd(),
+            "codex": stats.codex_cost_usd(),
+            "gemini": stats.gemini_cost_usd(),
+        },
+    })
+}
+
+/// One session's identity, status and (when its log resolved) stats.
+pub fn session_json(session: &Session, stats: Option<&SessionStats>) -> Value {
+    let status = if matches!(session.process_state, ProcessState::Exited { .. }) {
+        "exited"
+    } else if stats.is_some_and(SessionStats::is_working) {
+        "working"
+    } else {
+        "idle"
+    };
+    let stats = stats.map(|st| {
+        json!({
+            "model": st.last_model,
+            "turns": st.turns,
+            "tokens_in": st.tokens_in,
+            "tokens_out": st.tokens_out,
+            "tokens_cache_read": st.tokens_cache_read,
+            "tokens_cache_write": st.tokens_cache_write,
+            "tokens_last_hour": st.tokens_last_hour(),
+            "edits": st.edits,
+            "bash_cmds": st.bash_cmds,
+            "web_tools": st.web_tools,
+            "files": st.files.len(),
+            "modified_files": st.modified_files.len(),
+            "active_subagents": st.active_subagents,
+            "last_activity_at": st.last_activity_at().map(|ts| ts.to_rfc3339()),
+        })
+    });
+    json!({
+        "name": session.name,
+        "agent": session.agent_type.to_string().to_lowercase(),
+        "status": status,
+        "stats": stats,
+    })
+}
+
+/// The `/sessions` document for one project.
+pub fn sessions_json(project_id: &str, cwd: &str, sessions: Vec<Value>) -> Value {
+    json!({
+        "project_id": project_id,
+        "cwd": cwd,
+        "sessions": sessions,
+    })
+}
+
+/// Route a request to the cached document it asks for.
+pub fn handle(method: &str, target: &str, state: &ServeState) -> Response {
+    if method != "GET" {
+        return Response::error(405, "Method Not Allowed");
+    }
+    let path = target.split(['?', '#']).next().unwrap_or(target);
+    match path.trim_end_matches('/') {
+        "/stats" => Response::json(&state.stats),
+        "/sessions" => Response::json(&state.sessions),
+        _ => Response::error(404, "Not Found"),
+    }
+}
+
+/// Split `GET /stats HTTP/1.1` into method and target.
+fn parse_request_line(line: &str) -> Option<(&str, &str)> {
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?;
+    let target = parts.next()?;
+    parts
+        .next()?
+        .starts_with("HTTP/")
+        .then_some((method, target))
+}
+
+/// Read one request from `stream`, answer it and close.
+pub async fn serve_connection<S>(stream: S, state: Arc<RwLock<ServeState>>) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut reader = BufReader::new(stream);
+    let request_line = tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut reader)).await;
+    let response = match request_line {
+        Ok(Ok(line)) => match parse_request_line(&line) {
+            Some((method, target)) => handle(method, target, &*state.read().await),
+            None => Response::error(400, "Bad Request"),
+        },
+        Ok(Err(_)) => Response::error(400, "Bad Request"),
+        Err(_) => Response::error(408, "Request Timeout"),
+    };
+    let mut stream = reader.into_inner();
+    stream.write_all(&response.to_http()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Read the request line and skip past the headers. Bodies are ignored.
+async fn read_request<R: AsyncRead + Unpin>(reader: &mut BufReader<R>) -> Result<String> {
+    let request_line = read_bounded_line(reader).await?;
+    for _ in 0..MAX_HEADERS {
+        let header = read_bounded_line(reader).await?;
+        if header.trim().is_empty() {
+            return Ok(request_line);
+        }
+    }
+    anyhow::bail!("too many headers")
+}
+
+async fn read_bounded_line<R: AsyncRead + Unpin>(reader: &mut BufReader<R>) -> Result<String> {
+    let mut buf = Vec::new();
+    let mut limited = (&mut *reader).take(MAX_LINE_BYTES as u64);
+    let n = limited.read_until(b'\n', &mut buf).await?;
+    if n == 0 || !buf.ends_with(b"\n") {
+        anyhow::bail!("truncated request");
+    }
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// Accept connections forever, answering each from the cached state.
+pub async fn run(listener: TcpListener, state: Arc<RwLock<ServeState>>) -> Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let state = Arc::clone(&state);
+        tokio::spawn(async move {
+            let _ = serve_connection(stream, state).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{AgentState, AgentType};
+    use std::time::Instant;
+
+    fn make_session(name: &str, agent_type: AgentType, process_state: ProcessState) -> Session {
+        Session {
+            name: name.to_string(),
+            tmux_name: format!("hydra-test-{name}"),
+            agent_type,
+            process_state,
+            agent_state: AgentState::Idle,
+            last_activity_at: Instant::now(),
+            task_elapsed: None,
+            _alive: true,
+        }
+    }
+
+    fn seeded_state() -> ServeState {
+        let mut global = GlobalStats::default();
+        global.tokens_in = 1_000;
+        global.tokens_out = 500;
+        let stats = SessionStats {
+            turns: 3,
+            edits: 2,
+            active_subagents: 1,
+            ..Default::default()
+        };
+        let sessions = vec![
+            session_json(
+                &make_session("alpha", AgentType::Claude, ProcessState::Alive),
+                Some(&stats),
+            ),
+            session_json(
+                &make_session(
+                    "bravo",
+                    AgentType::Codex,
+                    ProcessState::Exited {
+                        exit_code: None,
+                        reason: None,
+                    },
+                ),
+                None,
+            ),
+        ];
+        ServeState {
+            stats: global_stats_json(&global),
+            sessions: sessions_json("hydra-1234", "/work/proj", sessions),
+        }
+    }
+
+    fn body(response: &Response) -> Value {
+        serde_json::from_str(&response.body).unwrap()
+    }
+
+    #[test]
+    fn stats_serves_cached_global_totals() {
+        let response = handle("GET", "/stats", &seeded_state());
+        assert_eq!(response.status, 200);
+        let body = body(&response);
+        assert_eq!(body["tokens_in"], 1_000);
+        assert_eq!(body["tokens_out"], 500);
+        assert!(body["cost_usd"]["total"].as_f64().unwrap() > 0.0);
+    }
+
+    #[test]
+    fn sessions_serves_per_session_json() {
+        let response = handle("GET", "/sessions?pretty=1", &seeded_state());
+        assert_eq!(response.status, 200);
+        let body = body(&response);
+        assert_eq!(body["project_id"], "hydra-1234");
+        let sessions = body["sessions"].as_array().unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0]["name"], "alpha");
+        assert_eq!(sessions[0]["agent"], "claude");
+        assert_eq!(sessions[0]["status"], "working");
+        assert_eq!(sessions[0]["stats"]["edits"], 2);
+        assert_eq!(sessions[1]["status"], "exited");
+        assert!(sessions[1]["stats"].is_null());
+    }
+
+    #[test]
+    fn unknown_paths_and_methods_are_rejected() {
+        let state = seeded_state();
+        assert_eq!(handle("GET", "/", &state).status, 404);
+        assert_eq!(handle("GET", "/stats/extra", &state).status, 404);
+        assert_eq!(handle("POST", "/stats", &state).status, 405);
+        assert_eq!(handle("GET", "/stats/", &state).status, 200);
+    }
+
+    #[test]
+    fn parse_request_line_requires_http_version() {
+        assert_eq!(
+            parse_request_line("GET /stats HTTP/1.1\r\n"),
+            Some(("GET", "/stats"))
+        );
+        assert_eq!(parse_request_line("GET /stats"), None);
+        assert_eq!(parse_request_line(""), None);
+    }
+
+    #[tokio::test]
+    async fn serve_connection_answers_over_a_stream() {
+        let state = Arc::new(RwLock::new(seeded_state()));
+        let (mut client, server) = tokio::io::duplex(64 * 1024);
+        let task = tokio::spawn(serve_connection(server, state));
+        client
+            .write_all(b"GET /stats HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut raw = String::new();
+        client.read_to_string(&mut raw).await.unwrap();
+        task.await.unwrap().unwrap();
+
+        assert!(raw.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(raw.contains("Content-Type: application/json"));
+        let (_, json) = raw.split_once("\r\n\r\n").unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(json).unwrap()["tokens_in"],
+            1_000
+        );
+    }
+
+    #[tokio::test]
+    async fn serve_connection_rejects_garbage() {
+        let state = Arc::new(RwLock::new(ServeState::default()));
+        let (mut client, server) = tokio::io::duplex(64 * 1024);
+        let task = tokio::spawn(serve_connection(server, state));
+        client.write_all(b"hello\r\n\r\n").await.unwrap();
+        let mut raw = String::new();
+        client.read_to_string(&mut raw).await.unwrap();
+        task.await.unwrap().unwrap();
+        assert!(raw.starts_with("HTTP/1.1 400 Bad Request"));
+    }
+}
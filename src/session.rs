@@ -1,4 +1,6 @@
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::time::Duration;
 
@@ -58,6 +60,8 @@ pub enum ProcessState {
         exit_code: Option<i32>,
         reason: Option<String>,
     },
+    /// No tmux session: `hydra stop` killed it and kept the manifest record.
+    Stopped,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -65,6 +69,8 @@ pub enum AgentState {
     Idle,
     Thinking,
     ExecutingTool(String),
+    /// The agent's log says it is blocked until the user acts.
+    WaitingForInput,
     Unknown,
 }
 
@@ -72,8 +78,10 @@ pub enum AgentState {
 pub enum VisualStatus {
     Idle,
     Running(String),
+    NeedsInput,
     Exited,
     Booting,
+    Stopped,
 }
 
 #[derive(Debug, Clone)]
@@ -89,14 +97,30 @@ pub struct Session {
 }
 
 impl Session {
+    /// Placeholder for a stopped manifest record, which has no tmux session.
+    pub fn stopped(project_id: &str, name: &str, agent_type: AgentType) -> Self {
+        Self {
+            name: name.to_string(),
+            tmux_name: tmux_session_name(project_id, name),
+            agent_type,
+            process_state: ProcessState::Stopped,
+            agent_state: AgentState::Idle,
+            last_activity_at: Instant::now(),
+            task_elapsed: None,
+            _alive: false,
+        }
+    }
+
     pub fn visual_status(&self) -> VisualStatus {
         match &self.process_state {
             ProcessState::Exited { .. } => VisualStatus::Exited,
             ProcessState::Booting => VisualStatus::Booting,
+            ProcessState::Stopped => VisualStatus::Stopped,
             ProcessState::Alive => match &self.agent_state {
                 AgentState::Idle => VisualStatus::Idle,
                 AgentState::Thinking => VisualStatus::Running("Thinking".to_string()),
                 AgentState::ExecutingTool(t) => VisualStatus::Running(format!("Running {}", t)),
+                AgentState::WaitingForInput => VisualStatus::NeedsInput,
                 AgentState::Unknown => {
                     if self.last_activity_at.elapsed() < Duration::from_secs(5) {
                         VisualStatus::Running("Running".to_string())
@@ -110,14 +134,58 @@ impl Session {
 
     pub fn sort_order(&self) -> u8 {
         match self.visual_status() {
-            VisualStatus::Idle => 0,
+            // Blocked sessions sit with the idle ones, which need the
+            // user's attention too.
+            VisualStatus::Idle | VisualStatus::NeedsInput => 0,
             VisualStatus::Running(_) => 1,
             VisualStatus::Booting => 1,
             VisualStatus::Exited => 2,
+            VisualStatus::Stopped => 3,
         }
     }
 }
 
+/// Sort the session list by status group, then by the user's manual
+/// order (positions keyed by session name), then by name. Within a group,
+/// sessions with a position come before those without.
+pub fn sort_sessions(sessions: &mut [Session], order: &HashMap<String, u32>) {
+    let position = |s: &Session| order.get(&s.name).map_or((1, 0), |&p| (0, p));
+    sessions.sort_by(|a, b| {
+        a.sort_order()
+            .cmp(&b.sort_order())
+            .then_with(|| position(a).cmp(&position(b)))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Swap `items[index]` with its neighbour above (`up`) or below. Returns
+/// the new index, or `None` when it's already at that end of the list.
+pub fn move_item<T>(items: &mut [T], index: usize, up: bool) -> Option<usize> {
+    let target = if up { index.checked_sub(1)? } else { index + 1 };
+    if index >= items.len() || target >= items.len() {
+        return None;
+    }
+    items.swap(index, target);
+    Some(target)
+}
+
+/// `full` with the names in `reordered` rearranged into that order and the
+/// rest left where they are, so a move made in a filtered list doesn't
+/// shuffle the sessions the filter hides.
+pub fn merge_order(full: &[String], reordered: &[String]) -> Vec<String> {
+    let moved: HashSet<&String> = reordered.iter().collect();
+    let mut next = reordered.iter();
+    full.iter()
+        .map(|name| {
+            if moved.contains(name) {
+                next.next().unwrap_or(name).clone()
+            } else {
+                name.clone()
+            }
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SessionId(String);
 
@@ -170,6 +238,65 @@ pub fn format_duration(d: Duration) -> String {
     }
 }
 
+/// How timestamps are rendered in the TUI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampMode {
+    /// Wall-clock time, e.g. `10:42:03`.
+    #[default]
+    Absolute,
+    /// Age relative to now, e.g. `3m ago`.
+    Relative,
+}
+
+impl TimestampMode {
+    pub fn toggled(self) -> Self {
+        match self {
+            TimestampMode::Absolute => TimestampMode::Relative,
+            TimestampMode::Relative => TimestampMode::Absolute,
+        }
+    }
+}
+
+impl fmt::Display for TimestampMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimestampMode::Absolute => write!(f, "absolute"),
+            TimestampMode::Relative => write!(f, "relative"),
+        }
+    }
+}
+
+/// Format a timestamp for display. Absolute mode renders the time of day in
+/// the timestamp's own timezone; relative mode renders its age against `now`.
+pub fn format_timestamp<Tz>(
+    ts: &chrono::DateTime<Tz>,
+    now: chrono::DateTime<chrono::Utc>,
+    mode: TimestampMode,
+) -> String
+where
+    Tz: chrono::TimeZone,
+    Tz::Offset: fmt::Display,
+{
+    match mode {
+        TimestampMode::Absolute => ts.format("%H:%M:%S").to_string(),
+        TimestampMode::Relative => {
+            let secs = (now - ts.with_timezone(&chrono::Utc)).num_seconds();
+            if secs < 5 {
+                "just now".to_string()
+            } else if secs < 60 {
+                format!("{secs}s ago")
+            } else if secs < 3600 {
+                format!("{}m ago", secs / 60)
+            } else if secs < 86_400 {
+                format!("{}h ago", secs / 3600)
+            } else {
+                format!("{}d ago", secs / 86_400)
+            }
+        }
+    }
+}
+
 /// Generate an 8-char hex hash from the absolute CWD path.
 pub fn project_id(cwd: &str) -> String {
     let mut hasher = Sha256::new();
@@ -178,9 +305,56 @@ pub fn project_id(cwd: &str) -> String {
     hex::encode(&result[..4])
 }
 
-/// Build the tmux session name: `hydra-<hash>-<name>`
+/// Build the tmux session name: `hydra-<hash>-<name>`, with `name` passed
+/// through [`sanitize_tmux_name`].
 pub fn tmux_session_name(project_id: &str, name: &str) -> String {
-    format!("hydra-{project_id}-{name}")
+    format!("hydra-{project_id}-{}", sanitize_tmux_name(name))
+}
+
+/// Make `name` safe for a tmux session name. tmux treats `.` and `:` as
+/// target separators (`session:window.pane`), and whitespace or control
+/// characters break unquoted targets, so each becomes `_`.
+pub fn sanitize_tmux_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c == '.' || c == ':' || c.is_whitespace() || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// The name in `existing` that `name` would share a tmux session with once
+/// sanitized, e.g. `a_b` for `a.b`. An identical name is not a clash.
+pub fn tmux_name_clash<'a>(
+    name: &str,
+    existing: impl IntoIterator<Item = &'a String>,
+) -> Option<&'a String> {
+    let sanitized = sanitize_tmux_name(name);
+    existing
+        .into_iter()
+        .find(|other| *other != name && sanitize_tmux_name(other) == sanitized)
+}
+
+/// Give sessions whose tmux name was sanitized their original display name
+/// back. `names` are manifest session names; names that sanitize to
+/// themselves are skipped.
+pub fn restore_display_names<'a>(
+    sessions: &mut [Session],
+    project_id: &str,
+    names: impl IntoIterator<Item = &'a String>,
+) {
+    for name in names {
+        if sanitize_tmux_name(name) == *name {
+            continue;
+        }
+        let tmux_name = tmux_session_name(project_id, name);
+        if let Some(session) = sessions.iter_mut().find(|s| s.tmux_name == tmux_name) {
+            session.name = name.clone();
+        }
+    }
 }
 
 /// Extract the user-facing session name from a tmux session name.
@@ -212,6 +386,23 @@ pub fn generate_name(existing: &[String]) -> String {
     }
 }
 
+/// Name for a throwaway session created at `now`: `scratch-YYYYMMDD-HHMMSS`,
+/// with `-2`, `-3`, ... appended when that is already taken.
+pub fn scratch_name(existing: &[String], now: chrono::NaiveDateTime) -> String {
+    let base = format!("scratch-{}", now.format("%Y%m%d-%H%M%S"));
+    if !existing.contains(&base) {
+        return base;
+    }
+    let mut i = 2;
+    loop {
+        let name = format!("{base}-{i}");
+        if !existing.contains(&name) {
+            return name;
+        }
+        i += 1;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,6 +455,50 @@ mod tests {
         assert_eq!(name, "hydra-abcd1234-");
     }
 
+    #[test]
+    fn tmux_name_clash_finds_names_that_sanitize_alike() {
+        let existing = ["a_b".to_string(), "c.d".to_string()];
+        assert_eq!(tmux_name_clash("a.b", &existing), Some(&existing[0]));
+        assert_eq!(tmux_name_clash("c:d", &existing), Some(&existing[1]));
+        assert_eq!(tmux_name_clash("a_b", &existing), None);
+        assert_eq!(tmux_name_clash("e.f", &existing), None);
+    }
+
+    #[test]
+    fn sanitize_tmux_name_replaces_separators_and_whitespace() {
+        assert_eq!(sanitize_tmux_name("my.session:1"), "my_session_1");
+        assert_eq!(sanitize_tmux_name("fix the bug"), "fix_the_bug");
+        assert_eq!(sanitize_tmux_name("tab\there\n"), "tab_here_");
+        assert_eq!(sanitize_tmux_name("v1.2.3"), "v1_2_3");
+        assert_eq!(sanitize_tmux_name("worker-1_ok"), "worker-1_ok");
+        assert_eq!(sanitize_tmux_name("ünïcode"), "ünïcode");
+        assert_eq!(
+            tmux_session_name("abcd1234", "a.b:c d"),
+            "hydra-abcd1234-a_b_c_d"
+        );
+    }
+
+    #[test]
+    fn restore_display_names_maps_sanitized_sessions_back() {
+        let pid = "abcd1234";
+        let session = |name: &str| Session {
+            name: sanitize_tmux_name(name),
+            tmux_name: tmux_session_name(pid, name),
+            agent_type: AgentType::Claude,
+            process_state: ProcessState::Alive,
+            agent_state: AgentState::Idle,
+            last_activity_at: Instant::now(),
+            task_elapsed: None,
+            _alive: true,
+        };
+        let mut sessions = vec![session("my.session:1"), session("plain")];
+        let names = ["my.session:1".to_string(), "plain".to_string()];
+        restore_display_names(&mut sessions, pid, &names);
+        assert_eq!(sessions[0].name, "my.session:1");
+        assert_eq!(sessions[0].tmux_name, "hydra-abcd1234-my_session_1");
+        assert_eq!(sessions[1].name, "plain");
+    }
+
     #[test]
     fn session_id_new_and_display() {
         let id = SessionId::new("hydra-abcd1234-alpha");
@@ -445,6 +680,45 @@ mod tests {
         assert_eq!(name, "agent-28");
     }
 
+    // ── scratch_name tests ───────────────────────────────────────────
+
+    fn scratch_time() -> chrono::NaiveDateTime {
+        "2026-10-16T09:05:07".parse().unwrap()
+    }
+
+    #[test]
+    fn scratch_name_uses_the_timestamp() {
+        let existing = vec!["alpha".to_string()];
+        assert_eq!(
+            scratch_name(&existing, scratch_time()),
+            "scratch-20261016-090507"
+        );
+    }
+
+    #[test]
+    fn scratch_name_adds_a_suffix_on_collision() {
+        let mut existing = vec!["scratch-20261016-090507".to_string()];
+        assert_eq!(
+            scratch_name(&existing, scratch_time()),
+            "scratch-20261016-090507-2"
+        );
+        existing.push("scratch-20261016-090507-2".to_string());
+        existing.push("scratch-20261016-090507-4".to_string());
+        assert_eq!(
+            scratch_name(&existing, scratch_time()),
+            "scratch-20261016-090507-3"
+        );
+    }
+
+    #[test]
+    fn scratch_name_is_a_valid_session_name() {
+        let name = scratch_name(&[], scratch_time());
+        assert!(name.len() <= crate::app::MAX_SESSION_NAME_LEN);
+        assert!(name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    }
+
     // ── format_duration tests ────────────────────────────────────────
 
     #[test]
@@ -474,6 +748,49 @@ mod tests {
         assert_eq!(format_duration(Duration::from_millis(1500)), "1s");
     }
 
+    // ── format_timestamp tests ───────────────────────────────────────
+
+    fn ts(s: &str) -> chrono::DateTime<chrono::Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn format_timestamp_absolute() {
+        let at = ts("2026-02-24T10:42:03Z");
+        let now = ts("2026-02-24T10:45:03Z");
+        assert_eq!(
+            format_timestamp(&at, now, TimestampMode::Absolute),
+            "10:42:03"
+        );
+    }
+
+    #[test]
+    fn format_timestamp_relative() {
+        let at = ts("2026-02-24T10:42:03Z");
+        let rel = |now: &str| format_timestamp(&at, ts(now), TimestampMode::Relative);
+        assert_eq!(rel("2026-02-24T10:42:05Z"), "just now");
+        assert_eq!(rel("2026-02-24T10:42:45Z"), "42s ago");
+        assert_eq!(rel("2026-02-24T10:45:03Z"), "3m ago");
+        assert_eq!(rel("2026-02-24T12:42:03Z"), "2h ago");
+        assert_eq!(rel("2026-02-27T10:42:03Z"), "3d ago");
+    }
+
+    #[test]
+    fn format_timestamp_relative_future_is_just_now() {
+        let at = ts("2026-02-24T10:42:03Z");
+        let now = ts("2026-02-24T10:40:00Z");
+        assert_eq!(
+            format_timestamp(&at, now, TimestampMode::Relative),
+            "just now"
+        );
+    }
+
+    #[test]
+    fn timestamp_mode_toggles() {
+        assert_eq!(TimestampMode::Absolute.toggled(), TimestampMode::Relative);
+        assert_eq!(TimestampMode::Relative.toggled(), TimestampMode::Absolute);
+    }
+
     // ── SessionStatus::sort_order tests ─────────────────────────────
 
     // ── proptest ──────────────────────────────────────────────────────
@@ -552,6 +869,22 @@ mod tests {
         assert_eq!(session.sort_order(), 0);
     }
 
+    #[test]
+    fn sort_order_needs_input_groups_with_idle() {
+        let session = Session {
+            name: "test".to_string(),
+            tmux_name: "test".to_string(),
+            agent_type: AgentType::Claude,
+            process_state: ProcessState::Alive,
+            agent_state: AgentState::WaitingForInput,
+            last_activity_at: std::time::Instant::now(),
+            task_elapsed: None,
+            _alive: true,
+        };
+        assert_eq!(session.visual_status(), VisualStatus::NeedsInput);
+        assert_eq!(session.sort_order(), 0);
+    }
+
     #[test]
     fn sort_order_running_is_middle() {
         let session = Session {
@@ -585,6 +918,14 @@ mod tests {
         assert_eq!(session.sort_order(), 2);
     }
 
+    #[test]
+    fn stopped_sessions_sort_after_exited() {
+        let session = Session::stopped("proj", "paused", AgentType::Codex);
+        assert_eq!(session.tmux_name, "hydra-proj-paused");
+        assert_eq!(session.visual_status(), VisualStatus::Stopped);
+        assert_eq!(session.sort_order(), 3);
+    }
+
     #[test]
     fn sort_order_produces_correct_ordering() {
         let s1 = Session {
@@ -625,4 +966,69 @@ mod tests {
         let names: Vec<&str> = statuses.into_iter().map(|s| s.name.as_str()).collect();
         assert_eq!(names, vec!["c", "b", "a"]);
     }
+
+    fn named(name: &str, agent_state: AgentState) -> Session {
+        Session {
+            name: name.to_string(),
+            tmux_name: name.to_string(),
+            agent_type: AgentType::Claude,
+            process_state: ProcessState::Alive,
+            agent_state,
+            last_activity_at: std::time::Instant::now(),
+            task_elapsed: None,
+            _alive: true,
+        }
+    }
+
+    #[test]
+    fn sort_sessions_applies_manual_order_within_each_group() {
+        let mut sessions = vec![
+            named("a", AgentState::Idle),
+            named("b", AgentState::Thinking),
+            named("c", AgentState::Idle),
+            named("d", AgentState::Idle),
+            named("e", AgentState::Thinking),
+        ];
+        let order = HashMap::from([
+            ("d".to_string(), 0),
+            ("c".to_string(), 1),
+            ("e".to_string(), 2),
+        ]);
+        sort_sessions(&mut sessions, &order);
+        let names: Vec<&str> = sessions.iter().map(|s| s.name.as_str()).collect();
+        // Unordered `a` trails the ordered idle sessions; `e` stays running.
+        assert_eq!(names, ["d", "c", "a", "e", "b"]);
+    }
+
+    #[test]
+    fn move_item_swaps_with_a_neighbour() {
+        let mut items = vec!["a", "b", "c"];
+        assert_eq!(move_item(&mut items, 1, true), Some(0));
+        assert_eq!(items, ["b", "a", "c"]);
+        assert_eq!(move_item(&mut items, 1, false), Some(2));
+        assert_eq!(items, ["b", "c", "a"]);
+    }
+
+    #[test]
+    fn move_item_stops_at_the_ends() {
+        let mut items = vec!["a", "b", "c"];
+        assert_eq!(move_item(&mut items, 0, true), None);
+        assert_eq!(move_item(&mut items, 2, false), None);
+        assert_eq!(move_item(&mut items, 3, true), None);
+        assert_eq!(items, ["a", "b", "c"]);
+        let mut empty: Vec<&str> = Vec::new();
+        assert_eq!(move_item(&mut empty, 0, false), None);
+        assert_eq!(move_item(&mut empty, 0, true), None);
+    }
+
+    #[test]
+    fn merge_order_leaves_hidden_names_in_place() {
+        let names = |list: &[&str]| list.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        let full = names(&["a", "x", "b", "y", "c"]);
+        assert_eq!(
+            merge_order(&full, &names(&["c", "a", "b"])),
+            names(&["c", "x", "a", "y", "b"])
+        );
+        assert_eq!(merge_order(&full, &[]), full);
+    }
 }
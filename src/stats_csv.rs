@@ -0,0 +1,55 @@
+//! CSV export of daily usage behind `hydra stats --csv`.
+
+use crate::logs::GlobalStats;
+
+/// Column order of the export. Kept stable so spreadsheets and scripts
+/// reading it don't break between releases.
+pub const CSV_HEADER: &str = "date,provider,tokens_in,tokens_out,cost_usd";
+
+/// Render one row per provider per day, days in the order given and
+/// providers in `GlobalStats::provider_usage` order. Costs are estimates in
+/// USD with four decimals.
+pub fn render<'a>(days: impl IntoIterator<Item = (&'a str, &'a GlobalStats)>) -> String {
+    let mut out = format!("{CSV_HEADER}\n");
+    for (date, stats) in days {
+        for (provider, tokens_in, tokens_out, cost) in stats.provider_usage() {
+            out.push_str(&format!(
+                "{date},{provider},{tokens_in},{tokens_out},{cost:.4}\n"
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_writes_header_and_a_row_per_provider() {
+        let mut stats = GlobalStats::default();
+        stats.claude_tokens_in = 1_000_000;
+        stats.claude_tokens_out = 200_000;
+        stats.codex_tokens_in = 5_000;
+        stats.codex_tokens_out = 700;
+
+        let csv = render([("2026-10-15", &stats)]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "date,provider,tokens_in,tokens_out,cost_usd");
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[1],
+            format!(
+                "2026-10-15,claude,1000000,200000,{:.4}",
+                stats.claude_cost_usd()
+            )
+        );
+        assert!(lines[2].starts_with("2026-10-15,codex,5000,700,"));
+        assert_eq!(lines[3], "2026-10-15,gemini,0,0,0.0000");
+    }
+
+    #[test]
+    fn render_without_days_is_just_the_header() {
+        assert_eq!(render([]), format!("{CSV_HEADER}\n"));
+    }
+}
@@ -0,0 +1,157 @@
+//! Markdown table of per-session stats, behind `hydra stats --markdown`
+//! and the TUI's copy action.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::logs::{format_cost, format_tokens, Currency, SessionStats};
+use crate::session::{format_duration, Session, VisualStatus};
+
+const HEADER: &str =
+    "| Session | Status | Tokens | Cost | Age |\n| --- | --- | ---: | ---: | ---: |\n";
+
+/// Placeholder for cells with no data yet.
+const EMPTY_CELL: &str = "—";
+
+/// `stopped`, `exited`, `needs input`, `working` or `idle`: the sidebar status, with log activity
+/// counting as working for sessions whose pane status isn't known.
+pub fn status_label(session: &Session, stats: Option<&SessionStats>) -> &'static str {
+    match session.visual_status() {
+        VisualStatus::Exited => "exited",
+        VisualStatus::Stopped => "stopped",
+        VisualStatus::NeedsInput => "needs input",
+        VisualStatus::Running(_) | VisualStatus::Booting => "working",
+        VisualStatus::Idle if stats.is_some_and(SessionStats::is_working) => "working",
+        VisualStatus::Idle => "idle",
+    }
+}
+
+/// Escape a value for use inside a table cell.
+fn cell(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace('\n', " ")
+}
+
+/// Render one row per session, in the order given. `stats` is keyed by
+/// tmux session name; age runs from the session's first user message to
+/// `now`. Costs are shown in `currency`.
+pub fn render(
+    sessions: &[Session],
+    stats: &HashMap<String, SessionStats>,
+    now: DateTime<Utc>,
+    currency: &Currency,
+) -> String {
+    let mut out = HEADER.to_string();
+    for session in sessions {
+        let st = stats.get(&session.tmux_name);
+        let tokens = st.map_or(EMPTY_CELL.to_string(), |st| {
+            format_tokens(st.tokens_in + st.tokens_out)
+        });
+        let cost = st.map_or(EMPTY_CELL.to_string(), |st| {
+            format_cost(st.estimated_cost_usd(&session.agent_type), currency)
+        });
+        let age = st
+            .and_then(SessionStats::started_at)
+            .and_then(|start| (now - start).to_std().ok())
+            .map_or(EMPTY_CELL.to_string(), format_duration);
+        out.push_str(&format!(
+            "| {} | {} | {tokens} | {cost} | {age} |\n",
+            cell(&session.name),
+            status_label(session, st),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{AgentState, AgentType, ProcessState};
+    use std::time::Instant;
+
+    fn make_session(name: &str, agent_state: AgentState, process_state: ProcessState) -> Session {
+        Session {
+            name: name.to_string(),
+            tmux_name: format!("hydra-test-{name}"),
+            agent_type: AgentType::Claude,
+            process_state,
+            agent_state,
+            last_activity_at: Instant::now(),
+            task_elapsed: None,
+            _alive: true,
+        }
+    }
+
+    fn seeded() -> (Vec<Session>, HashMap<String, SessionStats>) {
+        let sessions = vec![
+            make_session("alpha", AgentState::Thinking, ProcessState::Alive),
+            make_session("bravo", AgentState::Idle, ProcessState::Alive),
+            make_session(
+                "charlie",
+                AgentState::Idle,
+                ProcessState::Exited {
+                    exit_code: Some(0),
+                    reason: None,
+                },
+            ),
+        ];
+        let stats = HashMap::from([(
+            "hydra-test-alpha".to_string(),
+            SessionStats {
+                tokens_in: 1_000_000,
+                tokens_out: 200_000,
+                first_user_ts: Some("2026-10-16T09:00:00Z".to_string()),
+                ..Default::default()
+            },
+        )]);
+        (sessions, stats)
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2026-10-16T10:30:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn render_writes_header_and_a_row_per_session() {
+        let (sessions, stats) = seeded();
+        let table = render(&sessions, &stats, now(), &Currency::usd());
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "| Session | Status | Tokens | Cost | Age |");
+        assert_eq!(lines[1], "| --- | --- | ---: | ---: | ---: |");
+        assert_eq!(lines[2], "| alpha | working | 1.2M | $6.00 | 1h 30m |");
+        assert_eq!(lines[3], "| bravo | idle | — | — | — |");
+        assert_eq!(lines[4], "| charlie | exited | — | — | — |");
+        for line in &lines {
+            assert_eq!(line.matches('|').count(), 6, "{line}");
+        }
+    }
+
+    #[test]
+    fn render_escapes_pipes_in_names() {
+        let sessions = vec![make_session("a|b", AgentState::Idle, ProcessState::Alive)];
+        let table = render(&sessions, &HashMap::new(), now(), &Currency::usd());
+        assert!(table.ends_with("| a\\|b | idle | — | — | — |\n"));
+    }
+
+    #[test]
+    fn render_labels_sessions_waiting_on_the_user() {
+        let sessions = vec![make_session(
+            "blocked",
+            AgentState::WaitingForInput,
+            ProcessState::Alive,
+        )];
+        let table = render(&sessions, &HashMap::new(), now(), &Currency::usd());
+        assert!(table.ends_with("| blocked | needs input | — | — | — |\n"));
+    }
+
+    #[test]
+    fn render_without_sessions_is_just_the_header() {
+        assert_eq!(
+            render(&[], &HashMap::new(), now(), &Currency::usd()),
+            HEADER
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Per-session usage breakdown behind `hydra stats --summary`.
+
+use crate::logs::{format_cost, format_tokens, Currency, SessionStats};
+use crate::session::AgentType;
+
+/// One session's usage as it enters the summary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionUsage {
+    pub name: String,
+    pub agent: AgentType,
+    /// Input plus output tokens.
+    pub tokens: u64,
+    pub turns: u32,
+    pub cost_usd: f64,
+}
+
+impl SessionUsage {
+    pub fn new(name: &str, agent: AgentType, stats: &SessionStats) -> Self {
+        Self {
+            name: name.to_string(),
+            tokens: stats.tokens_in + stats.tokens_out,
+            turns: stats.turns,
+            cost_usd: stats.estimated_cost_usd(&agent),
+            agent,
+        }
+    }
+}
+
+/// Aggregates over a set of sessions, plus the heaviest few by tokens.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    /// Heaviest sessions first; ties are broken by name.
+    pub top: Vec<SessionUsage>,
+    pub sessions: usize,
+    pub total_tokens: u64,
+    pub total_cost_usd: f64,
+    pub avg_cost_usd: f64,
+    pub avg_turns: f64,
+    pub p50_tokens: u64,
+    pub p90_tokens: u64,
+}
+
+/// Summarize `sessions`, keeping the `top` heaviest by tokens.
+pub fn summarize(mut sessions: Vec<SessionUsage>, top: usize) -> Summary {
+    sessions.sort_by(|a, b| b.tokens.cmp(&a.tokens).then_with(|| a.name.cmp(&b.name)));
+    let count = sessions.len();
+    let total_tokens = sessions.iter().map(|s| s.tokens).sum();
+    let total_cost_usd: f64 = sessions.iter().map(|s| s.cost_usd).sum();
+    let total_turns: u64 = sessions.iter().map(|s| u64::from(s.turns)).sum();
+    let average = |total: f64| {
+        if count == 0 {
+            0.0
+        } else {
+            total / count as f64
+        }
+    };
+    let mut ascending: Vec<u64> = sessions.iter().map(|s| s.tokens).collect();
+    ascending.reverse();
+    sessions.truncate(top);
+    Summary {
+        top: sessions,
+        sessions: count,
+        total_tokens,
+        total_cost_usd,
+        avg_cost_usd: average(total_cost_usd),
+        avg_turns: average(total_turns as f64),
+        p50_tokens: percentile(&ascending, 50),
+        p90_tokens: percentile(&ascending, 90),
+    }
+}
+
+/// Nearest-rank percentile of `sorted` (ascending); 0 when empty.
+pub fn percentile(sorted: &[u64], pct: u32) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (pct as usize * sorted.len()).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
+
+/// Plain-text report: the aggregate lines, then one row per top session.
+/// Costs are shown in `currency`.
+pub fn render(summary: &Summary, currency: &Currency) -> String {
+    if summary.sessions == 0 {
+        return "No session activity today.\n".to_string();
+    }
+    let mut out = format!(
+        "Sessions active today: {}\n\
+         Tokens: {} total, {} median, {} p90\n\
+         Cost: {} total, {} avg per session\n\
+         Turns: {:.1} avg per session\n",
+        summary.sessions,
+        format_tokens(summary.total_tokens),
+        format_tokens(summary.p50_tokens),
+        format_tokens(summary.p90_tokens),
+        format_cost(summary.total_cost_usd, currency),
+        format_cost(summary.avg_cost_usd, currency),
+        summary.avg_turns,
+    );
+    if summary.top.is_empty() {
+        return out;
+    }
+    let labels: Vec<String> = summary
+        .top
+        .iter()
+        .map(|s| format!("{} [{}]", s.name, s.agent))
+        .collect();
+    let width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    out.push_str(&format!("\nTop {} by tokens:\n", summary.top.len()));
+    for (label, s) in labels.iter().zip(&summary.top) {
+        out.push_str(&format!(
+            "  {label:<width$}  {:>7}  {:>6}  {} turns\n",
+            format_tokens(s.tokens),
+            format_cost(s.cost_usd, currency),
+            s.turns
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(name: &str, tokens: u64, turns: u32, cost_usd: f64) -> SessionUsage {
+        SessionUsage {
+            name: name.to_string(),
+            agent: AgentType::Claude,
+            tokens,
+            turns,
+            cost_usd,
+        }
+    }
+
+    fn fixture() -> Vec<SessionUsage> {
+        vec![
+            usage("alpha", 12_000, 4, 0.10),
+            usage("bravo", 250_000, 20, 1.50),
+            usage("charlie", 80_000, 9, 0.60),
+            usage("delta", 80_000, 3, 0.40),
+        ]
+    }
+
+    #[test]
+    fn summarize_sorts_by_tokens_and_keeps_top_n() {
+        let summary = summarize(fixture(), 3);
+        let names: Vec<&str> = summary.top.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["bravo", "charlie", "delta"]);
+        assert_eq!(summary.sessions, 4);
+        assert_eq!(summary.total_tokens, 422_000);
+    }
+
+    #[test]
+    fn summarize_averages_over_every_session() {
+        let summary = summarize(fixture(), 1);
+        assert_eq!(summary.top.len(), 1);
+        assert!((summary.total_cost_usd - 2.6).abs() < 1e-9);
+        assert!((summary.avg_cost_usd - 0.65).abs() < 1e-9);
+        assert!((summary.avg_turns - 9.0).abs() < 1e-9);
+        assert_eq!(summary.p50_tokens, 80_000);
+        assert_eq!(summary.p90_tokens, 250_000);
+    }
+
+    #[test]
+    fn summarize_without_sessions_is_all_zero() {
+        let summary = summarize(Vec::new(), 5);
+        assert!(summary.top.is_empty());
+        assert_eq!(summary.avg_cost_usd, 0.0);
+        assert_eq!(summary.avg_turns, 0.0);
+        assert_eq!(
+            render(&summary, &Currency::usd()),
+            "No session activity today.\n"
+        );
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let sorted = [10, 20, 30, 40, 50, 60, 70, 80, 90, 100];
+        assert_eq!(percentile(&sorted, 50), 50);
+        assert_eq!(percentile(&sorted, 90), 90);
+        assert_eq!(percentile(&sorted, 100), 100);
+        assert_eq!(percentile(&sorted, 0), 10);
+        assert_eq!(percentile(&[7], 90), 7);
+        assert_eq!(percentile(&[], 50), 0);
+    }
+
+    #[test]
+    fn render_lists_top_sessions_aligned() {
+        let out = render(&summarize(fixture(), 2), &Currency::usd());
+        assert!(out.starts_with("Sessions active today: 4\n"));
+        assert!(out.contains("Tokens: 422.0k total, 80.0k median, 250.0k p90\n"));
+        assert!(out.contains("Cost: $2.60 total, $0.65 avg per session\n"));
+        assert!(out.contains("Turns: 9.0 avg per session\n"));
+        assert!(out.contains("\nTop 2 by tokens:\n"));
+        assert!(out.contains("  bravo [Claude]     250.0k   $1.50  20 turns\n"));
+        assert!(out.contains("  charlie [Claude]    80.0k   $0.60  9 turns\n"));
+        assert!(!out.contains("delta"));
+    }
+
+    #[test]
+    fn session_usage_prices_by_agent() {
+        let stats = SessionStats {
+            turns: 2,
+            tokens_in: 1_000_000,
+            tokens_out: 100_000,
+            ..Default::default()
+        };
+        let usage = SessionUsage::new("s1", AgentType::Codex, &stats);
+        assert_eq!(usage.tokens, 1_100_000);
+        assert_eq!(usage.turns, 2);
+        assert!((usage.cost_usd - 2.25).abs() < 1e-9);
+    }
+}
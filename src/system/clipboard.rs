@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// How long a clipboard tool may take before it is abandoned.
+const COPY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Clipboard tools tried in order. `tmux load-buffer -w` comes last: it is
+/// always available and forwards to the outer terminal's clipboard when
+/// tmux's `set-clipboard` option allows it.
+pub fn copy_commands(wayland: bool) -> Vec<(&'static str, &'static [&'static str])> {
+    let mut commands: Vec<(&'static str, &'static [&'static str])> = vec![("pbcopy", &[])];
+    if wayland {
+        commands.push(("wl-copy", &[]));
+    }
+    commands.extend([
+        ("xclip", &["-selection", "clipboard"][..]),
+        ("xsel", &["--clipboard", "--input"][..]),
+        ("tmux", &["load-buffer", "-w", "-"][..]),
+    ]);
+    commands
+}
+
+/// Copy `text` to the system clipboard using the first tool that succeeds.
+/// Returns the name of the tool used.
+pub async fn copy(text: &str) -> Result<&'static str> {
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    for (program, args) in copy_commands(wayland) {
+        if let Ok(Ok(())) = tokio::time::timeout(COPY_TIMEOUT, pipe_to(program, args, text)).await {
+            return Ok(program);
+        }
+    }
+    bail!("no clipboard tool available (tried pbcopy, wl-copy, xclip, xsel, tmux)")
+}
+
+async fn pipe_to(program: &str, args: &[&str], text: &str) -> Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("failed to run {program}"))?;
+    let mut stdin = child.stdin.take().context("no stdin")?;
+    stdin.write_all(text.as_bytes()).await?;
+    drop(stdin);
+    let status = child.wait().await?;
+    if !status.success() {
+        bail!("{program} exited with {status}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_commands_only_try_wl_copy_under_wayland() {
+        let names = |wayland| -> Vec<&str> {
+            copy_commands(wayland)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect()
+        };
+        assert_eq!(names(false), ["pbcopy", "xclip", "xsel", "tmux"]);
+        assert_eq!(names(true), ["pbcopy", "wl-copy", "xclip", "xsel", "tmux"]);
+    }
+}
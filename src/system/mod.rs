@@ -1,2 +1,4 @@
+pub mod clipboard;
 pub mod git;
 pub mod process;
+pub mod status_script;
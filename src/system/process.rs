@@ -13,7 +13,11 @@ pub async fn run_cmd_timeout(
 }
 
 /// Read tmux pane PID for a session.
+/// `None` for remote tmux: its PIDs mean nothing to the local process table.
 pub async fn get_tmux_pane_pid(tmux_name: &str) -> Option<u32> {
+    if crate::tmux::ssh_target().is_some() {
+        return None;
+    }
     let output = run_cmd_timeout(
         Command::new("tmux").args(["list-panes", "-t", tmux_name, "-F", "#{pane_pid}"]),
         std::time::Duration::from_secs(5),
@@ -29,6 +33,32 @@ pub async fn get_tmux_pane_pid(tmux_name: &str) -> Option<u32> {
     stdout.trim().parse::<u32>().ok()
 }
 
+/// Full command line of a process, or `None` if it is gone.
+pub async fn process_command(pid: u32) -> Option<String> {
+    let output = run_cmd_timeout(
+        Command::new("ps").args(["-p", &pid.to_string(), "-o", "command="]),
+        std::time::Duration::from_secs(5),
+    )
+    .await
+    .ok()?;
+    let command = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !command.is_empty()).then_some(command)
+}
+
+/// Parent PID of a process, or `None` if it is gone.
+pub async fn process_parent(pid: u32) -> Option<u32> {
+    let output = run_cmd_timeout(
+        Command::new("ps").args(["-p", &pid.to_string(), "-o", "ppid="]),
+        std::time::Duration::from_secs(5),
+    )
+    .await
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
 /// Collect descendant PIDs from the process tree rooted at `pid`.
 pub async fn collect_descendant_pids(pid: u32, max_depth: usize, max_pids: usize) -> Vec<u32> {
     let mut all_pids = Vec::with_capacity(max_pids.min(16));
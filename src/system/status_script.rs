@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use tokio::process::Command;
+
+/// How long a status script may run before it is killed.
+pub const STATUS_SCRIPT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Longest annotation shown next to a session; longer output is cut.
+pub const MAX_ANNOTATION_CHARS: usize = 40;
+
+/// What the script is told about the session it runs for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptContext {
+    pub name: String,
+    pub tmux_name: String,
+    pub agent: String,
+    pub cwd: String,
+}
+
+/// Turn script stdout into an annotation: the first non-blank line,
+/// trimmed, with ANSI color codes and other control characters dropped and
+/// cut to `MAX_ANNOTATION_CHARS`. `None` when there is nothing to show.
+pub fn parse_annotation(stdout: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(stdout);
+    let line = text.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let clean: String = crate::cli_style::strip_ansi(line)
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect();
+    let clean = clean.trim();
+    if clean.is_empty() {
+        return None;
+    }
+    if clean.chars().count() > MAX_ANNOTATION_CHARS {
+        let cut: String = clean.chars().take(MAX_ANNOTATION_CHARS - 1).collect();
+        return Some(format!("{}…", cut.trim_end()));
+    }
+    Some(clean.to_string())
+}
+
+/// Run `command` through `sh -c` for one session and parse its annotation.
+/// The session is passed as `HYDRA_SESSION`, `HYDRA_TMUX_SESSION`,
+/// `HYDRA_AGENT` and `HYDRA_CWD`; the script runs in the session's cwd
+/// with no stdin. A non-zero exit or a run longer than `timeout` is an
+/// error, and the script is killed on timeout.
+pub async fn run(command: &str, ctx: &ScriptContext, timeout: Duration) -> Result<Option<String>> {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(command)
+        .current_dir(&ctx.cwd)
+        .env("HYDRA_SESSION", &ctx.name)
+        .env("HYDRA_TMUX_SESSION", &ctx.tmux_name)
+        .env("HYDRA_AGENT", &ctx.agent)
+        .env("HYDRA_CWD", &ctx.cwd)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+    let output = match tokio::time::timeout(timeout, cmd.output()).await {
+        Ok(result) => result.context("status script failed to execute")?,
+        Err(_) => bail!("status script timed out after {}ms", timeout.as_millis()),
+    };
+    if !output.status.success() {
+        bail!("status script exited with {}", output.status);
+    }
+    Ok(parse_annotation(&output.stdout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> ScriptContext {
+        ScriptContext {
+            name: "alpha".to_string(),
+            tmux_name: "hydra-test-alpha".to_string(),
+            agent: "claude".to_string(),
+            cwd: std::env::temp_dir().to_string_lossy().into_owned(),
+        }
+    }
+
+    #[test]
+    fn parse_annotation_takes_first_non_blank_line() {
+        assert_eq!(
+            parse_annotation(b"\n  \n  CI: passing  \nextra\n"),
+            Some("CI: passing".to_string())
+        );
+        assert_eq!(parse_annotation(b""), None);
+        assert_eq!(parse_annotation(b"  \n\t\n"), None);
+    }
+
+    #[test]
+    fn parse_annotation_strips_control_characters() {
+        assert_eq!(
+            parse_annotation(b"\x1b[31mred\x1b[0m\r\n"),
+            Some("red".to_string())
+        );
+        assert_eq!(
+            parse_annotation(b"\x1b[1;32mCI:\x1b[0m pass\x07ing\n"),
+            Some("CI: passing".to_string())
+        );
+        assert_eq!(parse_annotation(b"\x07\x08\n"), None);
+    }
+
+    #[test]
+    fn parse_annotation_truncates_long_output() {
+        let long = "x".repeat(100);
+        let annotation = parse_annotation(long.as_bytes()).unwrap();
+        assert_eq!(annotation.chars().count(), MAX_ANNOTATION_CHARS);
+        assert!(annotation.ends_with('…'));
+    }
+
+    #[tokio::test]
+    async fn run_passes_session_env() {
+        let out = run(
+            "echo \"$HYDRA_SESSION $HYDRA_TMUX_SESSION $HYDRA_AGENT\"",
+            &ctx(),
+            STATUS_SCRIPT_TIMEOUT,
+        )
+        .await
+        .unwrap();
+        assert_eq!(out.as_deref(), Some("alpha hydra-test-alpha claude"));
+    }
+
+    #[tokio::test]
+    async fn run_times_out_slow_scripts() {
+        let started = std::time::Instant::now();
+        let err = run("sleep 5; echo late", &ctx(), Duration::from_millis(100))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("timed out"), "{err}");
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn run_fails_on_non_zero_exit() {
+        let err = run("echo nope; exit 3", &ctx(), STATUS_SCRIPT_TIMEOUT)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("exited"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn run_without_output_has_no_annotation() {
+        let out = run("true", &ctx(), STATUS_SCRIPT_TIMEOUT).await.unwrap();
+        assert_eq!(out, None);
+    }
+}
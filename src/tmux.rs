@@ -2,8 +2,10 @@ use anyhow::{bail, Context, Result};
 use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 use std::time::Duration;
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 
+use crate::config::{LayoutStep, SplitDirection};
 use crate::session::{parse_session_name, AgentType, Session};
 
 /// Default timeout for subprocess calls (2 seconds).
@@ -34,6 +36,103 @@ pub async fn run_status_timeout(cmd: &mut Command) -> Result<std::process::ExitS
     }
 }
 
+/// `user@host` whose tmux hydra drives over ssh; set once by `init_ssh_target`.
+static SSH_TARGET: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Choose the remote tmux host: the `--host` flag wins over `HYDRA_TMUX_SSH`,
+/// and a blank value means local tmux.
+pub fn ssh_target_from(flag: Option<&str>, env: Option<&str>) -> Option<String> {
+    flag.or(env)
+        .map(str::trim)
+        .filter(|target| !target.is_empty())
+        .map(str::to_string)
+}
+
+/// Configure the remote tmux host once at startup from the CLI flag and
+/// environment.
+pub fn init_ssh_target(flag: Option<&str>) {
+    let env = std::env::var("HYDRA_TMUX_SSH").ok();
+    if let Some(target) = ssh_target_from(flag, env.as_deref()) {
+        let _ = SSH_TARGET.set(target);
+    }
+}
+
+/// The host tmux runs on over ssh, or `None` for local tmux.
+pub fn ssh_target() -> Option<&'static str> {
+    SSH_TARGET.get().map(String::as_str)
+}
+
+/// Quote `arg` for the remote shell that ssh hands its command line to.
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./:@%+=,".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Full argv for `tmux ARGS`. With an ssh target the call becomes
+/// `ssh ... HOST -- tmux 'ARG'...`: ssh joins its arguments into one remote
+/// shell command, so each one is quoted. Connections are multiplexed so
+/// the many small tmux calls per tick don't each pay for a handshake.
+pub fn tmux_argv<S: AsRef<str>>(ssh: Option<&str>, args: &[S]) -> Vec<String> {
+    let args = args.iter().map(|a| a.as_ref());
+    match ssh {
+        None => std::iter::once("tmux")
+            .chain(args)
+            .map(str::to_string)
+            .collect(),
+        Some(target) => [
+            "ssh",
+            "-o",
+            "BatchMode=yes",
+            "-o",
+            "ControlMaster=auto",
+            "-o",
+            "ControlPath=~/.ssh/hydra-%C",
+            "-o",
+            "ControlPersist=60",
+            target,
+            "--",
+            "tmux",
+        ]
+        .into_iter()
+        .map(str::to_string)
+        .chain(args.map(shell_quote))
+        .collect(),
+    }
+}
+
+/// A `tmux ARGS` command, run over ssh when a remote host is configured.
+pub fn tmux_cmd<I, S>(args: I) -> Command
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let args: Vec<S> = args.into_iter().collect();
+    let argv = tmux_argv(ssh_target(), &args);
+    let mut cmd = Command::new(&argv[0]);
+    cmd.args(&argv[1..]);
+    cmd
+}
+
+/// Blocking variant of `tmux_cmd`, for cleanup paths that can't await.
+pub fn tmux_std_cmd<I, S>(args: I) -> std::process::Command
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let args: Vec<S> = args.into_iter().collect();
+    let argv = tmux_argv(ssh_target(), &args);
+    let mut cmd = std::process::Command::new(&argv[0]);
+    cmd.args(&argv[1..]);
+    cmd
+}
+
 #[async_trait::async_trait]
 pub trait SessionManager: Send + Sync {
     async fn list_sessions(&self, project_id: &str) -> Result<Vec<Session>>;
@@ -60,6 +159,12 @@ pub trait SessionManager: Send + Sync {
     }
     async fn capture_pane_scrollback(&self, tmux_name: &str) -> Result<String>;
 
+    /// Capture the last `lines` rows of the pane, reaching into scrollback
+    /// when the visible pane is shorter. Default impl captures the visible pane.
+    async fn capture_pane_tail(&self, tmux_name: &str, _lines: u32) -> Result<String> {
+        self.capture_pane(tmux_name).await
+    }
+
     /// Batch-capture pane content for multiple sessions. Default impl is sequential;
     /// `TmuxSessionManager` overrides with parallel subprocess calls.
     async fn capture_panes(&self, names: &[String]) -> Vec<Result<String>> {
@@ -78,9 +183,65 @@ pub trait SessionManager: Send + Sync {
         None
     }
 
+    /// Batch-query every pane's title (`#{pane_title}`).
+    /// Returns `session_name → title`. Default impl returns None (not supported).
+    async fn batch_pane_titles(&self) -> Option<HashMap<String, String>> {
+        None
+    }
+
+    /// Run a layout preset's tmux commands against a freshly created session.
+    async fn apply_layout(
+        &self,
+        _tmux_name: &str,
+        _cwd: &str,
+        _steps: &[LayoutStep],
+    ) -> Result<()> {
+        Ok(())
+    }
+
     /// Pre-populate the agent type cache from a known mapping (e.g. from manifest).
     /// Avoids `tmux show-environment HYDRA_AGENT_TYPE` queries for known sessions.
     fn prepopulate_agent_cache(&self, _mapping: &HashMap<String, AgentType>) {}
+
+    /// Whether the tmux server is up. Default impl assumes it is.
+    async fn server_running(&self) -> bool {
+        true
+    }
+}
+
+/// Where recorded sessions stand against the live tmux server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TmuxHealth {
+    /// Every recorded session is live (or nothing is recorded).
+    #[default]
+    Ok,
+    /// The server is up but this many recorded sessions died on their own.
+    SessionsGone(usize),
+    /// The server isn't running, taking all this many recorded sessions
+    /// with it.
+    ServerDown(usize),
+}
+
+/// Classify missing sessions: `recorded` are the tmux names of manifest
+/// sessions that should be running, `live` the ones tmux listed.
+pub fn classify_health(
+    server_running: bool,
+    recorded: &[String],
+    live: &HashSet<String>,
+) -> TmuxHealth {
+    let missing = recorded.iter().filter(|n| !live.contains(*n)).count();
+    match missing {
+        0 => TmuxHealth::Ok,
+        _ if !server_running => TmuxHealth::ServerDown(recorded.len()),
+        n => TmuxHealth::SessionsGone(n),
+    }
+}
+
+/// Whether tmux's stderr says there is no server to talk to, as opposed
+/// to a missing session or a bad argument.
+pub fn is_server_down_error(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    stderr.contains("no server running") || stderr.contains("error connecting to")
 }
 
 pub struct TmuxSessionManager {
@@ -109,8 +270,7 @@ fn prune_agent_cache(cache: &mut HashMap<String, AgentType>, live_sessions: &Has
 impl SessionManager for TmuxSessionManager {
     async fn list_sessions(&self, project_id: &str) -> Result<Vec<Session>> {
         let output =
-            run_cmd_timeout(Command::new("tmux").args(["list-sessions", "-F", "#{session_name}"]))
-                .await;
+            run_cmd_timeout(&mut tmux_cmd(["list-sessions", "-F", "#{session_name}"])).await;
 
         let output = match output {
             Ok(o) => o,
@@ -261,10 +421,41 @@ impl SessionManager for TmuxSessionManager {
         capture_pane_scrollback(tmux_name).await
     }
 
+    async fn capture_pane_tail(&self, tmux_name: &str, lines: u32) -> Result<String> {
+        capture_pane_tail(tmux_name, lines).await
+    }
+
+    async fn apply_layout(&self, tmux_name: &str, cwd: &str, steps: &[LayoutStep]) -> Result<()> {
+        apply_layout(tmux_name, cwd, steps).await
+    }
+
     async fn batch_pane_status(&self) -> Option<HashMap<String, (bool, u64)>> {
         batch_pane_status_impl().await
     }
 
+    async fn batch_pane_titles(&self) -> Option<HashMap<String, String>> {
+        let output = run_cmd_timeout(&mut tmux_cmd(["list-panes", "-a", "-F", PANE_TITLE_FORMAT]))
+            .await
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(parse_pane_titles(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    async fn server_running(&self) -> bool {
+        // Without a target, has-session succeeds if the server has any
+        // session at all, and fails with "no server running" if it's gone.
+        match run_cmd_timeout(&mut tmux_cmd(["has-session"])).await {
+            Ok(output) => {
+                output.status.success()
+                    || !is_server_down_error(&String::from_utf8_lossy(&output.stderr))
+            }
+            // Can't tell (tmux timed out or won't start); don't cry wolf.
+            Err(_) => true,
+        }
+    }
+
     fn prepopulate_agent_cache(&self, mapping: &HashMap<String, AgentType>) {
         let mut cache = self.agent_cache.lock().unwrap();
         for (tmux_name, agent) in mapping {
@@ -278,7 +469,7 @@ impl SessionManager for TmuxSessionManager {
 /// Batch-query all tmux panes for dead status and activity timestamp.
 /// Returns `session_name → (is_dead, pane_activity_epoch)`.
 async fn batch_pane_status_impl() -> Option<HashMap<String, (bool, u64)>> {
-    let output = run_cmd_timeout(Command::new("tmux").args([
+    let output = run_cmd_timeout(&mut tmux_cmd([
         "list-panes",
         "-a",
         "-F",
@@ -305,9 +496,27 @@ async fn batch_pane_status_impl() -> Option<HashMap<String, (bool, u64)>> {
     Some(result)
 }
 
+/// `list-panes` format for `batch_pane_titles`: session and title, tab-separated
+/// because titles may contain spaces.
+pub(crate) const PANE_TITLE_FORMAT: &str = "#{session_name}\t#{pane_title}";
+
+/// Parse `PANE_TITLE_FORMAT` lines into `session_name → title`. The first
+/// pane listed for a session wins.
+pub(crate) fn parse_pane_titles(output: &str) -> HashMap<String, String> {
+    let mut titles = HashMap::new();
+    for line in output.lines() {
+        if let Some((session, title)) = line.split_once('\t') {
+            titles
+                .entry(session.to_string())
+                .or_insert_with(|| title.to_string());
+        }
+    }
+    titles
+}
+
 /// Read the HYDRA_AGENT_TYPE env var from the tmux session.
 async fn get_agent_type(tmux_name: &str) -> Option<AgentType> {
-    let output = run_cmd_timeout(Command::new("tmux").args([
+    let output = run_cmd_timeout(&mut tmux_cmd([
         "show-environment",
         "-t",
         tmux_name,
@@ -355,7 +564,10 @@ pub async fn create_session(
     // Use env -u for each known var, plus unset any CLAUDE_CODE_* vars the shell inherited.
     let wrapped_cmd = wrap_agent_command(cmd);
 
-    let status = run_status_timeout(Command::new("tmux").args([
+    // Keep the pane alive after the command exits so we can detect Exited
+    // status. Set in the same tmux invocation, so an agent that dies at
+    // launch can't take the session with it first.
+    let status = run_status_timeout(&mut tmux_cmd([
         "new-session",
         "-d",
         "-s",
@@ -363,6 +575,12 @@ pub async fn create_session(
         "-c",
         cwd,
         &wrapped_cmd,
+        ";",
+        "set-option",
+        "-t",
+        &tmux_name,
+        "remain-on-exit",
+        "on",
     ]))
     .await
     .context("Failed to create tmux session")?;
@@ -371,16 +589,6 @@ pub async fn create_session(
         bail!("tmux new-session failed for '{tmux_name}'");
     }
 
-    // Keep pane alive after command exits so we can detect Exited status
-    let _ = run_status_timeout(Command::new("tmux").args([
-        "set-option",
-        "-t",
-        &tmux_name,
-        "remain-on-exit",
-        "on",
-    ]))
-    .await;
-
     // Unset Claude Code env vars in the session environment.
     // Must use -u (not -r): -r removes from session table causing fallthrough
     // to the global environment where the vars still exist. -u actively marks
@@ -390,7 +598,7 @@ pub async fn create_session(
         "CLAUDE_CODE_ENTRYPOINT",
         "CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS",
     ] {
-        let _ = run_status_timeout(Command::new("tmux").args([
+        let _ = run_status_timeout(&mut tmux_cmd([
             "set-environment",
             "-t",
             &tmux_name,
@@ -401,7 +609,7 @@ pub async fn create_session(
     }
 
     // Store agent type as env var on the session
-    let _ = run_status_timeout(Command::new("tmux").args([
+    let _ = run_status_timeout(&mut tmux_cmd([
         "set-environment",
         "-t",
         &tmux_name,
@@ -413,12 +621,80 @@ pub async fn create_session(
     Ok(tmux_name)
 }
 
+/// Output lines kept from a pane that died at launch.
+const LAUNCH_ERROR_LINES: usize = 5;
+
+/// Parse `#{pane_dead} #{pane_dead_status}`: `Some(exit code)` once the
+/// pane has exited, `None` while it runs.
+pub fn parse_dead_pane(line: &str) -> Option<Option<i32>> {
+    let mut fields = line.split_whitespace();
+    if fields.next()? != "1" {
+        return None;
+    }
+    Some(fields.next().and_then(|code| code.parse().ok()))
+}
+
+/// Why a pane died at launch: its exit status on the first line, then the
+/// last lines it printed, without tmux's own "Pane is dead" notice.
+pub fn launch_error_message(exit_code: Option<i32>, capture: &str) -> String {
+    let lines: Vec<&str> = capture
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty() && !line.starts_with("Pane is dead"))
+        .collect();
+    let status = match exit_code {
+        // The shell's own codes; the pane may not have caught its message.
+        Some(126) => "exited with status 126 (not executable)".to_string(),
+        Some(127) => "exited with status 127 (command not found)".to_string(),
+        Some(code) => format!("exited with status {code}"),
+        None => "exited".to_string(),
+    };
+    let tail = &lines[lines.len().saturating_sub(LAUNCH_ERROR_LINES)..];
+    if tail.is_empty() {
+        format!("{status} at launch, printing nothing")
+    } else {
+        format!("{status}\n{}", tail.join("\n"))
+    }
+}
+
+/// The line of a launch error most worth showing on its own: the agent's
+/// last output, or the exit status if it printed nothing.
+pub fn launch_error_summary(error: &str) -> &str {
+    error.lines().last().unwrap_or(error)
+}
+
+/// Give a freshly created session `wait` to start, then report why its
+/// agent died if it already has. `None` while it is still running.
+pub async fn check_launch(tmux_name: &str, wait: Duration) -> Option<String> {
+    tokio::time::sleep(wait).await;
+    let output = run_cmd_timeout(&mut tmux_cmd([
+        "display-message",
+        "-p",
+        "-t",
+        tmux_name,
+        "#{pane_dead} #{pane_dead_status}",
+    ]))
+    .await
+    .ok()?;
+    if !output.status.success() {
+        // Created a moment ago and gone already.
+        return Some(launch_error_message(None, ""));
+    }
+    let exit_code = parse_dead_pane(&String::from_utf8_lossy(&output.stdout))?;
+    let capture = run_cmd_timeout(&mut tmux_cmd(["capture-pane", "-p", "-t", tmux_name]))
+        .await
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        .unwrap_or_default();
+    Some(launch_error_message(exit_code, &capture))
+}
+
 /// Capture the current pane content of a tmux session.
 pub async fn capture_pane(tmux_name: &str) -> Result<String> {
-    let output =
-        run_cmd_timeout(Command::new("tmux").args(["capture-pane", "-t", tmux_name, "-p", "-e"]))
-            .await
-            .context("Failed to capture tmux pane")?;
+    let output = run_cmd_timeout(&mut tmux_cmd(["capture-pane", "-t", tmux_name, "-p", "-e"]))
+        .await
+        .context("Failed to capture tmux pane")?;
 
     if !output.status.success() {
         return Ok(String::from("[session not available]"));
@@ -429,13 +705,68 @@ pub async fn capture_pane(tmux_name: &str) -> Result<String> {
     Ok(trimmed.to_string())
 }
 
+/// Rows of pane content a preview `rect_height` rows tall shows inside its
+/// border, plus `scrollback` lines of history above them. Zero while the
+/// preview has no rows, meaning "capture the visible pane".
+pub fn preview_capture_lines(rect_height: u16, scrollback: u32) -> u32 {
+    match rect_height.saturating_sub(2) {
+        0 => 0,
+        rows => u32::from(rows) + scrollback,
+    }
+}
+
+/// `capture-pane` bounds for the last `lines` rows. `-S` reaches `lines`
+/// rows into history so a pane shorter than the preview still fills it;
+/// `-E -` ends at the bottom of the visible pane.
+pub fn capture_range_args(lines: u32) -> [String; 4] {
+    [
+        "-S".to_string(),
+        format!("-{lines}"),
+        "-E".to_string(),
+        "-".to_string(),
+    ]
+}
+
+/// The last `lines` lines of `text`.
+pub fn tail_lines(text: &str, lines: usize) -> &str {
+    if lines == 0 {
+        return "";
+    }
+    match text.rmatch_indices('\n').nth(lines - 1) {
+        Some((idx, _)) => &text[idx + 1..],
+        None => text,
+    }
+}
+
+/// Capture the last `lines` rows of a tmux session, trimmed of the blank
+/// padding tmux adds below short output.
+pub async fn capture_pane_tail(tmux_name: &str, lines: u32) -> Result<String> {
+    let mut args = vec![
+        "capture-pane".to_string(),
+        "-t".to_string(),
+        tmux_name.to_string(),
+        "-p".to_string(),
+        "-e".to_string(),
+    ];
+    args.extend(capture_range_args(lines));
+    let output = run_cmd_timeout(&mut tmux_cmd(&args))
+        .await
+        .context("Failed to capture tmux pane")?;
+
+    if !output.status.success() {
+        return Ok(String::from("[session not available]"));
+    }
+
+    let raw = String::from_utf8_lossy(&output.stdout);
+    let trimmed = raw.trim_end_matches('\n');
+    Ok(tail_lines(trimmed, lines as usize).to_string())
+}
+
 /// Capture the scrollback buffer of a tmux session (last 5000 lines).
 pub async fn capture_pane_scrollback(tmux_name: &str) -> Result<String> {
     let output = match tokio::time::timeout(
         CMD_TIMEOUT_LONG,
-        Command::new("tmux")
-            .args(["capture-pane", "-t", tmux_name, "-p", "-e", "-S", "-5000"])
-            .output(),
+        tmux_cmd(["capture-pane", "-t", tmux_name, "-p", "-e", "-S", "-5000"]).output(),
     )
     .await
     {
@@ -462,8 +793,7 @@ pub async fn capture_pane_scrollback(tmux_name: &str) -> Result<String> {
 /// The exit code provides no actionable info (session-not-found is discovered on next tick).
 pub async fn send_keys(tmux_name: &str, key: &str) -> Result<()> {
     let args = send_keys_args(tmux_name, key);
-    let mut child = Command::new("tmux")
-        .args(&args)
+    let mut child = tmux_cmd(&args)
         .spawn()
         .context("Failed to spawn tmux send-keys")?;
     tokio::spawn(async move {
@@ -476,8 +806,7 @@ pub async fn send_keys(tmux_name: &str, key: &str) -> Result<()> {
 /// Fire-and-forget: spawns the subprocess and reaps it in the background.
 pub async fn send_keys_literal(tmux_name: &str, text: &str) -> Result<()> {
     let args = send_keys_literal_args(tmux_name, text);
-    let mut child = Command::new("tmux")
-        .args(&args)
+    let mut child = tmux_cmd(&args)
         .spawn()
         .context("Failed to spawn tmux send-keys -l")?;
     tokio::spawn(async move {
@@ -499,7 +828,7 @@ pub async fn send_text_enter(tmux_name: &str, text: &str) -> Result<()> {
         send_multiline_paste(tmux_name, text).await?;
     } else {
         let literal_args = send_keys_literal_args(tmux_name, text);
-        let status = run_status_timeout(Command::new("tmux").args(&literal_args))
+        let status = run_status_timeout(&mut tmux_cmd(&literal_args))
             .await
             .context("Failed to send literal text to tmux")?;
         if !status.success() {
@@ -510,7 +839,7 @@ pub async fn send_text_enter(tmux_name: &str, text: &str) -> Result<()> {
     tokio::time::sleep(COMPOSE_SUBMIT_ENTER_DELAY).await;
 
     let enter_args = send_enter_args(tmux_name);
-    let status = run_status_timeout(Command::new("tmux").args(&enter_args))
+    let status = run_status_timeout(&mut tmux_cmd(&enter_args))
         .await
         .context("Failed to send Enter to tmux")?;
     if !status.success() {
@@ -520,32 +849,62 @@ pub async fn send_text_enter(tmux_name: &str, text: &str) -> Result<()> {
     Ok(())
 }
 
-/// Send multi-line text via tmux's native paste: write to a temp file,
-/// load into a tmux buffer, and paste with `-p` (bracketed paste mode).
-async fn send_multiline_paste(tmux_name: &str, text: &str) -> Result<()> {
-    let tmp = tempfile::NamedTempFile::new().context("Failed to create temp file for paste")?;
-    tokio::fs::write(tmp.path(), text.as_bytes())
-        .await
-        .context("Failed to write paste temp file")?;
-
-    let path_str = tmp.path().to_string_lossy();
+/// Wait until the pane has drawn something and stopped changing between two
+/// polls, so a freshly started agent is ready for input. Returns false on
+/// timeout.
+pub async fn wait_for_settled_pane(tmux_name: &str, timeout: Duration) -> bool {
+    const POLL: Duration = Duration::from_millis(500);
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut previous: Option<String> = None;
+    while tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(POLL).await;
+        let Ok(content) = capture_pane(tmux_name).await else {
+            continue;
+        };
+        if !content.trim().is_empty() && previous.as_deref() == Some(content.as_str()) {
+            return true;
+        }
+        previous = Some(content);
+    }
+    false
+}
 
-    let status = run_status_timeout(Command::new("tmux").args(["load-buffer", &path_str]))
+/// Load `text` into a new tmux paste buffer through `load-buffer -`'s stdin,
+/// which works the same whether tmux is local or behind ssh.
+pub async fn load_buffer(text: &str) -> Result<()> {
+    let mut child = tmux_cmd(["load-buffer", "-"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .context("Failed to spawn tmux load-buffer")?;
+    let mut stdin = child.stdin.take().context("Failed to get tmux stdin")?;
+    stdin
+        .write_all(text.as_bytes())
         .await
-        .context("Failed to load tmux buffer")?;
+        .context("Failed to write tmux buffer")?;
+    drop(stdin);
+    let status = match tokio::time::timeout(CMD_TIMEOUT, child.wait()).await {
+        Ok(result) => result.context("subprocess failed to execute")?,
+        Err(_) => bail!("subprocess timed out after {}s", CMD_TIMEOUT.as_secs()),
+    };
     if !status.success() {
-        bail!("tmux load-buffer failed for '{tmux_name}'");
+        bail!("tmux load-buffer exited with {status}");
     }
+    Ok(())
+}
 
-    let status = run_status_timeout(Command::new("tmux").args([
-        "paste-buffer",
-        "-t",
-        tmux_name,
-        "-p",
-        "-d",
-    ]))
-    .await
-    .context("Failed to paste tmux buffer")?;
+/// Send multi-line text via tmux's native paste: load it into a tmux
+/// buffer and paste with `-p` (bracketed paste mode).
+async fn send_multiline_paste(tmux_name: &str, text: &str) -> Result<()> {
+    load_buffer(text)
+        .await
+        .with_context(|| format!("tmux load-buffer failed for '{tmux_name}'"))?;
+
+    let status = run_status_timeout(&mut tmux_cmd(["paste-buffer", "-t", tmux_name, "-p", "-d"]))
+        .await
+        .context("Failed to paste tmux buffer")?;
     if !status.success() {
         bail!("tmux paste-buffer failed for '{tmux_name}'");
     }
@@ -562,12 +921,15 @@ fn send_keys_args(tmux_name: &str, key: &str) -> [String; 4] {
     ]
 }
 
-fn send_keys_literal_args(tmux_name: &str, text: &str) -> [String; 5] {
+/// `send-keys -l` for `text`. The `--` keeps text starting with `-` (a
+/// Markdown list, say) from being read as flags.
+fn send_keys_literal_args(tmux_name: &str, text: &str) -> [String; 6] {
     [
         "send-keys".to_string(),
         "-t".to_string(),
         tmux_name.to_string(),
         "-l".to_string(),
+        "--".to_string(),
         text.to_string(),
     ]
 }
@@ -639,9 +1001,168 @@ pub fn apply_tmux_modifiers(base: &str, modifiers: crossterm::event::KeyModifier
     key
 }
 
+/// tmux argument lists that apply a layout preset to `tmux_name`. New panes
+/// start in `cwd`. The agent pane (always top-left, since splits open right or
+/// below) is reselected at the end so previews and messages still target it.
+pub fn layout_commands(tmux_name: &str, cwd: &str, steps: &[LayoutStep]) -> Vec<Vec<String>> {
+    let mut commands: Vec<Vec<String>> = Vec::new();
+    for step in steps {
+        match step {
+            LayoutStep::Split(direction) => {
+                let flag = match direction {
+                    SplitDirection::Horizontal => "-h",
+                    SplitDirection::Vertical => "-v",
+                };
+                commands.push(
+                    ["split-window", flag, "-t", tmux_name, "-c", cwd]
+                        .map(String::from)
+                        .to_vec(),
+                );
+            }
+            LayoutStep::SendKeys(text) => {
+                commands.push(send_keys_literal_args(tmux_name, text).to_vec());
+                commands.push(send_enter_args(tmux_name).to_vec());
+            }
+            LayoutStep::SelectPane(index) => {
+                commands.push(vec![
+                    "select-pane".to_string(),
+                    "-t".to_string(),
+                    format!("{tmux_name}:.{index}"),
+                ]);
+            }
+            LayoutStep::SelectLayout(layout) => {
+                commands.push(
+                    ["select-layout", "-t", tmux_name, layout]
+                        .map(String::from)
+                        .to_vec(),
+                );
+            }
+        }
+    }
+    if !commands.is_empty() {
+        commands.push(vec![
+            "select-pane".to_string(),
+            "-t".to_string(),
+            format!("{tmux_name}:.{{top-left}}"),
+        ]);
+    }
+    commands
+}
+
+/// Apply a layout preset, stopping at the first tmux command that fails.
+pub async fn apply_layout(tmux_name: &str, cwd: &str, steps: &[LayoutStep]) -> Result<()> {
+    for args in layout_commands(tmux_name, cwd, steps) {
+        let status = run_status_timeout(&mut tmux_cmd(&args))
+            .await
+            .context("Failed to apply layout")?;
+        if !status.success() {
+            bail!("tmux {} failed for '{tmux_name}'", args[0]);
+        }
+    }
+    Ok(())
+}
+
+/// A live session's active pane, as listed by `list_session_panes`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionPane {
+    pub tmux_name: String,
+    pub pane_pid: Option<u32>,
+    pub cwd: String,
+    /// Process name tmux reports for the pane (`#{pane_current_command}`).
+    pub command: String,
+}
+
+/// Separates `SESSION_PANE_FORMAT` fields. Session names and paths may hold
+/// spaces, and tmux prints a tab as `_`, so a printable run nothing
+/// realistic contains is used instead.
+const SESSION_PANE_SEP: &str = "|:|";
+
+const SESSION_PANE_FORMAT: &str =
+    "#{session_name}|:|#{pane_pid}|:|#{pane_current_path}|:|#{pane_current_command}";
+
+/// Parse `SESSION_PANE_FORMAT` lines, skipping malformed ones.
+pub(crate) fn parse_session_panes(output: &str) -> Vec<SessionPane> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, SESSION_PANE_SEP);
+            let tmux_name = fields.next().filter(|n| !n.is_empty())?;
+            let pane_pid = fields.next()?.parse().ok();
+            let cwd = fields.next()?;
+            let command = fields.next().unwrap_or_default();
+            Some(SessionPane {
+                tmux_name: tmux_name.to_string(),
+                pane_pid,
+                cwd: cwd.to_string(),
+                command: command.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Every tmux session on the server, with its active pane. Empty when no
+/// server is running.
+pub async fn list_session_panes() -> Vec<SessionPane> {
+    let Ok(output) =
+        run_cmd_timeout(&mut tmux_cmd(["list-sessions", "-F", SESSION_PANE_FORMAT])).await
+    else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    parse_session_panes(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Take over a session started outside hydra: rename it to `new_name` and
+/// give it the settings `create_session` would have.
+pub async fn adopt_session(tmux_name: &str, new_name: &str, agent: &AgentType) -> Result<()> {
+    if tmux_name != new_name {
+        let status =
+            run_status_timeout(&mut tmux_cmd(["rename-session", "-t", tmux_name, new_name]))
+                .await
+                .context("Failed to rename tmux session")?;
+        if !status.success() {
+            bail!("tmux rename-session failed for '{tmux_name}'");
+        }
+    }
+    let _ = run_status_timeout(&mut tmux_cmd([
+        "set-option",
+        "-t",
+        new_name,
+        "remain-on-exit",
+        "on",
+    ]))
+    .await;
+    let _ = run_status_timeout(&mut tmux_cmd([
+        "set-environment",
+        "-t",
+        new_name,
+        "HYDRA_AGENT_TYPE",
+        &agent.to_string().to_lowercase(),
+    ]))
+    .await;
+    Ok(())
+}
+
+/// Name of the local tmux session this process runs inside, or `None`
+/// outside tmux. With an ssh target the local session can't be one hydra
+/// manages, so it isn't looked up.
+pub async fn current_session_name() -> Option<String> {
+    if ssh_target().is_some() {
+        return None;
+    }
+    let pane = std::env::var("TMUX_PANE").ok()?;
+    let output = run_cmd_timeout(&mut tmux_cmd(["display-message", "-p", "-t", &pane, "#S"]))
+        .await
+        .ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !name.is_empty()).then_some(name)
+}
+
 /// Kill a tmux session.
 pub async fn kill_session(tmux_name: &str) -> Result<()> {
-    let status = run_status_timeout(Command::new("tmux").args(["kill-session", "-t", tmux_name]))
+    let status = run_status_timeout(&mut tmux_cmd(["kill-session", "-t", tmux_name]))
         .await
         .context("Failed to kill tmux session")?;
 
@@ -657,6 +1178,149 @@ mod tests {
     use super::*;
     use crossterm::event::{KeyCode, KeyModifiers};
 
+    #[test]
+    fn tmux_argv_runs_tmux_directly_without_ssh() {
+        assert_eq!(
+            tmux_argv(None, &["kill-session", "-t", "hydra-a"]),
+            ["tmux", "kill-session", "-t", "hydra-a"]
+        );
+    }
+
+    #[test]
+    fn tmux_argv_wraps_with_ssh_and_quotes_args() {
+        let argv = tmux_argv(
+            Some("dev@box"),
+            &["send-keys", "-t", "hydra-a", "-l", "--", "it's done"],
+        );
+        let split = argv.iter().position(|a| a == "--").unwrap();
+        assert_eq!(argv[0], "ssh");
+        assert_eq!(argv[split - 1], "dev@box");
+        assert!(argv[..split].contains(&"BatchMode=yes".to_string()));
+        assert_eq!(
+            argv[split + 1..],
+            [
+                "tmux",
+                "send-keys",
+                "-t",
+                "hydra-a",
+                "-l",
+                "--",
+                "'it'\\''s done'"
+            ]
+        );
+    }
+
+    #[test]
+    fn shell_quote_leaves_plain_args_alone() {
+        assert_eq!(
+            shell_quote("hydra-abc123-worker_1"),
+            "hydra-abc123-worker_1"
+        );
+        assert_eq!(shell_quote("/tmp/proj"), "/tmp/proj");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(
+            shell_quote("#{session_name} #{pane_dead}"),
+            "'#{session_name} #{pane_dead}'"
+        );
+        assert_eq!(shell_quote("$HOME;rm"), "'$HOME;rm'");
+    }
+
+    #[test]
+    fn ssh_target_flag_wins_over_env_and_blank_is_local() {
+        assert_eq!(
+            ssh_target_from(Some("a@flag"), Some("b@env")).as_deref(),
+            Some("a@flag")
+        );
+        assert_eq!(
+            ssh_target_from(None, Some(" b@env ")).as_deref(),
+            Some("b@env")
+        );
+        assert_eq!(ssh_target_from(None, Some("  ")), None);
+        assert_eq!(ssh_target_from(None, None), None);
+    }
+
+    #[tokio::test]
+    async fn shell_quote_round_trips_through_sh() {
+        let tricky = ["it's", "a b", "$HOME", "`id`", "\\n", "#{pane_pid}", ""];
+        let script = tricky
+            .iter()
+            .map(|a| format!("printf '%s\\n' {}", shell_quote(a)))
+            .collect::<Vec<_>>()
+            .join("; ");
+        let output = Command::new("sh")
+            .args(["-c", &script])
+            .output()
+            .await
+            .unwrap();
+        let out = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(out.lines().collect::<Vec<_>>(), tricky);
+    }
+
+    #[test]
+    fn parse_session_panes_reads_each_field() {
+        let panes = parse_session_panes(
+            "work|:|4242|:|/home/me/my project|:|claude\nidle|:||:|/tmp|:|zsh\nbroken\n",
+        );
+        assert_eq!(
+            panes,
+            [
+                SessionPane {
+                    tmux_name: "work".to_string(),
+                    pane_pid: Some(4242),
+                    cwd: "/home/me/my project".to_string(),
+                    command: "claude".to_string(),
+                },
+                SessionPane {
+                    tmux_name: "idle".to_string(),
+                    pane_pid: None,
+                    cwd: "/tmp".to_string(),
+                    command: "zsh".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_pane_titles_keeps_first_pane_per_session() {
+        let titles =
+            parse_pane_titles("hydra-a\t✦ Working on it\nhydra-a\tsecond\nhydra-b\t\nbad\n");
+        assert_eq!(titles.len(), 2);
+        assert_eq!(titles["hydra-a"], "✦ Working on it");
+        assert_eq!(titles["hydra-b"], "");
+    }
+
+    // ── preview capture range ────────────────────────────────────────
+
+    #[test]
+    fn capture_range_fits_preview_rows() {
+        // A 30-row preview shows 28 rows inside its border.
+        assert_eq!(preview_capture_lines(30, 0), 28);
+        assert_eq!(capture_range_args(28), ["-S", "-28", "-E", "-"]);
+    }
+
+    #[test]
+    fn capture_range_adds_scrollback() {
+        assert_eq!(preview_capture_lines(30, 100), 128);
+        assert_eq!(
+            capture_range_args(preview_capture_lines(30, 100)),
+            ["-S", "-128", "-E", "-"]
+        );
+    }
+
+    #[test]
+    fn capture_range_is_empty_without_preview_rows() {
+        assert_eq!(preview_capture_lines(0, 0), 0);
+        assert_eq!(preview_capture_lines(2, 500), 0);
+    }
+
+    #[test]
+    fn tail_lines_keeps_last_lines() {
+        assert_eq!(tail_lines("a\nb\nc\nd", 2), "c\nd");
+        assert_eq!(tail_lines("a\nb", 5), "a\nb");
+        assert_eq!(tail_lines("a\nb", 2), "a\nb");
+        assert_eq!(tail_lines("a\nb", 0), "");
+    }
+
     // ── keycode_to_tmux: character keys ──────────────────────────────
 
     #[test]
@@ -906,11 +1570,64 @@ mod tests {
                 "-t".to_string(),
                 "hydra-test-alpha".to_string(),
                 "-l".to_string(),
+                "--".to_string(),
                 "echo hello".to_string()
             ]
         );
     }
 
+    #[test]
+    fn send_keys_literal_args_keep_leading_dash_as_text() {
+        let args = send_keys_literal_args("hydra-test-alpha", "- fix the build");
+        assert_eq!(args[4..], ["--".to_string(), "- fix the build".to_string()]);
+    }
+
+    // ── layout_commands ──────────────────────────────────────────────
+
+    #[test]
+    fn layout_commands_empty_preset_runs_nothing() {
+        assert!(layout_commands("hydra-test-alpha", "/tmp", &[]).is_empty());
+    }
+
+    #[test]
+    fn layout_commands_split_and_send_keys_sequence() {
+        let steps = [
+            LayoutStep::Split(SplitDirection::Vertical),
+            LayoutStep::SendKeys("tail -f app.log".to_string()),
+            LayoutStep::Split(SplitDirection::Horizontal),
+            LayoutStep::SelectLayout("tiled".to_string()),
+        ];
+        let commands = layout_commands("hydra-test-alpha", "/work", &steps);
+        let lines: Vec<String> = commands.iter().map(|c| c.join(" ")).collect();
+        assert_eq!(
+            lines,
+            [
+                "split-window -v -t hydra-test-alpha -c /work",
+                "send-keys -t hydra-test-alpha -l -- tail -f app.log",
+                "send-keys -t hydra-test-alpha Enter",
+                "split-window -h -t hydra-test-alpha -c /work",
+                "select-layout -t hydra-test-alpha tiled",
+                "select-pane -t hydra-test-alpha:.{top-left}",
+            ]
+        );
+    }
+
+    #[test]
+    fn layout_commands_send_keys_text_is_one_literal_arg() {
+        let steps = [LayoutStep::SendKeys("echo 'a b'; ls".to_string())];
+        let commands = layout_commands("hydra-test-alpha", "/tmp", &steps);
+        assert_eq!(commands[0].last().unwrap(), "echo 'a b'; ls");
+        assert_eq!(commands[0][3], "-l");
+    }
+
+    #[test]
+    fn layout_commands_select_pane_targets_index() {
+        let steps = [LayoutStep::SelectPane(2)];
+        let commands = layout_commands("hydra-test-alpha", "/tmp", &steps);
+        assert_eq!(commands[0], ["select-pane", "-t", "hydra-test-alpha:.2"]);
+        assert_eq!(commands.len(), 2, "agent pane is reselected last");
+    }
+
     // ── TmuxSessionManager agent cache ───────────────────────────────
 
     #[test]
@@ -986,6 +1703,110 @@ mod tests {
         );
     }
 
+    // ── Launch failures ─────────────────────────────────────────────
+
+    #[test]
+    fn parse_dead_pane_reads_the_exit_status() {
+        assert_eq!(parse_dead_pane("1 127\n"), Some(Some(127)));
+        assert_eq!(parse_dead_pane("1 \n"), Some(None));
+        assert_eq!(parse_dead_pane("0 \n"), None);
+        assert_eq!(parse_dead_pane(""), None);
+    }
+
+    #[test]
+    fn launch_error_message_keeps_the_last_output_lines() {
+        let capture = "\
+bash: line 1: exec: claude: not found
+
+
+Pane is dead (status 127, Fri Oct 16 15:43:48 2026)
+";
+        let error = launch_error_message(Some(127), capture);
+        assert_eq!(
+            error,
+            "exited with status 127 (command not found)\nbash: line 1: exec: claude: not found"
+        );
+        assert_eq!(
+            launch_error_summary(&error),
+            "bash: line 1: exec: claude: not found"
+        );
+
+        let long: String = (1..=8).map(|i| format!("line {i}\n")).collect();
+        let error = launch_error_message(Some(1), &long);
+        assert_eq!(
+            error.lines().collect::<Vec<_>>(),
+            [
+                "exited with status 1",
+                "line 4",
+                "line 5",
+                "line 6",
+                "line 7",
+                "line 8"
+            ]
+        );
+    }
+
+    #[test]
+    fn launch_error_message_without_output_names_the_status() {
+        let error = launch_error_message(Some(2), "Pane is dead (status 2, now)\n\n");
+        assert_eq!(error, "exited with status 2 at launch, printing nothing");
+        assert_eq!(launch_error_summary(&error), error);
+        assert_eq!(
+            launch_error_message(None, ""),
+            "exited at launch, printing nothing"
+        );
+    }
+
+    // ── Server health ───────────────────────────────────────────────
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn classify_health_server_down_when_everything_vanished() {
+        let recorded = names(&["hydra-p-a", "hydra-p-b"]);
+        assert_eq!(
+            classify_health(false, &recorded, &HashSet::new()),
+            TmuxHealth::ServerDown(2)
+        );
+    }
+
+    #[test]
+    fn classify_health_sessions_gone_while_server_is_up() {
+        let recorded = names(&["hydra-p-a", "hydra-p-b"]);
+        let live: HashSet<String> = names(&["hydra-p-a"]).into_iter().collect();
+        assert_eq!(
+            classify_health(true, &recorded, &live),
+            TmuxHealth::SessionsGone(1)
+        );
+        assert_eq!(
+            classify_health(true, &recorded, &HashSet::new()),
+            TmuxHealth::SessionsGone(2)
+        );
+    }
+
+    #[test]
+    fn classify_health_ok_when_nothing_is_missing() {
+        let recorded = names(&["hydra-p-a"]);
+        let live: HashSet<String> = names(&["hydra-p-a", "other"]).into_iter().collect();
+        assert_eq!(classify_health(true, &recorded, &live), TmuxHealth::Ok);
+        // Nothing recorded means nothing to lose, even with no server.
+        assert_eq!(classify_health(false, &[], &HashSet::new()), TmuxHealth::Ok);
+    }
+
+    #[test]
+    fn is_server_down_error_matches_only_server_failures() {
+        assert!(is_server_down_error(
+            "no server running on /tmp/tmux-1000/default\n"
+        ));
+        assert!(is_server_down_error(
+            "Error connecting to /tmp/tmux-1000/default (No such file or directory)\n"
+        ));
+        assert!(!is_server_down_error("can't find session: hydra-p-a\n"));
+        assert!(!is_server_down_error(""));
+    }
+
     // ── Default trait implementations ───────────────────────────────
 
     /// Minimal SessionManager impl to test default trait methods.
@@ -4,9 +4,10 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::process::{Child, Command};
+use tokio::process::Child;
 use tokio::sync::{broadcast, oneshot};
 
+use crate::config::LayoutStep;
 use crate::session::{parse_session_name, AgentType, Session};
 use crate::tmux::SessionManager;
 
@@ -158,13 +159,24 @@ fn quote_tmux_arg(s: &str) -> String {
     out
 }
 
+/// Join a tmux argument list into a control-mode command line, quoting
+/// every argument after the command name.
+fn layout_command_line(args: &[String]) -> String {
+    let mut line = args[0].clone();
+    for arg in &args[1..] {
+        line.push(' ');
+        line.push_str(&quote_tmux_arg(arg));
+    }
+    line
+}
+
 fn send_keys_command(tmux_name: &str, key: &str) -> String {
     format!("send-keys -t {tmux_name} {key}")
 }
 
 fn send_keys_literal_command(tmux_name: &str, text: &str) -> String {
     let quoted = quote_tmux_arg(text);
-    format!("send-keys -t {tmux_name} -l {quoted}")
+    format!("send-keys -t {tmux_name} -l -- {quoted}")
 }
 
 fn send_enter_command(tmux_name: &str) -> String {
@@ -216,8 +228,7 @@ impl TmuxControlConnection {
         let pid = std::process::id();
         let ctrl_session_name = format!("_hydra_ctrl_{pid}");
 
-        let mut child = Command::new("tmux")
-            .args(["-C", "new-session", "-s", &ctrl_session_name])
+        let mut child = crate::tmux::tmux_cmd(["-C", "new-session", "-s", &ctrl_session_name])
             .stdin(std::process::Stdio::piped())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::null())
@@ -416,8 +427,7 @@ impl TmuxControlConnection {
 
     /// Shut down the control mode connection.
     pub async fn shutdown(&self) {
-        let _ = tokio::process::Command::new("tmux")
-            .args(["kill-session", "-t", &self.ctrl_session_name])
+        let _ = crate::tmux::tmux_cmd(["kill-session", "-t", &self.ctrl_session_name])
             .output()
             .await;
     }
@@ -449,9 +459,7 @@ impl Drop for TmuxControlConnection {
     fn drop(&mut self) {
         // Best-effort cleanup — kill the control session
         let name = self.ctrl_session_name.clone();
-        let _ = std::process::Command::new("tmux")
-            .args(["kill-session", "-t", &name])
-            .output();
+        let _ = crate::tmux::tmux_std_cmd(["kill-session", "-t", &name]).output();
     }
 }
 
@@ -712,6 +720,25 @@ impl SessionManager for ControlModeSessionManager {
         Ok(trimmed.to_string())
     }
 
+    async fn capture_pane_tail(&self, tmux_name: &str, lines: u32) -> Result<String> {
+        let resp = self
+            .conn
+            .send_command(&format!(
+                "capture-pane -t {tmux_name} -p -e {}",
+                crate::tmux::capture_range_args(lines).join(" ")
+            ))
+            .await
+            .context("Failed to capture tmux pane")?;
+
+        if !resp.success {
+            return Ok(String::from("[session not available]"));
+        }
+
+        let decoded = decode_octal_escapes(&resp.output);
+        let trimmed = decoded.trim_end_matches('\n');
+        Ok(crate::tmux::tail_lines(trimmed, lines as usize).to_string())
+    }
+
     async fn kill_session(&self, tmux_name: &str) -> Result<()> {
         let resp = self
             .conn
@@ -739,6 +766,21 @@ impl SessionManager for ControlModeSessionManager {
         Ok(())
     }
 
+    async fn apply_layout(&self, tmux_name: &str, cwd: &str, steps: &[LayoutStep]) -> Result<()> {
+        for args in crate::tmux::layout_commands(tmux_name, cwd, steps) {
+            let line = layout_command_line(&args);
+            let resp = self
+                .conn
+                .send_command(&line)
+                .await
+                .context("Failed to apply layout")?;
+            if !resp.success {
+                bail!("tmux {} failed for '{tmux_name}': {}", args[0], resp.output);
+            }
+        }
+        Ok(())
+    }
+
     async fn send_keys_literal(&self, tmux_name: &str, text: &str) -> Result<()> {
         self.conn
             .send_command_fire_and_forget(&send_keys_literal_command(tmux_name, text))
@@ -755,21 +797,29 @@ impl SessionManager for ControlModeSessionManager {
         // We can't use send-keys -l for multi-line text in control mode
         // because newlines in the command text break the line-based protocol.
         if text.contains('\n') {
-            let tmp =
-                tempfile::NamedTempFile::new().context("Failed to create temp file for paste")?;
-            tokio::fs::write(tmp.path(), text.as_bytes())
-                .await
-                .context("Failed to write paste temp file")?;
-
-            let path_str = tmp.path().to_string_lossy();
-
-            let resp = self
-                .conn
-                .send_command(&format!("load-buffer {path_str}"))
-                .await
-                .context("Failed to load tmux buffer")?;
-            if !resp.success {
-                bail!("tmux load-buffer failed for '{tmux_name}': {}", resp.output);
+            if crate::tmux::ssh_target().is_some() {
+                // A temp file here would be on the wrong machine; pipe the
+                // text to the remote tmux instead.
+                crate::tmux::load_buffer(text)
+                    .await
+                    .with_context(|| format!("tmux load-buffer failed for '{tmux_name}'"))?;
+            } else {
+                let tmp = tempfile::NamedTempFile::new()
+                    .context("Failed to create temp file for paste")?;
+                tokio::fs::write(tmp.path(), text.as_bytes())
+                    .await
+                    .context("Failed to write paste temp file")?;
+
+                let path_str = tmp.path().to_string_lossy();
+
+                let resp = self
+                    .conn
+                    .send_command(&format!("load-buffer {path_str}"))
+                    .await
+                    .context("Failed to load tmux buffer")?;
+                if !resp.success {
+                    bail!("tmux load-buffer failed for '{tmux_name}': {}", resp.output);
+                }
             }
 
             let resp = self
@@ -814,6 +864,21 @@ impl SessionManager for ControlModeSessionManager {
         Ok(())
     }
 
+    async fn batch_pane_titles(&self) -> Option<std::collections::HashMap<String, String>> {
+        let resp = self
+            .conn
+            .send_command(&format!(
+                "list-panes -a -F '{}'",
+                crate::tmux::PANE_TITLE_FORMAT
+            ))
+            .await
+            .ok()?;
+        if !resp.success {
+            return None;
+        }
+        Some(crate::tmux::parse_pane_titles(&resp.output))
+    }
+
     async fn batch_pane_status(&self) -> Option<std::collections::HashMap<String, (bool, u64)>> {
         let resp = self
             .conn
@@ -970,6 +1035,15 @@ mod tests {
         assert_eq!(quote_tmux_arg(""), "''");
     }
 
+    #[test]
+    fn layout_command_line_quotes_arguments() {
+        let args = ["send-keys", "-t", "hydra-x", "-l", "tail -f $LOG"].map(String::from);
+        assert_eq!(
+            layout_command_line(&args),
+            "send-keys '-t' 'hydra-x' '-l' 'tail -f $LOG'"
+        );
+    }
+
     // ── send command builders ────────────────────────────────────────
 
     #[test]
@@ -991,7 +1065,7 @@ mod tests {
     fn send_keys_literal_command_quotes_text() {
         assert_eq!(
             send_keys_literal_command("hydra-test-alpha", "it's fine"),
-            "send-keys -t hydra-test-alpha -l 'it'\\''s fine'"
+            "send-keys -t hydra-test-alpha -l -- 'it'\\''s fine'"
         );
     }
 
@@ -3,10 +3,12 @@ pub mod state;
 mod conversation;
 mod diff;
 mod help;
+mod markdown;
 mod modals;
 mod preview;
 mod sidebar;
 mod stats;
+mod theme;
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -16,10 +18,13 @@ use ratatui::{
 use crate::app::{Mode, UiApp};
 
 // Re-exports for backward compatibility (benchmarks, lib.rs)
-pub use conversation::render_conversation;
+pub use conversation::{
+    render_conversation, render_conversation_with_view, tool_result_ids, ToolResultView,
+};
 pub use diff::build_diff_tree_lines;
 pub use preview::draw_preview;
-pub use sidebar::draw_sidebar;
+pub use sidebar::{draw_sidebar, spinner_frame};
+pub(crate) use sidebar::{session_list_area, session_list_items};
 pub use stats::draw_stats;
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -61,7 +66,11 @@ pub fn draw(frame: &mut Frame, app: &UiApp) {
     // Draw modal overlays
     match app.mode {
         Mode::NewSessionAgent => modals::draw_agent_select(frame, app),
+        Mode::NewSessionName => modals::draw_session_name(frame, app),
         Mode::ConfirmDelete => modals::draw_confirm_delete(frame, app),
+        Mode::ConfirmQuit => modals::draw_confirm_quit(frame, app),
+        Mode::EditNote => modals::draw_edit_note(frame, app),
+        Mode::Help => modals::draw_help_overlay(frame, app),
         _ => {}
     }
 
@@ -81,11 +90,6 @@ pub fn draw(frame: &mut Frame, app: &UiApp) {
     }
 }
 
-/// Truncate a string to at most `max` characters (Unicode-safe).
-pub(crate) fn truncate_chars(s: &str, max: usize) -> String {
-    s.chars().take(max).collect()
-}
-
 #[cfg(test)]
 fn inset_rect(area: Rect, margin: u16) -> Rect {
     let double = margin.saturating_mul(2);
@@ -127,6 +131,7 @@ mod tests {
         let (process_state, agent_state) = match visual_status {
             VisualStatus::Idle => (ProcessState::Alive, AgentState::Idle),
             VisualStatus::Running(_s) => (ProcessState::Alive, AgentState::Thinking),
+            VisualStatus::NeedsInput => (ProcessState::Alive, AgentState::WaitingForInput),
             VisualStatus::Exited => (
                 ProcessState::Exited {
                     exit_code: None,
@@ -135,6 +140,7 @@ mod tests {
                 AgentState::Idle,
             ),
             VisualStatus::Booting => (ProcessState::Booting, AgentState::Idle),
+            VisualStatus::Stopped => (ProcessState::Stopped, AgentState::Idle),
         };
         Session {
             name: name.to_string(),
@@ -198,6 +204,52 @@ mod tests {
         insta::assert_snapshot!(output);
     }
 
+    #[test]
+    fn browse_mode_empty_state_panel() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let app = make_app();
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        assert!(output.contains("No sessions for this project."));
+        insta::assert_snapshot!(output);
+    }
+
+    #[test]
+    fn browse_mode_server_down_banner() {
+        let backend = TestBackend::new(100, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        snap(&mut app).tmux_health = crate::tmux::TmuxHealth::ServerDown(3);
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        assert!(output.contains("tmux server not running  V: revive 3 recorded session(s)"));
+        assert!(output.contains("Press V to revive the recorded sessions."));
+        assert!(!output.contains("No sessions for this project."));
+    }
+
+    #[test]
+    fn new_session_name_modal() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        app.mode = Mode::NewSessionName;
+        app.agent_selection = 0;
+        app.new_session_name = "fix-bug".to_string();
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        insta::assert_snapshot!(output);
+    }
+
     #[test]
     fn new_session_agent_modal() {
         let backend = TestBackend::new(80, 24);
@@ -229,6 +281,90 @@ mod tests {
         insta::assert_snapshot!(output);
     }
 
+    #[test]
+    fn edit_note_modal() {
+        use crossterm::event::{KeyCode, KeyEvent};
+
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        snap(&mut app).sessions = vec![
+            make_session("alpha", AgentType::Claude),
+            make_session("bravo", AgentType::Codex),
+        ];
+        snap(&mut app).session_notes = std::collections::HashMap::from([
+            ("alpha".to_string(), "waiting on PR review".to_string()),
+            ("bravo".to_string(), "exploring approach B".to_string()),
+        ]);
+        app.handle_key(KeyEvent::from(KeyCode::Char('N')));
+        assert_eq!(app.mode, Mode::EditNote);
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        insta::assert_snapshot!(output);
+    }
+
+    #[test]
+    fn sidebar_shows_why_an_exited_session_died_at_launch() {
+        let backend = TestBackend::new(100, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        snap(&mut app).sessions = vec![
+            make_session_with_status("alpha", AgentType::Claude, VisualStatus::Exited),
+            make_session("bravo", AgentType::Codex),
+        ];
+        snap(&mut app).launch_errors = std::collections::HashMap::from([
+            (
+                "alpha".to_string(),
+                "exited with status 127\nzsh: command not found: claude".to_string(),
+            ),
+            ("bravo".to_string(), "exited with status 1".to_string()),
+        ]);
+
+        terminal
+            .draw(|f| super::draw_sidebar(f, &app, f.area()))
+            .unwrap();
+        let output = buffer_to_string(&terminal);
+
+        assert!(
+            output.contains("alpha [Claude] ✗ zsh: command not found: claude"),
+            "{output}"
+        );
+        // A live session's stale launch error stays hidden.
+        assert!(!output.contains("exited with status 1"), "{output}");
+    }
+
+    #[test]
+    fn confirm_quit_modal() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        let alpha = make_session("alpha", AgentType::Claude);
+        snap(&mut app).session_stats.insert(
+            alpha.tmux_name.clone(),
+            crate::logs::SessionStats {
+                tokens_in: 1_000_000,
+                tokens_out: 200_000,
+                ..Default::default()
+            },
+        );
+        snap(&mut app).sessions = vec![
+            alpha,
+            make_session("bravo", AgentType::Codex),
+            Session::stopped("test", "parked", AgentType::Gemini),
+        ];
+        app.mode = Mode::ConfirmQuit;
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        insta::assert_snapshot!(output);
+    }
+
     #[test]
     fn compose_mode() {
         let backend = TestBackend::new(80, 24);
@@ -287,6 +423,57 @@ mod tests {
         insta::assert_snapshot!(output);
     }
 
+    #[test]
+    fn browse_mode_todos_preview() {
+        use crate::logs::{TodoItem, TodoStatus};
+        let backend = TestBackend::new(80, 12);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        let s = snap(&mut app);
+        s.sessions = vec![make_session("w1", AgentType::Claude)];
+        let todo = |content: &str, status| TodoItem {
+            content: content.to_string(),
+            status,
+        };
+        s.session_stats.insert(
+            "hydra-testproj-w1".to_string(),
+            crate::logs::SessionStats {
+                todos: vec![
+                    todo("Write parser", TodoStatus::Completed),
+                    todo("Add tests", TodoStatus::InProgress),
+                    todo("Update docs", TodoStatus::Pending),
+                ],
+                ..Default::default()
+            },
+        );
+        app.selected = 0;
+        app.preview_view = crate::app::PreviewView::Todos;
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        insta::assert_snapshot!(output);
+    }
+
+    #[test]
+    fn browse_mode_stopped_session() {
+        let backend = TestBackend::new(80, 16);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        snap(&mut app).sessions = vec![
+            make_session_with_status("idle-one", AgentType::Claude, VisualStatus::Idle),
+            make_session_with_status("paused", AgentType::Codex, VisualStatus::Stopped),
+        ];
+        app.selected = 1;
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        insta::assert_snapshot!(output);
+    }
+
     #[test]
     fn browse_mode_with_task_elapsed() {
         let backend = TestBackend::new(80, 24);
@@ -306,6 +493,149 @@ mod tests {
         insta::assert_snapshot!(output);
     }
 
+    #[test]
+    fn browse_mode_with_tags_and_filter() {
+        let backend = TestBackend::new(140, 12);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        let s = snap(&mut app);
+        s.sessions = vec![make_session("worker-1", AgentType::Claude)];
+        s.session_tags.insert(
+            "worker-1".to_string(),
+            vec!["api".to_string(), "infra".to_string()],
+        );
+        s.tag_filter = Some("infra".to_string());
+        app.selected = 0;
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        insta::assert_snapshot!(output);
+    }
+
+    #[test]
+    fn browse_mode_with_status_annotation() {
+        let backend = TestBackend::new(140, 12);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        let s = snap(&mut app);
+        let session = make_session("worker-1", AgentType::Claude);
+        s.status_annotations
+            .insert(session.tmux_name.clone(), "CI: passing".to_string());
+        s.sessions = vec![session];
+        app.selected = 0;
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        insta::assert_snapshot!(output);
+    }
+
+    #[test]
+    fn browse_mode_with_session_needing_input() {
+        let backend = TestBackend::new(140, 12);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        let s = snap(&mut app);
+        s.sessions = vec![
+            make_session_with_status("blocked", AgentType::Claude, VisualStatus::NeedsInput),
+            make_session_with_status("ready", AgentType::Claude, VisualStatus::Idle),
+        ];
+        app.selected = 0;
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        insta::assert_snapshot!(output);
+    }
+
+    #[test]
+    fn browse_mode_with_shared_log_warning() {
+        let backend = TestBackend::new(140, 12);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        let s = snap(&mut app);
+        s.sessions = vec![
+            make_session("g1", AgentType::Gemini),
+            make_session("g2", AgentType::Gemini),
+            make_session("g3", AgentType::Gemini),
+        ];
+        for (name, path) in [
+            ("g1", "/chats/a.json"),
+            ("g2", "/chats/a.json"),
+            ("g3", "/chats/b.json"),
+        ] {
+            s.log_paths
+                .insert(format!("hydra-testproj-{name}"), path.to_string());
+        }
+        app.selected = 0;
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        insta::assert_snapshot!(output);
+    }
+
+    #[test]
+    fn browse_mode_with_activity_counts() {
+        let backend = TestBackend::new(140, 12);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        let s = snap(&mut app);
+        s.sessions = vec![make_session("w1", AgentType::Claude)];
+        s.session_stats.insert(
+            "hydra-testproj-w1".to_string(),
+            crate::logs::SessionStats {
+                edits: 12,
+                bash_cmds: 4,
+                web_tools: 2,
+                ..Default::default()
+            },
+        );
+        app.selected = 0;
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        insta::assert_snapshot!(output);
+    }
+
+    #[test]
+    fn browse_mode_stats_preview() {
+        let backend = TestBackend::new(100, 16);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        let s = snap(&mut app);
+        s.sessions = vec![make_session("w1", AgentType::Claude)];
+        let mut stats = crate::logs::SessionStats {
+            turns: 7,
+            tokens_in: 1_200_000,
+            tokens_out: 45_000,
+            tokens_cache_read: 800_000,
+            edits: 5,
+            bash_cmds: 3,
+            last_model: Some("claude-sonnet-4-20250514".to_string()),
+            ..Default::default()
+        };
+        stats.touch_modified_file("src/main.rs".to_string());
+        stats.touch_file("src/lib.rs".to_string());
+        s.session_stats
+            .insert("hydra-testproj-w1".to_string(), stats);
+        app.selected = 0;
+        app.preview_view = crate::app::PreviewView::Stats;
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        insta::assert_snapshot!(output);
+    }
+
     #[test]
     fn browse_mode_with_last_messages() {
         let backend = TestBackend::new(80, 24);
@@ -389,6 +719,20 @@ mod tests {
         insta::assert_snapshot!(output);
     }
 
+    #[test]
+    fn help_overlay() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut app = make_app();
+        app.mode = Mode::Help;
+
+        terminal.draw(|f| super::draw(f, &app)).unwrap();
+        let output = buffer_to_string(&terminal);
+
+        insta::assert_snapshot!(output);
+    }
+
     #[test]
     fn confirm_delete_no_sessions() {
         let backend = TestBackend::new(80, 24);
@@ -406,18 +750,6 @@ mod tests {
 
     // ── Unit tests for helper functions ───────────────────────────────
 
-    #[test]
-    fn truncate_chars_ascii() {
-        assert_eq!(super::truncate_chars("hello", 3), "hel");
-        assert_eq!(super::truncate_chars("hi", 10), "hi");
-    }
-
-    #[test]
-    fn truncate_chars_unicode() {
-        assert_eq!(super::truncate_chars("café", 3), "caf");
-        assert_eq!(super::truncate_chars("日本語テスト", 3), "日本語");
-    }
-
     // ── Snapshot with deletion-only diff ─────────────────────────────
 
     #[test]
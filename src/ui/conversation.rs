@@ -1,11 +1,17 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
 };
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
-use crate::logs::ConversationEntry;
+use super::markdown::markdown_lines;
+use crate::config::PreviewDensity;
+use crate::logs::{
+    format_run_result, todo_progress, ConversationEntry, Currency, TodoItem, TodoStatus,
+};
 
 fn push_component_title(lines: &mut Vec<Line<'static>>, title: &str, style: Style) {
     if !lines.is_empty() {
@@ -20,13 +26,44 @@ fn push_component_body(lines: &mut Vec<Line<'static>>, text: &str, style: Style)
     }
 }
 
+/// Lines of an expanded tool result shown before the rest is cut.
+const MAX_EXPANDED_LINES: usize = 500;
+
+/// Tool-result cursor and expansion state for `render_conversation_with_view`.
+#[derive(Default)]
+pub struct ToolResultView<'a> {
+    /// Index, among the shown tool results, of the one under the cursor.
+    pub selected: Option<usize>,
+    /// Tool-call ids of the results shown in full.
+    pub expanded: Option<&'a HashSet<String>>,
+    /// Full result text by tool-call id, once read from the log.
+    pub texts: Option<&'a HashMap<String, String>>,
+}
+
+/// Per-render progress through the shown tool results.
+struct ToolResultCursor<'a> {
+    view: &'a ToolResultView<'a>,
+    next_index: usize,
+    selected_line: Option<usize>,
+}
+
 fn push_tool_result_component(
     lines: &mut Vec<Line<'static>>,
+    title: &str,
     filenames: &[String],
     summary: Option<&str>,
     style: Style,
 ) {
-    push_component_title(lines, "TOOL RESULT", style);
+    push_component_title(lines, title, style);
+    push_tool_result_files(lines, filenames, style);
+    if let Some(summary) = summary {
+        for line in summary.lines().take(3) {
+            lines.push(Line::from(Span::styled(format!("  > {line}"), style)));
+        }
+    }
+}
+
+fn push_tool_result_files(lines: &mut Vec<Line<'static>>, filenames: &[String], style: Style) {
     let preview_count = filenames.len().min(4);
     for file in filenames.iter().take(preview_count) {
         lines.push(Line::from(Span::styled(format!("  - {file}"), style)));
@@ -37,13 +74,60 @@ fn push_tool_result_component(
             style.add_modifier(Modifier::DIM),
         )));
     }
-    if let Some(summary) = summary {
-        for line in summary.lines().take(3) {
-            lines.push(Line::from(Span::styled(format!("  > {line}"), style)));
-        }
+}
+
+/// A tool result under the cursor and/or expanded: `Ok` holds the full
+/// text, `Err` the usual summary.
+fn push_selectable_tool_result(
+    lines: &mut Vec<Line<'static>>,
+    title: &str,
+    filenames: &[String],
+    body: Result<&str, Option<&str>>,
+    selected: bool,
+    style: Style,
+) {
+    let (title, title_style) = if selected {
+        (format!("▶ {title}"), style.add_modifier(Modifier::REVERSED))
+    } else {
+        (title.to_string(), style)
+    };
+    push_component_title(lines, &title, title_style);
+    push_tool_result_files(lines, filenames, style);
+    let (text, max_lines) = match body {
+        Ok(text) => (text, MAX_EXPANDED_LINES),
+        Err(summary) => (summary.unwrap_or_default(), 3),
+    };
+    for line in text.lines().take(max_lines) {
+        lines.push(Line::from(Span::styled(format!("  > {line}"), style)));
+    }
+    let total = text.lines().count();
+    if body.is_ok() && total > max_lines {
+        lines.push(Line::from(Span::styled(
+            format!("  ... +{} more lines", total - max_lines),
+            style.add_modifier(Modifier::DIM),
+        )));
     }
 }
 
+/// One TODO item as a checkbox line: done items dimmed, the one in
+/// progress highlighted.
+pub(crate) fn todo_line(todo: &TodoItem, indent: &str) -> Line<'static> {
+    let (marker, style) = match todo.status {
+        TodoStatus::Completed => ("[x]", Style::default().add_modifier(Modifier::DIM)),
+        TodoStatus::InProgress => (
+            "[>]",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        TodoStatus::Pending => ("[ ]", Style::default()),
+    };
+    Line::from(Span::styled(
+        format!("{indent}{marker} {}", todo.content),
+        style,
+    ))
+}
+
 fn push_unparsed_component(
     lines: &mut Vec<Line<'static>>,
     reason: &str,
@@ -57,104 +141,180 @@ fn push_unparsed_component(
     ]));
 }
 
+/// Wrap `text` to `width` terminal columns. The first line starts with
+/// `indent`; continuation lines get `indent` plus two spaces so they hang
+/// under it. Breaks after spaces where possible and splits only between
+/// grapheme clusters, so wide characters are never cut in half. `None`
+/// leaves the text on one line.
+pub(crate) fn wrap_with_indent(text: &str, width: Option<usize>, indent: &str) -> Vec<String> {
+    let first = format!("{indent}{text}");
+    let Some(width) = width else {
+        return vec![first];
+    };
+    if first.width() <= width {
+        return vec![first];
+    }
+
+    let hang = format!("{indent}  ");
+    let mut lines = Vec::new();
+    let mut line = indent.to_string();
+    let mut line_width = indent.width();
+    let mut has_content = false;
+    for word in text.split_inclusive(' ') {
+        let word_width = word.trim_end().width();
+        if has_content && line_width + word_width > width {
+            lines.push(line.trim_end().to_string());
+            line = hang.clone();
+            line_width = hang.width();
+            has_content = false;
+        }
+        // A word longer than a whole line is split at the column limit;
+        // the indent always keeps at least one column for text.
+        for grapheme in word.graphemes(true) {
+            let w = grapheme.width();
+            if has_content && line_width + w > width && grapheme != " " {
+                lines.push(std::mem::replace(&mut line, hang.clone()));
+                line_width = hang.width();
+            }
+            line.push_str(grapheme);
+            line_width += w;
+            has_content = true;
+        }
+    }
+    let line = line.trim_end();
+    if line.width() > hang.width() || lines.is_empty() {
+        lines.push(line.to_string());
+    }
+    lines
+}
+
+/// Title styles for each entry kind.
+struct Styles {
+    user_title: Style,
+    assistant_title: Style,
+    tool_title: Style,
+    queue_title: Style,
+    progress_title: Style,
+    system_title: Style,
+    snapshot_title: Style,
+    body: Style,
+    dim: Style,
+    warn: Style,
+}
+
+impl Styles {
+    fn new() -> Self {
+        let title = |color| Style::default().fg(color).add_modifier(Modifier::BOLD);
+        Self {
+            user_title: title(Color::Cyan),
+            assistant_title: title(Color::LightGreen),
+            tool_title: title(Color::Yellow),
+            queue_title: title(Color::Magenta),
+            progress_title: title(Color::LightBlue),
+            system_title: title(Color::LightMagenta),
+            snapshot_title: title(Color::LightCyan),
+            body: Style::default(),
+            dim: Style::default().add_modifier(Modifier::DIM),
+            warn: Style::default().fg(Color::Magenta),
+        }
+    }
+}
+
+/// Whether `entry` is shown at `density`. Compact keeps only user and
+/// assistant messages, including those from subagent logs.
+pub fn shows_entry(entry: &ConversationEntry, density: PreviewDensity) -> bool {
+    match density {
+        PreviewDensity::Detailed => true,
+        PreviewDensity::Compact => match entry {
+            ConversationEntry::UserMessage { .. }
+            | ConversationEntry::AssistantText { .. }
+            | ConversationEntry::HistoryTruncated { .. }
+            | ConversationEntry::HistoryOmitted { .. } => true,
+            ConversationEntry::Sidechain { entry, .. } => shows_entry(entry, density),
+            _ => false,
+        },
+    }
+}
+
+/// Tool-call ids of the tool results shown at `density`, in display
+/// order (`None` for results without one). Indexes match
+/// `ToolResultView::selected`.
+pub fn tool_result_ids(
+    entries: &VecDeque<ConversationEntry>,
+    density: PreviewDensity,
+) -> Vec<Option<String>> {
+    fn id_of(entry: &ConversationEntry) -> Option<Option<String>> {
+        match entry {
+            ConversationEntry::ToolResult { tool_use_id, .. } => Some(tool_use_id.clone()),
+            ConversationEntry::Sidechain { entry, .. } => id_of(entry),
+            _ => None,
+        }
+    }
+    entries
+        .iter()
+        .filter(|entry| shows_entry(entry, density))
+        .filter_map(id_of)
+        .collect()
+}
+
 /// Render conversation entries into styled `Text` for the preview pane.
-pub fn render_conversation(entries: &VecDeque<ConversationEntry>) -> ratatui::text::Text<'static> {
-    if entries.is_empty() {
-        return ratatui::text::Text::from(Line::from(Span::styled(
+/// `width` is the pane's inner width, used to wrap long tool details;
+/// costs are shown in `currency`.
+pub fn render_conversation(
+    entries: &VecDeque<ConversationEntry>,
+    density: PreviewDensity,
+    width: Option<usize>,
+    currency: &Currency,
+) -> ratatui::text::Text<'static> {
+    render_conversation_with_view(
+        entries,
+        density,
+        width,
+        currency,
+        &ToolResultView::default(),
+    )
+    .0
+}
+
+/// Like [`render_conversation`], with tool results selected or expanded
+/// per `view`. Also returns the line of the selected result's title.
+pub fn render_conversation_with_view(
+    entries: &VecDeque<ConversationEntry>,
+    density: PreviewDensity,
+    width: Option<usize>,
+    currency: &Currency,
+    view: &ToolResultView,
+) -> (ratatui::text::Text<'static>, Option<usize>) {
+    if !entries.iter().any(|entry| shows_entry(entry, density)) {
+        let text = ratatui::text::Text::from(Line::from(Span::styled(
             "Waiting for agent output...",
             Style::default().add_modifier(Modifier::DIM),
         )));
+        return (text, None);
     }
 
-    let user_title = Style::default()
-        .fg(Color::Cyan)
-        .add_modifier(Modifier::BOLD);
-    let assistant_title = Style::default()
-        .fg(Color::LightGreen)
-        .add_modifier(Modifier::BOLD);
-    let tool_title = Style::default()
-        .fg(Color::Yellow)
-        .add_modifier(Modifier::BOLD);
-    let queue_title = Style::default()
-        .fg(Color::Magenta)
-        .add_modifier(Modifier::BOLD);
-    let progress_title = Style::default()
-        .fg(Color::LightBlue)
-        .add_modifier(Modifier::BOLD);
-    let system_title = Style::default()
-        .fg(Color::LightMagenta)
-        .add_modifier(Modifier::BOLD);
-    let snapshot_title = Style::default()
-        .fg(Color::LightCyan)
-        .add_modifier(Modifier::BOLD);
-    let body = Style::default();
-    let dim = Style::default().add_modifier(Modifier::DIM);
-    let warn = Style::default().fg(Color::Magenta);
-
+    let render = Render {
+        styles: Styles::new(),
+        width,
+        currency,
+    };
     let mut lines: Vec<Line<'static>> = Vec::new();
     let mut unparsed_lines: Vec<Line<'static>> = Vec::new();
+    let mut cursor = ToolResultCursor {
+        view,
+        next_index: 0,
+        selected_line: None,
+    };
 
-    for entry in entries {
-        match entry {
-            ConversationEntry::UserMessage { text } => {
-                push_component_title(&mut lines, "USER", user_title);
-                push_component_body(&mut lines, text, body);
-            }
-            ConversationEntry::AssistantText { text } => {
-                push_component_title(&mut lines, "ASSISTANT", assistant_title);
-                push_component_body(&mut lines, text, body);
-            }
-            ConversationEntry::ToolUse { tool_name, details } => {
-                push_component_title(&mut lines, "TOOL", tool_title);
-                lines.push(Line::from(Span::styled(format!("  {tool_name}"), dim)));
-                if let Some(details) = details {
-                    lines.push(Line::from(Span::styled(format!("  {details}"), dim)));
-                }
-            }
-            ConversationEntry::ToolResult { filenames, summary } => {
-                push_tool_result_component(&mut lines, filenames, summary.as_deref(), dim);
-            }
-            ConversationEntry::QueueOperation { operation, task_id } => {
-                push_component_title(&mut lines, "SUBAGENT", queue_title);
-                let text = match task_id {
-                    Some(task_id) => format!("  {operation} ({task_id})"),
-                    None => format!("  {operation}"),
-                };
-                lines.push(Line::from(Span::styled(text, dim)));
-            }
-            ConversationEntry::Progress { kind, detail } => {
-                push_component_title(&mut lines, &format!("PROGRESS ({kind})"), progress_title);
-                lines.push(Line::from(Span::styled(format!("  {detail}"), dim)));
-            }
-            ConversationEntry::SystemEvent { subtype, detail } => {
-                push_component_title(&mut lines, &format!("SYSTEM ({subtype})"), system_title);
-                lines.push(Line::from(Span::styled(format!("  {detail}"), dim)));
-            }
-            ConversationEntry::FileHistorySnapshot {
-                tracked_files,
-                files,
-                is_update,
-            } => {
-                push_component_title(&mut lines, "FILE SNAPSHOT", snapshot_title);
-                let kind = if *is_update { "update" } else { "new" };
-                lines.push(Line::from(Span::styled(
-                    format!("  {kind}: {tracked_files} tracked file(s)"),
-                    dim,
-                )));
-                for file in files {
-                    lines.push(Line::from(Span::styled(format!("  - {file}"), dim)));
-                }
-                if *tracked_files > files.len() {
-                    lines.push(Line::from(Span::styled(
-                        format!("  ... +{} more", tracked_files - files.len()),
-                        dim,
-                    )));
-                }
-            }
-            ConversationEntry::Unparsed { reason, raw } => {
-                push_unparsed_component(&mut unparsed_lines, reason, raw, warn, dim);
-            }
-        }
+    for entry in entries.iter().filter(|entry| shows_entry(entry, density)) {
+        push_entry(
+            &mut lines,
+            &mut unparsed_lines,
+            entry,
+            None,
+            &render,
+            &mut cursor,
+        );
     }
 
     if !unparsed_lines.is_empty() {
@@ -163,12 +323,186 @@ pub fn render_conversation(entries: &VecDeque<ConversationEntry>) -> ratatui::te
         }
         lines.push(Line::from(Span::styled(
             "UNPARSED JSONL",
-            warn.add_modifier(Modifier::BOLD),
+            render.styles.warn.add_modifier(Modifier::BOLD),
         )));
         lines.extend(unparsed_lines);
     }
 
-    ratatui::text::Text::from(lines)
+    (ratatui::text::Text::from(lines), cursor.selected_line)
+}
+
+/// Settings shared by every entry of one render.
+struct Render<'a> {
+    styles: Styles,
+    /// Inner width of the pane, for wrapping tool details.
+    width: Option<usize>,
+    currency: &'a Currency,
+}
+
+/// Render one entry. `agent` tags titles of entries from a subagent log.
+fn push_entry(
+    lines: &mut Vec<Line<'static>>,
+    unparsed_lines: &mut Vec<Line<'static>>,
+    entry: &ConversationEntry,
+    agent: Option<&str>,
+    render: &Render,
+    cursor: &mut ToolResultCursor,
+) {
+    let styles = &render.styles;
+    let title = |name: &str| match agent {
+        Some(agent) => format!("{name} [{agent}]"),
+        None => name.to_string(),
+    };
+    let dim = styles.dim;
+    match entry {
+        ConversationEntry::UserMessage { text } => {
+            push_component_title(lines, &title("USER"), styles.user_title);
+            push_component_body(lines, text, styles.body);
+        }
+        ConversationEntry::AssistantText { text } => {
+            push_component_title(lines, &title("ASSISTANT"), styles.assistant_title);
+            lines.extend(markdown_lines(text, "  ", styles.body));
+        }
+        ConversationEntry::ToolUse { tool_name, details } => {
+            push_component_title(lines, &title("TOOL"), styles.tool_title);
+            lines.push(Line::from(Span::styled(format!("  {tool_name}"), dim)));
+            if let Some(details) = details {
+                lines.extend(
+                    wrap_with_indent(details, render.width, "  ")
+                        .into_iter()
+                        .map(|line| Line::from(Span::styled(line, dim))),
+                );
+            }
+        }
+        ConversationEntry::ToolResult {
+            filenames,
+            summary,
+            tool_use_id,
+        } => {
+            let index = cursor.next_index;
+            cursor.next_index += 1;
+            let selected = cursor.view.selected == Some(index);
+            let expanded = tool_use_id
+                .as_ref()
+                .filter(|id| cursor.view.expanded.is_some_and(|set| set.contains(*id)));
+            if !selected && expanded.is_none() {
+                push_tool_result_component(
+                    lines,
+                    &title("TOOL RESULT"),
+                    filenames,
+                    summary.as_deref(),
+                    dim,
+                );
+                return;
+            }
+            if selected {
+                // The title follows a blank separator unless it is first.
+                cursor.selected_line = Some(lines.len() + usize::from(!lines.is_empty()));
+            }
+            let body = match expanded {
+                Some(id) => Ok(cursor
+                    .view
+                    .texts
+                    .and_then(|texts| texts.get(id))
+                    .map_or("(loading full result…)", String::as_str)),
+                None => Err(summary.as_deref()),
+            };
+            push_selectable_tool_result(
+                lines,
+                &title("TOOL RESULT"),
+                filenames,
+                body,
+                selected,
+                dim,
+            );
+        }
+        ConversationEntry::QueueOperation { operation, task_id } => {
+            push_component_title(lines, &title("SUBAGENT"), styles.queue_title);
+            let text = match task_id {
+                Some(task_id) => format!("  {operation} ({task_id})"),
+                None => format!("  {operation}"),
+            };
+            lines.push(Line::from(Span::styled(text, dim)));
+        }
+        ConversationEntry::Progress { kind, detail } => {
+            push_component_title(
+                lines,
+                &title(&format!("PROGRESS ({kind})")),
+                styles.progress_title,
+            );
+            lines.push(Line::from(Span::styled(format!("  {detail}"), dim)));
+        }
+        ConversationEntry::SystemEvent { subtype, detail } => {
+            push_component_title(
+                lines,
+                &title(&format!("SYSTEM ({subtype})")),
+                styles.system_title,
+            );
+            lines.push(Line::from(Span::styled(format!("  {detail}"), dim)));
+        }
+        ConversationEntry::FileHistorySnapshot {
+            tracked_files,
+            files,
+            is_update,
+        } => {
+            push_component_title(lines, &title("FILE SNAPSHOT"), styles.snapshot_title);
+            let kind = if *is_update { "update" } else { "new" };
+            lines.push(Line::from(Span::styled(
+                format!("  {kind}: {tracked_files} tracked file(s)"),
+                dim,
+            )));
+            for file in files {
+                lines.push(Line::from(Span::styled(format!("  - {file}"), dim)));
+            }
+            if *tracked_files > files.len() {
+                lines.push(Line::from(Span::styled(
+                    format!("  ... +{} more", tracked_files - files.len()),
+                    dim,
+                )));
+            }
+        }
+        ConversationEntry::Unparsed { reason, raw } => {
+            push_unparsed_component(unparsed_lines, reason, raw, styles.warn, dim);
+        }
+        ConversationEntry::HistoryTruncated { dropped } => {
+            lines.push(Line::from(Span::styled(
+                format!("… earlier history truncated ({dropped} entries)"),
+                dim,
+            )));
+        }
+        ConversationEntry::HistoryOmitted { bytes } => {
+            lines.push(Line::from(Span::styled(
+                format!("… earlier history omitted ({} KB)", bytes.div_ceil(1024)),
+                dim,
+            )));
+        }
+        ConversationEntry::Sidechain { agent_id, entry } => {
+            push_entry(lines, unparsed_lines, entry, Some(agent_id), render, cursor);
+        }
+        ConversationEntry::TodoUpdate { todos } => {
+            let (done, total) = todo_progress(todos);
+            push_component_title(
+                lines,
+                &title(&format!("TODOS ({done}/{total})")),
+                styles.tool_title,
+            );
+            lines.extend(todos.iter().map(|todo| todo_line(todo, "  ")));
+        }
+        ConversationEntry::RunResult {
+            cost_usd,
+            duration_ms,
+            turns,
+        } => {
+            push_component_title(lines, &title("RUN RESULT"), styles.system_title);
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  {}",
+                    format_run_result(*cost_usd, *duration_ms, *turns, render.currency)
+                ),
+                dim,
+            )));
+        }
+    }
 }
 
 #[cfg(test)]
@@ -191,13 +525,84 @@ macro_rules! assert_text_snapshot {
 
 #[cfg(test)]
 mod tests {
-    use crate::logs::ConversationEntry;
-    use std::collections::VecDeque;
+    use super::shows_entry;
+    use crate::config::PreviewDensity;
+    use crate::logs::{ConversationEntry, Currency};
+    use std::collections::{HashMap, HashSet, VecDeque};
+
+    #[test]
+    fn wrap_with_indent_hangs_continuation_lines() {
+        let lines = super::wrap_with_indent(
+            "id=t1 | cmd=cargo test --workspace --all-targets",
+            Some(24),
+            "  ",
+        );
+        assert_eq!(
+            lines,
+            [
+                "  id=t1 | cmd=cargo test",
+                "    --workspace",
+                "    --all-targets",
+            ]
+        );
+        assert!(lines.iter().all(|l| l.len() <= 24));
+    }
+
+    #[test]
+    fn wrap_with_indent_splits_long_paths() {
+        let lines = super::wrap_with_indent("file=/very/long/path/to/a/file.rs", Some(16), "  ");
+        assert_eq!(
+            lines,
+            ["  file=/very/lon", "    g/path/to/a/", "    file.rs"]
+        );
+    }
+
+    #[test]
+    fn wrap_with_indent_counts_wide_characters() {
+        let lines = super::wrap_with_indent("path=日本語のファイル名", Some(12), "  ");
+        for line in &lines {
+            assert!(
+                unicode_width::UnicodeWidthStr::width(line.as_str()) <= 12,
+                "{line:?}"
+            );
+        }
+        assert_eq!(lines.concat().replace(' ', ""), "path=日本語のファイル名");
+    }
+
+    #[test]
+    fn wrap_with_indent_leaves_short_or_unbounded_text_alone() {
+        assert_eq!(
+            super::wrap_with_indent("cmd=ls", Some(40), "  "),
+            ["  cmd=ls"]
+        );
+        let long = "x".repeat(200);
+        assert_eq!(
+            super::wrap_with_indent(&long, None, "  "),
+            [format!("  {long}")]
+        );
+    }
+
+    #[test]
+    fn conversation_wraps_tool_details_to_width() {
+        let mut entries = VecDeque::new();
+        entries.push_back(ConversationEntry::ToolUse {
+            tool_name: "Bash".to_string(),
+            details: Some("id=t1 | cmd=cargo build --workspace && cargo test".to_string()),
+        });
+        let text = super::render_conversation(
+            &entries,
+            PreviewDensity::Detailed,
+            Some(30),
+            &Currency::usd(),
+        );
+        assert_text_snapshot!(text);
+    }
 
     #[test]
     fn conversation_empty() {
         let entries = VecDeque::new();
-        let text = super::render_conversation(&entries);
+        let text =
+            super::render_conversation(&entries, PreviewDensity::Detailed, None, &Currency::usd());
         assert_text_snapshot!(text);
     }
 
@@ -217,11 +622,13 @@ mod tests {
         entries.push_back(ConversationEntry::ToolResult {
             filenames: vec!["src/main.rs".to_string()],
             summary: Some("updated file successfully".to_string()),
+            tool_use_id: None,
         });
         entries.push_back(ConversationEntry::AssistantText {
             text: "Done! The bug is fixed.".to_string(),
         });
-        let text = super::render_conversation(&entries);
+        let text =
+            super::render_conversation(&entries, PreviewDensity::Detailed, None, &Currency::usd());
         assert_text_snapshot!(text);
     }
 
@@ -241,6 +648,7 @@ mod tests {
         entries.push_back(ConversationEntry::ToolResult {
             filenames: vec!["src/app.rs".to_string()],
             summary: None,
+            tool_use_id: None,
         });
         entries.push_back(ConversationEntry::ToolUse {
             tool_name: "Read".to_string(),
@@ -249,6 +657,7 @@ mod tests {
         entries.push_back(ConversationEntry::ToolResult {
             filenames: vec!["src/ui.rs".to_string()],
             summary: None,
+            tool_use_id: None,
         });
         entries.push_back(ConversationEntry::ToolUse {
             tool_name: "Edit".to_string(),
@@ -257,14 +666,54 @@ mod tests {
         entries.push_back(ConversationEntry::ToolResult {
             filenames: vec!["src/app.rs".to_string(), "src/ui.rs".to_string()],
             summary: Some("2 files modified".to_string()),
+            tool_use_id: None,
         });
         entries.push_back(ConversationEntry::AssistantText {
             text: "Refactoring complete.".to_string(),
         });
-        let text = super::render_conversation(&entries);
+        let text =
+            super::render_conversation(&entries, PreviewDensity::Detailed, None, &Currency::usd());
+        assert_text_snapshot!(text);
+    }
+
+    #[test]
+    fn conversation_selected_and_expanded_tool_results() {
+        let mut entries = VecDeque::new();
+        for (id, summary) in [("toolu_1", "first"), ("toolu_2", "second")] {
+            entries.push_back(ConversationEntry::ToolResult {
+                filenames: Vec::new(),
+                summary: Some(summary.to_string()),
+                tool_use_id: Some(id.to_string()),
+            });
+        }
+        let expanded = HashSet::from(["toolu_1".to_string()]);
+        let texts = HashMap::from([("toolu_1".to_string(), "all\nof\nit".to_string())]);
+        let view = super::ToolResultView {
+            selected: Some(1),
+            expanded: Some(&expanded),
+            texts: Some(&texts),
+        };
+        let (text, selected_line) = super::render_conversation_with_view(
+            &entries,
+            PreviewDensity::Detailed,
+            None,
+            &Currency::usd(),
+            &view,
+        );
+        assert_eq!(selected_line, Some(5));
         assert_text_snapshot!(text);
     }
 
+    #[test]
+    fn tool_result_ids_follow_density() {
+        let entries = density_sample();
+        assert_eq!(
+            super::tool_result_ids(&entries, PreviewDensity::Detailed),
+            [None]
+        );
+        assert!(super::tool_result_ids(&entries, PreviewDensity::Compact).is_empty());
+    }
+
     #[test]
     fn conversation_with_unparsed_logs() {
         let mut entries = VecDeque::new();
@@ -279,7 +728,160 @@ mod tests {
             operation: "enqueue".to_string(),
             task_id: Some("task-1".to_string()),
         });
-        let text = super::render_conversation(&entries);
+        let text =
+            super::render_conversation(&entries, PreviewDensity::Detailed, None, &Currency::usd());
+        assert_text_snapshot!(text);
+    }
+
+    fn density_sample() -> VecDeque<ConversationEntry> {
+        VecDeque::from([
+            ConversationEntry::UserMessage {
+                text: "Fix the bug".to_string(),
+            },
+            ConversationEntry::ToolUse {
+                tool_name: "Edit".to_string(),
+                details: None,
+            },
+            ConversationEntry::ToolResult {
+                filenames: vec!["src/main.rs".to_string()],
+                summary: None,
+                tool_use_id: None,
+            },
+            ConversationEntry::SystemEvent {
+                subtype: "api_error".to_string(),
+                detail: "retry".to_string(),
+            },
+            ConversationEntry::Progress {
+                kind: "hook".to_string(),
+                detail: "running".to_string(),
+            },
+            ConversationEntry::Sidechain {
+                agent_id: "agent-a".to_string(),
+                entry: Box::new(ConversationEntry::AssistantText {
+                    text: "found it".to_string(),
+                }),
+            },
+            ConversationEntry::Sidechain {
+                agent_id: "agent-a".to_string(),
+                entry: Box::new(ConversationEntry::ToolUse {
+                    tool_name: "Grep".to_string(),
+                    details: None,
+                }),
+            },
+            ConversationEntry::AssistantText {
+                text: "Done".to_string(),
+            },
+        ])
+    }
+
+    #[test]
+    fn detailed_density_shows_every_entry() {
+        let entries = density_sample();
+        assert!(entries
+            .iter()
+            .all(|e| shows_entry(e, PreviewDensity::Detailed)));
+    }
+
+    #[test]
+    fn compact_density_keeps_only_messages() {
+        let entries = density_sample();
+        let kept: Vec<usize> = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| shows_entry(e, PreviewDensity::Compact))
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(kept, [0, 5, 7]);
+    }
+
+    #[test]
+    fn compact_conversation_renders_messages_only() {
+        let text = super::render_conversation(
+            &density_sample(),
+            PreviewDensity::Compact,
+            None,
+            &Currency::usd(),
+        );
+        assert_text_snapshot!(text);
+    }
+
+    #[test]
+    fn truncation_marker_renders_at_every_density() {
+        let entries = VecDeque::from([
+            ConversationEntry::HistoryTruncated { dropped: 12 },
+            ConversationEntry::UserMessage {
+                text: "next".to_string(),
+            },
+        ]);
+        for density in [PreviewDensity::Detailed, PreviewDensity::Compact] {
+            let text = super::render_conversation(&entries, density, None, &Currency::usd());
+            let first: String = text.lines[0]
+                .spans
+                .iter()
+                .map(|s| s.content.as_ref())
+                .collect();
+            assert_eq!(first, "… earlier history truncated (12 entries)");
+        }
+    }
+
+    #[test]
+    fn omitted_history_marker_renders_at_every_density() {
+        let entries = VecDeque::from([
+            ConversationEntry::HistoryOmitted {
+                bytes: 3 * 1024 * 1024,
+            },
+            ConversationEntry::UserMessage {
+                text: "next".to_string(),
+            },
+        ]);
+        for density in [PreviewDensity::Detailed, PreviewDensity::Compact] {
+            let text = super::render_conversation(&entries, density, None, &Currency::usd());
+            let first: String = text.lines[0]
+                .spans
+                .iter()
+                .map(|s| s.content.as_ref())
+                .collect();
+            assert_eq!(first, "… earlier history omitted (3072 KB)");
+        }
+    }
+
+    #[test]
+    fn conversation_sidechain_titles_carry_agent_id() {
+        let mut entries = VecDeque::new();
+        entries.push_back(ConversationEntry::Sidechain {
+            agent_id: "agent-a".to_string(),
+            entry: Box::new(ConversationEntry::ToolUse {
+                tool_name: "Grep".to_string(),
+                details: None,
+            }),
+        });
+        let text =
+            super::render_conversation(&entries, PreviewDensity::Detailed, None, &Currency::usd());
+        let title: String = text.lines[0]
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(title, "TOOL [agent-a]");
+    }
+
+    #[test]
+    fn todo_updates_render_as_a_checklist() {
+        use crate::logs::{TodoItem, TodoStatus};
+        let todo = |content: &str, status| TodoItem {
+            content: content.to_string(),
+            status,
+        };
+        let mut entries = VecDeque::new();
+        entries.push_back(ConversationEntry::TodoUpdate {
+            todos: vec![
+                todo("Write parser", TodoStatus::Completed),
+                todo("Add tests", TodoStatus::InProgress),
+                todo("Update docs", TodoStatus::Pending),
+            ],
+        });
+        let text =
+            super::render_conversation(&entries, PreviewDensity::Detailed, None, &Currency::usd());
         assert_text_snapshot!(text);
     }
 
@@ -300,7 +902,8 @@ mod tests {
             is_update: true,
         });
 
-        let text = super::render_conversation(&entries);
+        let text =
+            super::render_conversation(&entries, PreviewDensity::Detailed, None, &Currency::usd());
         let rendered: String = text
             .lines
             .iter()
@@ -7,8 +7,9 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
-use crate::ui::truncate_chars;
+use crate::logs::truncate_to_width_with;
 
 #[derive(Clone, Copy, Debug, Default)]
 struct DiffAggregate {
@@ -119,23 +120,14 @@ fn push_tree_line(
 
     let inner_w = width.saturating_sub(1); // leave 1 char margin
     let stat = format_tree_stat(aggregate);
-    let stat_len = stat.chars().count();
-    let prefix_len = prefix.chars().count();
+    let stat_len = stat.width();
+    let prefix_len = prefix.width();
     let min_gap = usize::from(!stat.is_empty());
     let available = inner_w.saturating_sub(prefix_len + stat_len + min_gap);
 
-    let label_chars = label.chars().count();
-    let clipped_label = if available == 0 {
-        String::new()
-    } else if label_chars > available && available > 1 {
-        format!("{}…", truncate_chars(&label, available - 1))
-    } else if label_chars > available {
-        truncate_chars(&label, available)
-    } else {
-        label
-    };
+    let clipped_label = truncate_to_width_with(&label, available, "…");
 
-    let clipped_chars = clipped_label.chars().count();
+    let clipped_chars = clipped_label.width();
     let padding = if stat.is_empty() {
         0
     } else {
@@ -7,18 +7,23 @@ use ratatui::{
 };
 
 use crate::app::{Mode, UiApp};
+use crate::tmux::TmuxHealth;
 
 pub fn draw_help_bar(frame: &mut Frame, app: &UiApp, area: Rect) {
     let help_text = match app.mode {
         Mode::Browse if !app.mouse_captured => "SELECT TEXT TO COPY  |  c: exit copy mode",
         Mode::Browse => {
-            "j/k: nav  PgUp/Dn: scroll  Enter: compose  n: new  d: del  c: copy  q: quit"
+            "j/k: nav  PgUp/Dn: scroll  Enter: compose  n: new  d: del  c: copy  ?: help  q: quit"
         }
         Mode::Compose => {
             "Enter: send  Shift+Enter: newline  Up/Dn: history  Esc: cancel (draft kept)"
         }
         Mode::NewSessionAgent => "j/k: select agent  Enter: confirm  Esc: cancel",
+        Mode::NewSessionName => "Type a name  Enter: create (blank = generated)  Esc: cancel",
         Mode::ConfirmDelete => "y: confirm delete  Esc: cancel",
+        Mode::ConfirmQuit => "l: leave running and quit  K: kill all and quit  Esc: cancel",
+        Mode::EditNote => "Type a note  Enter: save (blank clears)  Esc: cancel",
+        Mode::Help => "j/k: scroll  Esc: close help",
     };
 
     let status = if let Some(msg) = &app.status_message {
@@ -27,11 +32,21 @@ pub fn draw_help_bar(frame: &mut Frame, app: &UiApp, area: Rect) {
         format!(" {help_text}")
     };
 
+    // A dead server takes every session with it; say so instead of
+    // leaving an empty sidebar to explain itself.
+    let (status, bg) = match app.snapshot.tmux_health {
+        TmuxHealth::ServerDown(n) if app.mode == Mode::Browse => (
+            format!(" tmux server not running  V: revive {n} recorded session(s) |{status}"),
+            Color::Red,
+        ),
+        _ => (status, Color::Cyan),
+    };
+
     let bar = Paragraph::new(Line::from(Span::styled(
         status,
         Style::default()
             .fg(Color::Black)
-            .bg(Color::Cyan)
+            .bg(bg)
             .add_modifier(Modifier::BOLD),
     )));
 
@@ -0,0 +1,237 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Lines longer than this (in bytes) skip inline parsing and render plain,
+/// so a pathological line can't make span matching go quadratic.
+const MAX_INLINE_BYTES: usize = 4096;
+
+/// Deepest bullet nesting level rendered; deeper items are clamped.
+const MAX_LIST_DEPTH: usize = 6;
+
+/// Render the common Markdown subset of assistant text as styled lines:
+/// headers, bullet lists, fenced code blocks and `**bold**` / `*italic*` /
+/// `` `code` `` spans. Anything else passes through as plain text. Every line
+/// is prefixed with `indent`.
+pub(crate) fn markdown_lines(text: &str, indent: &str, base: Style) -> Vec<Line<'static>> {
+    let code = base.fg(Color::LightYellow);
+    let fence = base.add_modifier(Modifier::DIM);
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+
+    for raw in text.lines() {
+        if raw.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            lines.push(Line::from(Span::styled(format!("{indent}{raw}"), fence)));
+            continue;
+        }
+        if in_fence {
+            lines.push(Line::from(Span::styled(format!("{indent}{raw}"), code)));
+            continue;
+        }
+
+        let mut spans = vec![Span::raw(indent.to_string())];
+        if let Some((level, title)) = parse_header(raw) {
+            let mut style = base.add_modifier(Modifier::BOLD);
+            if level == 1 {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            spans.extend(inline_spans(title, style));
+        } else if let Some((depth, item)) = parse_bullet(raw) {
+            spans.push(Span::styled(format!("{}• ", "  ".repeat(depth)), base));
+            spans.extend(inline_spans(item, base));
+        } else {
+            spans.extend(inline_spans(raw, base));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// `# Title` → `(1, "Title")`. Requires a space after the hashes.
+fn parse_header(line: &str) -> Option<(usize, &str)> {
+    let level = line.bytes().take_while(|&b| b == b'#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    let title = rest.strip_prefix(' ')?;
+    Some((level, title.trim()))
+}
+
+/// `  - item` → `(1, "item")`. Two leading spaces (or a tab) per level.
+fn parse_bullet(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start_matches([' ', '\t']);
+    let lead = &line[..line.len() - trimmed.len()];
+    let width: usize = lead.chars().map(|c| if c == '\t' { 2 } else { 1 }).sum();
+    let item = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| trimmed.strip_prefix(marker))?;
+    Some(((width / 2).min(MAX_LIST_DEPTH), item))
+}
+
+/// Split a line into styled spans for `**bold**`, `*italic*` and `` `code` ``.
+/// Unterminated markers are kept as literal text.
+fn inline_spans(text: &str, base: Style) -> Vec<Span<'static>> {
+    if text.len() > MAX_INLINE_BYTES {
+        return vec![Span::styled(text.to_string(), base)];
+    }
+
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let styled = match c {
+            '`' => {
+                delimited(rest, "`").map(|(inner, len)| (inner, base.fg(Color::LightYellow), len))
+            }
+            '*' if rest.starts_with("**") => delimited(rest, "**")
+                .map(|(inner, len)| (inner, base.add_modifier(Modifier::BOLD), len)),
+            '*' => delimited(rest, "*")
+                .filter(|(inner, _)| !inner.starts_with(' '))
+                .map(|(inner, len)| (inner, base.add_modifier(Modifier::ITALIC), len)),
+            _ => None,
+        };
+        match styled {
+            Some((inner, style, len)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut plain), base));
+                }
+                spans.push(Span::styled(inner.to_string(), style));
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
+/// If `text` opens with `marker` and has a matching close, return the
+/// non-empty inner text and the total byte length consumed.
+fn delimited<'a>(text: &'a str, marker: &str) -> Option<(&'a str, usize)> {
+    let body = text.strip_prefix(marker)?;
+    let end = body.find(marker)?;
+    (end > 0).then(|| (&body[..end], marker.len() * 2 + end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    fn span<'a>(line: &'a Line, content: &str) -> &'a Span<'a> {
+        line.spans
+            .iter()
+            .find(|s| s.content == content)
+            .unwrap_or_else(|| panic!("no span {content:?} in {line:?}"))
+    }
+
+    #[test]
+    fn headers_drop_hashes_and_are_bold() {
+        let lines = markdown_lines("# Plan\n### Step two\n#nospace", "  ", Style::default());
+        assert_eq!(plain(&lines[0]), "  Plan");
+        let h1 = span(&lines[0], "Plan").style;
+        assert!(h1
+            .add_modifier
+            .contains(Modifier::BOLD | Modifier::UNDERLINED));
+        assert_eq!(plain(&lines[1]), "  Step two");
+        assert!(span(&lines[1], "Step two")
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+        assert_eq!(plain(&lines[2]), "  #nospace");
+    }
+
+    #[test]
+    fn bullet_lists_are_indented_by_depth() {
+        let lines = markdown_lines(
+            "- one\n  * two\n    + three\n-not a bullet",
+            "",
+            Style::default(),
+        );
+        let rendered: Vec<String> = lines.iter().map(plain).collect();
+        assert_eq!(
+            rendered,
+            ["• one", "  • two", "    • three", "-not a bullet"]
+        );
+    }
+
+    #[test]
+    fn bullet_depth_is_clamped() {
+        let deep = format!("{}- deep", " ".repeat(200));
+        let lines = markdown_lines(&deep, "", Style::default());
+        assert_eq!(
+            plain(&lines[0]),
+            format!("{}• deep", "  ".repeat(MAX_LIST_DEPTH))
+        );
+    }
+
+    #[test]
+    fn code_fences_are_styled_and_not_parsed() {
+        let text = "Run:\n```sh\n# not a header\n- not a list **x**\n```\nafter";
+        let lines = markdown_lines(text, "", Style::default());
+        let rendered: Vec<String> = lines.iter().map(plain).collect();
+        assert_eq!(
+            rendered,
+            [
+                "Run:",
+                "```sh",
+                "# not a header",
+                "- not a list **x**",
+                "```",
+                "after"
+            ]
+        );
+        assert_eq!(lines[2].spans.len(), 1);
+        assert_eq!(lines[2].spans[0].style.fg, Some(Color::LightYellow));
+        assert!(lines[1].spans[0].style.add_modifier.contains(Modifier::DIM));
+        assert_eq!(lines[5].spans[1].style.fg, None);
+    }
+
+    #[test]
+    fn unterminated_fence_runs_to_end() {
+        let lines = markdown_lines("```\nlet x = 1;", "", Style::default());
+        assert_eq!(lines[1].spans[0].style.fg, Some(Color::LightYellow));
+    }
+
+    #[test]
+    fn inline_bold_italic_and_code() {
+        let lines = markdown_lines("a **b** *c* `d` e", "", Style::default());
+        let line = &lines[0];
+        assert_eq!(plain(line), "a b c d e");
+        assert!(span(line, "b").style.add_modifier.contains(Modifier::BOLD));
+        assert!(span(line, "c")
+            .style
+            .add_modifier
+            .contains(Modifier::ITALIC));
+        assert_eq!(span(line, "d").style.fg, Some(Color::LightYellow));
+    }
+
+    #[test]
+    fn unmatched_markers_stay_literal() {
+        let lines = markdown_lines("2 * 3 = 6, **open and `tick", "", Style::default());
+        assert_eq!(plain(&lines[0]), "2 * 3 = 6, **open and `tick");
+        assert_eq!(
+            plain(&markdown_lines("snake_case_name", "", Style::default())[0]),
+            "snake_case_name"
+        );
+    }
+
+    #[test]
+    fn oversized_lines_render_plain() {
+        let long = "**x** ".repeat(MAX_INLINE_BYTES);
+        let lines = markdown_lines(&long, "", Style::default());
+        assert_eq!(lines[0].spans.len(), 2);
+        assert_eq!(plain(&lines[0]), long.trim_end_matches('\n'));
+    }
+}
@@ -1,12 +1,14 @@
 use ratatui::{
-    layout::Rect,
+    layout::{Position, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
     Frame,
 };
 
-use crate::app::UiApp;
+use crate::app::{Mode, UiApp};
+use crate::keybindings::{bindings_for, mode_title};
+use crate::logs::format_cost;
 use crate::session::AgentType;
 
 pub(crate) fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
@@ -51,6 +53,77 @@ pub fn draw_agent_select(frame: &mut Frame, app: &UiApp) {
     frame.render_widget(list, area);
 }
 
+pub fn draw_session_name(frame: &mut Frame, app: &UiApp) {
+    let area = centered_rect(40, 4, frame.area());
+    frame.render_widget(Clear, area);
+
+    let agent = AgentType::all()
+        .get(app.agent_selection)
+        .map(|a| a.to_string())
+        .unwrap_or_default();
+    let (text, style) = if app.new_session_name.is_empty() {
+        (
+            "(blank = generated name)".to_string(),
+            Style::default().add_modifier(Modifier::DIM),
+        )
+    } else {
+        (app.new_session_name.clone(), Style::default())
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" New {agent} Session "))
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    let prompt = Paragraph::new(vec![
+        Line::from(vec![Span::raw(" Name: "), Span::styled(text, style)]),
+        Line::from(Span::styled(
+            " Enter: create  Esc: cancel",
+            Style::default().add_modifier(Modifier::DIM),
+        )),
+    ])
+    .block(block);
+    frame.render_widget(prompt, area);
+
+    let cursor_x = inner.x + 7 + app.new_session_name.len() as u16;
+    if cursor_x < inner.x + inner.width {
+        frame.set_cursor_position(Position::new(cursor_x, inner.y));
+    }
+}
+
+pub fn draw_edit_note(frame: &mut Frame, app: &UiApp) {
+    let area = centered_rect(60, 4, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Note: {} ", app.note_target_name().unwrap_or("?")))
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    // Keep the end of a long note, where the cursor is, in view.
+    let room = inner.width.saturating_sub(2) as usize;
+    let len = app.note_input.chars().count();
+    let visible: String = app
+        .note_input
+        .chars()
+        .skip(len.saturating_sub(room))
+        .collect();
+    let cursor_offset = visible.chars().count() as u16;
+    let prompt = Paragraph::new(vec![
+        Line::from(format!(" {visible}")),
+        Line::from(Span::styled(
+            " Enter: save (blank clears)  Esc: cancel",
+            Style::default().add_modifier(Modifier::DIM),
+        )),
+    ])
+    .block(block);
+    frame.render_widget(prompt, area);
+
+    let cursor_x = inner.x + 1 + cursor_offset;
+    if cursor_x < inner.x + inner.width {
+        frame.set_cursor_position(Position::new(cursor_x, inner.y));
+    }
+}
+
 pub fn draw_confirm_delete(frame: &mut Frame, app: &UiApp) {
     let area = centered_rect(40, 5, frame.area());
     frame.render_widget(Clear, area);
@@ -75,6 +148,109 @@ pub fn draw_confirm_delete(frame: &mut Frame, app: &UiApp) {
     frame.render_widget(confirm, area);
 }
 
+/// Most sessions listed in the quit confirmation before "and N more".
+const QUIT_LIST_MAX: usize = 8;
+
+pub fn draw_confirm_quit(frame: &mut Frame, app: &UiApp) {
+    let running: Vec<(String, f64)> = app
+        .running_sessions()
+        .map(|s| {
+            let cost = app
+                .snapshot
+                .session_stats
+                .get(&s.tmux_name)
+                .map_or(0.0, |st| st.estimated_cost_usd(&s.agent_type));
+            (format!("{} [{}]", s.name, s.agent_type), cost)
+        })
+        .collect();
+    let total: f64 = running.iter().map(|(_, cost)| cost).sum();
+    let currency = app.config.currency();
+    let noun = if running.len() == 1 {
+        "session is"
+    } else {
+        "sessions are"
+    };
+
+    let dim = Style::default().add_modifier(Modifier::DIM);
+    let mut lines = vec![Line::from(format!(
+        " {} {noun} still running ({} so far):",
+        running.len(),
+        format_cost(total, &currency)
+    ))];
+    for (label, cost) in running.iter().take(QUIT_LIST_MAX) {
+        lines.push(Line::from(vec![
+            Span::raw(format!("   {label:<32}")),
+            Span::styled(format!("{:>8}", format_cost(*cost, &currency)), dim),
+        ]));
+    }
+    if running.len() > QUIT_LIST_MAX {
+        lines.push(Line::styled(
+            format!("   and {} more", running.len() - QUIT_LIST_MAX),
+            dim,
+        ));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        " l: leave running  K: kill all  Esc: cancel",
+        dim,
+    ));
+
+    let area = centered_rect(50, lines.len() as u16 + 2, frame.area());
+    frame.render_widget(Clear, area);
+    let confirm = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Quit Hydra ")
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(confirm, area);
+}
+
+pub fn draw_help_overlay(frame: &mut Frame, app: &UiApp) {
+    let frame_area = frame.area();
+    let area = centered_rect(72, frame_area.height.saturating_sub(2), frame_area);
+    frame.render_widget(Clear, area);
+
+    let heading = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let key_style = Style::default().fg(Color::Yellow);
+    let key_width = 25;
+
+    let mut lines: Vec<Line> = Vec::new();
+    for mode in [
+        Mode::Browse,
+        Mode::Compose,
+        Mode::NewSessionAgent,
+        Mode::NewSessionName,
+        Mode::ConfirmDelete,
+        Mode::ConfirmQuit,
+        Mode::EditNote,
+    ] {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(mode_title(&mode), heading)));
+        for binding in bindings_for(&mode) {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<key_width$}", binding.keys), key_style),
+                Span::raw(binding.description),
+            ]));
+        }
+    }
+
+    let max_scroll = (lines.len() as u16).saturating_sub(area.height.saturating_sub(2));
+    let help = Paragraph::new(lines)
+        .scroll((app.help_scroll.min(max_scroll), 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Key Bindings ")
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+    frame.render_widget(help, area);
+}
+
 #[cfg(test)]
 mod tests {
     use ratatui::layout::Rect;
@@ -1,18 +1,64 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Position, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Paragraph},
+    widgets::{Block, BorderType, Borders, Padding, Paragraph},
     Frame,
 };
 
-use crate::app::{Mode, UiApp};
+use crate::app::{Mode, PreviewView, UiApp};
+use crate::logs::{
+    format_cost, format_run_result, format_tokens, short_model_name, todo_progress, Currency,
+    SessionStats,
+};
+use crate::session::{format_duration, format_timestamp, AgentType, ProcessState, Session};
+use crate::tmux::TmuxHealth;
+use crate::ui::conversation::todo_line;
+use crate::ui::theme::agent_style;
+
+/// Most recently modified files listed in the stats view.
+const STATS_VIEW_MAX_FILES: usize = 10;
 
 pub fn draw_preview(frame: &mut Frame, app: &UiApp, area: Rect) {
-    let title = app
+    let last_activity = app
+        .active_preview_stats()
+        .and_then(|stats| stats.last_activity_at())
+        .map(|ts| {
+            format_timestamp(
+                &ts.with_timezone(&chrono::Local),
+                chrono::Utc::now(),
+                app.config.timestamp_mode,
+            )
+        });
+    let mut title = match (app.active_preview_name(), last_activity) {
+        (Some(name), Some(ts)) => format!(" {name} · {ts} "),
+        (Some(name), None) => format!(" {name} "),
+        (None, _) => " Preview ".to_string(),
+    };
+    if let Some(note) = app
         .active_preview_name()
-        .map(|name| format!(" {name} "))
-        .unwrap_or_else(|| " Preview ".to_string());
+        .and_then(|name| app.snapshot.session_notes.get(name))
+    {
+        title.push_str(&format!("✎ {note} "));
+    }
+    if app.preview_view != PreviewView::Conversation {
+        title.push_str(&format!("[{}] ", app.preview_view));
+    }
+    if app.pinned_preview.is_some() && app.mode != Mode::Compose {
+        title.push_str("[pinned] ");
+    }
+    // The header takes the previewed session's agent accent.
+    let agent = app.active_preview_name().and_then(|name| {
+        app.snapshot
+            .sessions
+            .iter()
+            .find(|session| session.name == name)
+            .map(|session| &session.agent_type)
+    });
+    let title = match agent {
+        Some(agent) => Line::styled(title, agent_style(agent, &app.config.agent_colors)),
+        None => Line::raw(title),
+    };
 
     if app.mode == Mode::Compose {
         // Compose mode: split preview area into conversation + compose input
@@ -70,7 +116,26 @@ pub fn draw_preview(frame: &mut Frame, app: &UiApp, area: Rect) {
             .title(title)
             .border_style(border_style);
 
-        let preview = if let Some(ref text) = app.preview.text {
+        let selected = app.snapshot.sessions.get(app.selected);
+        let centered = empty_state_lines(app).or_else(|| selected.and_then(stopped_lines));
+        let preview = if let Some(lines) = centered {
+            let inner = block.inner(area);
+            let pad = inner.height.saturating_sub(lines.len() as u16) / 2;
+            Paragraph::new(lines)
+                .alignment(Alignment::Center)
+                .block(block.padding(Padding::top(pad)))
+        } else if let (PreviewView::Stats, Some(session)) = (app.preview_view, selected) {
+            let stats = app.snapshot.session_stats.get(&session.tmux_name);
+            Paragraph::new(stats_view_lines(
+                stats,
+                &session.agent_type,
+                &app.config.currency(),
+            ))
+            .block(block.padding(Padding::horizontal(1)))
+        } else if let (PreviewView::Todos, Some(session)) = (app.preview_view, selected) {
+            let stats = app.snapshot.session_stats.get(&session.tmux_name);
+            Paragraph::new(todos_view_lines(stats)).block(block.padding(Padding::horizontal(1)))
+        } else if let Some(ref text) = app.preview.text {
             Paragraph::new(text.clone())
                 .block(block)
                 .scroll((scroll_y, 0))
@@ -84,6 +149,214 @@ pub fn draw_preview(frame: &mut Frame, app: &UiApp, area: Rect) {
     }
 }
 
+/// The stats view: the selected session's log-derived usage, one labelled
+/// row per figure, with the files it modified listed and the ones it only
+/// read counted.
+/// Response latency: tenths of a second under a minute, else as a duration.
+fn format_latency(d: std::time::Duration) -> String {
+    if d.as_secs() < 60 {
+        format!("{:.1}s", d.as_secs_f64())
+    } else {
+        format_duration(d)
+    }
+}
+
+pub(crate) fn stats_view_lines(
+    stats: Option<&SessionStats>,
+    agent: &AgentType,
+    currency: &Currency,
+) -> Vec<Line<'static>> {
+    let dim = Style::default().add_modifier(Modifier::DIM);
+    let Some(stats) = stats else {
+        return vec![Line::from(Span::styled(
+            "No log stats for this session yet.",
+            dim,
+        ))];
+    };
+    let row = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(
+                format!("{label:<10}"),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(value),
+        ])
+    };
+
+    let mut tokens = format!(
+        "{} in · {} out",
+        format_tokens(stats.tokens_in),
+        format_tokens(stats.tokens_out)
+    );
+    for (count, label) in [
+        (stats.tokens_cache_read, "cache read"),
+        (stats.tokens_cache_write, "cache write"),
+    ] {
+        if count > 0 {
+            tokens.push_str(&format!(" · {} {label}", format_tokens(count)));
+        }
+    }
+    let mut lines = vec![
+        row("Tokens", tokens),
+        row(
+            "Cost",
+            format_cost(stats.estimated_cost_usd(agent), currency),
+        ),
+        row("Turns", stats.turns.to_string()),
+    ];
+    if let Some(run) = stats.last_run {
+        lines.push(row(
+            "Last run",
+            format!(
+                "{} (logged by Claude)",
+                format_run_result(run.cost_usd, run.duration_ms, run.turns, currency)
+            ),
+        ));
+    }
+    if let Some(model) = stats.last_model.as_deref() {
+        lines.push(row("Model", short_model_name(model).to_string()));
+    }
+    if let Some(duration) = stats.session_duration() {
+        lines.push(row("Duration", format_duration(duration)));
+    }
+    if let Some(tool_time) = stats.tool_time() {
+        lines.push(row("Tool time", format_duration(tool_time)));
+    }
+    if let (Some(average), Some(last)) = (stats.ttft.average(), stats.ttft.last()) {
+        lines.push(row(
+            "TTFT",
+            format!(
+                "avg {} · last {} ({} prompts)",
+                format_latency(average),
+                format_latency(last),
+                stats.ttft.count
+            ),
+        ));
+    }
+    lines.push(row(
+        "Tools",
+        format!(
+            "{} edits · {} commands · {} web",
+            stats.edits, stats.bash_cmds, stats.web_tools
+        ),
+    ));
+    lines.push(row("Modified", stats.modified_files.len().to_string()));
+    let modified = stats
+        .recent_files
+        .iter()
+        .rev()
+        .filter(|file| stats.modified_files.contains(*file));
+    for file in modified.take(STATS_VIEW_MAX_FILES) {
+        lines.push(Line::from(Span::styled(
+            format!("  {file}"),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    lines.push(row("Read", stats.read_only_files().len().to_string()));
+    if stats.skipped_lines > 0 {
+        lines.push(row(
+            "Skipped",
+            format!("{} oversized log line(s), not parsed", stats.skipped_lines),
+        ));
+    }
+    lines
+}
+
+/// The todos view: the agent's latest TODO list with a done count.
+fn todos_view_lines(stats: Option<&SessionStats>) -> Vec<Line<'static>> {
+    let todos = stats.map(|st| st.todos.as_slice()).unwrap_or_default();
+    if todos.is_empty() {
+        return vec![Line::from(Span::styled(
+            "No TODO list from this agent yet.",
+            Style::default().add_modifier(Modifier::DIM),
+        ))];
+    }
+    let (done, total) = todo_progress(todos);
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("{done}/{total} done"),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    lines.extend(todos.iter().map(|todo| todo_line(todo, "")));
+    lines
+}
+
+/// Guidance shown in place of the preview when the project has no sessions
+/// (or none match the tag filter, or the tmux server is gone). Hidden behind modals so it can't bleed
+/// past their edges.
+fn empty_state_lines(app: &UiApp) -> Option<Vec<Line<'static>>> {
+    if app.mode != Mode::Browse
+        || !app.snapshot.sessions.is_empty()
+        || app.preview.text.is_some()
+        || !app.preview.content.is_empty()
+    {
+        return None;
+    }
+    let key = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let server_down = matches!(app.snapshot.tmux_health, TmuxHealth::ServerDown(_));
+    let (headline, hint) = match &app.snapshot.tag_filter {
+        _ if server_down => (
+            "tmux server not running.".to_string(),
+            vec![
+                Span::raw("Press "),
+                Span::styled("V", key),
+                Span::raw(" to revive the recorded sessions."),
+            ],
+        ),
+        Some(tag) => (
+            format!("No sessions tagged '{tag}'."),
+            vec![
+                Span::raw("Press "),
+                Span::styled("f", key),
+                Span::raw(" to change the filter."),
+            ],
+        ),
+        None => (
+            "No sessions for this project.".to_string(),
+            vec![
+                Span::raw("Press "),
+                Span::styled("n", key),
+                Span::raw(" to create one."),
+            ],
+        ),
+    };
+    Some(vec![
+        Line::from(Span::styled(
+            headline,
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(hint),
+    ])
+}
+
+/// Shown in place of the preview for a session stopped with `hydra stop`,
+/// which has no pane to capture.
+fn stopped_lines(session: &Session) -> Option<Vec<Line<'static>>> {
+    if session.process_state != ProcessState::Stopped {
+        return None;
+    }
+    let command = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    Some(vec![
+        Line::from(Span::styled(
+            format!("'{}' is stopped.", session.name),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("Run "),
+            Span::styled(format!("hydra resume {}", session.name), command),
+            Span::raw(" to restart it."),
+        ]),
+    ])
+}
+
 fn draw_compose_input(frame: &mut Frame, app: &UiApp, area: Rect) {
     let compose_style = Style::default()
         .fg(Color::LightGreen)
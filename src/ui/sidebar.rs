@@ -6,22 +6,47 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::UiApp;
+use crate::app::{max_list_scroll, UiApp};
+use crate::logs::{format_activity, format_subagents, format_tokens, short_model_name};
+use crate::logs::{truncate_to_width, truncate_to_width_with};
 use crate::session::{format_duration, VisualStatus};
 use crate::ui::diff::draw_diff_tree;
 use crate::ui::stats::draw_stats;
-use crate::ui::truncate_chars;
+use crate::ui::theme::agent_style;
+
+/// Columns of a session's note shown in its list row.
+const NOTE_MAX_WIDTH: usize = 30;
+
+/// Columns of the last message shown under a session's list row.
+const LAST_MESSAGE_MAX_WIDTH: usize = 53;
+
+fn truncate_note(note: &str) -> String {
+    truncate_to_width_with(note, NOTE_MAX_WIDTH, "…")
+}
+
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// UI ticks each spinner frame is shown for.
+const TICKS_PER_SPINNER_FRAME: u64 = 2;
+
+/// Spinner glyph for working sessions at UI tick `tick`.
+pub fn spinner_frame(tick: u64) -> char {
+    SPINNER_FRAMES[(tick / TICKS_PER_SPINNER_FRAME) as usize % SPINNER_FRAMES.len()]
+}
 
 fn status_color(status: &VisualStatus) -> Color {
     match status {
         VisualStatus::Idle => Color::Green,
         VisualStatus::Running(_) => Color::Red,
+        VisualStatus::NeedsInput => Color::Magenta,
         VisualStatus::Exited => Color::Yellow,
         VisualStatus::Booting => Color::Gray,
+        VisualStatus::Stopped => Color::DarkGray,
     }
 }
 
-pub fn draw_sidebar(frame: &mut Frame, app: &UiApp, area: Rect) {
+/// Split the sidebar column into the session list, stats and diff tree.
+fn sidebar_chunks(app: &UiApp, area: Rect) -> [Rect; 3] {
     // Show stats when there is any machine-wide agent usage.
     let has_stats = app.snapshot.global_stats.has_usage();
 
@@ -36,18 +61,46 @@ pub fn draw_sidebar(frame: &mut Frame, app: &UiApp, area: Rect) {
         (tree_lines.len() as u16 + 2).min(max_tree_rows + 2) // +2 for top/bottom border
     };
 
-    let sidebar_chunks = Layout::default()
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Min(1),
             Constraint::Length(stats_height),
             Constraint::Length(tree_height),
         ])
-        .split(area);
+        .areas(area)
+}
+
+/// The session list's area (borders included) within the sidebar column.
+pub(crate) fn session_list_area(app: &UiApp, area: Rect) -> Rect {
+    sidebar_chunks(app, area)[0]
+}
+
+/// Row height and session index of each session-list item, in display
+/// order. Group headers are one row and map to no session.
+pub(crate) fn session_list_items(app: &UiApp) -> Vec<(usize, Option<usize>)> {
+    let mut items = Vec::new();
+    let mut current_group: Option<u8> = None;
+    for (i, session) in app.snapshot.sessions.iter().enumerate() {
+        let group = session.sort_order();
+        if current_group != Some(group) {
+            current_group = Some(group);
+            items.push((1, None));
+        }
+        let height = if app.snapshot.last_messages.contains_key(&session.tmux_name) {
+            2
+        } else {
+            1
+        };
+        items.push((height, Some(i)));
+    }
+    items
+}
 
-    let list_area = sidebar_chunks[0];
-    let stats_area = sidebar_chunks[1];
-    let tree_area = sidebar_chunks[2];
+pub fn draw_sidebar(frame: &mut Frame, app: &UiApp, area: Rect) {
+    let has_stats = app.snapshot.global_stats.has_usage();
+    let [list_area, stats_area, tree_area] = sidebar_chunks(app, area);
+    let tree_lines = &app.diff_tree_cache.2;
 
     // Build session list with status group headers.
     // Sessions are already sorted by status group then name in app.rs.
@@ -59,6 +112,7 @@ pub fn draw_sidebar(frame: &mut Frame, app: &UiApp, area: Rect) {
     let mut items: Vec<ListItem> = Vec::new();
     let mut selected_visual_row: usize = 0;
     let mut current_group: Option<u8> = None;
+    let shared_logs = app.shared_log_sessions();
 
     for (i, session) in app.snapshot.sessions.iter().enumerate() {
         let group = session.sort_order();
@@ -66,12 +120,19 @@ pub fn draw_sidebar(frame: &mut Frame, app: &UiApp, area: Rect) {
         if current_group != Some(group) {
             current_group = Some(group);
             // Build header: "── ● Running ──────"
-            let label = match &visual_status {
-                VisualStatus::Idle => " Idle ".to_string(),
+            // Sessions waiting on the user share the idle group.
+            let group_status = if visual_status == VisualStatus::NeedsInput {
+                VisualStatus::Idle
+            } else {
+                visual_status.clone()
+            };
+            let label = match &group_status {
+                VisualStatus::Idle | VisualStatus::NeedsInput => " Idle ".to_string(),
                 VisualStatus::Running(_) | VisualStatus::Booting => " Running ".to_string(),
                 VisualStatus::Exited => " Exited ".to_string(),
+                VisualStatus::Stopped => " Stopped ".to_string(),
             };
-            let dot_color = status_color(&visual_status);
+            let dot_color = status_color(&group_status);
             let dashes_left = "── ";
             let dashes_right_len = inner_width.saturating_sub(dashes_left.len() + 2 + label.len()); // 2 for "● "
             let dashes_right: String = "─".repeat(dashes_right_len);
@@ -96,44 +157,128 @@ pub fn draw_sidebar(frame: &mut Frame, app: &UiApp, area: Rect) {
         } else {
             Style::default()
         };
+        let dot = match visual_status {
+            VisualStatus::Running(_) if !app.config.disable_spinner => {
+                format!("{} ", spinner_frame(app.spinner_tick))
+            }
+            _ => "● ".to_string(),
+        };
         let mut spans = vec![
             Span::styled(marker, name_style),
-            Span::styled("● ", Style::default().fg(status_color(&visual_status))),
+            Span::styled(dot, Style::default().fg(status_color(&visual_status))),
+            Span::styled(session.name.clone(), name_style),
             Span::styled(
-                format!("{} [{}]", session.name, session.agent_type),
-                name_style,
+                format!(" [{}]", session.agent_type),
+                agent_style(&session.agent_type, &app.config.agent_colors),
             ),
         ];
+        if visual_status == VisualStatus::NeedsInput {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                " needs input ",
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        if shared_logs.contains(&session.tmux_name) {
+            spans.push(Span::styled(" ⚠ dup log", Style::default().fg(Color::Red)));
+        }
+        if visual_status == VisualStatus::Exited {
+            if let Some(error) = app.snapshot.launch_errors.get(&session.name) {
+                let summary = crate::tmux::launch_error_summary(error);
+                spans.push(Span::styled(
+                    format!(" ✗ {}", truncate_note(summary)),
+                    Style::default().fg(Color::Red),
+                ));
+            }
+        }
+        let stats = app.snapshot.session_stats.get(&session.tmux_name);
+        if let Some(model) = stats.and_then(|st| st.last_model.as_deref()) {
+            spans.push(Span::styled(
+                format!(" {}", short_model_name(model)),
+                Style::default().add_modifier(Modifier::DIM),
+            ));
+        }
+        if let Some(tags) = app.snapshot.session_tags.get(&session.name) {
+            let tags: Vec<String> = tags.iter().map(|t| format!("#{t}")).collect();
+            spans.push(Span::styled(
+                format!(" {}", tags.join(" ")),
+                Style::default().fg(Color::Blue),
+            ));
+        }
+        if let Some(note) = app.snapshot.session_notes.get(&session.name) {
+            spans.push(Span::styled(
+                format!(" ✎ {}", truncate_note(note)),
+                Style::default().add_modifier(Modifier::ITALIC | Modifier::DIM),
+            ));
+        }
+        if let Some(annotation) = app.snapshot.status_annotations.get(&session.tmux_name) {
+            spans.push(Span::styled(
+                format!(" {annotation}"),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
         if let Some(elapsed) = session.task_elapsed {
             spans.push(Span::styled(
                 format!(" {}", format_duration(elapsed)),
                 Style::default(),
             ));
         }
-        if let Some(stats) = app.snapshot.session_stats.get(&session.tmux_name) {
-            if stats.active_subagents > 0 {
+        if let Some(stats) = stats {
+            if let Some(subagents) = format_subagents(stats.active_subagents) {
                 spans.push(Span::styled(
-                    format!(" [{}T]", stats.active_subagents),
+                    format!(" {subagents}"),
                     Style::default().fg(Color::Magenta),
                 ));
             }
+            let activity = format_activity(stats.edits, stats.bash_cmds, stats.web_tools);
+            if !activity.is_empty() {
+                spans.push(Span::styled(
+                    format!(" {activity}"),
+                    Style::default().add_modifier(Modifier::DIM),
+                ));
+            }
+            let last_hour = stats.tokens_last_hour();
+            if last_hour > 0 {
+                spans.push(Span::styled(
+                    format!(" {}/h", format_tokens(last_hour)),
+                    Style::default().add_modifier(Modifier::DIM),
+                ));
+            }
+            let long_bash = app
+                .config
+                .long_bash_after()
+                .and_then(|threshold| stats.long_running_bash(chrono::Utc::now(), threshold));
+            if let Some(running_for) = long_bash {
+                spans.push(Span::styled(
+                    format!(" bash running {}", format_duration(running_for)),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            if let Some(tool_time) = stats.tool_time() {
+                spans.push(Span::styled(
+                    format!(" tools: {}", format_duration(tool_time)),
+                    Style::default().add_modifier(Modifier::DIM),
+                ));
+            }
         }
         let mut lines = vec![Line::from(spans)];
         if let Some(msg) = app.snapshot.last_messages.get(&session.tmux_name) {
-            let max_chars = 50;
-            let display = if msg.chars().count() > max_chars {
-                let truncated = truncate_chars(msg, max_chars);
-                format!("     {truncated}...")
-            } else {
-                format!("     {msg}")
-            };
+            let display = format!("     {}", truncate_to_width(msg, LAST_MESSAGE_MAX_WIDTH));
             lines.push(Line::from(Span::styled(display, Style::default())));
         }
         items.push(ListItem::new(lines));
     }
 
     let session_count = app.snapshot.sessions.len();
-    let title = format!(" Sessions ({session_count}) ");
+    let title = match &app.snapshot.tag_filter {
+        Some(tag) => format!(" Sessions ({session_count}) #{tag} "),
+        None => format!(" Sessions ({session_count}) "),
+    };
     let list = List::new(items)
         .block(
             Block::default()
@@ -144,9 +289,17 @@ pub fn draw_sidebar(frame: &mut Frame, app: &UiApp, area: Rect) {
         .highlight_style(Style::default()) // selection handled manually via ">>"
         .highlight_symbol("");
 
-    // Use stateful rendering to scroll the list to the selected visual row.
+    // Use stateful rendering to scroll the list to the selected visual row,
+    // unless the viewport was scrolled manually (Ctrl-D / Ctrl-U).
     let mut list_state = ratatui::widgets::ListState::default();
-    list_state.select(Some(selected_visual_row));
+    match app.list_scroll {
+        Some(offset) => {
+            let heights: Vec<usize> = session_list_items(app).iter().map(|(h, _)| *h).collect();
+            let viewport = list_area.height.saturating_sub(2) as usize;
+            *list_state.offset_mut() = offset.min(max_list_scroll(&heights, viewport));
+        }
+        None => list_state.select(Some(selected_visual_row)),
+    }
     frame.render_stateful_widget(list, list_area, &mut list_state);
 
     // Draw aggregate stats across all sessions
@@ -166,6 +319,19 @@ mod tests {
     use crate::session::VisualStatus;
     use ratatui::style::Color;
 
+    #[test]
+    fn spinner_frame_cycles_through_every_glyph() {
+        let frames: Vec<char> = (0..20).map(super::spinner_frame).collect();
+        assert_eq!(frames[0], '⠋');
+        assert_eq!(frames[1], '⠋');
+        assert_eq!(frames[2], '⠙');
+        assert_eq!(frames[19], '⠏');
+        assert_eq!(super::spinner_frame(20), super::spinner_frame(0));
+        let mut distinct = frames.clone();
+        distinct.dedup();
+        assert_eq!(distinct, super::SPINNER_FRAMES);
+    }
+
     #[test]
     fn status_color_maps_correctly() {
         assert_eq!(super::status_color(&VisualStatus::Idle), Color::Green);
@@ -174,5 +340,10 @@ mod tests {
             Color::Red
         );
         assert_eq!(super::status_color(&VisualStatus::Exited), Color::Yellow);
+        assert_eq!(super::status_color(&VisualStatus::Stopped), Color::DarkGray);
+        assert_eq!(
+            super::status_color(&VisualStatus::NeedsInput),
+            Color::Magenta
+        );
     }
 }
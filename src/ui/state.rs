@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 
 use ratatui::text::Text;
 
@@ -300,6 +300,13 @@ pub struct PreviewState {
     /// Cached preview line count to avoid O(n) line scans every frame.
     pub line_count: u16,
     pub scroll_offset: u16,
+    /// Index, among the conversation's shown tool results, of the one
+    /// under the cursor (`[` / `]`).
+    pub tool_cursor: Option<usize>,
+    /// Tool-call ids of the results shown in full (`x`).
+    pub expanded_tool_results: HashSet<String>,
+    /// Line of the tool result under the cursor, from the last render.
+    pub(crate) tool_cursor_line: Option<usize>,
 }
 
 impl PreviewState {
@@ -309,6 +316,9 @@ impl PreviewState {
             text: None,
             line_count: 0,
             scroll_offset: 0,
+            tool_cursor: None,
+            expanded_tool_results: HashSet::new(),
+            tool_cursor_line: None,
         }
     }
 
@@ -345,6 +355,8 @@ impl PreviewState {
     /// Reset scroll/cache state when the selected session changes.
     pub(crate) fn reset_on_selection_change(&mut self) {
         self.scroll_offset = 0;
+        self.tool_cursor = None;
+        self.expanded_tool_results.clear();
     }
 }
 
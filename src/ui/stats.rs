@@ -5,31 +5,32 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::app::UiApp;
-use crate::logs::{format_cost, format_tokens};
-use crate::ui::truncate_chars;
+use crate::logs::{format_cost, format_tokens, truncate_to_width};
 
 pub fn draw_stats(frame: &mut Frame, app: &UiApp, area: Rect) {
     let inner_width = area.width.saturating_sub(2) as usize;
+    let currency = app.config.currency();
 
     let specs = [
         StatsLineSpec {
             label: "Claude",
             short_label: "Cl",
-            cost: format_cost(app.snapshot.global_stats.claude_cost_usd()),
+            cost: format_cost(app.snapshot.global_stats.claude_cost_usd(), &currency),
             tokens: format_tokens(app.snapshot.global_stats.claude_display_tokens()),
         },
         StatsLineSpec {
             label: "Codex",
             short_label: "Cx",
-            cost: format_cost(app.snapshot.global_stats.codex_cost_usd()),
+            cost: format_cost(app.snapshot.global_stats.codex_cost_usd(), &currency),
             tokens: format_tokens(app.snapshot.global_stats.codex_display_tokens()),
         },
         StatsLineSpec {
             label: "Gemini",
             short_label: "Ge",
-            cost: format_cost(app.snapshot.global_stats.gemini_cost_usd()),
+            cost: format_cost(app.snapshot.global_stats.gemini_cost_usd(), &currency),
             tokens: format_tokens(app.snapshot.global_stats.gemini_display_tokens()),
         },
     ];
@@ -60,7 +61,11 @@ pub fn draw_stats(frame: &mut Frame, app: &UiApp, area: Rect) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(" Stats ")
+        .title(if app.snapshot.global_stats.is_paused() {
+            " Stats (paused) "
+        } else {
+            " Stats "
+        })
         .border_style(Style::default().fg(ratatui::style::Color::Cyan));
 
     let paragraph = Paragraph::new(lines).block(block);
@@ -192,24 +197,24 @@ fn format_agent_stats_line_compact(
     }
 
     let full = format!("{label} {cost} {tokens}");
-    if full.chars().count() <= inner_width {
+    if full.width() <= inner_width {
         return full;
     }
 
     let short_with_tokens = format!("{short_label} {cost} {tokens}");
-    if short_with_tokens.chars().count() <= inner_width {
+    if short_with_tokens.width() <= inner_width {
         return short_with_tokens;
     }
 
     let no_tokens = format!("{label} {cost}");
-    if no_tokens.chars().count() <= inner_width {
+    if no_tokens.width() <= inner_width {
         return no_tokens;
     }
 
     let short_no_tokens = format!("{short_label} {cost}");
-    if short_no_tokens.chars().count() <= inner_width {
+    if short_no_tokens.width() <= inner_width {
         return short_no_tokens;
     }
 
-    truncate_chars(&short_no_tokens, inner_width)
+    truncate_to_width(&short_no_tokens, inner_width)
 }
@@ -0,0 +1,84 @@
+//! Per-agent accent colors, so mixed session lists are easy to scan.
+
+use std::collections::HashMap;
+
+use ratatui::style::{Color, Style};
+
+use crate::session::AgentType;
+
+/// Built-in accent color for `agent`.
+pub fn default_agent_color(agent: &AgentType) -> Color {
+    match agent {
+        AgentType::Claude => Color::LightRed,
+        AgentType::Codex => Color::LightCyan,
+        AgentType::Gemini => Color::LightBlue,
+    }
+}
+
+/// Accent style for `agent`. `overrides` is the `agent_colors` config,
+/// keyed by lowercase agent name; a value that isn't a color name, `#rrggbb`
+/// or 0-255 palette index falls back to the default.
+pub fn agent_style(agent: &AgentType, overrides: &HashMap<String, String>) -> Style {
+    let color = overrides
+        .get(&agent.to_string().to_lowercase())
+        .and_then(|value| value.trim().parse::<Color>().ok())
+        .unwrap_or_else(|| default_agent_color(agent));
+    Style::default().fg(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AGENTS: [AgentType; 3] = [AgentType::Claude, AgentType::Codex, AgentType::Gemini];
+
+    #[test]
+    fn agents_get_distinct_default_colors() {
+        let none = HashMap::new();
+        let colors: Vec<Option<Color>> = AGENTS.iter().map(|a| agent_style(a, &none).fg).collect();
+        assert_eq!(
+            colors,
+            [
+                Some(Color::LightRed),
+                Some(Color::LightCyan),
+                Some(Color::LightBlue)
+            ]
+        );
+    }
+
+    #[test]
+    fn configured_colors_override_the_defaults() {
+        let overrides = HashMap::from([
+            ("claude".to_string(), "#d97757".to_string()),
+            ("codex".to_string(), "green".to_string()),
+            ("gemini".to_string(), "141".to_string()),
+        ]);
+        let colors: Vec<Option<Color>> = AGENTS
+            .iter()
+            .map(|a| agent_style(a, &overrides).fg)
+            .collect();
+        assert_eq!(
+            colors,
+            [
+                Some(Color::Rgb(0xd9, 0x77, 0x57)),
+                Some(Color::Green),
+                Some(Color::Indexed(141))
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_overrides_fall_back_to_the_default() {
+        let overrides = HashMap::from([("codex".to_string(), "not-a-color".to_string())]);
+        assert_eq!(
+            agent_style(&AgentType::Codex, &overrides).fg,
+            Some(Color::LightCyan)
+        );
+        // Keys are lowercase agent names.
+        let overrides = HashMap::from([("Claude".to_string(), "green".to_string())]);
+        assert_eq!(
+            agent_style(&AgentType::Claude, &overrides).fg,
+            Some(Color::LightRed)
+        );
+    }
+}
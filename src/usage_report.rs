@@ -0,0 +1,71 @@
+//! Per-provider usage over a date range, behind `hydra report`.
+
+use chrono::NaiveDate;
+
+use crate::logs::{format_cost, format_tokens, Currency, GlobalStats};
+
+/// Render a provider table for `stats`, summed from `from` through `to`,
+/// in `GlobalStats::provider_usage` order with a total row. Costs are
+/// shown in `currency`.
+pub fn render(from: NaiveDate, to: NaiveDate, stats: &GlobalStats, currency: &Currency) -> String {
+    let mut out = format!("Usage {from} to {to}\n");
+    if !stats.has_usage() {
+        out.push_str("No usage logged in this range.\n");
+        return out;
+    }
+    let mut rows: Vec<(&str, u64, u64, f64)> = stats.provider_usage().to_vec();
+    let total = rows.iter().fold((0, 0, 0.0), |(i, o, c), row| {
+        (i + row.1, o + row.2, c + row.3)
+    });
+    rows.push(("total", total.0, total.1, total.2));
+    for (provider, tokens_in, tokens_out, cost) in rows {
+        out.push_str(&format!(
+            "  {provider:<7} {:>7} in  {:>7} out  {:>7}\n",
+            format_tokens(tokens_in),
+            format_tokens(tokens_out),
+            format_cost(cost, currency)
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dates() -> (NaiveDate, NaiveDate) {
+        (
+            NaiveDate::from_ymd_opt(2026, 10, 5).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 10, 11).unwrap(),
+        )
+    }
+
+    #[test]
+    fn render_lists_each_provider_and_a_total() {
+        let mut stats = GlobalStats::default();
+        stats.claude_tokens_in = 1_000_000;
+        stats.claude_tokens_out = 200_000;
+        stats.codex_tokens_in = 5_000;
+        stats.codex_tokens_out = 700;
+
+        let (from, to) = dates();
+        let report = render(from, to, &stats, &Currency::usd());
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "Usage 2026-10-05 to 2026-10-11");
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with("  claude     1.0M in"), "{}", lines[1]);
+        assert!(lines[2].starts_with("  codex"));
+        assert!(lines[3].starts_with("  gemini"));
+        assert!(lines[4].starts_with("  total      1.0M in"), "{}", lines[4]);
+        assert!(lines[4].ends_with(&format_cost(stats.cost_usd(), &Currency::usd())));
+    }
+
+    #[test]
+    fn render_without_usage_says_so() {
+        let (from, to) = dates();
+        assert_eq!(
+            render(from, to, &GlobalStats::default(), &Currency::usd()),
+            "Usage 2026-10-05 to 2026-10-11\nNo usage logged in this range.\n"
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! `hydra new --worktree`: give a session its own git worktree, and
+//! `hydra kill` to drop it again (asked, or `--remove-worktree`).
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::process::Command;
+
+use crate::system::process::run_cmd_timeout;
+
+/// Timeout for each git call; `worktree add` checks out a full tree.
+const GIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Runs git commands, so tests can script the results instead.
+#[async_trait::async_trait]
+pub trait Git: Send + Sync {
+    /// Run `git <args>` in `dir`, returning stdout. A non-zero exit is an
+    /// error carrying git's stderr.
+    async fn run(&self, dir: &Path, args: &[String]) -> Result<String>;
+}
+
+/// The `git` binary on `PATH`.
+pub struct SystemGit;
+
+#[async_trait::async_trait]
+impl Git for SystemGit {
+    async fn run(&self, dir: &Path, args: &[String]) -> Result<String> {
+        let output =
+            run_cmd_timeout(Command::new("git").args(args).current_dir(dir), GIT_TIMEOUT).await?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("git {} failed: {}", args.join(" "), stderr.trim());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// A worktree made for a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Worktree {
+    pub path: PathBuf,
+    pub branch: String,
+    /// Whether the branch was created along with the worktree.
+    pub new_branch: bool,
+}
+
+/// Where the worktree for `branch` goes: `<repo>-worktrees/<branch>` next
+/// to the repository, with `/` in the branch name flattened to `-`.
+pub fn worktree_path(repo_root: &Path, branch: &str) -> PathBuf {
+    let repo_name = repo_root
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "repo".to_string());
+    let parent = repo_root.parent().unwrap_or(repo_root);
+    parent
+        .join(format!("{repo_name}-worktrees"))
+        .join(branch.replace('/', "-"))
+}
+
+/// `git worktree add` arguments: check out `branch` if it exists,
+/// otherwise create it from `HEAD`.
+pub fn add_args(path: &Path, branch: &str, branch_exists: bool) -> Vec<String> {
+    let path = path.to_string_lossy().into_owned();
+    let mut args = vec!["worktree".to_string(), "add".to_string()];
+    if branch_exists {
+        args.extend([path, branch.to_string()]);
+    } else {
+        args.extend(["-b".to_string(), branch.to_string(), path]);
+    }
+    args
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+/// Add a worktree for `branch` to the repository containing `cwd`.
+pub async fn create(git: &dyn Git, cwd: &Path, branch: &str) -> Result<Worktree> {
+    if branch.trim().is_empty() || branch.starts_with('-') {
+        bail!("Invalid worktree branch name '{branch}'");
+    }
+    let root = git
+        .run(cwd, &args(&["rev-parse", "--show-toplevel"]))
+        .await
+        .context("--worktree needs a git repository")?;
+    let path = worktree_path(Path::new(root.trim()), branch);
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+    let branch_ref = format!("refs/heads/{branch}");
+    let branch_exists = git
+        .run(
+            cwd,
+            &args(&["rev-parse", "--verify", "--quiet", &branch_ref]),
+        )
+        .await
+        .is_ok();
+    git.run(cwd, &add_args(&path, branch, branch_exists))
+        .await?;
+    Ok(Worktree {
+        path,
+        branch: branch.to_string(),
+        new_branch: !branch_exists,
+    })
+}
+
+/// Remove the worktree at `path`. Git refuses if it has uncommitted
+/// changes; the branch is kept.
+pub async fn remove(git: &dyn Git, cwd: &Path, path: &Path) -> Result<()> {
+    let path = path.to_string_lossy();
+    git.run(cwd, &args(&["worktree", "remove", &path]))
+        .await
+        .map(|_| ())
+}
+
+/// Undo `create` after the session could not be started: remove the
+/// worktree, and the branch too if it was new.
+pub async fn roll_back(git: &dyn Git, cwd: &Path, worktree: &Worktree) -> Result<()> {
+    remove(git, cwd, &worktree.path).await?;
+    if worktree.new_branch {
+        git.run(cwd, &args(&["branch", "-D", &worktree.branch]))
+            .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Records each call; `rev-parse --show-toplevel` answers `root`, a
+    /// branch lookup succeeds for `existing`, and `fail` makes any command
+    /// starting with it fail.
+    struct MockGit {
+        root: String,
+        existing: Vec<String>,
+        fail: Option<&'static str>,
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl MockGit {
+        fn new(root: &Path) -> Self {
+            Self {
+                root: format!("{}\n", root.display()),
+                existing: Vec::new(),
+                fail: None,
+                calls: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Git for MockGit {
+        async fn run(&self, _dir: &Path, args: &[String]) -> Result<String> {
+            let call = args.join(" ");
+            self.calls.lock().unwrap().push(call.clone());
+            if self.fail.is_some_and(|prefix| call.starts_with(prefix)) {
+                bail!("git {call} failed");
+            }
+            if call == "rev-parse --show-toplevel" {
+                return Ok(self.root.clone());
+            }
+            if let Some(branch) = call.strip_prefix("rev-parse --verify --quiet refs/heads/") {
+                if !self.existing.iter().any(|b| b == branch) {
+                    bail!("no such branch");
+                }
+            }
+            Ok(String::new())
+        }
+    }
+
+    #[test]
+    fn worktree_path_sits_next_to_the_repo() {
+        assert_eq!(
+            worktree_path(Path::new("/src/hydra"), "feature-x"),
+            PathBuf::from("/src/hydra-worktrees/feature-x")
+        );
+        assert_eq!(
+            worktree_path(Path::new("/src/hydra"), "fix/login"),
+            PathBuf::from("/src/hydra-worktrees/fix-login")
+        );
+    }
+
+    #[test]
+    fn add_args_create_the_branch_only_when_missing() {
+        let path = Path::new("/src/hydra-worktrees/feat");
+        assert_eq!(
+            add_args(path, "feat", false),
+            ["worktree", "add", "-b", "feat", "/src/hydra-worktrees/feat"]
+        );
+        assert_eq!(
+            add_args(path, "feat", true),
+            ["worktree", "add", "/src/hydra-worktrees/feat", "feat"]
+        );
+    }
+
+    #[tokio::test]
+    async fn create_runs_git_in_order_and_makes_a_new_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("app");
+        let git = MockGit::new(&root);
+
+        let worktree = create(&git, &root, "feature-x").await.unwrap();
+        let path = dir.path().join("app-worktrees").join("feature-x");
+        assert_eq!(
+            worktree,
+            Worktree {
+                path: path.clone(),
+                branch: "feature-x".to_string(),
+                new_branch: true,
+            }
+        );
+        assert_eq!(
+            git.calls(),
+            [
+                "rev-parse --show-toplevel".to_string(),
+                "rev-parse --verify --quiet refs/heads/feature-x".to_string(),
+                format!("worktree add -b feature-x {}", path.display()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn create_checks_out_an_existing_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("app");
+        let mut git = MockGit::new(&root);
+        git.existing.push("fix/login".to_string());
+
+        let worktree = create(&git, &root, "fix/login").await.unwrap();
+        assert!(!worktree.new_branch);
+        let path = dir.path().join("app-worktrees").join("fix-login");
+        assert_eq!(
+            git.calls().last().unwrap(),
+            &format!("worktree add {} fix/login", path.display())
+        );
+    }
+
+    #[tokio::test]
+    async fn create_stops_outside_a_repo_or_on_an_existing_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("app");
+
+        let mut git = MockGit::new(&root);
+        git.fail = Some("rev-parse --show-toplevel");
+        let err = create(&git, &root, "feat").await.unwrap_err();
+        assert!(err.to_string().contains("git repository"), "{err}");
+        assert_eq!(git.calls().len(), 1);
+
+        std::fs::create_dir_all(dir.path().join("app-worktrees").join("feat")).unwrap();
+        let git = MockGit::new(&root);
+        let err = create(&git, &root, "feat").await.unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+        assert_eq!(git.calls(), ["rev-parse --show-toplevel"]);
+
+        let err = create(&git, &root, "--force").await.unwrap_err();
+        assert!(err.to_string().contains("Invalid"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn roll_back_deletes_only_a_branch_it_created() {
+        let root = Path::new("/src/app");
+        let mut worktree = Worktree {
+            path: PathBuf::from("/src/app-worktrees/feat"),
+            branch: "feat".to_string(),
+            new_branch: true,
+        };
+        let git = MockGit::new(root);
+        roll_back(&git, root, &worktree).await.unwrap();
+        assert_eq!(
+            git.calls(),
+            ["worktree remove /src/app-worktrees/feat", "branch -D feat"]
+        );
+
+        worktree.new_branch = false;
+        let git = MockGit::new(root);
+        roll_back(&git, root, &worktree).await.unwrap();
+        assert_eq!(git.calls(), ["worktree remove /src/app-worktrees/feat"]);
+    }
+}
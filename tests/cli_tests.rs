@@ -9,6 +9,34 @@ fn test_ls_runs() {
     cmd.assert().success();
 }
 
+/// Test that `hydra ls --tag` rejects a tag that can't match anything
+/// instead of listing every session.
+#[test]
+fn test_ls_invalid_tag() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("hydra");
+    cmd.args(["ls", "--tag", "two words"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("not a valid tag"));
+}
+
+/// Test that `hydra tag` and `hydra new --tag` reject a tag that would be
+/// dropped instead of saved.
+#[test]
+fn test_tag_invalid_tag() {
+    for args in [
+        &["tag", "s1", "two words"][..],
+        &["tag", "s1", "--remove", "#"],
+        &["new", "claude", "s1", "--tag", "two words"],
+    ] {
+        let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("hydra");
+        cmd.args(args);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("not a valid tag"));
+    }
+}
+
 /// Test that `hydra --help` shows usage information.
 #[test]
 fn test_help_flag() {
@@ -56,3 +84,48 @@ fn test_unknown_subcommand() {
     cmd.arg("foobar");
     cmd.assert().failure();
 }
+
+/// Test that `hydra new --print-command` prints each agent's launch command
+/// without creating a session.
+#[test]
+fn test_new_print_command_per_agent() {
+    let cases = [
+        (
+            "claude",
+            r"^claude --dangerously-skip-permissions --session-id [0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\n$",
+        ),
+        (
+            "codex",
+            r"^codex -c check_for_update_on_startup=false --yolo\n$",
+        ),
+        ("gemini", r"^gemini --yolo\n$"),
+    ];
+    for (agent, expected) in cases {
+        let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("hydra");
+        cmd.args(["new", agent, "print-command-test", "--print-command"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::is_match(expected).unwrap());
+    }
+}
+
+/// Test that `--quiet` drops confirmation output but errors still surface.
+#[test]
+fn test_quiet_keeps_errors() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("hydra");
+    cmd.args(["--quiet", "kill", "no-such-session-for-quiet-test"]);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::is_empty().not());
+}
+
+/// Test that `hydra stop` refuses names it has no record for.
+#[test]
+fn test_stop_unknown_session() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("hydra");
+    cmd.args(["stop", "no-such-session-for-stop-test"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("No session named"));
+}